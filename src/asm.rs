@@ -0,0 +1,459 @@
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+
+use crate::formatter::Formatter;
+use crate::literal::quote_str;
+use crate::transform::Transform;
+
+/// The macros that an assembly invocation can use.
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum AsmKind {
+    /// `asm!`, inside of a function.
+    Asm,
+    /// `global_asm!`, at the module level.
+    Global,
+    /// `naked_asm!`, the body of a naked function.
+    Naked,
+}
+
+/// Defines an `asm!`, `global_asm!`, or `naked_asm!` invocation with its
+/// template strings, operands, and options.
+#[derive(Debug, Clone)]
+pub struct Asm {
+    /// The macro to invoke.
+    kind: AsmKind,
+    /// The template strings, usually one per instruction.
+    templates: Vec<Cow<'static, str>>,
+    /// The operands, e.g. `in(reg) x`.
+    operands: Vec<String>,
+    /// The options, e.g. `nostack`.
+    options: Vec<Cow<'static, str>>,
+}
+
+impl Asm {
+    /// Return a new `asm!` invocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Asm;
+    ///
+    /// let mut nop = Asm::new();
+    /// nop.template("nop");
+    /// ```
+    pub fn new() -> Self {
+        Asm::with_kind(AsmKind::Asm)
+    }
+
+    /// Return a new `global_asm!` invocation, which can only be pushed to a
+    /// scope and only accepts `sym` and `const` operands.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Asm;
+    ///
+    /// let mut vectors = Asm::global();
+    /// vectors.template(".section .vectors").template("b reset");
+    /// ```
+    pub fn global() -> Self {
+        Asm::with_kind(AsmKind::Global)
+    }
+
+    /// Return a new `naked_asm!` invocation, which is the body of a naked
+    /// function and only accepts `sym` and `const` operands.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Asm,Function};
+    ///
+    /// let mut trampoline = Asm::naked();
+    /// trampoline.template("jmp {}").sym("handler");
+    ///
+    /// let mut trampoline_fn = Function::new("trampoline");
+    /// trampoline_fn.naked().extern_abi("C").push_asm(&trampoline);
+    /// ```
+    pub fn naked() -> Self {
+        Asm::with_kind(AsmKind::Naked)
+    }
+
+    /// Returns a new invocation of the given macro.
+    fn with_kind(kind: AsmKind) -> Self {
+        Asm {
+            kind,
+            templates: vec![],
+            operands: vec![],
+            options: vec![],
+        }
+    }
+
+    /// Add a template string, written as a string literal. Placeholders such
+    /// as `{0}` or `{x}` refer to the operands.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The template string, e.g. `mov {0}, 5`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Asm;
+    ///
+    /// let mut mov = Asm::new();
+    /// mov.template("mov {0}, 5");
+    /// ```
+    pub fn template<T>(&mut self, template: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.templates.push(template.into());
+        self
+    }
+
+    /// Add an `in(reg) expr` operand.
+    ///
+    /// # Arguments
+    ///
+    /// * `reg` - The register or register class, e.g. `reg` or `"eax"`.
+    /// * `expr` - The input expression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Asm;
+    ///
+    /// let mut add = Asm::new();
+    /// add.template("add {0}, {1}").inout("reg", "x").input("reg", "y");
+    /// ```
+    pub fn input(&mut self, reg: &str, expr: &str) -> &mut Self {
+        self.register_operand("in", reg, expr)
+    }
+
+    /// Add a named `name = in(reg) expr` operand, referred to as `{name}` in
+    /// the templates. Named operands have to come after positional ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the operand.
+    /// * `reg` - The register or register class.
+    /// * `expr` - The input expression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Asm;
+    ///
+    /// let mut add = Asm::new();
+    /// add.template("add {x}, {y}").inout_named("x", "reg", "x").input_named("y", "reg", "y");
+    /// ```
+    pub fn input_named(&mut self, name: &str, reg: &str, expr: &str) -> &mut Self {
+        self.named_register_operand(name, "in", reg, expr)
+    }
+
+    /// Add an `out(reg) place` operand.
+    ///
+    /// # Arguments
+    ///
+    /// * `reg` - The register or register class.
+    /// * `place` - The place to write the output to, or `_` to discard it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Asm;
+    ///
+    /// let mut mov = Asm::new();
+    /// mov.template("mov {0}, 5").output("reg", "x");
+    /// ```
+    pub fn output(&mut self, reg: &str, place: &str) -> &mut Self {
+        self.register_operand("out", reg, place)
+    }
+
+    /// Add a named `name = out(reg) place` operand, referred to as `{name}`
+    /// in the templates.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the operand.
+    /// * `reg` - The register or register class.
+    /// * `place` - The place to write the output to, or `_` to discard it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Asm;
+    ///
+    /// let mut mov = Asm::new();
+    /// mov.template("mov {tmp}, 5").output_named("tmp", "reg", "_");
+    /// ```
+    pub fn output_named(&mut self, name: &str, reg: &str, place: &str) -> &mut Self {
+        self.named_register_operand(name, "out", reg, place)
+    }
+
+    /// Add a `lateout(reg) place` operand, which may share a register with
+    /// an input.
+    ///
+    /// # Arguments
+    ///
+    /// * `reg` - The register or register class.
+    /// * `place` - The place to write the output to, or `_` to discard it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Asm;
+    ///
+    /// let mut rdtsc = Asm::new();
+    /// rdtsc.template("rdtsc").lateout("\"eax\"", "lo").lateout("\"edx\"", "hi");
+    /// ```
+    pub fn lateout(&mut self, reg: &str, place: &str) -> &mut Self {
+        self.register_operand("lateout", reg, place)
+    }
+
+    /// Add an `inout(reg) expr` operand, e.g. `x` or `x => y`.
+    ///
+    /// # Arguments
+    ///
+    /// * `reg` - The register or register class.
+    /// * `expr` - The expression that is read and written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Asm;
+    ///
+    /// let mut inc = Asm::new();
+    /// inc.template("inc {0}").inout("reg", "x");
+    /// ```
+    pub fn inout(&mut self, reg: &str, expr: &str) -> &mut Self {
+        self.register_operand("inout", reg, expr)
+    }
+
+    /// Add a named `name = inout(reg) expr` operand, referred to as `{name}`
+    /// in the templates.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the operand.
+    /// * `reg` - The register or register class.
+    /// * `expr` - The expression that is read and written.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Asm;
+    ///
+    /// let mut inc = Asm::new();
+    /// inc.template("inc {x}").inout_named("x", "reg", "x");
+    /// ```
+    pub fn inout_named(&mut self, name: &str, reg: &str, expr: &str) -> &mut Self {
+        self.named_register_operand(name, "inout", reg, expr)
+    }
+
+    /// Add a `sym path` operand referring to a function or static.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the symbol.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Asm;
+    ///
+    /// let mut call = Asm::new();
+    /// call.template("call {}").sym("handler").clobber_abi("C");
+    /// ```
+    pub fn sym(&mut self, path: &str) -> &mut Self {
+        self.operands.push(format!("sym {}", path));
+        self
+    }
+
+    /// Add a `const expr` operand.
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - The constant expression.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Asm;
+    ///
+    /// let mut stack = Asm::global();
+    /// stack.template(".space {}").r#const("STACK_SIZE");
+    /// ```
+    pub fn r#const(&mut self, expr: &str) -> &mut Self {
+        self.operands.push(format!("const {}", expr));
+        self
+    }
+
+    /// Add a `clobber_abi("abi")` operand.
+    ///
+    /// # Arguments
+    ///
+    /// * `abi` - The calling convention whose clobbered registers to mark.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Asm;
+    ///
+    /// let mut call = Asm::new();
+    /// call.template("call {}").sym("handler").clobber_abi("C");
+    /// ```
+    pub fn clobber_abi(&mut self, abi: &str) -> &mut Self {
+        self.assert_register_operands("clobber_abi");
+        self.operands.push(format!("clobber_abi({})", quote_str(abi)));
+        self
+    }
+
+    /// Add an option, e.g. `nostack`, `nomem`, or `att_syntax`. All of the
+    /// options are rendered in a single `options(...)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `option` - The option to add.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Asm;
+    ///
+    /// let mut nop = Asm::new();
+    /// nop.template("nop").option("nomem").option("nostack");
+    /// ```
+    pub fn option<T>(&mut self, option: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.options.push(option.into());
+        self
+    }
+
+    /// Returns whether this is a `global_asm!` invocation.
+    pub(crate) fn is_global(&self) -> bool {
+        self.kind == AsmKind::Global
+    }
+
+    /// Returns the invocation without the trailing newline, e.g. to check
+    /// the lines of a function, or `None` if it can't be formatted.
+    pub(crate) fn to_line(&self) -> Option<String> {
+        let mut ret = String::new();
+        self.fmt(&mut Formatter::new(&mut ret)).ok()?;
+        ret.pop();
+        Some(ret)
+    }
+
+    /// Applies the given transform to the operands, e.g. to rewrite the path
+    /// of a `sym` operand.
+    ///
+    /// # Arguments
+    ///
+    /// * `transform` - The transform to apply.
+    pub(crate) fn transform(&mut self, transform: &Transform) {
+        for operand in self.operands.iter_mut() {
+            *operand = transform.apply(operand);
+        }
+    }
+
+    /// Adds a register operand with the given direction.
+    fn register_operand(&mut self, dir: &str, reg: &str, expr: &str) -> &mut Self {
+        self.assert_register_operands(dir);
+        self.operands.push(format!("{}({}) {}", dir, reg, expr));
+        self
+    }
+
+    /// Adds a named register operand with the given direction.
+    fn named_register_operand(
+        &mut self,
+        name: &str,
+        dir: &str,
+        reg: &str,
+        expr: &str,
+    ) -> &mut Self {
+        self.assert_register_operands(dir);
+        self.operands.push(format!("{} = {}({}) {}", name, dir, reg, expr));
+        self
+    }
+
+    /// Asserts that the invocation accepts register operands.
+    fn assert_register_operands(&self, operand: &str) {
+        assert!(
+            self.kind == AsmKind::Asm,
+            "`{}` operands are only allowed in `asm!`",
+            operand
+        );
+    }
+
+    /// Returns an estimate of the rendered length of the invocation.
+    pub(crate) fn estimated_len(&self) -> usize {
+        let templates: usize = self.templates.iter().map(|t| t.len() + 8).sum();
+        let operands: usize = self.operands.iter().map(|o| o.len() + 6).sum();
+        let options: usize = self.options.iter().map(|o| o.len() + 2).sum();
+
+        templates + operands + options + 32
+    }
+
+    /// Formats the invocation using the given formatter. A single template
+    /// without operands or options is written on one line, otherwise every
+    /// argument is written on its own line. An invocation without template
+    /// strings can't be formatted and returns an error.
+    ///
+    /// # Arguments
+    ///
+    /// * `fmt` - The formatter to use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::*;
+    ///
+    /// let mut dest = String::new();
+    /// let mut fmt = Formatter::new(&mut dest);
+    ///
+    /// let mut nop = Asm::new();
+    /// nop.template("nop");
+    /// nop.fmt(&mut fmt);
+    /// ```
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if self.templates.is_empty() {
+            return Err(fmt::Error);
+        }
+
+        let name = match self.kind {
+            AsmKind::Asm => "core::arch::asm!",
+            AsmKind::Global => "core::arch::global_asm!",
+            AsmKind::Naked => "core::arch::naked_asm!",
+        };
+
+        if self.templates.len() == 1 && self.operands.is_empty() && self.options.is_empty() {
+            return writeln!(fmt, "{}({});", name, quote_str(&self.templates[0]));
+        }
+
+        writeln!(fmt, "{}(", name)?;
+        fmt.indent(|fmt| {
+            for template in &self.templates {
+                writeln!(fmt, "{},", quote_str(template))?;
+            }
+
+            for operand in &self.operands {
+                writeln!(fmt, "{},", operand)?;
+            }
+
+            if !self.options.is_empty() {
+                writeln!(fmt, "options({}),", self.options.join(", "))?;
+            }
+
+            Ok(())
+        })?;
+        writeln!(fmt, ");")
+    }
+}
+
+impl Default for Asm {
+    fn default() -> Self {
+        Asm::new()
+    }
+}
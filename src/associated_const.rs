@@ -0,0 +1,205 @@
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+
+use crate::attributes::Attributes;
+use crate::docs::Docs;
+use crate::formatter::Formatter;
+use crate::visibility::{map_vis, Visibility};
+
+use crate::r#type::Type;
+
+/// Defines an associated constant in an impl block or trait (`const A: T = V;`).
+#[derive(Debug, Clone)]
+pub struct AssociatedConst {
+    /// The name of the constant.
+    name: Cow<'static, str>,
+    /// The type of the constant.
+    ty: Type,
+    /// The value of the constant.
+    value: Cow<'static, str>,
+    /// The visibility of the constant.
+    vis: Option<String>,
+    /// The documentation of the constant.
+    docs: Option<Docs>,
+    /// The attributes of the constant.
+    attributes: Attributes,
+}
+
+impl AssociatedConst {
+    /// Returns a new associated constant.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the constant.
+    /// * `ty` - The type of the constant.
+    /// * `value` - The value of the constant.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::AssociatedConst;
+    /// 
+    /// let max_const = AssociatedConst::new("MAX", "u8", "255");
+    /// ```
+    pub fn new<N, T, V>(name: N, ty: T, value: V) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+        T: Into<Type>,
+        V: Into<Cow<'static, str>>,
+    {
+        AssociatedConst {
+            name: name.into(),
+            ty: ty.into(),
+            value: value.into(),
+            vis: None,
+            docs: None,
+            attributes: Attributes::new(),
+        }
+    }
+
+    /// Returns the name of the constant.
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the visibility of the constant.
+    pub(crate) fn get_vis(&self) -> Option<&str> {
+        self.vis.as_deref()
+    }
+
+    /// Set the visibility of the constant.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `vis` - The visibility to set.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::AssociatedConst;
+    /// 
+    /// let mut max_const = AssociatedConst::new("MAX", "u8", "255");
+    /// max_const.vis("pub");
+    /// ```
+    pub fn vis(&mut self, vis: &str) -> &mut Self {
+        self.vis = Some(vis.to_string());
+        self
+    }
+
+    /// Set the documentation of the constant.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `docs` - The documentation to set.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::AssociatedConst;
+    /// 
+    /// let mut max_const = AssociatedConst::new("MAX", "u8", "255");
+    /// max_const.doc("The largest value.");
+    /// ```
+    pub fn doc<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.docs = Some(Docs::new(docs));
+        self
+    }
+
+    /// Set the documentation of the constant, written as is without
+    /// escaping markdown.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `docs` - The documentation to set.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::AssociatedConst;
+    /// 
+    /// let mut max_const = AssociatedConst::new("MAX", "u8", "255");
+    /// max_const.doc_raw("Same as [`u8::MAX`].");
+    /// ```
+    pub fn doc_raw<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let mut docs = Docs::new(docs);
+        docs.raw(true);
+
+        self.docs = Some(docs);
+        self
+    }
+
+    /// Add an attribute to the constant.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attribute` - The attribute to add, without the surrounding `#[...]`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::AssociatedConst;
+    /// 
+    /// let mut max_const = AssociatedConst::new("MAX", "u8", "255");
+    /// max_const.attr("cfg(target_pointer_width = \"64\")");
+    /// ```
+    pub fn attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.meta(attribute);
+        self
+    }
+
+    /// Calls the given function with the type of the constant.
+    pub(crate) fn visit_types_mut(&mut self, f: &mut dyn FnMut(&mut Type)) {
+        self.ty.visit_types_mut(f);
+    }
+
+    /// Maps the visibility of the associated constant with the given function.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `f` - The function mapping the visibility.
+    pub(crate) fn map_visibility(&mut self, f: &dyn Fn(Visibility) -> Visibility) {
+        map_vis(&mut self.vis, f);
+    }
+
+    /// Formats the associated constant using the given formatter.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `fmt` - The formatter to use.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::*;
+    /// 
+    /// let mut dest = String::new();
+    /// let mut fmt = Formatter::new(&mut dest);
+    /// 
+    /// let max_const = AssociatedConst::new("MAX", "u8", "255");
+    /// max_const.fmt(&mut fmt);
+    /// ```
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref docs) = self.docs {
+            docs.fmt(fmt)?;
+        }
+
+        self.attributes.fmt(fmt)?;
+
+        if let Some(ref vis) = self.vis {
+            write!(fmt, "{} ", vis)?;
+        }
+
+        write!(fmt, "const {}: ", self.name)?;
+        self.ty.fmt(fmt)?;
+        writeln!(fmt, " = {};", self.value)
+    }
+}
@@ -1,11 +1,55 @@
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+
+use crate::attributes::Attributes;
 use crate::bound::Bound;
+use crate::docs::Docs;
+use crate::formatter::{fmt_bound_rhs, fmt_short_bounds_end, Formatter};
+
 use crate::r#type::Type;
 
-/// Defines an associated type.
+/// Defines an associated type in a trait (`type A: B;`).
 #[derive(Debug, Clone)]
-pub struct AssociatedType(pub Bound);
+pub struct AssociatedType {
+    /// The name of the associated type.
+    name: Cow<'static, str>,
+    /// The bounds of the associated type, e.g. `Iterator`.
+    bounds: Vec<Type>,
+    /// The `where` bounds of the associated type.
+    where_bounds: Vec<Bound>,
+    /// The documentation of the associated type.
+    docs: Option<Docs>,
+    /// The attributes of the associated type.
+    attributes: Attributes,
+}
 
 impl AssociatedType {
+    /// Returns a new associated type.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the associated type.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::AssociatedType;
+    /// 
+    /// let item_type = AssociatedType::new("Item");
+    /// ```
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        AssociatedType {
+            name: name.into(),
+            bounds: vec![],
+            where_bounds: vec![],
+            docs: None,
+            attributes: Attributes::new(),
+        }
+    }
+
     /// Add a bound to the associated type.
     /// 
     /// # Arguments
@@ -26,7 +70,153 @@ impl AssociatedType {
     where
         T: Into<Type>,
     {
-        self.0.bound.push(ty.into());
+        self.bounds.push(ty.into());
+        self
+    }
+
+    /// Add a `where` bound to the associated type.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the bound.
+    /// * `ty` - The type of the bound.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Trait;
+    /// 
+    /// let mut trait_foo = Trait::new("Foo");
+    /// 
+    /// trait_foo.associated_type("Iter<'a>").bound("Iterator").where_bound("Self", "'a");
+    /// ```
+    pub fn where_bound<T>(&mut self, name: &str, ty: T) -> &mut Self
+    where
+        T: Into<Type>,
+    {
+        self.where_bounds.push(Bound {
+            name: name.to_string(),
+            bound: vec![ty.into()],
+        });
+        self
+    }
+
+    /// Set the documentation of the associated type.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `docs` - The documentation to set.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Trait;
+    /// 
+    /// let mut foo_trait = Trait::new("Foo");
+    /// let item_type = foo_trait.associated_type("Item");
+    /// item_type.doc("The type of the items.");
+    /// ```
+    pub fn doc<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.docs = Some(Docs::new(docs));
         self
     }
+
+    /// Set the documentation of the associated type, written as is without
+    /// escaping markdown.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `docs` - The documentation to set.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Trait;
+    /// 
+    /// let mut foo_trait = Trait::new("Foo");
+    /// let item_type = foo_trait.associated_type("Item");
+    /// item_type.doc_raw("Same as [`Iterator::Item`].");
+    /// ```
+    pub fn doc_raw<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let mut docs = Docs::new(docs);
+        docs.raw(true);
+
+        self.docs = Some(docs);
+        self
+    }
+
+    /// Add an attribute to the associated type.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attribute` - The attribute to add, without the surrounding `#[...]`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Trait;
+    /// 
+    /// let mut foo_trait = Trait::new("Foo");
+    /// let item_type = foo_trait.associated_type("Item");
+    /// item_type.attr("cfg(feature = \"bytes\")");
+    /// ```
+    pub fn attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.meta(attribute);
+        self
+    }
+
+    /// Calls the given function with the types of the bounds and the
+    /// `where` bounds.
+    pub(crate) fn visit_types_mut(&mut self, f: &mut dyn FnMut(&mut Type)) {
+        for ty in &mut self.bounds {
+            ty.visit_types_mut(f);
+        }
+
+        for bound in &mut self.where_bounds {
+            bound.visit_types_mut(f);
+        }
+    }
+
+    /// Formats the associated type using the given formatter.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `fmt` - The formatter to use.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::*;
+    /// 
+    /// let mut dest = String::new();
+    /// let mut fmt = Formatter::new(&mut dest);
+    /// 
+    /// let item_type = AssociatedType::new("Item");
+    /// item_type.fmt(&mut fmt);
+    /// ```
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref docs) = self.docs {
+            docs.fmt(fmt)?;
+        }
+
+        self.attributes.fmt(fmt)?;
+
+        write!(fmt, "type {}", self.name)?;
+
+        if !self.bounds.is_empty() {
+            write!(fmt, ": ")?;
+            fmt_bound_rhs(&self.bounds, fmt)?;
+        }
+
+        fmt_short_bounds_end(&self.where_bounds, fmt)
+    }
 }
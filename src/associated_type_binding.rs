@@ -0,0 +1,189 @@
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+
+use crate::attributes::Attributes;
+use crate::bound::Bound;
+use crate::docs::Docs;
+use crate::formatter::{fmt_short_bounds_end, Formatter};
+
+use crate::r#type::Type;
+
+/// Defines an associated type binding in an impl block (`type A = B;`).
+#[derive(Debug, Clone)]
+pub struct AssociatedTypeBinding {
+    /// The name of the associated type.
+    name: Cow<'static, str>,
+    /// The type that the associated type is bound to.
+    ty: Type,
+    /// The `where` bounds of the associated type.
+    bounds: Vec<Bound>,
+    /// The documentation of the associated type.
+    docs: Option<Docs>,
+    /// The attributes of the associated type.
+    attributes: Attributes,
+}
+
+impl AssociatedTypeBinding {
+    /// Returns a new associated type binding.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the associated type.
+    /// * `ty` - The type that the associated type is bound to.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::AssociatedTypeBinding;
+    /// 
+    /// let item_binding = AssociatedTypeBinding::new("Item", "u8");
+    /// ```
+    pub fn new<N, T>(name: N, ty: T) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+        T: Into<Type>,
+    {
+        AssociatedTypeBinding {
+            name: name.into(),
+            ty: ty.into(),
+            bounds: vec![],
+            docs: None,
+            attributes: Attributes::new(),
+        }
+    }
+
+    /// Add a `where` bound to the associated type.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the bound.
+    /// * `ty` - The type of the bound.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::AssociatedTypeBinding;
+    /// 
+    /// let mut iter_binding = AssociatedTypeBinding::new("Iter<'a>", "std::slice::Iter<'a, T>");
+    /// iter_binding.bound("Self", "'a");
+    /// ```
+    pub fn bound<T>(&mut self, name: &str, ty: T) -> &mut Self
+    where
+        T: Into<Type>,
+    {
+        self.bounds.push(Bound {
+            name: name.to_string(),
+            bound: vec![ty.into()],
+        });
+        self
+    }
+
+    /// Set the documentation of the associated type.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `docs` - The documentation to set.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::AssociatedTypeBinding;
+    /// 
+    /// let mut item_binding = AssociatedTypeBinding::new("Item", "u8");
+    /// item_binding.doc("The type of the items.");
+    /// ```
+    pub fn doc<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.docs = Some(Docs::new(docs));
+        self
+    }
+
+    /// Set the documentation of the associated type, written as is without
+    /// escaping markdown.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `docs` - The documentation to set.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::AssociatedTypeBinding;
+    /// 
+    /// let mut item_binding = AssociatedTypeBinding::new("Item", "u8");
+    /// item_binding.doc_raw("Same as [`Iterator::Item`].");
+    /// ```
+    pub fn doc_raw<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let mut docs = Docs::new(docs);
+        docs.raw(true);
+
+        self.docs = Some(docs);
+        self
+    }
+
+    /// Add an attribute to the associated type.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attribute` - The attribute to add, without the surrounding `#[...]`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::AssociatedTypeBinding;
+    /// 
+    /// let mut item_binding = AssociatedTypeBinding::new("Item", "u8");
+    /// item_binding.attr("cfg(feature = \"bytes\")");
+    /// ```
+    pub fn attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.meta(attribute);
+        self
+    }
+
+    /// Calls the given function with the bound type and the types of the
+    /// `where` bounds.
+    pub(crate) fn visit_types_mut(&mut self, f: &mut dyn FnMut(&mut Type)) {
+        self.ty.visit_types_mut(f);
+
+        for bound in &mut self.bounds {
+            bound.visit_types_mut(f);
+        }
+    }
+
+    /// Formats the associated type binding using the given formatter.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `fmt` - The formatter to use.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::*;
+    /// 
+    /// let mut dest = String::new();
+    /// let mut fmt = Formatter::new(&mut dest);
+    /// 
+    /// let item_binding = AssociatedTypeBinding::new("Item", "u8");
+    /// item_binding.fmt(&mut fmt);
+    /// ```
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref docs) = self.docs {
+            docs.fmt(fmt)?;
+        }
+
+        self.attributes.fmt(fmt)?;
+
+        write!(fmt, "type {} = ", self.name)?;
+        self.ty.fmt(fmt)?;
+        fmt_short_bounds_end(&self.bounds, fmt)
+    }
+}
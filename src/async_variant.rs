@@ -0,0 +1,160 @@
+use std::borrow::Cow;
+
+/// The return type template used by [`AsyncVariant::boxed_future`].
+const BOXED_FUTURE: &str = "std::pin::Pin<Box<dyn std::future::Future<Output = {}> + Send>>";
+
+/// The return type template used by [`AsyncVariant::boxed_future`] for
+/// functions that take references, as the future captures them for the
+/// lifetime standing in for `{lifetime}`.
+const BOXED_FUTURE_REF: &str =
+    "std::pin::Pin<Box<dyn std::future::Future<Output = {}> + Send + {lifetime}>>";
+
+/// The strategy used to support async functions in traits.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AsyncTraitStrategy {
+    /// Rely on native `async fn` in traits, removing any `#[async_trait]`.
+    Native,
+    /// Use the `async-trait` crate, adding `#[async_trait]` to traits that
+    /// contain async functions and to the impl blocks implementing them.
+    ///
+    /// Function bodies are left as they are, since `#[async_trait]` boxes
+    /// the futures of `async fn` bodies itself.
+    AsyncTrait,
+}
+
+/// Returns the last path segment of the given trait name without its
+/// generics, e.g. `Store` for `storage::Store<T>`, so that impl blocks can be
+/// matched against the traits they implement however the path is written.
+pub(crate) fn trait_ident(name: &str) -> &str {
+    let name = name.split('<').next().unwrap_or(name);
+
+    name.rsplit("::").next().unwrap_or(name).trim()
+}
+
+/// Configures how the async variant of a function, trait, or impl block is
+/// generated.
+#[derive(Debug, Clone, Default)]
+pub struct AsyncVariant {
+    /// The template used to wrap the return type, with `{}` standing in for
+    /// the original return type.
+    ret_wrapper: Option<Cow<'static, str>>,
+    /// Whether the return type is wrapped in a boxed future.
+    boxed_future: bool,
+    /// Whether `#[async_trait]` should be added to traits and impl blocks.
+    async_trait: bool,
+}
+
+impl AsyncVariant {
+    /// Returns a new async variant configuration that simply marks every
+    /// function as `async`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::AsyncVariant;
+    ///
+    /// let variant = AsyncVariant::new();
+    /// ```
+    pub fn new() -> Self {
+        AsyncVariant {
+            ret_wrapper: None,
+            boxed_future: false,
+            async_trait: false,
+        }
+    }
+
+    /// Wrap the return type of every function instead of marking it `async`.
+    ///
+    /// The template must contain `{}` which is replaced by the original
+    /// return type (or `()` if the function has none). Bodies are wrapped in
+    /// `Box::pin(async move { ... })`.
+    ///
+    /// # Arguments
+    ///
+    /// * `template` - The return type template.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::AsyncVariant;
+    ///
+    /// let mut variant = AsyncVariant::new();
+    /// variant.wrap_return("BoxFuture<'static, {}>");
+    /// ```
+    pub fn wrap_return<T>(&mut self, template: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.ret_wrapper = Some(template.into());
+        self.boxed_future = false;
+        self
+    }
+
+    /// Wrap the return type of every function in
+    /// `Pin<Box<dyn Future<Output = T> + Send>>`. `Pin` and `Future` are
+    /// written with their full paths, so no imports are needed.
+    ///
+    /// The future captures the references that the function takes, so their
+    /// elided lifetimes, including the one of `&self`, are named `'a` (or
+    /// the lifetime of the receiver if it has one) and the future is bounded
+    /// by it, e.g. `fn get<'a>(&'a self, key: &'a str) -> Pin<Box<dyn
+    /// Future<Output = usize> + Send + 'a>>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::AsyncVariant;
+    ///
+    /// let mut variant = AsyncVariant::new();
+    /// variant.boxed_future();
+    /// ```
+    pub fn boxed_future(&mut self) -> &mut Self {
+        self.ret_wrapper = None;
+        self.boxed_future = true;
+        self
+    }
+
+    /// Set whether `#[async_trait]` should be added to generated traits and
+    /// impl blocks.
+    ///
+    /// # Arguments
+    ///
+    /// * `async_trait` - Indicates whether to add `#[async_trait]` or not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::AsyncVariant;
+    ///
+    /// let mut variant = AsyncVariant::new();
+    /// variant.async_trait(true);
+    /// ```
+    pub fn async_trait(&mut self, async_trait: bool) -> &mut Self {
+        self.async_trait = async_trait;
+        self
+    }
+
+    /// Returns the return type template for a function, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `lifetime` - The lifetime of the references that the function
+    ///   takes, if any.
+    pub(crate) fn ret_wrapper(&self, lifetime: Option<&str>) -> Option<Cow<'_, str>> {
+        match (self.boxed_future, lifetime) {
+            (true, Some(lifetime)) => Some(BOXED_FUTURE_REF.replace("{lifetime}", lifetime).into()),
+            (true, None) => Some(BOXED_FUTURE.into()),
+            (false, _) => self.ret_wrapper.as_deref().map(Cow::Borrowed),
+        }
+    }
+
+    /// Returns whether the return type is wrapped in a boxed future.
+    pub(crate) fn is_boxed_future(&self) -> bool {
+        self.boxed_future
+    }
+
+    /// Returns whether `#[async_trait]` should be added.
+    pub(crate) fn uses_async_trait(&self) -> bool {
+        self.async_trait
+    }
+}
@@ -0,0 +1,118 @@
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+
+use crate::formatter::Formatter;
+
+/// Defines an attribute of an item, e.g. `#[derive(Debug)]`.
+#[derive(Debug, Clone)]
+pub enum Attribute {
+    /// A `#[derive(...)]` attribute with the types to derive.
+    Derive(Vec<Cow<'static, str>>),
+    /// An `#[allow(...)]` lint attribute.
+    Allow(Cow<'static, str>),
+    /// A `#[repr(...)]` attribute.
+    Repr(Cow<'static, str>),
+    /// A `#[cfg(...)]` attribute with the configuration predicate, e.g.
+    /// `feature = "serde"`.
+    Cfg(Cow<'static, str>),
+    /// Any other attribute, without the surrounding `#[...]`.
+    Meta(Cow<'static, str>),
+    /// An attribute or attribute macro written out verbatim, e.g.
+    /// `#[async_trait]`.
+    Raw(Cow<'static, str>),
+}
+
+/// Defines the kind of an attribute, e.g. to order the attributes of every
+/// item with [`Policy::attribute_order`].
+///
+/// [`Policy::attribute_order`]: crate::Policy::attribute_order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum AttributeKind {
+    /// `#[derive(...)]`, along with its helper attributes.
+    Derive,
+    /// `#[allow(...)]`.
+    Allow,
+    /// `#[repr(...)]`.
+    Repr,
+    /// `#[cfg(...)]`.
+    Cfg,
+    /// Any other attribute, e.g. `#[serde(...)]` or `#[inline]`.
+    Meta,
+    /// Attributes and attribute macros written out verbatim, e.g.
+    /// `#[async_trait]`.
+    Raw,
+}
+
+impl Attribute {
+    /// Returns the kind of the attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Attribute, AttributeKind};
+    ///
+    /// let attribute = Attribute::Meta("inline".into());
+    /// assert_eq!(attribute.kind(), AttributeKind::Meta);
+    /// ```
+    pub fn kind(&self) -> AttributeKind {
+        match *self {
+            Attribute::Derive(..) => AttributeKind::Derive,
+            Attribute::Allow(..) => AttributeKind::Allow,
+            Attribute::Repr(..) => AttributeKind::Repr,
+            Attribute::Cfg(..) => AttributeKind::Cfg,
+            Attribute::Meta(..) => AttributeKind::Meta,
+            Attribute::Raw(..) => AttributeKind::Raw,
+        }
+    }
+
+    /// Formats the attribute using the given formatter.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `fmt` - The formatter to use.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::*;
+    /// 
+    /// let mut dest = String::new();
+    /// let mut fmt = Formatter::new(&mut dest);
+    /// 
+    /// let attribute = Attribute::Meta("inline".into());
+    /// attribute.fmt(&mut fmt);
+    /// ```
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            // Long derive lists are wrapped with one type per line.
+            Attribute::Derive(ref names) => fmt.wrap_list("#[derive(", names, ",", ")]")?,
+            _ => self.fmt_inline(fmt)?,
+        }
+
+        write!(fmt, "\n")
+    }
+
+    /// Formats the attribute without a trailing newline, e.g. for function
+    /// arguments.
+    pub(crate) fn fmt_inline(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        match *self {
+            Attribute::Derive(ref names) => {
+                write!(fmt, "#[derive(")?;
+
+                for (i, name) in names.iter().enumerate() {
+                    if i != 0 {
+                        write!(fmt, ", ")?
+                    }
+                    write!(fmt, "{}", name)?;
+                }
+
+                write!(fmt, ")]")
+            }
+            Attribute::Allow(ref allow) => write!(fmt, "#[allow({})]", allow),
+            Attribute::Repr(ref repr) => write!(fmt, "#[repr({})]", repr),
+            Attribute::Cfg(ref cfg) => write!(fmt, "#[cfg({})]", cfg),
+            Attribute::Meta(ref meta) => write!(fmt, "#[{}]", meta),
+            Attribute::Raw(ref raw) => write!(fmt, "{}", raw),
+        }
+    }
+}
@@ -0,0 +1,449 @@
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+
+use crate::attribute::{Attribute, AttributeKind};
+use crate::formatter::Formatter;
+use crate::literal::quote_str;
+
+/// Defines the ordered list of attributes of an item.
+///
+/// Attributes are rendered after the docs of the item, in the order in which
+/// they were added unless they are sorted by kind with [`sort_by_kind`],
+/// e.g. by [`Policy::attribute_order`]. Derive helper attributes always
+/// directly follow the `#[derive(...)]` attribute, and markers such as
+/// `#[automatically_derived]` always come first.
+///
+/// [`sort_by_kind`]: Attributes::sort_by_kind
+/// [`Policy::attribute_order`]: crate::Policy::attribute_order
+#[derive(Debug, Clone, Default)]
+pub struct Attributes {
+    /// The attributes in the order they were added.
+    list: Vec<Attribute>,
+    /// The helper attributes of the derive macros.
+    derive_helpers: Vec<Attribute>,
+    /// The markers rendered before every other attribute, e.g.
+    /// `#[automatically_derived]`.
+    markers: Vec<Attribute>,
+}
+
+impl Attributes {
+    /// Returns an empty list of attributes.
+    pub fn new() -> Self {
+        Attributes {
+            list: vec![],
+            derive_helpers: vec![],
+            markers: vec![],
+        }
+    }
+
+    /// Pushes an attribute to the end of the list.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attribute` - The attribute to push.
+    pub fn push(&mut self, attribute: Attribute) -> &mut Self {
+        self.list.push(attribute);
+        self
+    }
+
+    /// Adds a marker, e.g. `automatically_derived` or a tool attribute such
+    /// as `my_tool::generated`, that is rendered before every other attribute
+    /// regardless of the order of the list. Adding a marker twice has no
+    /// effect.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `meta` - The contents of the marker, without the surrounding `#[...]`.
+    pub fn marker<T>(&mut self, meta: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let meta = meta.into();
+
+        if !self
+            .markers
+            .iter()
+            .any(|m| matches!(*m, Attribute::Meta(ref m) if *m == meta))
+        {
+            self.markers.push(Attribute::Meta(meta));
+        }
+
+        self
+    }
+
+    /// Sorts the attributes by their kind in the given order. The sort is
+    /// stable, so attributes of the same kind keep the order in which they
+    /// were added, and the kinds that aren't in the order follow the others.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `order` - The kinds of attributes in the order to render them.
+    pub fn sort_by_kind(&mut self, order: &[AttributeKind]) -> &mut Self {
+        if !order.is_empty() {
+            self.list.sort_by_key(|attribute| {
+                order
+                    .iter()
+                    .position(|kind| *kind == attribute.kind())
+                    .unwrap_or(order.len())
+            });
+        }
+
+        self
+    }
+
+    /// Adds a type to derive. All of the derives are merged into the first
+    /// `#[derive(...)]` attribute of the list.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the type to derive.
+    pub fn derive<T>(&mut self, name: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let name = name.into();
+
+        for attribute in self.list.iter_mut() {
+            if let Attribute::Derive(ref mut names) = *attribute {
+                // Deriving a type twice doesn't compile.
+                if !names.contains(&name) {
+                    names.push(name);
+                }
+
+                return self;
+            }
+        }
+
+        self.push(Attribute::Derive(vec![name]))
+    }
+
+    /// Adds default types to derive before the existing derives, skipping
+    /// the ones that are already derived.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `defaults` - The names of the types to derive.
+    pub(crate) fn derive_defaults(&mut self, defaults: &[Cow<'static, str>]) {
+        if defaults.is_empty() {
+            return;
+        }
+
+        for attribute in self.list.iter_mut() {
+            if let Attribute::Derive(ref mut names) = *attribute {
+                let mut merged: Vec<Cow<'static, str>> = defaults
+                    .iter()
+                    .filter(|name| !names.contains(name))
+                    .cloned()
+                    .collect();
+
+                merged.append(names);
+                *names = merged;
+                return;
+            }
+        }
+
+        let mut names: Vec<Cow<'static, str>> = vec![];
+
+        for name in defaults {
+            if !names.contains(name) {
+                names.push(name.clone());
+            }
+        }
+
+        self.list.insert(0, Attribute::Derive(names));
+    }
+
+    /// Returns whether there is an attribute with the given name, e.g.
+    /// `inline` for `#[inline]` or `#[inline(always)]`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the attribute.
+    pub(crate) fn has_named(&self, name: &str) -> bool {
+        self.markers
+            .iter()
+            .chain(&self.list)
+            .any(|attribute| is_named(attribute, name))
+    }
+
+    /// Removes every attribute with the given name, e.g. `inline` for
+    /// `#[inline]` or `#[inline(always)]`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the attribute.
+    pub(crate) fn remove_named(&mut self, name: &str) -> &mut Self {
+        self.list.retain(|attribute| !is_named(attribute, name));
+        self
+    }
+
+    /// Adds `#[no_mangle]` unless it's there already, replacing any
+    /// `#[export_name = "..."]` since the two can't be combined.
+    pub(crate) fn no_mangle(&mut self) -> &mut Self {
+        self.remove_named("export_name");
+
+        if !self.has_named("no_mangle") {
+            self.meta("no_mangle");
+        }
+
+        self
+    }
+
+    /// Sets `#[export_name = "..."]`, replacing any existing one and any
+    /// `#[no_mangle]` since the two can't be combined.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The symbol name.
+    pub(crate) fn export_name(&mut self, name: &str) -> &mut Self {
+        self.remove_named("no_mangle")
+            .remove_named("export_name")
+            .meta(format!("export_name = {}", quote_str(name)))
+    }
+
+    /// Sets `#[link_section = "..."]`, replacing any existing one.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `section` - The name of the section.
+    pub(crate) fn link_section(&mut self, section: &str) -> &mut Self {
+        self.remove_named("link_section")
+            .meta(format!("link_section = {}", quote_str(section)))
+    }
+
+    /// Returns the contents of the first attribute with the given name,
+    /// without the surrounding `#[...]`, e.g. `export_name = "foo"`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the attribute.
+    pub(crate) fn get_named(&self, name: &str) -> Option<&str> {
+        self.list
+            .iter()
+            .filter(|attribute| is_named(attribute, name))
+            .find_map(|attribute| match *attribute {
+                Attribute::Meta(ref meta) => Some(&**meta),
+                Attribute::Raw(ref raw) => raw[2..].strip_suffix(']'),
+                _ => None,
+            })
+    }
+
+    /// Keeps the `#[cfg(...)]` and `#[allow(...)]` attributes and the ones
+    /// with the given names, removing every other attribute.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `names` - The names of the attributes to keep.
+    pub(crate) fn retain_named(&mut self, names: &[&str]) -> &mut Self {
+        self.list.retain(|attribute| match *attribute {
+            Attribute::Cfg(..) | Attribute::Allow(..) => true,
+            _ => names.iter().any(|name| is_named(attribute, name)),
+        });
+        self.derive_helpers.clear();
+        self.markers.clear();
+        self
+    }
+
+    /// Adds a type to derive along with the helper attributes of its derive
+    /// macro (e.g. `serde(rename_all = "camelCase")`), which are rendered
+    /// directly after the `#[derive(...)]` attribute.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the type to derive.
+    /// * `helpers` - The helper attributes, without the surrounding `#[...]`.
+    pub fn derive_with<T, I, H>(&mut self, name: T, helpers: I) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+        I: IntoIterator<Item = H>,
+        H: Into<Cow<'static, str>>,
+    {
+        self.derive(name);
+        self.derive_helpers
+            .extend(helpers.into_iter().map(|h| Attribute::Meta(h.into())));
+        self
+    }
+
+    /// Adds a lint to allow.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `allow` - The lint to allow.
+    pub fn allow<T>(&mut self, allow: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.push(Attribute::Allow(allow.into()))
+    }
+
+    /// Adds a `#[cfg(...)]` attribute.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `cfg` - The configuration predicate, e.g. `feature = "serde"`.
+    pub fn cfg<T>(&mut self, cfg: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.push(Attribute::Cfg(cfg.into()))
+    }
+
+    /// Sets the representation, replacing any existing `#[repr(...)]`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `repr` - The representation to set.
+    pub fn repr<T>(&mut self, repr: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        for attribute in self.list.iter_mut() {
+            if let Attribute::Repr(ref mut current) = *attribute {
+                *current = repr.into();
+                return self;
+            }
+        }
+
+        self.push(Attribute::Repr(repr.into()))
+    }
+
+    /// Returns the `#[repr(...)]` of the item, if any.
+    pub fn get_repr(&self) -> Option<&str> {
+        self.list.iter().find_map(|attribute| match *attribute {
+            Attribute::Repr(ref repr) => Some(&**repr),
+            _ => None,
+        })
+    }
+
+    /// Adds an attribute without the surrounding `#[...]`. An attribute that
+    /// is already wrapped, e.g. `#[test]`, is unwrapped first so that it
+    /// isn't rendered as `#[#[test]]`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `meta` - The contents of the attribute.
+    pub fn meta<T>(&mut self, meta: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.push(Attribute::Meta(unwrap_attr(meta.into())))
+    }
+
+    /// Adds an attribute that is written out verbatim.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `raw` - The attribute, including the surrounding `#[...]`.
+    pub fn raw<T>(&mut self, raw: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.push(Attribute::Raw(raw.into()))
+    }
+
+    /// Returns whether the list contains the given verbatim attribute.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `raw` - The attribute to look for.
+    pub fn has_raw(&self, raw: &str) -> bool {
+        self.list
+            .iter()
+            .any(|a| matches!(*a, Attribute::Raw(ref r) if r == raw))
+    }
+
+    /// Removes every occurrence of the given verbatim attribute.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `raw` - The attribute to remove.
+    pub fn remove_raw(&mut self, raw: &str) -> &mut Self {
+        self.list
+            .retain(|a| !matches!(*a, Attribute::Raw(ref r) if r == raw));
+        self
+    }
+
+    /// Returns an estimate of the rendered length of the attributes.
+    pub(crate) fn estimated_len(&self) -> usize {
+        (self.markers.len() + self.list.len() + self.derive_helpers.len()) * 24
+    }
+
+    /// Formats every attribute using the given formatter.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `fmt` - The formatter to use.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        for marker in &self.markers {
+            marker.fmt(fmt)?;
+        }
+
+        for attribute in &self.list {
+            attribute.fmt(fmt)?;
+
+            if let Attribute::Derive(..) = *attribute {
+                for helper in &self.derive_helpers {
+                    helper.fmt(fmt)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Formats the attributes on the same line, each followed by a space,
+    /// e.g. for function arguments.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `fmt` - The formatter to use.
+    pub fn fmt_inline(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        for attribute in self
+            .markers
+            .iter()
+            .chain(&self.list)
+            .chain(&self.derive_helpers)
+        {
+            attribute.fmt_inline(fmt)?;
+            write!(fmt, " ")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Returns whether the given attribute has the given name, e.g. `inline`
+/// for `#[inline]` or `#[inline(always)]` but not for `#[inline_more]`.
+fn is_named(attribute: &Attribute, name: &str) -> bool {
+    let named = |meta: &str| {
+        meta.starts_with(name)
+            && !matches!(meta[name.len()..].chars().next(), Some(c) if c.is_alphanumeric() || c == '_')
+    };
+
+    match *attribute {
+        Attribute::Meta(ref meta) => named(meta),
+        Attribute::Raw(ref raw) => raw.starts_with("#[") && named(&raw[2..]),
+        _ => false,
+    }
+}
+
+/// Returns the contents of the given attribute without the surrounding
+/// `#[...]`, if any.
+///
+/// # Arguments
+///
+/// * `attribute` - The attribute, with or without the surrounding `#[...]`.
+fn unwrap_attr(attribute: Cow<'static, str>) -> Cow<'static, str> {
+    fn contents(attribute: &str) -> Option<&str> {
+        let contents = attribute.trim().strip_prefix("#[")?.strip_suffix(']')?;
+        Some(contents.trim())
+    }
+
+    match attribute {
+        Cow::Borrowed(s) => Cow::Borrowed(contents(s).unwrap_or(s)),
+        Cow::Owned(s) => match contents(&s) {
+            Some(contents) => Cow::Owned(contents.to_string()),
+            None => Cow::Owned(s),
+        },
+    }
+}
@@ -1,17 +1,25 @@
+use std::borrow::Cow;
 use std::fmt::{self, Write};
 
+use crate::asm::Asm;
+use crate::attributes::Attributes;
 use crate::body::Body;
-use crate::formatter::Formatter;
+use crate::formatter::{fmt_to_code_string, Formatter};
+use crate::transform::Transform;
 
 /// Defines a code block. This is used to define a function body.
 #[derive(Debug, Clone)]
 pub struct Block {
     /// Content before the block.
-    before: Option<String>,
+    before: Option<Cow<'static, str>>,
     /// Content after the block.
-    after: Option<String>,
+    after: Option<Cow<'static, str>>,
     /// The contents inside the block.
     body: Vec<Body>,
+    /// The attributes of the block, e.g. `#[cfg(unix)]`.
+    attributes: Attributes,
+    /// The label of the block, without the leading `'`.
+    label: Option<Cow<'static, str>>,
 }
 
 impl Block {
@@ -28,14 +36,190 @@ impl Block {
     /// 
     /// let mut block = Block::new("");
     /// ```
-    pub fn new(before: &str) -> Self {
+    pub fn new<T>(before: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
         Block {
-            before: Some(before.to_string()),
+            before: Some(before.into()),
             after: None,
             body: vec![],
+            attributes: Attributes::new(),
+            label: None,
         }
     }
 
+    /// Adds a `#[cfg(...)]` attribute to the block, rendered on the line
+    /// before it, e.g. for platform-specific branches.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `cfg` - The configuration predicate, e.g. `unix`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Block;
+    /// 
+    /// let mut block = Block::new("");
+    /// block.cfg("unix").line("unix_only();");
+    /// ```
+    pub fn cfg<T>(&mut self, cfg: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.cfg(cfg);
+        self
+    }
+
+    /// Adds an attribute to the block, rendered on the line before it,
+    /// without the surrounding `#[...]` (e.g. `"allow(unused_variables)"`).
+    /// An attribute that is already wrapped is accepted as well.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attribute` - The attribute to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Block;
+    /// 
+    /// let mut block = Block::new("");
+    /// block.attr("rustfmt::skip");
+    /// ```
+    pub fn attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.meta(attribute);
+        self
+    }
+
+    /// Add a macro or attribute to the block that is written as is, on the
+    /// line before it (e.g. `"#[rustfmt::skip]"`).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `r#macro` - The macro to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Block;
+    /// 
+    /// let mut block = Block::new("");
+    /// block.r#macro("#[rustfmt::skip]");
+    /// ```
+    pub fn r#macro<T>(&mut self, r#macro: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.raw(r#macro);
+        self
+    }
+
+    /// Set the label of the block so that `break_to` and `break_with` can
+    /// exit it. Loops are labeled before their keyword (`'outer: loop {`) and
+    /// other blocks directly before the brace (`let x = 'outer: {`).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `label` - The label, with or without the leading `'`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Block;
+    /// 
+    /// let mut block = Block::new("loop");
+    /// block.label("outer");
+    /// ```
+    pub fn label<T>(&mut self, label: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.label = Some(match label.into() {
+            Cow::Borrowed(label) => Cow::Borrowed(label.trim_start_matches('\'')),
+            Cow::Owned(label) if label.starts_with('\'') => {
+                Cow::Owned(label.trim_start_matches('\'').to_string())
+            }
+            label => label,
+        });
+        self
+    }
+
+    /// Push a `break 'label;` line exiting the block with the given label.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `label` - The label of the block to exit, with or without the
+    ///   leading `'`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Block;
+    /// 
+    /// let mut block = Block::new("if done");
+    /// block.break_to("outer");
+    /// ```
+    pub fn break_to(&mut self, label: &str) -> &mut Self {
+        self.line(format!("break '{};", label.trim_start_matches('\'')))
+    }
+
+    /// Push a `break 'label value;` line exiting the block with the given
+    /// label with a value.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `label` - The label of the block to exit, with or without the
+    ///   leading `'`.
+    /// * `value` - The value of the labeled block.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Block;
+    /// 
+    /// let mut block = Block::new("if input.is_empty()");
+    /// block.break_with("parse", "State::Empty");
+    /// ```
+    pub fn break_with<T>(&mut self, label: &str, value: T) -> &mut Self
+    where
+        T: AsRef<str>,
+    {
+        self.line(format!(
+            "break '{} {};",
+            label.trim_start_matches('\''),
+            value.as_ref()
+        ))
+    }
+
+    /// Push a line to the code block with a `#[cfg(...)]` attribute on the
+    /// line before it.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `cfg` - The configuration predicate, e.g. `feature = "tracing"`.
+    /// * `line` - The line to add to the code block.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Block;
+    /// 
+    /// let mut block = Block::new("");
+    /// block.line_cfg("feature = \"tracing\"", "tracing::info!(\"start\");");
+    /// ```
+    pub fn line_cfg<C, T>(&mut self, cfg: C, line: T) -> &mut Self
+    where
+        C: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.line(cfg_line(cfg.as_ref(), line.as_ref()))
+    }
+
     /// Push a line to the code block.
     /// 
     /// # Arguments 
@@ -52,9 +236,33 @@ impl Block {
     /// ```
     pub fn line<T>(&mut self, line: T) -> &mut Self
     where
-        T: ToString,
+        T: Into<Cow<'static, str>>,
     {
-        self.body.push(Body::String(line.to_string()));
+        self.body.push(Body::String(line.into()));
+        self
+    }
+
+    /// Push an `asm!` invocation to the code block. A `global_asm!`
+    /// invocation makes formatting the block fail, as it has to be pushed to
+    /// a scope.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `asm` - The assembly to push.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Asm,Block};
+    /// 
+    /// let mut nop = Asm::new();
+    /// nop.template("nop");
+    /// 
+    /// let mut block = Block::new("unsafe");
+    /// block.push_asm(&nop);
+    /// ```
+    pub fn push_asm(&mut self, asm: &Asm) -> &mut Self {
+        self.body.push(Body::Asm(asm.clone()));
         self
     }
 
@@ -82,6 +290,16 @@ impl Block {
         self
     }
 
+    /// Push a line, block, or lazy body to the block.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `body` - The body to push.
+    pub(crate) fn push_body(&mut self, body: Body) -> &mut Self {
+        self.body.push(body);
+        self
+    }
+
     /// Add a snippet after the block.
     /// 
     /// # Arguments 
@@ -96,11 +314,81 @@ impl Block {
     /// let mut block = Block::new("This is before");
     /// block.after("This is after");
     /// ```
-    pub fn after(&mut self, after: &str) -> &mut Self {
-        self.after = Some(after.to_string());
+    pub fn after<T>(&mut self, after: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.after = Some(after.into());
         self
     }
 
+    /// Returns the block as a string of code formatted with a fresh
+    /// formatter, e.g. to render it without a scope.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Block;
+    /// 
+    /// let mut block = Block::new("loop");
+    /// block.line("break;");
+    /// 
+    /// assert_eq!(block.to_code_string(), "loop {\n    break;\n}");
+    /// ```
+    pub fn to_code_string(&self) -> String {
+        fmt_to_code_string(|fmt| self.fmt(fmt))
+    }
+
+    /// Applies the given transform to the lines of the block and the code
+    /// before and after it.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `transform` - The transform to apply.
+    pub(crate) fn transform(&mut self, transform: &Transform) {
+        for snippet in self.before.iter_mut().chain(self.after.iter_mut()) {
+            let transformed = transform.apply(snippet);
+
+            if transformed != *snippet {
+                *snippet = transformed.into();
+            }
+        }
+
+        for body in &mut self.body {
+            body.transform(transform);
+        }
+    }
+
+    /// Pushes the code before the block, its lines, and the code after it to
+    /// the given list.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lines` - The list to push the lines to.
+    pub(crate) fn collect_lines(&self, lines: &mut Vec<String>) {
+        if let Some(ref before) = self.before {
+            lines.push(before.to_string());
+        }
+
+        for body in &self.body {
+            body.collect_lines(lines);
+        }
+
+        if let Some(ref after) = self.after {
+            lines.push(after.to_string());
+        }
+    }
+
+    /// Returns an estimate of the rendered length of the block, including
+    /// its indentation.
+    pub(crate) fn estimated_len(&self) -> usize {
+        let before = self.before.as_ref().map_or(0, |before| before.len());
+        let after = self.after.as_ref().map_or(0, |after| after.len());
+        let body: usize = self.body.iter().map(|body| body.estimated_len() + 4).sum();
+
+        self.attributes.estimated_len() + before + after + body + 12
+    }
+
     /// Formats the block using the given formatter.
     /// 
     /// # Arguments
@@ -119,16 +407,41 @@ impl Block {
     /// block.fmt(&mut fmt);
     /// ```
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        self.attributes.fmt(fmt)?;
+
+        // Loops are labeled before their keyword, e.g. `'outer: loop {`, and
+        // block expressions directly before the brace, e.g. `let x = 'a: {`.
+        let labels_loop = match self.before {
+            Some(ref before) => ["loop", "while ", "for "]
+                .iter()
+                .any(|keyword| before.starts_with(keyword)),
+            None => false,
+        };
+
+        if labels_loop {
+            if let Some(ref label) = self.label {
+                write!(fmt, "'{}: ", label)?;
+            }
+        }
+
         if let Some(ref before) = self.before {
             write!(fmt, "{}", before)?;
         }
 
-        // Inlined `Formatter::fmt`
+        if !labels_loop {
+            if let Some(ref label) = self.label {
+                if !fmt.is_start_of_line() {
+                    write!(fmt, " ")?;
+                }
 
-        if !fmt.is_start_of_line() {
-            write!(fmt, " ")?;
+                write!(fmt, "'{}:", label)?;
+            }
         }
 
+        // Inlined `Formatter::fmt`
+
+        fmt.fmt_brace_space()?;
+
         write!(fmt, "{{\n")?;
 
         fmt.indent(|fmt| {
@@ -149,3 +462,8 @@ impl Block {
         Ok(())
     }
 }
+
+/// Returns the line with a `#[cfg(...)]` attribute on the line before it.
+pub(crate) fn cfg_line(cfg: &str, line: &str) -> String {
+    format!("#[cfg({})]\n{}", cfg, line)
+}
@@ -1,19 +1,81 @@
+use std::borrow::Cow;
 use std::fmt::{self, Write};
 
+use crate::asm::Asm;
 use crate::block::Block;
+use crate::body_builder::LazyBody;
 use crate::formatter::Formatter;
+use crate::transform::Transform;
 
 /// Defines the types of content that go in functions and blocks.
 #[derive(Debug, Clone)]
 pub enum Body {
     /// Used to push lines to blocks.
-    String(String),
+    String(Cow<'static, str>),
     /// Used to create blocks.
     Block(Block),
+    /// Used to build lines and blocks when rendering, e.g. to skip expensive
+    /// bodies when they aren't rendered.
+    Lazy(LazyBody),
+    /// Used to push `asm!` and `naked_asm!` invocations, which are formatted
+    /// when rendering.
+    Asm(Asm),
 }
 
 impl Body {
-    /// Formats the string or block with the given formatter.
+    /// Applies the given transform to the line or the lines of the block.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `transform` - The transform to apply.
+    pub(crate) fn transform(&mut self, transform: &Transform) {
+        match self {
+            Body::String(s) => {
+                let transformed = transform.apply(s);
+
+                if transformed != *s {
+                    *s = transformed.into();
+                }
+            }
+            Body::Block(b) => b.transform(transform),
+            // The transform applies once the lazy body is built.
+            Body::Lazy(lazy) => *lazy = lazy.transformed(transform),
+            Body::Asm(asm) => asm.transform(transform),
+        }
+    }
+
+    /// Pushes the line or the lines of the block to the given list. Lazy
+    /// bodies are built to collect their lines.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `lines` - The list to push the lines to.
+    pub(crate) fn collect_lines(&self, lines: &mut Vec<String>) {
+        match self {
+            Body::String(s) => lines.push(s.to_string()),
+            Body::Block(b) => b.collect_lines(lines),
+            Body::Lazy(lazy) => {
+                for body in lazy.build() {
+                    body.collect_lines(lines);
+                }
+            }
+            Body::Asm(asm) => lines.extend(asm.to_line()),
+        }
+    }
+
+    /// Returns an estimate of the rendered length of the line or block,
+    /// including its indentation.
+    pub(crate) fn estimated_len(&self) -> usize {
+        match self {
+            Body::String(s) => s.len() + 9,
+            Body::Block(b) => b.estimated_len(),
+            Body::Lazy(_) => 0,
+            Body::Asm(asm) => asm.estimated_len(),
+        }
+    }
+
+    /// Formats the string or block with the given formatter. A `global_asm!`
+    /// invocation can't be formatted in a body and returns an error.
     /// 
     /// # Arguments
     /// 
@@ -22,6 +84,15 @@ impl Body {
         match &self {
             Body::String(s) => write!(fmt, "{}\n", s),
             Body::Block(b) => b.fmt(fmt),
+            Body::Lazy(lazy) => {
+                for body in lazy.build() {
+                    body.fmt(fmt)?;
+                }
+
+                Ok(())
+            }
+            Body::Asm(asm) if asm.is_global() => Err(fmt::Error),
+            Body::Asm(asm) => asm.fmt(fmt),
         }
     }
 }
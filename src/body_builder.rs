@@ -0,0 +1,108 @@
+use std::borrow::Cow;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::block::Block;
+use crate::body::Body;
+use crate::transform::Transform;
+
+/// Defines the lines and blocks of a body built by a closure at render time,
+/// see [`Function::body_with`].
+///
+/// [`Function::body_with`]: crate::Function::body_with
+#[derive(Debug, Default)]
+pub struct BodyBuilder {
+    /// The lines and blocks, in order.
+    pub(crate) body: Vec<Body>,
+}
+
+impl BodyBuilder {
+    /// Push a line to the body.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The line to push.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Function;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.body_with(|body| {
+    ///     body.line("let answer = 42;");
+    /// });
+    /// ```
+    pub fn line<T>(&mut self, line: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.body.push(Body::String(line.into()));
+        self
+    }
+
+    /// Push a block to the body.
+    ///
+    /// # Arguments
+    ///
+    /// * `block` - The block to push.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Block, Function};
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.body_with(|body| {
+    ///     let mut block = Block::new("loop");
+    ///     block.line("break;");
+    ///
+    ///     body.push_block(block);
+    /// });
+    /// ```
+    pub fn push_block(&mut self, block: Block) -> &mut Self {
+        self.body.push(Body::Block(block));
+        self
+    }
+}
+
+/// Defines a body built by a closure each time it is rendered, see
+/// [`Function::body_with`].
+///
+/// [`Function::body_with`]: crate::Function::body_with
+#[derive(Clone)]
+pub struct LazyBody(pub(crate) Arc<dyn Fn(&mut BodyBuilder) + Send + Sync>);
+
+impl LazyBody {
+    /// Returns the lines and blocks built by the closure.
+    pub(crate) fn build(&self) -> Vec<Body> {
+        let mut builder = BodyBuilder::default();
+        (self.0)(&mut builder);
+        builder.body
+    }
+
+    /// Returns a lazy body building the same lines and blocks with the given
+    /// transform applied to them, so that the closure still runs only when
+    /// the body is rendered.
+    ///
+    /// # Arguments
+    ///
+    /// * `transform` - The transform to apply.
+    pub(crate) fn transformed(&self, transform: &Transform) -> LazyBody {
+        let inner = self.clone();
+        let transform = transform.clone();
+
+        LazyBody(Arc::new(move |builder| {
+            for mut body in inner.build() {
+                body.transform(&transform);
+                builder.body.push(body);
+            }
+        }))
+    }
+}
+
+impl fmt::Debug for LazyBody {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("LazyBody(..)")
+    }
+}
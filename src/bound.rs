@@ -8,3 +8,12 @@ pub struct Bound {
     /// The types of the bound.
     pub bound: Vec<Type>,
 }
+
+impl Bound {
+    /// Calls the given function with every type of the bound.
+    pub(crate) fn visit_types_mut(&mut self, f: &mut dyn FnMut(&mut Type)) {
+        for ty in &mut self.bound {
+            ty.visit_types_mut(f);
+        }
+    }
+}
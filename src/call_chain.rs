@@ -0,0 +1,256 @@
+use std::borrow::Cow;
+
+use crate::expr::Expr;
+
+/// The default maximum width of a chain rendered on a single line, matching
+/// the `chain_width` default of rustfmt.
+const DEFAULT_CHAIN_WIDTH: usize = 60;
+
+/// Defines a link of a call chain.
+#[derive(Debug, Clone)]
+enum Link {
+    /// A method call, e.g. `.get(url)`.
+    Method(Cow<'static, str>, Vec<Cow<'static, str>>),
+    /// A field access, e.g. `.headers`.
+    Field(Cow<'static, str>),
+    /// An `.await`.
+    Await,
+    /// A `?`, written directly after the previous link.
+    Try,
+}
+
+/// Defines a chain of method calls, e.g. `client.get(url).send().await?`,
+/// broken onto indented `.method()` lines when it doesn't fit on one line.
+#[derive(Debug, Clone)]
+pub struct CallChain {
+    /// The expression the chain starts from.
+    receiver: Cow<'static, str>,
+    /// The links of the chain.
+    links: Vec<Link>,
+    /// The maximum width of the chain on a single line.
+    width: usize,
+}
+
+impl CallChain {
+    /// Returns a new call chain starting from the given expression.
+    ///
+    /// # Arguments
+    ///
+    /// * `receiver` - The expression the chain starts from, e.g. `client`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::CallChain;
+    ///
+    /// let chain = CallChain::new("client");
+    /// ```
+    pub fn new<T>(receiver: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        CallChain {
+            receiver: receiver.into(),
+            links: vec![],
+            width: DEFAULT_CHAIN_WIDTH,
+        }
+    }
+
+    /// Call a method with the given arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the method, e.g. `get` or `collect::<Vec<_>>`.
+    /// * `args` - The expressions of the arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::CallChain;
+    ///
+    /// let mut chain = CallChain::new("client");
+    /// chain.method("get", ["url"]).method("timeout", vec![format!("Duration::from_secs({})", 30)]);
+    /// ```
+    pub fn method<T, I, A>(&mut self, name: T, args: I) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+        I: IntoIterator<Item = A>,
+        A: Into<Cow<'static, str>>,
+    {
+        self.links.push(Link::Method(
+            name.into(),
+            args.into_iter().map(Into::into).collect(),
+        ));
+        self
+    }
+
+    /// Call a method without arguments.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the method, e.g. `send` or `collect::<Vec<_>>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::CallChain;
+    ///
+    /// let mut chain = CallChain::new("client");
+    /// chain.method("get", ["url"]).call("send");
+    /// ```
+    pub fn call<T>(&mut self, name: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.links.push(Link::Method(name.into(), vec![]));
+        self
+    }
+
+    /// Access a field.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::CallChain;
+    ///
+    /// let mut chain = CallChain::new("response");
+    /// chain.field("headers").call("len");
+    /// ```
+    pub fn field<T>(&mut self, name: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.links.push(Link::Field(name.into()));
+        self
+    }
+
+    /// Await the chain so far with `.await`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::CallChain;
+    ///
+    /// let mut chain = CallChain::new("client");
+    /// chain.call("send").await_();
+    /// ```
+    pub fn await_(&mut self) -> &mut Self {
+        self.links.push(Link::Await);
+        self
+    }
+
+    /// Propagate the error of the chain so far with `?`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::CallChain;
+    ///
+    /// let mut chain = CallChain::new("client");
+    /// chain.call("send").await_().try_();
+    /// ```
+    pub fn try_(&mut self) -> &mut Self {
+        self.links.push(Link::Try);
+        self
+    }
+
+    /// Set the maximum width of the chain on a single line, 60 by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `width` - The maximum width, not counting the indentation of the
+    ///   line the chain is written on.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::CallChain;
+    ///
+    /// let mut chain = CallChain::new("client");
+    /// chain.width(100);
+    /// ```
+    pub fn width(&mut self, width: usize) -> &mut Self {
+        self.width = width;
+        self
+    }
+
+    /// Returns the chain as code, on a single line when it fits within the
+    /// width and with a link per indented line otherwise.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::CallChain;
+    ///
+    /// let mut chain = CallChain::new("client");
+    /// chain.method("get", ["url"]).call("send").await_();
+    ///
+    /// assert_eq!(chain.to_code_string(), "client.get(url).send().await");
+    /// ```
+    pub fn to_code_string(&self) -> String {
+        let inline = self.fmt_links("");
+
+        if inline.len() <= self.width && !inline.contains('\n') {
+            return inline;
+        }
+
+        self.fmt_links("\n    ")
+    }
+
+    /// Returns the chain as an expression, e.g. to wrap it in `Ok(...)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::CallChain;
+    ///
+    /// let mut chain = CallChain::new("client");
+    /// chain.call("send").await_().try_();
+    ///
+    /// assert_eq!(chain.to_expr().ok().to_code_string(), "Ok(client.send().await?)");
+    /// ```
+    pub fn to_expr(&self) -> Expr {
+        Expr::new(self.to_code_string())
+    }
+
+    /// Returns the chain with the given separator before each link.
+    ///
+    /// # Arguments
+    ///
+    /// * `sep` - The separator written before the `.` of each link.
+    fn fmt_links(&self, sep: &str) -> String {
+        let mut ret = self.receiver.to_string();
+
+        for link in &self.links {
+            match link {
+                Link::Method(name, args) => {
+                    let call = format!(".{}({})", name, args.join(", "));
+
+                    ret.push_str(sep);
+
+                    // Indent multi-line arguments along with the link.
+                    if sep.is_empty() {
+                        ret.push_str(&call);
+                    } else {
+                        ret.push_str(&call.replace('\n', sep));
+                    }
+                }
+                Link::Field(name) => {
+                    ret.push_str(sep);
+                    ret.push_str(&format!(".{}", name));
+                }
+                Link::Await => {
+                    ret.push_str(sep);
+                    ret.push_str(".await");
+                }
+                Link::Try => ret.push('?'),
+            }
+        }
+
+        ret
+    }
+}
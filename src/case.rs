@@ -0,0 +1,50 @@
+/// Converts a `PascalCase` or `camelCase` name to `snake_case`, keeping
+/// acronyms together (e.g. `HTTPError` becomes `http_error`).
+///
+/// # Arguments
+///
+/// * `name` - The name to convert.
+pub(crate) fn to_snake_case(name: &str) -> String {
+    let chars: Vec<char> = name.chars().collect();
+    let mut ret = String::new();
+
+    for (i, c) in chars.iter().enumerate() {
+        if c.is_uppercase() && i != 0 {
+            let prev = chars[i - 1];
+            let next_is_lower = matches!(chars.get(i + 1), Some(n) if n.is_lowercase());
+
+            if prev != '_'
+                && (prev.is_lowercase()
+                    || prev.is_numeric()
+                    || (prev.is_uppercase() && next_is_lower))
+            {
+                ret.push('_');
+            }
+        }
+
+        ret.extend(c.to_lowercase());
+    }
+
+    ret
+}
+
+/// Converts a `snake_case`, `kebab-case`, or space separated name to
+/// `PascalCase` (e.g. `add-user` becomes `AddUser`).
+///
+/// # Arguments
+///
+/// * `name` - The name to convert.
+pub(crate) fn to_pascal_case(name: &str) -> String {
+    let mut ret = String::new();
+
+    for part in name.split(&['_', '-', ' '][..]) {
+        let mut chars = part.chars();
+
+        if let Some(first) = chars.next() {
+            ret.extend(first.to_uppercase());
+            ret.extend(chars);
+        }
+    }
+
+    ret
+}
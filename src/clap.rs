@@ -0,0 +1,599 @@
+//! Scaffolding for command line interfaces using the clap derive API.
+//!
+//! ```
+//! use rust_codegen::clap::Cli;
+//! use rust_codegen::Scope;
+//!
+//! let mut cli = Cli::new("Cli");
+//! cli.about("Manages the server.");
+//!
+//! cli.new_subcommand("serve")
+//!     .about("Starts the server.")
+//!     .arg("port", "u16")
+//!     .long()
+//!     .default_value("8080");
+//!
+//! let mut scope = Scope::new();
+//! cli.push_to(&mut scope);
+//! ```
+
+use std::borrow::Cow;
+
+use crate::block::Block;
+use crate::case::to_pascal_case;
+use crate::field::Field;
+use crate::function::Function;
+use crate::literal::quote_str;
+use crate::scope::Scope;
+use crate::variant::Variant;
+
+use crate::r#enum::Enum;
+use crate::r#struct::Struct;
+use crate::r#type::Type;
+
+/// Defines an argument of a subcommand.
+#[derive(Debug, Clone)]
+pub struct Arg {
+    /// The name of the argument, e.g. `dry-run`.
+    name: Cow<'static, str>,
+    /// The type of the argument.
+    ty: Type,
+    /// The help text of the argument.
+    help: Option<Cow<'static, str>>,
+    /// Whether the argument is passed as `--name`.
+    long: bool,
+    /// The short flag of the argument, e.g. `p` for `-p`.
+    short: Option<char>,
+    /// The default value of the argument.
+    default_value: Option<Cow<'static, str>>,
+}
+
+impl Arg {
+    /// Return a new positional argument with the given name and type.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the argument.
+    /// * `ty` - The type of the argument.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::Arg;
+    ///
+    /// let path_arg = Arg::new("path", "std::path::PathBuf");
+    /// ```
+    pub fn new<N, T>(name: N, ty: T) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+        T: Into<Type>,
+    {
+        Arg {
+            name: name.into(),
+            ty: ty.into(),
+            help: None,
+            long: false,
+            short: None,
+            default_value: None,
+        }
+    }
+
+    /// Set the help text of the argument, written as its doc comment.
+    ///
+    /// # Arguments
+    ///
+    /// * `help` - The help text of the argument.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::Arg;
+    ///
+    /// let mut path_arg = Arg::new("path", "std::path::PathBuf");
+    /// path_arg.help("The file to read.");
+    /// ```
+    pub fn help<T>(&mut self, help: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.help = Some(help.into());
+        self
+    }
+
+    /// Pass the argument as `--name` instead of positionally.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::Arg;
+    ///
+    /// let mut port_arg = Arg::new("port", "u16");
+    /// port_arg.long();
+    /// ```
+    pub fn long(&mut self) -> &mut Self {
+        self.long = true;
+        self
+    }
+
+    /// Set the short flag of the argument, e.g. `p` for `-p`.
+    ///
+    /// # Arguments
+    ///
+    /// * `short` - The short flag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::Arg;
+    ///
+    /// let mut port_arg = Arg::new("port", "u16");
+    /// port_arg.short('p');
+    /// ```
+    pub fn short(&mut self, short: char) -> &mut Self {
+        self.short = Some(short);
+        self
+    }
+
+    /// Set the default value of the argument as it would be passed on the
+    /// command line.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The default value.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::Arg;
+    ///
+    /// let mut port_arg = Arg::new("port", "u16");
+    /// port_arg.long().default_value("8080");
+    /// ```
+    pub fn default_value<T>(&mut self, value: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.default_value = Some(value.into());
+        self
+    }
+
+    /// Returns the name of the field holding the argument.
+    fn field_name(&self) -> String {
+        self.name.replace('-', "_")
+    }
+
+    /// Returns the argument as a field with its help text and `#[arg(...)]`
+    /// attribute.
+    fn to_field(&self) -> Field {
+        let mut field = Field::new(self.field_name(), self.ty.clone());
+
+        if let Some(ref help) = self.help {
+            field.doc_str(help.clone());
+        }
+
+        let mut args = vec![];
+
+        if self.long {
+            args.push("long".to_string());
+        }
+
+        if let Some(short) = self.short {
+            args.push(format!("short = '{}'", short));
+        }
+
+        if let Some(ref value) = self.default_value {
+            args.push(format!("default_value = {}", quote_str(value)));
+        }
+
+        if !args.is_empty() {
+            field.push_annotation(format!("#[arg({})]", args.join(", ")));
+        }
+
+        field
+    }
+}
+
+/// Defines a subcommand, generated as a variant of the subcommand enum.
+#[derive(Debug, Clone)]
+pub struct Subcommand {
+    /// The name of the subcommand, e.g. `add-user`.
+    name: Cow<'static, str>,
+    /// The description of the subcommand.
+    about: Option<Cow<'static, str>>,
+    /// The arguments of the subcommand.
+    args: Vec<Arg>,
+}
+
+impl Subcommand {
+    /// Return a new subcommand with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the subcommand as typed on the command line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::Subcommand;
+    ///
+    /// let serve_cmd = Subcommand::new("serve");
+    /// ```
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        Subcommand {
+            name: name.into(),
+            about: None,
+            args: vec![],
+        }
+    }
+
+    /// Set the description of the subcommand, written as the doc comment of
+    /// its variant.
+    ///
+    /// # Arguments
+    ///
+    /// * `about` - The description of the subcommand.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::Subcommand;
+    ///
+    /// let mut serve_cmd = Subcommand::new("serve");
+    /// serve_cmd.about("Starts the server.");
+    /// ```
+    pub fn about<T>(&mut self, about: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.about = Some(about.into());
+        self
+    }
+
+    /// Add a new argument to the subcommand, returning a mutable reference to
+    /// it.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the argument.
+    /// * `ty` - The type of the argument.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::Subcommand;
+    ///
+    /// let mut serve_cmd = Subcommand::new("serve");
+    /// serve_cmd.arg("port", "u16").long();
+    /// ```
+    pub fn arg<N, T>(&mut self, name: N, ty: T) -> &mut Arg
+    where
+        N: Into<Cow<'static, str>>,
+        T: Into<Type>,
+    {
+        self.push_arg(Arg::new(name, ty));
+        self.args.last_mut().unwrap()
+    }
+
+    /// Push an argument to the subcommand.
+    ///
+    /// # Arguments
+    ///
+    /// * `arg` - The argument to push.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::{Arg, Subcommand};
+    ///
+    /// let mut serve_cmd = Subcommand::new("serve");
+    /// serve_cmd.push_arg(Arg::new("port", "u16"));
+    /// ```
+    pub fn push_arg(&mut self, arg: Arg) -> &mut Self {
+        self.args.push(arg);
+        self
+    }
+
+    /// Returns the name of the variant of the subcommand.
+    fn variant_name(&self) -> String {
+        to_pascal_case(&self.name)
+    }
+
+    /// Returns the variant of the subcommand.
+    fn to_variant(&self) -> Variant {
+        let mut variant = Variant::new(self.variant_name());
+
+        if let Some(ref about) = self.about {
+            variant.doc(about.clone());
+        }
+
+        for arg in &self.args {
+            variant.push_field(arg.to_field());
+        }
+
+        variant
+    }
+
+    /// Returns the pattern matching the variant of the subcommand and
+    /// binding its arguments.
+    fn to_pattern(&self, enum_name: &str) -> String {
+        let mut pattern = format!("{}::{}", enum_name, self.variant_name());
+
+        if !self.args.is_empty() {
+            let names: Vec<String> = self.args.iter().map(|arg| arg.field_name()).collect();
+            pattern.push_str(&format!(" {{ {} }}", names.join(", ")));
+        }
+
+        pattern
+    }
+}
+
+/// Defines a command line interface made of subcommands, generated as a
+/// `clap::Parser` struct, a `clap::Subcommand` enum, and a function
+/// dispatching over the subcommands.
+#[derive(Debug, Clone)]
+pub struct Cli {
+    /// The name of the parser struct.
+    name: Cow<'static, str>,
+    /// The name of the subcommand enum.
+    command_name: Cow<'static, str>,
+    /// The description of the command line interface.
+    about: Option<Cow<'static, str>>,
+    /// The visibility of the generated items.
+    vis: Option<String>,
+    /// The subcommands.
+    subcommands: Vec<Subcommand>,
+}
+
+impl Cli {
+    /// Return a new command line interface whose parser struct has the given
+    /// name. The subcommand enum is named `Command`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the parser struct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::Cli;
+    ///
+    /// let cli = Cli::new("Cli");
+    /// ```
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        Cli {
+            name: name.into(),
+            command_name: "Command".into(),
+            about: None,
+            vis: None,
+            subcommands: vec![],
+        }
+    }
+
+    /// Set the name of the subcommand enum.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the subcommand enum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::Cli;
+    ///
+    /// let mut cli = Cli::new("Cli");
+    /// cli.command_name("Action");
+    /// ```
+    pub fn command_name<T>(&mut self, name: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.command_name = name.into();
+        self
+    }
+
+    /// Set the description of the command line interface, written as the
+    /// doc comment of the parser struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `about` - The description.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::Cli;
+    ///
+    /// let mut cli = Cli::new("Cli");
+    /// cli.about("Manages the server.");
+    /// ```
+    pub fn about<T>(&mut self, about: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.about = Some(about.into());
+        self
+    }
+
+    /// Set the visibility of the generated items.
+    ///
+    /// # Arguments
+    ///
+    /// * `vis` - The visibility of the generated items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::Cli;
+    ///
+    /// let mut cli = Cli::new("Cli");
+    /// cli.vis("pub");
+    /// ```
+    pub fn vis(&mut self, vis: &str) -> &mut Self {
+        self.vis = Some(vis.to_string());
+        self
+    }
+
+    /// Add a new subcommand, returning a mutable reference to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the subcommand as typed on the command line.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::Cli;
+    ///
+    /// let mut cli = Cli::new("Cli");
+    /// cli.new_subcommand("serve").about("Starts the server.");
+    /// ```
+    pub fn new_subcommand<T>(&mut self, name: T) -> &mut Subcommand
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.push_subcommand(Subcommand::new(name));
+        self.subcommands.last_mut().unwrap()
+    }
+
+    /// Push a subcommand.
+    ///
+    /// # Arguments
+    ///
+    /// * `subcommand` - The subcommand to push.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::{Cli, Subcommand};
+    ///
+    /// let mut cli = Cli::new("Cli");
+    /// cli.push_subcommand(Subcommand::new("serve"));
+    /// ```
+    pub fn push_subcommand(&mut self, subcommand: Subcommand) -> &mut Self {
+        self.subcommands.push(subcommand);
+        self
+    }
+
+    /// Returns the `#[derive(Parser)]` struct holding the subcommand.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::Cli;
+    ///
+    /// let cli = Cli::new("Cli");
+    /// let cli_struct = cli.to_struct();
+    /// ```
+    pub fn to_struct(&self) -> Struct {
+        let mut item = Struct::new(self.name.clone());
+        item.derive("Debug").derive("Parser");
+
+        if let Some(ref vis) = self.vis {
+            item.vis(vis);
+        }
+
+        if let Some(ref about) = self.about {
+            item.doc(about.clone());
+        }
+
+        let mut command = Field::new("command", self.command_name.clone());
+        command.push_annotation("#[command(subcommand)]");
+        item.push_field(command);
+
+        item
+    }
+
+    /// Returns the `#[derive(Subcommand)]` enum with a variant per
+    /// subcommand.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::Cli;
+    ///
+    /// let mut cli = Cli::new("Cli");
+    /// cli.new_subcommand("serve");
+    ///
+    /// let command_enum = cli.to_enum();
+    /// ```
+    pub fn to_enum(&self) -> Enum {
+        let mut item = Enum::new(self.command_name.clone());
+        item.derive("Debug").derive("Subcommand");
+
+        if let Some(ref vis) = self.vis {
+            item.vis(vis);
+        }
+
+        for subcommand in &self.subcommands {
+            item.push_variant(subcommand.to_variant());
+        }
+
+        item
+    }
+
+    /// Returns the `run` function matching the parsed subcommand, with a
+    /// `todo!()` arm per subcommand binding its arguments.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::Cli;
+    ///
+    /// let mut cli = Cli::new("Cli");
+    /// cli.new_subcommand("serve");
+    ///
+    /// let run_fn = cli.dispatch_fn();
+    /// ```
+    pub fn dispatch_fn(&self) -> Function {
+        let mut func = Function::new("run");
+        func.arg("cli", self.name.clone());
+
+        if let Some(ref vis) = self.vis {
+            func.vis(vis);
+        }
+
+        let mut block = Block::new("match cli.command");
+
+        for subcommand in &self.subcommands {
+            block.line(format!(
+                "{} => todo!(),",
+                subcommand.to_pattern(&self.command_name)
+            ));
+        }
+
+        func.push_block(block);
+        func
+    }
+
+    /// Push the `use clap::{Parser, Subcommand};` imports, the parser
+    /// struct, the subcommand enum, and the `run` function to the given
+    /// scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - The scope to push the command line interface to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::clap::Cli;
+    /// use rust_codegen::Scope;
+    ///
+    /// let mut scope = Scope::new();
+    /// Cli::new("Cli").push_to(&mut scope);
+    /// ```
+    pub fn push_to(&self, scope: &mut Scope) {
+        scope.import("clap", "Parser");
+        scope.import("clap", "Subcommand");
+
+        scope.push_struct(self.to_struct());
+        scope.push_enum(self.to_enum());
+        scope.push_fn(self.dispatch_fn());
+    }
+}
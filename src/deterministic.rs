@@ -0,0 +1,129 @@
+use std::path::PathBuf;
+
+/// Returns the given rendered code normalized for reproducible builds: line
+/// endings are `\n`, lines have no trailing whitespace, and the absolute
+/// paths of the build in comments are made relative.
+///
+/// # Arguments
+///
+/// * `rendered` - The rendered code.
+///
+/// # Panics
+///
+/// If a comment contains a timestamp, e.g. `2024-01-31 12:00`.
+pub(crate) fn normalize(rendered: &str) -> String {
+    // The build directory may be under the manifest directory, which may be
+    // the current directory, so the most specific root is replaced first.
+    let mut roots: Vec<(String, &str)> = vec![];
+
+    if let Some(dir) = std::env::var_os("OUT_DIR") {
+        roots.extend(path_prefix(PathBuf::from(dir)).map(|root| (root, "$OUT_DIR/")));
+    }
+
+    if let Some(dir) = std::env::var_os("CARGO_MANIFEST_DIR") {
+        roots.extend(path_prefix(PathBuf::from(dir)).map(|root| (root, "")));
+    }
+
+    if let Ok(dir) = std::env::current_dir() {
+        roots.extend(path_prefix(dir).map(|root| (root, "")));
+    }
+
+    let lines: Vec<String> = rendered
+        .split('\n')
+        .map(|line| {
+            let mut line = line.trim_end().to_string();
+
+            if line.trim_start().starts_with("//") {
+                if let Some(timestamp) = find_timestamp(&line) {
+                    panic!(
+                        "deterministic output must not contain timestamps, found `{}` in `{}`",
+                        timestamp,
+                        line.trim()
+                    );
+                }
+
+                for (root, replacement) in &roots {
+                    line = replace_root(&line, root, replacement);
+                }
+            }
+
+            line
+        })
+        .collect();
+
+    lines.join("\n")
+}
+
+/// Returns the given directory as a prefix of the paths under it, or `None`
+/// if the directory is too close to the root of the file system, e.g. `/`
+/// or `C:/`, to be told apart from the other slashes of a comment.
+fn path_prefix(dir: PathBuf) -> Option<String> {
+    /// The length of the shortest directory that is replaced, e.g. `/src`.
+    const MIN_LEN: usize = 4;
+
+    let dir = dir.to_string_lossy().replace('\\', "/");
+    let dir = dir.trim_end_matches('/');
+
+    let is_drive = dir.len() == 2 && dir.ends_with(':');
+
+    if dir.len() < MIN_LEN || is_drive {
+        return None;
+    }
+
+    Some(format!("{}/", dir))
+}
+
+/// Replaces every occurrence of the given root in the line that starts a
+/// path, i.e. that isn't preceded by a character of a path or a URL, e.g.
+/// `/src/` in `/home/src/` or in `https://example.com/src/`.
+///
+/// # Arguments
+///
+/// * `line` - The line to replace the root in.
+/// * `root` - The root, ending with `/`.
+/// * `replacement` - The text to replace the root with.
+fn replace_root(line: &str, root: &str, replacement: &str) -> String {
+    let mut ret = String::with_capacity(line.len());
+    let mut last = 0;
+
+    for (start, _) in line.match_indices(root) {
+        let in_path = matches!(
+            line[..start].chars().next_back(),
+            Some(c) if c.is_alphanumeric() || "/\\.:_-~".contains(c)
+        );
+
+        if in_path {
+            continue;
+        }
+
+        ret.push_str(&line[last..start]);
+        ret.push_str(replacement);
+        last = start + root.len();
+    }
+
+    ret.push_str(&line[last..]);
+    ret
+}
+
+/// Returns the first timestamp in the given line, e.g. `2024-01-31T12:00`,
+/// if any.
+fn find_timestamp(line: &str) -> Option<&str> {
+    // `dddd-dd-dd` followed by `T` or a space and `dd:dd`.
+    const SHAPE: &[u8] = b"dddd-dd-dd_dd:dd";
+
+    let bytes = line.as_bytes();
+
+    (0..bytes.len().saturating_sub(SHAPE.len() - 1))
+        .find(|&start| {
+            SHAPE.iter().enumerate().all(|(i, &expected)| {
+                let c = bytes[start + i];
+
+                match expected {
+                    b'd' => c.is_ascii_digit(),
+                    b'_' => c == b'T' || c == b' ',
+                    _ => c == expected,
+                }
+            })
+        })
+        .map(|start| &line[start..start + SHAPE.len()])
+}
@@ -0,0 +1,95 @@
+use std::fmt;
+
+use crate::semver::ApiShape;
+
+/// Defines how an item changed between two scopes.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChangeKind {
+    /// The item only exists in the new scope.
+    Added,
+    /// The item only exists in the old scope.
+    Removed,
+    /// The item exists in both scopes but renders differently.
+    Changed,
+}
+
+impl fmt::Display for ChangeKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match *self {
+            ChangeKind::Added => "added",
+            ChangeKind::Removed => "removed",
+            ChangeKind::Changed => "changed",
+        };
+
+        f.write_str(name)
+    }
+}
+
+/// Defines a change to a single item between two scopes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemChange {
+    /// How the item changed.
+    pub change: ChangeKind,
+    /// The kind of the item, e.g. `struct` or `fn`.
+    pub kind: &'static str,
+    /// The path of the item from the root of the scope, e.g.
+    /// `handlers::login`.
+    pub path: String,
+    /// The rendered item in the old scope, if it exists there.
+    pub before: Option<String>,
+    /// The rendered item in the new scope, if it exists there.
+    pub after: Option<String>,
+    /// The public API of the item in the old scope, if it exists there.
+    pub(crate) before_api: Option<ApiShape>,
+    /// The public API of the item in the new scope, if it exists there.
+    pub(crate) after_api: Option<ApiShape>,
+}
+
+/// Defines the item-level changes between two scopes, returned by
+/// [`Scope::diff`].
+///
+/// [`Scope::diff`]: crate::Scope::diff
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScopeDiff {
+    /// The changes, in the order of the items of the old scope followed by
+    /// the items added in the new scope.
+    pub changes: Vec<ItemChange>,
+}
+
+impl ScopeDiff {
+    /// Returns whether the scopes have no item-level changes.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    /// Returns the added items.
+    pub fn added(&self) -> impl Iterator<Item = &ItemChange> {
+        self.of(ChangeKind::Added)
+    }
+
+    /// Returns the removed items.
+    pub fn removed(&self) -> impl Iterator<Item = &ItemChange> {
+        self.of(ChangeKind::Removed)
+    }
+
+    /// Returns the changed items.
+    pub fn changed(&self) -> impl Iterator<Item = &ItemChange> {
+        self.of(ChangeKind::Changed)
+    }
+
+    /// Returns the changes of the given kind.
+    fn of(&self, change: ChangeKind) -> impl Iterator<Item = &ItemChange> {
+        self.changes.iter().filter(move |c| c.change == change)
+    }
+}
+
+impl fmt::Display for ScopeDiff {
+    /// Formats the diff as one line per change, e.g. `added fn api::login`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(f, "{} {} {}", change.change, change.kind, change.path)?;
+        }
+
+        Ok(())
+    }
+}
@@ -1,12 +1,40 @@
+use std::borrow::Cow;
 use std::fmt::{self, Write};
 
 use crate::formatter::Formatter;
+use crate::function::Function;
+
+/// Defines how rustdoc treats the code block of an example.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DocTest {
+    /// The example is compiled and run.
+    Run,
+    /// The example is compiled but not run, e.g. because it needs a network.
+    NoRun,
+    /// The example is neither compiled nor run.
+    Ignore,
+}
+
+impl DocTest {
+    /// Returns the opening fence of the code block.
+    fn fence(&self) -> &'static str {
+        match *self {
+            DocTest::Run => "```",
+            DocTest::NoRun => "```no_run",
+            DocTest::Ignore => "```ignore",
+        }
+    }
+}
 
 /// Used to apply documentation to the module, trait, etc.
 #[derive(Debug, Clone)]
 pub struct Docs {
     /// The documentation to add.
-    docs: String,
+    docs: Cow<'static, str>,
+    /// The code of the examples and how they are tested.
+    examples: Vec<(DocTest, Cow<'static, str>)>,
+    /// Whether the documentation is written as is instead of being escaped.
+    raw: bool,
 }
 
 impl Docs {
@@ -15,10 +43,206 @@ impl Docs {
     /// # Arguments
     /// 
     /// * `docs` - The docs to add.
-    pub fn new(docs: &str) -> Self {
+    pub fn new<T>(docs: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
         Docs {
-            docs: docs.to_string(),
+            docs: docs.into(),
+            examples: vec![],
+            raw: false,
+        }
+    }
+
+    /// Replaces the text of the documentation, keeping any examples.
+    pub(crate) fn set_text<T>(&mut self, docs: T, raw: bool)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.docs = docs.into();
+        self.raw = raw;
+    }
+
+    /// Set whether the documentation is written as is. By default, text that
+    /// would break rustdoc is escaped: `[`, `]`, `<`, and `>` outside of code
+    /// spans, unmatched backticks, and bare URLs, which are wrapped in `<>`.
+    /// Code blocks without a language are marked as `text` so that they
+    /// aren't compiled as doctests.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - Indicates whether to skip escaping or not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Docs;
+    ///
+    /// let mut docs = Docs::new("See [`Client`].");
+    /// docs.raw(true);
+    /// ```
+    pub fn raw(&mut self, raw: bool) -> &mut Self {
+        self.raw = raw;
+        self
+    }
+
+    /// Adds an example, rendered as a code block under an `# Examples`
+    /// heading after the documentation.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The code of the example.
+    /// * `mode` - Whether rustdoc should run, only compile, or ignore it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{DocTest,Docs};
+    ///
+    /// let mut docs = Docs::new("Connects to the server.");
+    /// docs.example("let client = connect(\"localhost\");", DocTest::NoRun);
+    /// ```
+    pub fn example<T>(&mut self, code: T, mode: DocTest) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.examples.push((mode, code.into()));
+        self
+    }
+
+    /// Returns a usage example of the given function built from its
+    /// signature. The arguments are taken from a hidden wrapper function
+    /// with the same signature. Methods are called on a `value` binding,
+    /// which the rest of the example has to provide, and the function isn't
+    /// imported, so the example should be added with [`DocTest::Ignore`]
+    /// unless it is completed. See [`Docs::example_from_fn_in`] for an
+    /// example that compiles as is.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function to show the usage of.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Docs,Function};
+    ///
+    /// let mut parse_fn = Function::new("parse");
+    /// parse_fn.arg("input", "&str").ret("u32");
+    ///
+    /// assert_eq!(
+    ///     Docs::example_from_fn(&parse_fn),
+    ///     "# fn example(input: &str) {\nlet result = parse(input);\n# }"
+    /// );
+    /// ```
+    pub fn example_from_fn(func: &Function) -> String {
+        func.usage_example(None, None)
+    }
+
+    /// Returns a usage example of the given function like
+    /// [`Docs::example_from_fn`], which imports everything from the module
+    /// declaring the function and creates the `value` binding of methods
+    /// with the given expression, so that the example compiles as is.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function to show the usage of.
+    /// * `path` - The path of the module declaring the function, e.g.
+    ///   `my_crate::parser`.
+    /// * `receiver` - The expression creating the value the method is called
+    ///   on, e.g. `Parser::new()`, or `None` for free functions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Docs,Function};
+    ///
+    /// let mut reset_fn = Function::new("reset");
+    /// reset_fn.arg_mut_self();
+    ///
+    /// assert_eq!(
+    ///     Docs::example_from_fn_in(&reset_fn, "my_crate::counter", Some("Counter::new()")),
+    ///     "# use my_crate::counter::*;\nlet mut value = Counter::new();\nvalue.reset();"
+    /// );
+    /// ```
+    pub fn example_from_fn_in(func: &Function, path: &str, receiver: Option<&str>) -> String {
+        func.usage_example(Some(path), receiver)
+    }
+
+    /// Returns a markdown bullet list of the given items, to use as part of
+    /// the documentation. Continuation lines of multi-line items are
+    /// indented so that they stay part of the item.
+    ///
+    /// # Arguments
+    ///
+    /// * `items` - The items of the list.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Docs,Struct};
+    ///
+    /// let mut pet_struct = Struct::new("Pet");
+    /// pet_struct.doc(format!(
+    ///     "A pet.\n\nStatuses:\n{}",
+    ///     Docs::bullet_list(&["available", "sold"])
+    /// ));
+    /// ```
+    pub fn bullet_list<I, T>(items: I) -> String
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let mut ret = String::new();
+
+        for item in items {
+            let item = item.as_ref().replace("\r\n", "\n");
+
+            for (i, line) in item.lines().enumerate() {
+                if i == 0 {
+                    ret.push_str("* ");
+                } else if !line.is_empty() {
+                    ret.push_str("  ");
+                }
+
+                ret.push_str(line);
+                ret.push('\n');
+            }
         }
+
+        ret.pop();
+        ret
+    }
+
+    /// Returns a fenced markdown code block, to use as part of the
+    /// documentation. Code blocks without a language are marked as `text`
+    /// when rendered so that rustdoc doesn't run them as doctests.
+    ///
+    /// # Arguments
+    ///
+    /// * `lang` - The language of the code, e.g. `json`, or empty for none.
+    /// * `code` - The code in the block.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Docs;
+    ///
+    /// assert_eq!(Docs::code_block("json", "{}"), "```json\n{}\n```");
+    /// ```
+    pub fn code_block(lang: &str, code: &str) -> String {
+        format!("```{}\n{}\n```", lang, code.replace("\r\n", "\n").trim_end())
+    }
+
+    /// Returns an estimate of the rendered length of the documentation.
+    pub(crate) fn estimated_len(&self) -> usize {
+        let examples: usize = self
+            .examples
+            .iter()
+            .map(|(_, code)| code.len() + code.lines().count() * 4 + 16)
+            .sum();
+
+        self.docs.len() + self.docs.lines().count() * 4 + examples
     }
 
     /// Formats the documentation using the provided formatter. This will also 
@@ -28,10 +252,154 @@ impl Docs {
     /// 
     /// * `fmt` - The formatter to use.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        for line in self.docs.lines() {
-            write!(fmt, "/// {}\n", line)?;
+        // Normalize Windows and old Mac line endings so that no `\r` ends up
+        // in the output.
+        let docs = self.docs.replace("\r\n", "\n").replace('\r', "\n");
+
+        if self.raw {
+            for line in docs.lines() {
+                fmt_line(line, fmt)?;
+            }
+        } else {
+            let mut in_fence = false;
+
+            for line in docs.lines() {
+                if line.trim_start().starts_with("```") {
+                    if !in_fence && line.trim() == "```" {
+                        fmt_line(&format!("{}text", line.trim_end()), fmt)?;
+                    } else {
+                        fmt_line(line, fmt)?;
+                    }
+
+                    in_fence = !in_fence;
+                } else if in_fence {
+                    fmt_line(line, fmt)?;
+                } else {
+                    fmt_line(&escape_markdown(line), fmt)?;
+                }
+            }
+
+            if in_fence {
+                write!(fmt, "/// ```\n")?;
+            }
+        }
+
+        if !self.examples.is_empty() {
+            if !docs.is_empty() {
+                write!(fmt, "///\n")?;
+            }
+
+            write!(fmt, "/// # Examples\n")?;
+
+            for (mode, code) in &self.examples {
+                write!(fmt, "///\n")?;
+                write!(fmt, "/// {}\n", mode.fence())?;
+
+                for line in code.lines() {
+                    fmt_line(line, fmt)?;
+                }
+
+                write!(fmt, "/// ```\n")?;
+            }
         }
 
         Ok(())
     }
 }
+
+/// Writes a line of documentation, leaving blank lines as `///`.
+fn fmt_line(line: &str, fmt: &mut Formatter<'_>) -> fmt::Result {
+    if line.trim().is_empty() {
+        write!(fmt, "///\n")
+    } else {
+        write!(fmt, "/// {}\n", line)
+    }
+}
+
+/// Escapes a line of documentation so that rustdoc renders it as written.
+fn escape_markdown(line: &str) -> String {
+    let mut ticks = line.matches('`').count();
+    let stray_tick = ticks % 2 == 1;
+
+    let mut ret = String::with_capacity(line.len());
+    let mut in_code = false;
+    let mut rest = line;
+
+    while let Some(c) = rest.chars().next() {
+        if !in_code {
+            if let Some(len) = link_len(rest) {
+                ret.push_str(&rest[..len]);
+                rest = &rest[len..];
+                continue;
+            }
+
+            if is_url(rest) {
+                let end = rest
+                    .find(|c: char| c.is_whitespace() || c == '<' || c == '>')
+                    .unwrap_or(rest.len());
+                let url = rest[..end]
+                    .trim_end_matches(&['.', ',', ';', ':', '!', '?', ')'][..]);
+
+                ret.push('<');
+                ret.push_str(url);
+                ret.push('>');
+                rest = &rest[url.len()..];
+                continue;
+            }
+        }
+
+        match c {
+            '`' => {
+                ticks -= 1;
+
+                if ticks == 0 && stray_tick {
+                    ret.push_str("\\`");
+                } else {
+                    in_code = !in_code;
+                    ret.push('`');
+                }
+            }
+            '[' | ']' | '<' | '>' if !in_code => {
+                ret.push('\\');
+                ret.push(c);
+            }
+            c => ret.push(c),
+        }
+
+        rest = &rest[c.len_utf8()..];
+    }
+
+    ret
+}
+
+/// Returns whether the text starts with an `http` or `https` URL.
+fn is_url(text: &str) -> bool {
+    text.starts_with("http://") || text.starts_with("https://")
+}
+
+/// Returns the length of the autolink, e.g. `<https://example.com>`, or the
+/// inline link to a URL, e.g. `[docs](https://example.com)`, at the start of
+/// the text, which are kept as is.
+fn link_len(text: &str) -> Option<usize> {
+    if text.starts_with('<') && is_url(&text[1..]) {
+        let end = text.find(|c: char| c.is_whitespace() || c == '>')?;
+        return if text[end..].starts_with('>') {
+            Some(end + 1)
+        } else {
+            None
+        };
+    }
+
+    if text.starts_with('[') {
+        let label_end = text.find("](")?;
+
+        if text[1..label_end].contains(&['[', '`'][..]) || !is_url(&text[label_end + 2..]) {
+            return None;
+        }
+
+        let end = label_end + text[label_end..].find(')')?;
+        return Some(end + 1);
+    }
+
+    None
+}
@@ -1,9 +1,19 @@
+use std::borrow::Cow;
 use std::fmt;
 
-use crate::formatter::Formatter;
+use crate::block::Block;
+use crate::case::to_snake_case;
+use crate::docs::DocTest;
+use crate::fields::Fields;
+use crate::formatter::{fmt_to_code_string, Formatter};
+use crate::metadata::Metadata;
+use crate::policy::Policy;
+use crate::serde::ContainerAttrs;
 use crate::type_def::TypeDef;
 use crate::variant::Variant;
+use crate::visibility::Visibility;
 
+use crate::r#impl::Impl;
 use crate::r#type::Type;
 
 /// Defines an enumeration.
@@ -27,7 +37,10 @@ impl Enum {
     /// 
     /// let foo_enum = Enum::new("Foo");
     /// ```
-    pub fn new(name: &str) -> Self {
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
         Enum {
             type_def: TypeDef::new(name),
             variants: vec![],
@@ -48,6 +61,27 @@ impl Enum {
         &self.type_def.ty
     }
 
+    /// Returns the metadata of the enum, which is kept when it is cloned
+    /// and never rendered.
+    pub fn metadata(&self) -> &Metadata {
+        self.type_def.metadata()
+    }
+
+    /// Returns a mutable reference to the metadata of the enum, e.g. to tag
+    /// it in a multi-pass generator.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Enum;
+    /// 
+    /// let mut foo_enum = Enum::new("Foo");
+    /// foo_enum.metadata_mut().set("schema", "foo.json");
+    /// ```
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        self.type_def.metadata_mut()
+    }
+
     /// Set the enum's visibility.
     /// 
     /// # Arguments
@@ -123,11 +157,76 @@ impl Enum {
     /// let mut foo_enum = Enum::new("Foo");
     /// foo_enum.doc("Sample Foo enum documentation");
     /// ```
-    pub fn doc(&mut self, docs: &str) -> &mut Self {
+    pub fn doc<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.type_def.doc(docs);
         self
     }
 
+    /// Set the enum documentation, written as is without escaping
+    /// markdown, e.g. for intra-doc links.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `docs` - The documentation to set for the enum.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Enum;
+    /// 
+    /// let mut foo_enum = Enum::new("Foo");
+    /// foo_enum.doc_raw("See [`Bar`].");
+    /// ```
+    pub fn doc_raw<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.type_def.doc_raw(docs);
+        self
+    }
+
+    /// Add an example to the enum documentation.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `code` - The code of the example.
+    /// * `mode` - Whether rustdoc should run, only compile, or ignore it.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{DocTest,Enum};
+    /// 
+    /// let mut foo_enum = Enum::new("Foo");
+    /// foo_enum.doc_example("let foo = Foo::default();", DocTest::Run);
+    /// ```
+    pub fn doc_example<T>(&mut self, code: T, mode: DocTest) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.type_def.doc_example(code, mode);
+        self
+    }
+
+    /// Opt out of the default derives of the scope, see
+    /// `Scope::default_derives`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Enum;
+    /// 
+    /// let mut foo_enum = Enum::new("Foo");
+    /// foo_enum.no_default_derives();
+    /// ```
+    pub fn no_default_derives(&mut self) -> &mut Self {
+        self.type_def.no_default_derives();
+        self
+    }
+
     /// Add a new type that the enum should derive.
     /// 
     /// # Arguments
@@ -142,11 +241,41 @@ impl Enum {
     /// let mut foo_enum = Enum::new("Foo");
     /// foo_enum.derive("Debug");
     /// ```
-    pub fn derive(&mut self, name: &str) -> &mut Self {
+    pub fn derive<T>(&mut self, name: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.type_def.derive(name);
         self
     }
 
+    /// Add a new type that the enum should derive along with the helper
+    /// attributes of its derive macro, which are kept directly after the
+    /// `#[derive(...)]` attribute.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the type to derive.
+    /// * `helpers` - The helper attributes, without the surrounding `#[...]`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Enum;
+    /// 
+    /// let mut foo_enum = Enum::new("Foo");
+    /// foo_enum.derive_with("serde::Serialize", ["serde(rename_all = \"camelCase\")"]);
+    /// ```
+    pub fn derive_with<T, I, H>(&mut self, name: T, helpers: I) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+        I: IntoIterator<Item = H>,
+        H: Into<Cow<'static, str>>,
+    {
+        self.type_def.derive_with(name, helpers);
+        self
+    }
+
     /// Specify lint attribute to supress a warning or error.
     /// 
     /// # Arguments
@@ -161,7 +290,10 @@ impl Enum {
     /// let mut foo_enum = Enum::new("Foo");
     /// foo_enum.allow("dead_code");
     /// ```
-    pub fn allow(&mut self, allow: &str) -> &mut Self {
+    pub fn allow<T>(&mut self, allow: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.type_def.allow(allow);
         self
     }
@@ -180,11 +312,107 @@ impl Enum {
     /// let mut foo_enum = Enum::new("Foo");
     /// foo_enum.repr("C");
     /// ```
-    pub fn repr(&mut self, repr: &str) -> &mut Self {
+    pub fn repr<T>(&mut self, repr: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.type_def.repr(repr);
         self
     }
 
+    /// Adds an attribute to the enum, without the surrounding `#[...]`
+    /// (e.g. `"serde(tag = \"type\")"`). An attribute that is already
+    /// wrapped, e.g. `"#[repr(u8)]"`, is accepted as well.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attribute` - The attribute to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Enum;
+    /// 
+    /// let mut foo_enum = Enum::new("Foo");
+    /// foo_enum.attr("serde(tag = \"type\")");
+    /// ```
+    pub fn attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.type_def.attr(attribute);
+        self
+    }
+
+    /// Add a macro or attribute to the enum that is written as is (e.g.
+    /// `"#[pyclass]"`).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `r#macro` - The macro to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Enum;
+    /// 
+    /// let mut foo_enum = Enum::new("Foo");
+    /// foo_enum.r#macro("#[pyclass]");
+    /// ```
+    pub fn r#macro<T>(&mut self, r#macro: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.type_def.r#macro(r#macro);
+        self
+    }
+
+    /// Add a tool attribute to the enum, e.g. `rustfmt::skip` to keep a table
+    /// of aligned discriminants as it is. Tool attributes are rendered before
+    /// the derives and every other attribute.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attribute` - The attribute to add, without the surrounding `#[...]`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Enum;
+    /// 
+    /// let mut foo_enum = Enum::new("Foo");
+    /// foo_enum.tool_attr("rustfmt::skip");
+    /// ```
+    pub fn tool_attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.type_def.attributes.marker(attribute);
+        self
+    }
+
+    /// Adds the given serde container attributes to the enum.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attrs` - The serde attributes to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Enum;
+    /// use rust_codegen::serde::ContainerAttrs;
+    /// 
+    /// let mut foo_enum = Enum::new("Foo");
+    /// foo_enum.serde(ContainerAttrs::new().tag("type"));
+    /// ```
+    pub fn serde(&mut self, attrs: &ContainerAttrs) -> &mut Self {
+        if let Some(meta) = attrs.meta() {
+            self.type_def.attributes.meta(meta);
+        }
+        self
+    }
+
     /// Push a variant to the enum, returning a mutable reference to it.
     /// 
     /// # Arguments
@@ -199,7 +427,10 @@ impl Enum {
     /// let mut foo_enum = Enum::new("Foo");
     /// foo_enum.new_variant("FirstVariant");
     /// ```
-    pub fn new_variant(&mut self, name: &str) -> &mut Variant {
+    pub fn new_variant<T>(&mut self, name: T) -> &mut Variant
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.push_variant(Variant::new(name));
         self.variants.last_mut().unwrap()
     }
@@ -225,6 +456,185 @@ impl Enum {
         self
     }
 
+    /// Returns whether the enum has a C compatible layout.
+    pub(crate) fn is_repr_c(&self) -> bool {
+        self.type_def.is_repr_c()
+    }
+
+    /// Returns the variants of the enum.
+    pub(crate) fn variants(&self) -> &[Variant] {
+        &self.variants
+    }
+
+    /// Returns an impl block with an `is_*`, `as_*`, and `into_*` accessor
+    /// for every variant of the enum. Unit variants only get an `is_*`
+    /// accessor.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Enum;
+    /// 
+    /// let mut shape_enum = Enum::new("Shape");
+    /// shape_enum.new_variant("Circle").tuple("f64");
+    /// 
+    /// let accessors = shape_enum.accessors();
+    /// ```
+    pub fn accessors(&self) -> Impl {
+        let mut accessors = Impl::new(&self.type_def.ty);
+
+        for generic in self.type_def.ty.generics() {
+            accessors.generic(generic.name());
+        }
+
+        let fallback = self.variants.len() > 1;
+
+        for variant in &self.variants {
+            let snake = to_snake_case(variant.name());
+
+            let (pattern, bindings, tys) = match *variant.fields() {
+                Fields::Empty => {
+                    accessors
+                        .new_fn(format!("is_{}", snake))
+                        .vis("pub")
+                        .arg_ref_self()
+                        .ret("bool")
+                        .line(format!("matches!(self, Self::{})", variant.name()));
+                    continue;
+                }
+                Fields::Tuple(ref tys) => {
+                    let bindings: Vec<String> =
+                        (0..tys.len()).map(|i| format!("v{}", i)).collect();
+                    let pattern = format!("Self::{}({})", variant.name(), bindings.join(", "));
+                    let tys: Vec<String> = tys.iter().map(|t| t.fmt_to_string()).collect();
+
+                    (pattern, bindings, tys)
+                }
+                Fields::Named(ref fields) => {
+                    let bindings: Vec<String> =
+                        fields.iter().map(|f| f.name.to_string()).collect();
+                    let pattern =
+                        format!("Self::{} {{ {} }}", variant.name(), bindings.join(", "));
+                    let tys: Vec<String> = fields.iter().map(|f| f.ty.fmt_to_string()).collect();
+
+                    (pattern, bindings, tys)
+                }
+            };
+
+            let wildcard = match *variant.fields() {
+                Fields::Named(_) => format!("Self::{} {{ .. }}", variant.name()),
+                _ => format!("Self::{}(..)", variant.name()),
+            };
+
+            let (ref_ty, owned_ty, value) = if tys.len() == 1 {
+                (format!("&{}", tys[0]), tys[0].clone(), bindings[0].clone())
+            } else {
+                let ref_tys: Vec<String> = tys.iter().map(|t| format!("&{}", t)).collect();
+
+                (
+                    format!("({})", ref_tys.join(", ")),
+                    format!("({})", tys.join(", ")),
+                    format!("({})", bindings.join(", ")),
+                )
+            };
+
+            accessors
+                .new_fn(format!("is_{}", snake))
+                .vis("pub")
+                .arg_ref_self()
+                .ret("bool")
+                .line(format!("matches!(self, {})", wildcard));
+
+            let mut as_body = Block::new("match self");
+            as_body.line(format!("{} => Some({}),", pattern, value));
+            if fallback {
+                as_body.line("_ => None,");
+            }
+
+            accessors
+                .new_fn(format!("as_{}", snake))
+                .vis("pub")
+                .arg_ref_self()
+                .ret(format!("Option<{}>", ref_ty))
+                .push_block(as_body);
+
+            let mut into_body = Block::new("match self");
+            into_body.line(format!("{} => Ok({}),", pattern, value));
+            if fallback {
+                into_body.line("other => Err(other),");
+            }
+
+            accessors
+                .new_fn(format!("into_{}", snake))
+                .vis("pub")
+                .arg_self()
+                .ret(format!("Result<{}, Self>", owned_ty))
+                .push_block(into_body);
+        }
+
+        accessors.mark_accessors();
+        accessors
+    }
+
+    /// Applies the conventions of the given policy to the enum.
+    pub(crate) fn apply_policy(&mut self, policy: &Policy) {
+        self.type_def.derive_defaults(policy.get_enum_derives());
+        self.type_def.attributes.sort_by_kind(policy.get_attribute_order());
+    }
+
+    /// Returns the head of the enum without docs, attributes, or
+    /// visibility, e.g. `enum Foo<T>`.
+    pub(crate) fn signature(&self) -> String {
+        self.type_def.signature("enum", &[])
+    }
+
+    /// Returns the type definition of the enum.
+    pub(crate) fn type_def(&self) -> &TypeDef {
+        &self.type_def
+    }
+
+    /// Calls the given function with every type of the enum and its variants.
+    pub(crate) fn visit_types_mut(&mut self, f: &mut dyn FnMut(&mut Type)) {
+        self.type_def.visit_types_mut(f);
+
+        for variant in &mut self.variants {
+            variant.visit_types_mut(f);
+        }
+    }
+
+    /// Maps the visibility of the enum with the given function.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `f` - The function mapping the visibility.
+    pub(crate) fn map_visibility(&mut self, f: &dyn Fn(Visibility) -> Visibility) {
+        self.type_def.map_visibility(f);
+    }
+
+    /// Returns the enum as a string of code formatted with a fresh
+    /// formatter, e.g. to render it without a scope.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Enum;
+    /// 
+    /// let mut foo_enum = Enum::new("Foo");
+    /// foo_enum.new_variant("Bar");
+    /// 
+    /// assert_eq!(foo_enum.to_code_string(), "enum Foo {\n    Bar,\n}");
+    /// ```
+    pub fn to_code_string(&self) -> String {
+        fmt_to_code_string(|fmt| self.fmt(fmt))
+    }
+
+    /// Returns an estimate of the rendered length of the enum.
+    pub(crate) fn estimated_len(&self) -> usize {
+        let variants: usize = self.variants.iter().map(|variant| variant.estimated_len()).sum();
+
+        self.type_def.estimated_len() + variants
+    }
+
     /// Formats the enum using the given formatter.
     /// 
     /// # Arguments
@@ -246,8 +656,9 @@ impl Enum {
         self.type_def.fmt_head("enum", &[], fmt)?;
 
         fmt.block(|fmt| {
-            for variant in &self.variants {
-                variant.fmt(fmt)?;
+            for (i, variant) in self.variants.iter().enumerate() {
+                let comma = fmt.list_comma(i, self.variants.len());
+                variant.fmt_with_comma(comma, fmt)?;
             }
 
             Ok(())
@@ -0,0 +1,330 @@
+use std::borrow::Cow;
+
+use crate::block::Block;
+use crate::case::to_snake_case;
+use crate::fields::Fields;
+use crate::literal::quote_str;
+use crate::scope::Scope;
+use crate::struct_literal::StructLiteral;
+use crate::visibility::strip_vis;
+
+use crate::r#enum::Enum;
+use crate::r#impl::Impl;
+use crate::r#struct::Struct;
+
+/// Generates a `from_env` constructor for a config struct that reads each
+/// named field from an environment variable and parses it with `FromStr`,
+/// along with the error enum returned when a variable is missing or invalid.
+///
+/// Variables are named after their fields in `SCREAMING_SNAKE_CASE` after a
+/// prefix, e.g. `APP_DATABASE_URL` for `database_url`. `Option` fields are
+/// `None` when their variable isn't set.
+#[derive(Debug, Clone, Default)]
+pub struct EnvLoader {
+    /// The prefix of the variable names, e.g. `APP_`.
+    prefix: Cow<'static, str>,
+    /// The name of the error enum, `{Struct}EnvError` by default.
+    error_name: Option<Cow<'static, str>>,
+    /// The variable names that replace the default names, by field.
+    vars: Vec<(String, String)>,
+    /// The default values of fields whose variable isn't set, by field.
+    defaults: Vec<(String, String)>,
+}
+
+impl EnvLoader {
+    /// Returns a new loader without a prefix.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::EnvLoader;
+    ///
+    /// let loader = EnvLoader::new();
+    /// ```
+    pub fn new() -> Self {
+        EnvLoader {
+            prefix: "".into(),
+            error_name: None,
+            vars: vec![],
+            defaults: vec![],
+        }
+    }
+
+    /// Set the prefix of the variable names.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The prefix, e.g. `APP_`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::EnvLoader;
+    ///
+    /// let mut loader = EnvLoader::new();
+    /// loader.prefix("APP_");
+    /// ```
+    pub fn prefix<T>(&mut self, prefix: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.prefix = prefix.into();
+        self
+    }
+
+    /// Set the name of the error enum, `{Struct}EnvError` by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the error enum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::EnvLoader;
+    ///
+    /// let mut loader = EnvLoader::new();
+    /// loader.error_name("ConfigError");
+    /// ```
+    pub fn error_name<T>(&mut self, name: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.error_name = Some(name.into());
+        self
+    }
+
+    /// Read the given field from a variable with the given name instead of
+    /// the default name. The prefix isn't added to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The name of the field.
+    /// * `var` - The name of the variable, e.g. `DATABASE_URL`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::EnvLoader;
+    ///
+    /// let mut loader = EnvLoader::new();
+    /// loader.var("database_url", "DATABASE_URL");
+    /// ```
+    pub fn var(&mut self, field: &str, var: &str) -> &mut Self {
+        self.vars.push((field.to_string(), var.to_string()));
+        self
+    }
+
+    /// Use the given expression for the given field when its variable isn't
+    /// set.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The name of the field.
+    /// * `value` - The expression of the default value, e.g. `8080`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::EnvLoader;
+    ///
+    /// let mut loader = EnvLoader::new();
+    /// loader.default_value("port", "8080");
+    /// ```
+    pub fn default_value(&mut self, field: &str, value: &str) -> &mut Self {
+        self.defaults.push((field.to_string(), value.to_string()));
+        self
+    }
+
+    /// Returns the error enum of the given struct, with a `Missing` variant
+    /// for unset variables and an `Invalid` variant for values that can't be
+    /// parsed.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The config struct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{EnvLoader,Struct};
+    ///
+    /// let config_struct = Struct::new("Config");
+    /// let error = EnvLoader::new().to_error_enum(&config_struct);
+    /// ```
+    pub fn to_error_enum(&self, target: &Struct) -> Enum {
+        let mut error = Enum::new(self.error_name_of(target));
+
+        if let Some(vis) = target.type_def().get_vis() {
+            error.vis(vis);
+        }
+
+        error.derive("Debug");
+        error
+            .new_variant("Missing")
+            .doc("The environment variable with the given name isn't set.")
+            .tuple("&'static str");
+        error
+            .new_variant("Invalid")
+            .doc("The environment variable with the given name has a value that can't be parsed.")
+            .tuple("&'static str")
+            .tuple("String");
+
+        error
+    }
+
+    /// Returns the `Display` and `std::error::Error` impls of the error enum,
+    /// followed by the impl block of the given struct with `from_env`.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The config struct, which must have named fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{EnvLoader,Struct};
+    ///
+    /// let mut config_struct = Struct::new("Config");
+    /// config_struct.field("port", "u16");
+    ///
+    /// let impls = EnvLoader::new().to_impls(&config_struct);
+    /// ```
+    pub fn to_impls(&self, target: &Struct) -> Vec<Impl> {
+        let fields = match target.fields() {
+            Fields::Named(ref fields) => fields,
+            _ => panic!("`EnvLoader` requires named fields"),
+        };
+
+        let names = target.field_names();
+
+        for (field, _) in self.vars.iter().chain(&self.defaults) {
+            if !names.contains(field) {
+                panic!("unknown field `{}` in `EnvLoader`", field);
+            }
+        }
+
+        let error_name = self.error_name_of(target);
+
+        let mut display = Impl::new(error_name.clone());
+        display.impl_trait("std::fmt::Display");
+
+        let mut body = Block::new("match self");
+        body.line(format!(
+            "{}::Missing(var) => write!(f, \"missing environment variable `{{}}`\", var),",
+            error_name
+        ));
+        body.line(format!(
+            "{}::Invalid(var, value) => {{\n    write!(f, \"invalid value `{{}}` for environment variable `{{}}`\", value, var)\n}}",
+            error_name
+        ));
+
+        display
+            .new_fn("fmt")
+            .arg_ref_self()
+            .arg("f", "&mut std::fmt::Formatter<'_>")
+            .ret("std::fmt::Result")
+            .push_block(body);
+
+        let mut error = Impl::new(error_name.clone());
+        error.impl_trait("std::error::Error");
+
+        let mut target_impl = Impl::new(target.ty());
+        let from_env = target_impl
+            .new_fn("from_env")
+            .doc("Reads the config from the environment variables.")
+            .ret(format!("Result<Self, {}>", error_name));
+
+        if let Some(vis) = target.type_def().get_vis() {
+            from_env.vis(vis);
+        }
+
+        let mut literal = StructLiteral::new("Self");
+
+        for field in fields {
+            let name = strip_vis(&field.name);
+            let var = self.var_name(name);
+            let var_literal = quote_str(&var);
+
+            let parse = format!(
+                "value.parse().map_err(|_| {}::Invalid({}, value))?",
+                error_name, var_literal
+            );
+
+            let ty = field.ty.fmt_to_string();
+            let optional = ty.starts_with("Option<") || ty.starts_with("std::option::Option<");
+            let default = self
+                .defaults
+                .iter()
+                .find(|(f, _)| f == name)
+                .map(|(_, value)| value.clone());
+
+            let (found, missing) = match (optional, default) {
+                (_, Some(default)) => (parse, default),
+                (true, None) => (format!("Some({})", parse), "None".to_string()),
+                (false, None) => (
+                    parse,
+                    format!("return Err({}::Missing({}))", error_name, var_literal),
+                ),
+            };
+
+            let mut read = Block::new(format!(
+                "let {} = match std::env::var({})",
+                name, var_literal
+            ));
+            read.line(format!("Ok(value) => {},", found))
+                .line(format!("Err(_) => {},", missing))
+                .after(";");
+
+            from_env.push_block(read);
+            literal.shorthand(name.to_string());
+        }
+
+        from_env.line(format!("Ok({})", literal.to_code_string()));
+
+        vec![display, error, target_impl]
+    }
+
+    /// Push the error enum and the impls of the given struct to the given
+    /// scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The config struct.
+    /// * `scope` - The scope to push the items to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{EnvLoader,Scope,Struct};
+    ///
+    /// let mut scope = Scope::new();
+    /// let mut config_struct = Struct::new("Config");
+    /// config_struct.field("port", "u16");
+    ///
+    /// EnvLoader::new().push_to(&config_struct, &mut scope);
+    /// ```
+    pub fn push_to(&self, target: &Struct, scope: &mut Scope) {
+        scope.push_enum(self.to_error_enum(target));
+
+        for item in self.to_impls(target) {
+            scope.push_impl(item);
+        }
+    }
+
+    /// Returns the name of the variable of the given field.
+    fn var_name(&self, field: &str) -> String {
+        match self.vars.iter().find(|(f, _)| f == field) {
+            Some((_, var)) => var.clone(),
+            None => format!("{}{}", self.prefix, to_snake_case(field).to_uppercase()),
+        }
+    }
+
+    /// Returns the name of the error enum of the given struct.
+    fn error_name_of(&self, target: &Struct) -> Cow<'static, str> {
+        match self.error_name {
+            Some(ref name) => name.clone(),
+            None => format!("{}EnvError", target.ty().name()).into(),
+        }
+    }
+}
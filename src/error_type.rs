@@ -0,0 +1,371 @@
+use std::borrow::Cow;
+
+use crate::block::Block;
+use crate::scope::Scope;
+
+use crate::r#enum::Enum;
+use crate::r#impl::Impl;
+use crate::r#type::Type;
+
+/// Defines a variant of a generated error type.
+#[derive(Debug, Clone)]
+pub struct ErrorVariant {
+    /// The name of the variant.
+    name: Cow<'static, str>,
+    /// The display message, a format string that can refer to the source
+    /// with `{0}`.
+    message: Cow<'static, str>,
+    /// The type of the underlying error, if any.
+    source: Option<Type>,
+}
+
+impl ErrorVariant {
+    /// Return a new error variant with the given name and display message.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variant.
+    /// * `message` - The display message, which can refer to the source with
+    ///   `{0}`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::ErrorVariant;
+    ///
+    /// let not_found = ErrorVariant::new("NotFound", "resource not found");
+    /// ```
+    pub fn new<N, M>(name: N, message: M) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+        M: Into<Cow<'static, str>>,
+    {
+        ErrorVariant {
+            name: name.into(),
+            message: message.into(),
+            source: None,
+        }
+    }
+
+    /// Set the type of the underlying error. A `From` impl is generated for
+    /// it and it is returned by `Error::source`.
+    ///
+    /// # Arguments
+    ///
+    /// * `ty` - The type of the underlying error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::ErrorVariant;
+    ///
+    /// let mut io = ErrorVariant::new("Io", "i/o error: {0}");
+    /// io.source("std::io::Error");
+    /// ```
+    pub fn source<T>(&mut self, ty: T) -> &mut Self
+    where
+        T: Into<Type>,
+    {
+        self.source = Some(ty.into());
+        self
+    }
+
+    /// Returns whether the message refers to the source.
+    fn uses_source(&self) -> bool {
+        self.message.contains("{0")
+    }
+}
+
+/// Generates a structured error enum along with its `Display`,
+/// `std::error::Error`, and `From` impls.
+#[derive(Debug, Clone)]
+pub struct ErrorType {
+    /// The name of the error enum.
+    name: Cow<'static, str>,
+    /// The visibility of the error enum.
+    vis: Option<String>,
+    /// The error variants.
+    variants: Vec<ErrorVariant>,
+    /// Whether to use `thiserror` attributes instead of handwritten impls.
+    thiserror: bool,
+}
+
+impl ErrorType {
+    /// Return a new error type with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the error enum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::ErrorType;
+    ///
+    /// let client_error = ErrorType::new("ClientError");
+    /// ```
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        ErrorType {
+            name: name.into(),
+            vis: None,
+            variants: vec![],
+            thiserror: false,
+        }
+    }
+
+    /// Set the visibility of the error enum.
+    ///
+    /// # Arguments
+    ///
+    /// * `vis` - The visibility to set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::ErrorType;
+    ///
+    /// let mut client_error = ErrorType::new("ClientError");
+    /// client_error.vis("pub");
+    /// ```
+    pub fn vis(&mut self, vis: &str) -> &mut Self {
+        self.vis = Some(vis.to_string());
+        self
+    }
+
+    /// Set whether to derive `thiserror::Error` and use `#[error(...)]` and
+    /// `#[from]` attributes instead of generating the impls by hand.
+    ///
+    /// # Arguments
+    ///
+    /// * `thiserror` - Indicates whether to use `thiserror` or not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::ErrorType;
+    ///
+    /// let mut client_error = ErrorType::new("ClientError");
+    /// client_error.thiserror(true);
+    /// ```
+    pub fn thiserror(&mut self, thiserror: bool) -> &mut Self {
+        self.thiserror = thiserror;
+        self
+    }
+
+    /// Push a new variant, returning a mutable reference to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variant.
+    /// * `message` - The display message, which can refer to the source with
+    ///   `{0}`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::ErrorType;
+    ///
+    /// let mut client_error = ErrorType::new("ClientError");
+    /// client_error.new_variant("Io", "i/o error: {0}").source("std::io::Error");
+    /// ```
+    pub fn new_variant<N, M>(&mut self, name: N, message: M) -> &mut ErrorVariant
+    where
+        N: Into<Cow<'static, str>>,
+        M: Into<Cow<'static, str>>,
+    {
+        self.push_variant(ErrorVariant::new(name, message));
+        self.variants.last_mut().unwrap()
+    }
+
+    /// Push a variant.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - The variant to push.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{ErrorType,ErrorVariant};
+    ///
+    /// let mut client_error = ErrorType::new("ClientError");
+    /// client_error.push_variant(ErrorVariant::new("NotFound", "resource not found"));
+    /// ```
+    pub fn push_variant(&mut self, item: ErrorVariant) -> &mut Self {
+        self.variants.push(item);
+        self
+    }
+
+    /// Returns the error enum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::ErrorType;
+    ///
+    /// let client_error = ErrorType::new("ClientError");
+    /// let client_error_enum = client_error.to_enum();
+    /// ```
+    pub fn to_enum(&self) -> Enum {
+        let mut r#enum = Enum::new(self.name.clone());
+        r#enum.derive("Debug");
+
+        if let Some(ref vis) = self.vis {
+            r#enum.vis(vis);
+        }
+
+        if self.thiserror {
+            r#enum.derive("thiserror::Error");
+        }
+
+        for variant in &self.variants {
+            let v = r#enum.new_variant(variant.name.clone());
+
+            if self.thiserror {
+                v.attr(format!("error({:?})", variant.message));
+            }
+
+            if let Some(ref source) = variant.source {
+                if self.thiserror {
+                    v.tuple(&format!("#[from] {}", source.fmt_to_string()));
+                } else {
+                    v.tuple(&source.fmt_to_string());
+                }
+            }
+        }
+
+        r#enum
+    }
+
+    /// Returns the `Display`, `std::error::Error`, and `From` impls of the
+    /// error enum. Empty if `thiserror` is used.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::ErrorType;
+    ///
+    /// let client_error = ErrorType::new("ClientError");
+    /// let impls = client_error.to_impls();
+    /// ```
+    pub fn to_impls(&self) -> Vec<Impl> {
+        if self.thiserror {
+            return vec![];
+        }
+
+        let mut impls = vec![self.display_impl(), self.error_impl()];
+
+        for variant in &self.variants {
+            if let Some(ref source) = variant.source {
+                let mut from = Impl::new(self.name.clone());
+                from.impl_trait(format!("From<{}>", source.fmt_to_string()));
+                from.new_fn("from")
+                    .arg("source", source)
+                    .ret("Self")
+                    .line(format!("{}::{}(source)", self.name, variant.name));
+
+                impls.push(from);
+            }
+        }
+
+        impls
+    }
+
+    /// Push the error enum and its impls to the given scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - The scope to push the error type to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{ErrorType,Scope};
+    ///
+    /// let mut scope = Scope::new();
+    /// let mut client_error = ErrorType::new("ClientError");
+    /// client_error.new_variant("NotFound", "resource not found");
+    ///
+    /// client_error.push_to(&mut scope);
+    /// ```
+    pub fn push_to(&self, scope: &mut Scope) {
+        scope.push_enum(self.to_enum());
+
+        for item in self.to_impls() {
+            scope.push_impl(item);
+        }
+    }
+
+    /// Returns the `impl std::fmt::Display` block.
+    fn display_impl(&self) -> Impl {
+        let mut display = Impl::new(self.name.clone());
+        display.impl_trait("std::fmt::Display");
+
+        let mut body = Block::new("match self");
+
+        for variant in &self.variants {
+            let line = match variant.source {
+                Some(_) if variant.uses_source() => format!(
+                    "{}::{}(source) => write!(f, {:?}, source),",
+                    self.name, variant.name, variant.message
+                ),
+                Some(_) => format!(
+                    "{}::{}(_) => write!(f, {:?}),",
+                    self.name, variant.name, variant.message
+                ),
+                None => format!(
+                    "{}::{} => write!(f, {:?}),",
+                    self.name, variant.name, variant.message
+                ),
+            };
+
+            body.line(line);
+        }
+
+        let fmt_fn = display
+            .new_fn("fmt")
+            .arg_ref_self()
+            .arg("f", "&mut std::fmt::Formatter<'_>")
+            .ret("std::fmt::Result");
+
+        if self.variants.is_empty() {
+            fmt_fn.line("match *self {}");
+        } else {
+            fmt_fn.push_block(body);
+        }
+
+        display
+    }
+
+    /// Returns the `impl std::error::Error` block.
+    fn error_impl(&self) -> Impl {
+        let mut error = Impl::new(self.name.clone());
+        error.impl_trait("std::error::Error");
+
+        if self.variants.iter().any(|v| v.source.is_some()) {
+            let mut body = Block::new("match self");
+
+            for variant in &self.variants {
+                if variant.source.is_some() {
+                    body.line(format!("{}::{}(source) => Some(source),", self.name, variant.name));
+                }
+            }
+
+            if self.variants.iter().any(|v| v.source.is_none()) {
+                body.line("_ => None,");
+            }
+
+            error
+                .new_fn("source")
+                .arg_ref_self()
+                .ret("Option<&(dyn std::error::Error + 'static)>")
+                .push_block(body);
+        }
+
+        error
+    }
+}
+
@@ -0,0 +1,106 @@
+use std::borrow::Cow;
+
+/// Defines an expression to compose function bodies with, e.g. to await an
+/// expression, propagate its error, and wrap it in `Ok(...)` without adding
+/// the suffixes by hand.
+#[derive(Debug, Clone)]
+pub struct Expr {
+    /// The code of the expression.
+    code: String,
+}
+
+impl Expr {
+    /// Returns a new expression with the given code.
+    ///
+    /// # Arguments
+    ///
+    /// * `code` - The code of the expression, e.g. `fetch(url)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Expr;
+    ///
+    /// let expr = Expr::new("fetch(url)");
+    /// ```
+    pub fn new<T>(code: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        Expr {
+            code: code.into().into_owned(),
+        }
+    }
+
+    /// Await the expression with `.await`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Expr;
+    ///
+    /// let mut expr = Expr::new("fetch(url)");
+    /// expr.await_();
+    ///
+    /// assert_eq!(expr.to_code_string(), "fetch(url).await");
+    /// ```
+    pub fn await_(&mut self) -> &mut Self {
+        self.code.push_str(".await");
+        self
+    }
+
+    /// Propagate the error of the expression with `?`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Expr;
+    ///
+    /// let mut expr = Expr::new("fetch(url)");
+    /// expr.await_().try_();
+    ///
+    /// assert_eq!(expr.to_code_string(), "fetch(url).await?");
+    /// ```
+    pub fn try_(&mut self) -> &mut Self {
+        self.code.push('?');
+        self
+    }
+
+    /// Wrap the expression in `Ok(...)`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Expr;
+    ///
+    /// let mut expr = Expr::new("user");
+    /// expr.ok();
+    ///
+    /// assert_eq!(expr.to_code_string(), "Ok(user)");
+    /// ```
+    pub fn ok(&mut self) -> &mut Self {
+        self.code = format!("Ok({})", self.code);
+        self
+    }
+
+    /// Returns the expression as code.
+    pub fn to_code_string(&self) -> String {
+        self.code.clone()
+    }
+
+    /// Returns the expression as a `return ...;` statement.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Expr;
+    ///
+    /// let mut expr = Expr::new("user");
+    /// expr.ok();
+    ///
+    /// assert_eq!(expr.to_return(), "return Ok(user);");
+    /// ```
+    pub fn to_return(&self) -> String {
+        format!("return {};", self.code)
+    }
+}
@@ -0,0 +1,149 @@
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+
+use crate::formatter::Formatter;
+use crate::function::Function;
+use crate::visibility::Visibility;
+
+use crate::r#type::Type;
+
+/// Defines an `extern` block of foreign function declarations.
+#[derive(Debug, Clone)]
+pub struct ExternBlock {
+    /// The ABI of the block, e.g. `C`.
+    abi: Cow<'static, str>,
+    /// The declared functions.
+    fns: Vec<Function>,
+}
+
+impl ExternBlock {
+    /// Return a new extern block with the given ABI.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `abi` - The ABI of the block.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::ExternBlock;
+    /// 
+    /// let block = ExternBlock::new("C");
+    /// ```
+    pub fn new<T>(abi: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        ExternBlock {
+            abi: abi.into(),
+            fns: vec![],
+        }
+    }
+
+    /// Push a new function declaration, returning a mutable reference to it.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the function.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::ExternBlock;
+    /// 
+    /// let mut block = ExternBlock::new("C");
+    /// block.new_fn("abs").arg("input", "i32").ret("i32");
+    /// ```
+    pub fn new_fn<T>(&mut self, name: T) -> &mut Function
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let mut func = Function::new(name);
+        func.body = None;
+
+        self.push_fn(func);
+        self.fns.last_mut().unwrap()
+    }
+
+    /// Push a function declaration. Any body, attributes, visibility, or ABI
+    /// of the function are dropped so that only the signature is declared.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `item` - The function to declare.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{ExternBlock,Function};
+    /// 
+    /// let mut block = ExternBlock::new("C");
+    /// let mut abs_fn = Function::new("abs");
+    /// abs_fn.export_c().arg("input", "i32").ret("i32");
+    /// 
+    /// block.push_fn(abs_fn);
+    /// ```
+    pub fn push_fn(&mut self, item: Function) -> &mut Self {
+        self.fns.push(item.to_extern_decl());
+        self
+    }
+
+    /// Calls the given function with every type of the declared functions.
+    pub(crate) fn visit_types_mut(&mut self, f: &mut dyn FnMut(&mut Type)) {
+        for func in &mut self.fns {
+            func.visit_types_mut(f);
+        }
+    }
+
+    /// Maps the visibility of the functions of the block with the given function.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `f` - The function mapping the visibility.
+    pub(crate) fn map_visibility(&mut self, f: &dyn Fn(Visibility) -> Visibility) {
+        for func in &mut self.fns {
+            func.map_visibility(f);
+        }
+    }
+
+    /// Returns the ABI of the block.
+    pub(crate) fn abi(&self) -> &str {
+        &self.abi
+    }
+
+    /// Returns an estimate of the rendered length of the extern block.
+    pub(crate) fn estimated_len(&self) -> usize {
+        let fns: usize = self.fns.iter().map(|func| func.estimated_len()).sum();
+
+        self.abi.len() + fns + 16
+    }
+
+    /// Formats the extern block using the given formatter.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `fmt` - The formatter to use.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::*;
+    /// 
+    /// let mut dest = String::new();
+    /// let mut fmt = Formatter::new(&mut dest);
+    /// 
+    /// let block = ExternBlock::new("C");
+    /// block.fmt(&mut fmt);
+    /// ```
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        write!(fmt, "extern \"{}\"", self.abi)?;
+
+        fmt.block(|fmt| {
+            for func in &self.fns {
+                func.fmt(true, fmt)?;
+            }
+
+            Ok(())
+        })
+    }
+}
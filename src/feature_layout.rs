@@ -0,0 +1,61 @@
+/// The key of the [`Metadata`] entry tagging an item with the cargo feature
+/// it belongs to, see [`Metadata::feature`].
+///
+/// [`Metadata`]: crate::Metadata
+/// [`Metadata::feature`]: crate::Metadata::feature
+pub(crate) const FEATURE_KEY: &str = "feature";
+
+/// Defines how [`Scope::render_features`] lays out the items tagged with a
+/// cargo feature.
+///
+/// [`Scope::render_features`]: crate::Scope::render_features
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FeatureLayout {
+    /// A single file where each tagged item has a `#[cfg(feature = "..")]`
+    /// attribute.
+    Cfg,
+    /// A file per feature holding its tagged items, declared as a module of
+    /// the main file behind a `#[cfg(feature = "..")]` attribute and
+    /// re-exported with a glob `pub use`.
+    Files,
+}
+
+/// Returns the `cfg` attribute of the given feature.
+///
+/// # Arguments
+///
+/// * `feature` - The name of the feature.
+pub(crate) fn cfg_feature(feature: &str) -> String {
+    format!("cfg(feature = \"{}\")", feature)
+}
+
+/// Returns the name of the module of the given feature, e.g. `serde_json`
+/// for `serde-json`.
+///
+/// # Arguments
+///
+/// * `feature` - The name of the feature.
+pub(crate) fn feature_module(feature: &str) -> String {
+    feature.replace('-', "_")
+}
+
+/// Returns the directory of the child modules of the file at the given path,
+/// e.g. `src` for `src/lib.rs` and `src/api` for `src/api.rs`.
+///
+/// # Arguments
+///
+/// * `path` - The path of the file.
+pub(crate) fn module_dir(path: &str) -> String {
+    let (dir, file) = match path.rfind('/') {
+        Some(i) => (&path[..i], &path[i + 1..]),
+        None => ("", path),
+    };
+
+    let stem = file.strip_suffix(".rs").unwrap_or(file);
+
+    match (stem, dir) {
+        ("lib" | "main" | "mod", _) => dir.to_string(),
+        (_, "") => stem.to_string(),
+        _ => format!("{}/{}", dir, stem),
+    }
+}
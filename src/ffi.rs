@@ -0,0 +1,48 @@
+/// The primitive types that can be passed across the C ABI as is.
+const PRIMITIVES: &[&str] = &[
+    "()", "bool", "i8", "i16", "i32", "i64", "isize", "u8", "u16", "u32", "u64", "usize", "f32",
+    "f64",
+];
+
+/// Returns whether the given type looks like it can be passed across the C
+/// ABI: primitives, `c_*` types, raw pointers, `extern "C"` function
+/// pointers, `NonNull`, nullable pointer-like `Option`s, and the
+/// `#[repr(C)]` types in `repr_c`.
+///
+/// # Arguments
+///
+/// * `ty` - The type to check.
+/// * `repr_c` - The names of the known `#[repr(C)]` types.
+pub(crate) fn looks_ffi_safe(ty: &str, repr_c: &[String]) -> bool {
+    let ty = ty.trim();
+
+    if ty.starts_with("*const ")
+        || ty.starts_with("*mut ")
+        || ty.starts_with("extern \"C\" fn")
+        || ty.starts_with("unsafe extern \"C\" fn")
+    {
+        return true;
+    }
+
+    let generic = ty.find('<');
+    let path = generic.map_or(ty, |i| &ty[..i]);
+    let last = path.rsplit("::").next().unwrap_or(path);
+
+    match (last, generic) {
+        ("NonNull", _) => true,
+        // A bare `Option` without its parameter isn't a complete type.
+        ("Option", None) => false,
+        ("Option", Some(i)) => {
+            let inner = ty[i + 1..].trim_end_matches('>').trim();
+
+            inner.starts_with('&')
+                || inner.starts_with("extern \"C\" fn")
+                || inner.starts_with("unsafe extern \"C\" fn")
+                || inner.starts_with("NonNull<")
+                || inner.contains("::NonNull<")
+                || inner.starts_with("Box<")
+        }
+        (_, Some(_)) => false,
+        _ => PRIMITIVES.contains(&last) || last.starts_with("c_") || repr_c.iter().any(|r| r == last),
+    }
+}
@@ -1,19 +1,32 @@
+use std::borrow::Cow;
+
+use crate::attributes::Attributes;
+use crate::serde::FieldAttrs;
+
 use crate::r#type::Type;
 
 /// Defines a struct field.
+///
+/// Fields are built with [`Field::new`], as the attributes of the field are
+/// private and more may be added, while the public ones can be read and
+/// changed directly.
 #[derive(Debug, Clone)]
+#[non_exhaustive]
 pub struct Field {
     /// Field name
-    pub name: String,
+    pub name: Cow<'static, str>,
 
     /// Field type
     pub ty: Type,
 
     /// Field documentation
-    pub documentation: Vec<String>,
+    pub documentation: Vec<Cow<'static, str>>,
 
     /// Field annotation
-    pub annotation: Vec<String>,
+    pub annotation: Vec<Cow<'static, str>>,
+
+    /// Field attributes, e.g. `#[cfg(feature = "serde")]`
+    attributes: Attributes,
 }
 
 impl Field {
@@ -31,8 +44,9 @@ impl Field {
     /// 
     /// let count_field = Field::new("count", "i32");
     /// ```
-    pub fn new<T>(name: &str, ty: T) -> Self
+    pub fn new<N, T>(name: N, ty: T) -> Self
     where
+        N: Into<Cow<'static, str>>,
         T: Into<Type>,
     {
         Field {
@@ -40,6 +54,7 @@ impl Field {
             ty: ty.into(),
             documentation: Vec::new(),
             annotation: Vec::new(),
+            attributes: Attributes::new(),
         }
     }
 
@@ -56,7 +71,58 @@ impl Field {
     /// 
     /// let count_field = Field::new("count", "i32").doc(Vec::from(["The number of Foos"]));
     pub fn doc(&mut self, documentation: Vec<&str>) -> &mut Self {
-        self.documentation = documentation.iter().map(|doc| doc.to_string()).collect();
+        self.documentation = documentation.iter().map(|doc| doc.to_string().into()).collect();
+        self
+    }
+
+    /// Set the field's documentation from a string, with a doc line per line
+    /// of the string.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `documentation` - The documentation to set for the field.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Field;
+    /// 
+    /// let mut count_field = Field::new("count", "i32");
+    /// count_field.doc_str("The number of Foos.\n\nNever negative.");
+    /// ```
+    pub fn doc_str<T>(&mut self, documentation: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let documentation = documentation.into();
+
+        self.documentation = documentation
+            .replace("\r\n", "\n")
+            .lines()
+            .map(|line| line.to_string().into())
+            .collect();
+        self
+    }
+
+    /// Add a line to the field's documentation.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `line` - The line of documentation to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Field;
+    /// 
+    /// let mut count_field = Field::new("count", "i32");
+    /// count_field.push_doc_line("The number of Foos.");
+    /// ```
+    pub fn push_doc_line<T>(&mut self, line: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.documentation.push(line.into());
         self
     }
 
@@ -73,7 +139,101 @@ impl Field {
     /// 
     /// let count_field = Field::new("count", "i32").annotation(Vec::from(["serde(rename = \"name\")"]));
     pub fn annotation(&mut self, annotation: Vec<&str>) -> &mut Self {
-        self.annotation = annotation.iter().map(|ann| ann.to_string()).collect();
+        self.annotation = annotation.iter().map(|ann| ann.to_string().into()).collect();
+        self
+    }
+
+    /// Add an annotation to the field, e.g. `#[serde(default)]`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `annotation` - The annotation to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Field;
+    /// 
+    /// let mut count_field = Field::new("count", "i32");
+    /// count_field.push_annotation("#[serde(default)]");
+    /// ```
+    pub fn push_annotation<T>(&mut self, annotation: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.annotation.push(annotation.into());
+        self
+    }
+
+    /// Specify a lint to allow on the field, e.g. `dead_code`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `allow` - The lint to allow.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Field;
+    /// 
+    /// let mut count_field = Field::new("count", "i32");
+    /// count_field.allow("dead_code");
+    /// ```
+    pub fn allow<T>(&mut self, allow: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.allow(allow);
+        self
+    }
+
+    /// Only compile the field when the given configuration predicate holds,
+    /// e.g. for fields behind a feature.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `cfg` - The configuration predicate, e.g. `feature = "serde"`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Field;
+    /// 
+    /// let mut trace_field = Field::new("trace_id", "u64");
+    /// trace_field.cfg("feature = \"tracing\"");
+    /// ```
+    pub fn cfg<T>(&mut self, cfg: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.cfg(cfg);
+        self
+    }
+
+    /// Returns the attributes of the field.
+    pub(crate) fn attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    /// Adds the given serde attributes to the field's annotations.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attrs` - The serde attributes to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Field;
+    /// use rust_codegen::serde::FieldAttrs;
+    /// 
+    /// let mut count_field = Field::new("count", "i32");
+    /// count_field.serde(FieldAttrs::new().default().skip_serializing_if("Option::is_none"));
+    /// ```
+    pub fn serde(&mut self, attrs: &FieldAttrs) -> &mut Self {
+        if let Some(meta) = attrs.meta() {
+            self.annotation.push(format!("#[{}]", meta).into());
+        }
         self
     }
 }
@@ -1,7 +1,9 @@
+use std::borrow::Cow;
 use std::fmt::{self, Write};
 
 use crate::field::Field;
 use crate::formatter::Formatter;
+use crate::visibility::{map_prefix_vis_mut, Visibility};
 
 use crate::r#type::Type;
 
@@ -41,16 +43,12 @@ impl Fields {
     /// 
     /// * `name` - The name of the field.
     /// * `ty` - The type of the field.
-    pub fn named<T>(&mut self, name: &str, ty: T) -> &mut Self
+    pub fn named<N, T>(&mut self, name: N, ty: T) -> &mut Self
     where
+        N: Into<Cow<'static, str>>,
         T: Into<Type>,
     {
-        self.push_named(Field {
-            name: name.to_string(),
-            ty: ty.into(),
-            documentation: Vec::new(),
-            annotation: Vec::new(),
-        })
+        self.push_named(Field::new(name, ty))
     }
 
     /// Pushes a type.
@@ -75,6 +73,61 @@ impl Fields {
         self
     }
 
+    /// Calls the given function with every type of the fields.
+    pub(crate) fn visit_types_mut(&mut self, f: &mut dyn FnMut(&mut Type)) {
+        match *self {
+            Fields::Empty => {}
+            Fields::Tuple(ref mut tys) => {
+                for ty in tys {
+                    ty.visit_types_mut(f);
+                }
+            }
+            Fields::Named(ref mut fields) => {
+                for field in fields {
+                    field.ty.visit_types_mut(f);
+                }
+            }
+        }
+    }
+
+    /// Maps the visibility written before the fields with the given function.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `f` - The function mapping the visibility.
+    pub(crate) fn map_visibility(&mut self, f: &dyn Fn(Visibility) -> Visibility) {
+        match *self {
+            Fields::Empty => {}
+            Fields::Tuple(ref mut tys) => {
+                for ty in tys {
+                    ty.map_prefix_vis(f);
+                }
+            }
+            Fields::Named(ref mut fields) => {
+                for field in fields {
+                    map_prefix_vis_mut(&mut field.name, f);
+                }
+            }
+        }
+    }
+
+    /// Returns an estimate of the rendered length of the fields.
+    pub(crate) fn estimated_len(&self) -> usize {
+        match *self {
+            Fields::Empty => 0,
+            Fields::Tuple(ref tys) => tys.len() * 16,
+            Fields::Named(ref fields) => fields
+                .iter()
+                .map(|field| {
+                    let docs: usize = field.documentation.iter().map(|doc| doc.len() + 8).sum();
+                    let annotations: usize = field.annotation.iter().map(|a| a.len() + 5).sum();
+
+                    field.name.len() + 24 + docs + annotations + field.attributes().estimated_len()
+                })
+                .sum(),
+        }
+    }
+
     /// Formats the fields using the provided formatter.
     /// 
     /// * `fmt` - The formatter to use.
@@ -83,25 +136,7 @@ impl Fields {
             Fields::Named(ref fields) => {
                 assert!(!fields.is_empty());
 
-                fmt.block(|fmt| {
-                    for f in fields {
-                        if !f.documentation.is_empty() {
-                            for doc in &f.documentation {
-                                write!(fmt, "/// {}\n", doc)?;
-                            }
-                        }
-                        if !f.annotation.is_empty() {
-                            for ann in &f.annotation {
-                                write!(fmt, "{}\n", ann)?;
-                            }
-                        }
-                        write!(fmt, "{}: ", f.name)?;
-                        f.ty.fmt(fmt)?;
-                        write!(fmt, ",\n")?;
-                    }
-
-                    Ok(())
-                })?;
+                fmt.block(|fmt| fmt_named_fields(fields, fmt))?;
             }
             Fields::Tuple(ref tys) => {
                 assert!(!tys.is_empty());
@@ -123,3 +158,28 @@ impl Fields {
         Ok(())
     }
 }
+
+/// Formats the named fields with their docs and annotations, one per line.
+pub(crate) fn fmt_named_fields(fields: &[Field], fmt: &mut Formatter<'_>) -> fmt::Result {
+    for (i, f) in fields.iter().enumerate() {
+        for doc in &f.documentation {
+            if doc.is_empty() {
+                write!(fmt, "///\n")?;
+            } else {
+                write!(fmt, "/// {}\n", doc)?;
+            }
+        }
+
+        f.attributes().fmt(fmt)?;
+
+        for ann in &f.annotation {
+            write!(fmt, "{}\n", ann)?;
+        }
+
+        write!(fmt, "{}: ", f.name)?;
+        f.ty.fmt(fmt)?;
+        write!(fmt, "{}\n", fmt.list_comma(i, fields.len()))?;
+    }
+
+    Ok(())
+}
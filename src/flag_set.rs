@@ -0,0 +1,412 @@
+use std::borrow::Cow;
+use std::fmt::Write;
+
+use crate::docs::Docs;
+use crate::formatter::Formatter;
+use crate::scope::Scope;
+
+use crate::r#impl::Impl;
+use crate::r#struct::Struct;
+
+/// Defines a single flag of a flag set.
+#[derive(Debug, Clone)]
+pub struct Flag {
+    /// The name of the flag.
+    name: Cow<'static, str>,
+    /// The value of the flag, e.g. `1 << 0`.
+    value: Cow<'static, str>,
+    /// The documentation of the flag.
+    docs: Option<Cow<'static, str>>,
+}
+
+impl Flag {
+    /// Return a new flag with the given name and value.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the flag.
+    /// * `value` - The value of the flag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Flag;
+    ///
+    /// let read = Flag::new("READ", "1 << 0");
+    /// ```
+    pub fn new<N, V>(name: N, value: V) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+        V: Into<Cow<'static, str>>,
+    {
+        Flag {
+            name: name.into(),
+            value: value.into(),
+            docs: None,
+        }
+    }
+
+    /// Set the documentation of the flag.
+    ///
+    /// # Arguments
+    ///
+    /// * `docs` - The documentation to set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Flag;
+    ///
+    /// let mut read = Flag::new("READ", "1 << 0");
+    /// read.doc("Read access.");
+    /// ```
+    pub fn doc<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.docs = Some(docs.into());
+        self
+    }
+}
+
+/// Generates a set of bit flags, either as a `bitflags!` invocation or as a
+/// plain struct with associated constants that needs no dependencies.
+#[derive(Debug, Clone)]
+pub struct FlagSet {
+    /// The name of the flag set.
+    name: Cow<'static, str>,
+    /// The underlying integer type, e.g. `u32`.
+    bits: Cow<'static, str>,
+    /// The visibility of the flag set.
+    vis: Option<String>,
+    /// The documentation of the flag set.
+    docs: Option<Cow<'static, str>>,
+    /// The flags.
+    flags: Vec<Flag>,
+    /// Whether to generate a `bitflags!` invocation.
+    bitflags: bool,
+}
+
+impl FlagSet {
+    /// Return a new flag set with the given name and integer type.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the flag set.
+    /// * `bits` - The underlying integer type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FlagSet;
+    ///
+    /// let permissions = FlagSet::new("Permissions", "u32");
+    /// ```
+    pub fn new<N, B>(name: N, bits: B) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+        B: Into<Cow<'static, str>>,
+    {
+        FlagSet {
+            name: name.into(),
+            bits: bits.into(),
+            vis: None,
+            docs: None,
+            flags: vec![],
+            bitflags: false,
+        }
+    }
+
+    /// Set the visibility of the flag set.
+    ///
+    /// # Arguments
+    ///
+    /// * `vis` - The visibility to set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FlagSet;
+    ///
+    /// let mut permissions = FlagSet::new("Permissions", "u32");
+    /// permissions.vis("pub");
+    /// ```
+    pub fn vis(&mut self, vis: &str) -> &mut Self {
+        self.vis = Some(vis.to_string());
+        self
+    }
+
+    /// Set the documentation of the flag set.
+    ///
+    /// # Arguments
+    ///
+    /// * `docs` - The documentation to set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FlagSet;
+    ///
+    /// let mut permissions = FlagSet::new("Permissions", "u32");
+    /// permissions.doc("File permissions.");
+    /// ```
+    pub fn doc<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.docs = Some(docs.into());
+        self
+    }
+
+    /// Set whether to generate a `bitflags!` invocation instead of a plain
+    /// struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `bitflags` - Indicates whether to use the `bitflags` crate or not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FlagSet;
+    ///
+    /// let mut permissions = FlagSet::new("Permissions", "u32");
+    /// permissions.bitflags(true);
+    /// ```
+    pub fn bitflags(&mut self, bitflags: bool) -> &mut Self {
+        self.bitflags = bitflags;
+        self
+    }
+
+    /// Push a new flag, returning a mutable reference to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the flag.
+    /// * `value` - The value of the flag.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FlagSet;
+    ///
+    /// let mut permissions = FlagSet::new("Permissions", "u32");
+    /// permissions.new_flag("READ", "1 << 0").doc("Read access.");
+    /// ```
+    pub fn new_flag<N, V>(&mut self, name: N, value: V) -> &mut Flag
+    where
+        N: Into<Cow<'static, str>>,
+        V: Into<Cow<'static, str>>,
+    {
+        self.push_flag(Flag::new(name, value));
+        self.flags.last_mut().unwrap()
+    }
+
+    /// Push a flag.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - The flag to push.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Flag,FlagSet};
+    ///
+    /// let mut permissions = FlagSet::new("Permissions", "u32");
+    /// permissions.push_flag(Flag::new("READ", "1 << 0"));
+    /// ```
+    pub fn push_flag(&mut self, item: Flag) -> &mut Self {
+        self.flags.push(item);
+        self
+    }
+
+    /// Push the flag set to the given scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - The scope to push the flag set to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{FlagSet,Scope};
+    ///
+    /// let mut scope = Scope::new();
+    /// let mut permissions = FlagSet::new("Permissions", "u32");
+    /// permissions.new_flag("READ", "1 << 0");
+    ///
+    /// permissions.push_to(&mut scope);
+    /// ```
+    pub fn push_to(&self, scope: &mut Scope) {
+        if self.bitflags {
+            scope.raw(self.to_bitflags());
+        } else {
+            scope.push_struct(self.to_struct());
+
+            for item in self.to_impls() {
+                scope.push_impl(item);
+            }
+        }
+    }
+
+    /// Returns the `bitflags!` invocation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FlagSet;
+    ///
+    /// let mut permissions = FlagSet::new("Permissions", "u32");
+    /// permissions.new_flag("READ", "1 << 0");
+    ///
+    /// let invocation = permissions.to_bitflags();
+    /// ```
+    pub fn to_bitflags(&self) -> String {
+        let mut ret = String::new();
+        let mut fmt = Formatter::new(&mut ret);
+
+        write!(fmt, "bitflags::bitflags!").unwrap();
+        fmt.block(|fmt| {
+            if let Some(ref docs) = self.docs {
+                Docs::new(docs.clone()).fmt(fmt)?;
+            }
+
+            if let Some(ref vis) = self.vis {
+                write!(fmt, "{} ", vis)?;
+            }
+
+            write!(fmt, "struct {}: {}", self.name, self.bits)?;
+            fmt.block(|fmt| {
+                for flag in &self.flags {
+                    if let Some(ref docs) = flag.docs {
+                        Docs::new(docs.clone()).fmt(fmt)?;
+                    }
+
+                    writeln!(fmt, "const {} = {};", flag.name, flag.value)?;
+                }
+
+                Ok(())
+            })
+        })
+        .unwrap();
+
+        ret.pop();
+        ret
+    }
+
+    /// Returns the plain struct wrapping the integer type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FlagSet;
+    ///
+    /// let permissions = FlagSet::new("Permissions", "u32");
+    /// let permissions_struct = permissions.to_struct();
+    /// ```
+    pub fn to_struct(&self) -> Struct {
+        let mut r#struct = Struct::new(self.name.clone());
+
+        if let Some(ref docs) = self.docs {
+            r#struct.doc(docs.clone());
+        }
+
+        if let Some(ref vis) = self.vis {
+            r#struct.vis(vis);
+        }
+
+        for derive in &["Debug", "Clone", "Copy", "PartialEq", "Eq", "Hash", "Default"] {
+            r#struct.derive(*derive);
+        }
+
+        r#struct.tuple_field(self.bits.clone());
+        r#struct
+    }
+
+    /// Returns the impl block with a constant per flag and the bit operator
+    /// impls of the plain struct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FlagSet;
+    ///
+    /// let permissions = FlagSet::new("Permissions", "u32");
+    /// let impls = permissions.to_impls();
+    /// ```
+    pub fn to_impls(&self) -> Vec<Impl> {
+        let mut consts = Impl::new(self.name.clone());
+
+        for flag in &self.flags {
+            let r#const = consts.new_associate_const(
+                flag.name.clone(),
+                self.name.clone(),
+                format!("{}({})", self.name, flag.value),
+            );
+            r#const.vis("pub");
+
+            if let Some(ref docs) = flag.docs {
+                r#const.doc(docs.clone());
+            }
+        }
+
+        consts
+            .new_fn("empty")
+            .vis("pub")
+            .ret("Self")
+            .line(format!("{}(0)", self.name));
+
+        consts
+            .new_fn("bits")
+            .vis("pub")
+            .arg_ref_self()
+            .ret(self.bits.clone())
+            .line("self.0");
+
+        consts
+            .new_fn("contains")
+            .vis("pub")
+            .arg_ref_self()
+            .arg("other", "Self")
+            .ret("bool")
+            .line("self.0 & other.0 == other.0");
+
+        consts
+            .new_fn("insert")
+            .vis("pub")
+            .arg_mut_self()
+            .arg("other", "Self")
+            .line("self.0 |= other.0;");
+
+        consts
+            .new_fn("remove")
+            .vis("pub")
+            .arg_mut_self()
+            .arg("other", "Self")
+            .line("self.0 &= !other.0;");
+
+        let mut impls = vec![consts];
+
+        for (op, method, symbol) in &[
+            ("BitOr", "bitor", "|"),
+            ("BitAnd", "bitand", "&"),
+            ("BitXor", "bitxor", "^"),
+        ] {
+            let mut op_impl = Impl::new(self.name.clone());
+            op_impl
+                .impl_trait(format!("std::ops::{}", op))
+                .associate_type("Output", "Self");
+            op_impl
+                .new_fn(*method)
+                .arg_self()
+                .arg("rhs", "Self")
+                .ret("Self")
+                .line(format!("{}(self.0 {} rhs.0)", self.name, symbol));
+
+            impls.push(op_impl);
+        }
+
+        impls
+    }
+}
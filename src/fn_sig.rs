@@ -0,0 +1,418 @@
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+
+use crate::bound::Bound;
+use crate::field::Field;
+use crate::formatter::{fmt_bounds, fmt_generics, fmt_to_code_string, Formatter};
+use crate::receiver::Receiver;
+
+use crate::r#type::Type;
+
+/// Defines the signature of a function: its name, generics, arguments,
+/// return type, bounds, and qualifiers.
+///
+/// A signature can be reused between a trait and its impls, e.g. by adding
+/// it to a trait with [`Trait::push_sig`] and building the impl function
+/// with [`Function::from_sig`]. Each of them stores its own copy, so editing
+/// the signature afterwards changes only the copy that is edited. Edit the
+/// signature on the trait and call [`Scope::sync_trait_sigs`] to copy it to
+/// the impls.
+///
+/// [`Trait::push_sig`]: crate::Trait::push_sig
+/// [`Function::from_sig`]: crate::Function::from_sig
+/// [`Scope::sync_trait_sigs`]: crate::Scope::sync_trait_sigs
+#[derive(Debug, Clone)]
+pub struct FnSig {
+    /// The name of the function.
+    pub(crate) name: Cow<'static, str>,
+    /// Function generics.
+    pub(crate) generics: Vec<String>,
+    /// How the function takes `self`, if it does.
+    pub(crate) receiver: Option<Receiver>,
+    /// Function arguments.
+    pub(crate) args: Vec<Field>,
+    /// Return type.
+    pub(crate) ret: Option<Type>,
+    /// Where bounds.
+    pub(crate) bounds: Vec<Bound>,
+    /// Function `extern` ABI.
+    pub(crate) extern_abi: Option<String>,
+    /// Whether or not this function is `async` or not.
+    pub(crate) r#async: bool,
+    /// Whether this function is a `const fn`.
+    pub(crate) r#const: bool,
+}
+
+impl FnSig {
+    /// Return a new function signature.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FnSig;
+    ///
+    /// let foo_sig = FnSig::new("foo_fn");
+    /// ```
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        FnSig {
+            name: name.into(),
+            generics: vec![],
+            receiver: None,
+            args: vec![],
+            ret: None,
+            bounds: vec![],
+            extern_abi: None,
+            r#async: false,
+            r#const: false,
+        }
+    }
+
+    /// Returns the name of the function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FnSig;
+    ///
+    /// let foo_sig = FnSig::new("foo_fn");
+    /// assert_eq!(foo_sig.name(), "foo_fn");
+    /// ```
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Set whether this function is async or not.
+    ///
+    /// # Arguments
+    ///
+    /// * `async` - Indicates whether this function is async or not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FnSig;
+    ///
+    /// let mut foo_sig = FnSig::new("foo_fn");
+    /// foo_sig.set_async(true);
+    /// ```
+    pub fn set_async(&mut self, r#async: bool) -> &mut Self {
+        self.r#async = r#async;
+        self
+    }
+
+    /// Set whether this function is a `const fn`.
+    ///
+    /// # Arguments
+    ///
+    /// * `r#const` - Indicates whether this function is const or not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FnSig;
+    ///
+    /// let mut foo_sig = FnSig::new("foo_fn");
+    /// foo_sig.set_const(true);
+    /// ```
+    pub fn set_const(&mut self, r#const: bool) -> &mut Self {
+        self.r#const = r#const;
+        self
+    }
+
+    /// Add a generic to the function.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the generic to add.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FnSig;
+    ///
+    /// let mut foo_sig = FnSig::new("foo_fn");
+    /// foo_sig.generic("T");
+    /// ```
+    pub fn generic(&mut self, name: &str) -> &mut Self {
+        self.generics.push(name.to_string());
+        self
+    }
+
+    /// Add `self` as a function argument.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FnSig;
+    ///
+    /// let mut foo_sig = FnSig::new("foo_fn");
+    /// foo_sig.arg_self();
+    /// ```
+    pub fn arg_self(&mut self) -> &mut Self {
+        self.receiver(Receiver::Owned)
+    }
+
+    /// Add `&self` as a function argument.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FnSig;
+    ///
+    /// let mut foo_sig = FnSig::new("foo_fn");
+    /// foo_sig.arg_ref_self();
+    /// ```
+    pub fn arg_ref_self(&mut self) -> &mut Self {
+        self.receiver(Receiver::Ref)
+    }
+
+    /// Add `&mut self` as a function argument.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FnSig;
+    ///
+    /// let mut foo_sig = FnSig::new("foo_fn");
+    /// foo_sig.arg_mut_self();
+    /// ```
+    pub fn arg_mut_self(&mut self) -> &mut Self {
+        self.receiver(Receiver::RefMut)
+    }
+
+    /// Set how the function takes `self`.
+    ///
+    /// # Arguments
+    ///
+    /// * `receiver` - The receiver of the function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{FnSig,Receiver};
+    ///
+    /// let mut foo_sig = FnSig::new("foo_fn");
+    /// foo_sig.receiver(Receiver::Typed("Box<Self>".into()));
+    /// ```
+    pub fn receiver(&mut self, receiver: Receiver) -> &mut Self {
+        self.receiver = Some(receiver);
+        self
+    }
+
+    /// Returns how the function takes `self`, or `None` if it doesn't.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{FnSig,Receiver};
+    ///
+    /// let mut foo_sig = FnSig::new("foo_fn");
+    /// foo_sig.arg_mut_self();
+    ///
+    /// assert!(matches!(foo_sig.get_receiver(), Some(Receiver::RefMut)));
+    /// ```
+    pub fn get_receiver(&self) -> Option<&Receiver> {
+        self.receiver.as_ref()
+    }
+
+    /// Add a function argument.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the argument.
+    /// * `ty` - The type of the argument.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FnSig;
+    ///
+    /// let mut foo_sig = FnSig::new("foo_fn");
+    /// foo_sig.arg("name", "&str");
+    /// ```
+    pub fn arg<N, T>(&mut self, name: N, ty: T) -> &mut Self
+    where
+        N: Into<Cow<'static, str>>,
+        T: Into<Type>,
+    {
+        // While a `Field` is used here, `documentation` and `annotation` do
+        // not make sense for function arguments and are not rendered.
+        self.args.push(Field::new(name, ty));
+
+        self
+    }
+
+    /// Push a function argument, e.g. one with `#[cfg(...)]` or
+    /// `#[allow(...)]` attributes. The documentation and annotations of the
+    /// field are not rendered.
+    ///
+    /// # Arguments
+    ///
+    /// * `arg` - The argument to push.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Field,FnSig};
+    ///
+    /// let mut trace_arg = Field::new("trace_id", "u64");
+    /// trace_arg.cfg("feature = \"tracing\"");
+    ///
+    /// let mut foo_sig = FnSig::new("foo_fn");
+    /// foo_sig.push_arg(trace_arg);
+    /// ```
+    pub fn push_arg(&mut self, arg: Field) -> &mut Self {
+        self.args.push(arg);
+        self
+    }
+
+    /// Set the function return type.
+    ///
+    /// # Arguments
+    ///
+    /// * `ty` - The return type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FnSig;
+    ///
+    /// let mut foo_sig = FnSig::new("foo_fn");
+    /// foo_sig.ret("String");
+    /// ```
+    pub fn ret<T>(&mut self, ty: T) -> &mut Self
+    where
+        T: Into<Type>,
+    {
+        self.ret = Some(ty.into());
+        self
+    }
+
+    /// Add a `where` bound to the function.
+    ///
+    /// # Arguments
+    ///
+    /// * `name ` - The name of the bound.
+    /// * `ty` - The type of the bound.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FnSig;
+    ///
+    /// let mut foo_sig = FnSig::new("foo_fn");
+    /// foo_sig.bound("A", "TraitA");
+    /// ```
+    pub fn bound<T>(&mut self, name: &str, ty: T) -> &mut Self
+    where
+        T: Into<Type>,
+    {
+        self.bounds.push(Bound {
+            name: name.to_string(),
+            bound: vec![ty.into()],
+        });
+        self
+    }
+
+    /// Specify an `extern` ABI for the function.
+    ///
+    /// # Arguments
+    ///
+    /// * `abi` - The extern ABI to add.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FnSig;
+    ///
+    /// let mut foo_sig = FnSig::new("foo_fn");
+    /// foo_sig.extern_abi("C");
+    /// ```
+    pub fn extern_abi(&mut self, abi: &str) -> &mut Self {
+        self.extern_abi.replace(abi.to_string());
+        self
+    }
+
+    /// Returns the signature as a string of code formatted with a fresh
+    /// formatter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::FnSig;
+    ///
+    /// let mut foo_sig = FnSig::new("foo");
+    /// foo_sig.arg_ref_self().ret("u32");
+    ///
+    /// assert_eq!(foo_sig.to_code_string(), "fn foo(&self) -> u32");
+    /// ```
+    pub fn to_code_string(&self) -> String {
+        fmt_to_code_string(|fmt| self.fmt(fmt))
+    }
+
+    /// Formats the signature using the given formatter, from the qualifiers
+    /// to the `where` bounds.
+    ///
+    /// # Arguments
+    ///
+    /// * `fmt` - The formatter to use.
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_head(fmt)?;
+        fmt_bounds(&self.bounds, fmt)
+    }
+
+    /// Formats the signature without its `where` bounds, so that the caller
+    /// can end them, e.g. with `;` for a function without a body.
+    ///
+    /// # Arguments
+    ///
+    /// * `fmt` - The formatter to use.
+    pub(crate) fn fmt_head(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if self.r#const {
+            write!(fmt, "const ")?;
+        }
+
+        if let Some(ref extern_abi) = self.extern_abi {
+            write!(fmt, "extern \"{extern_abi}\" ", extern_abi = extern_abi)?;
+        }
+
+        if self.r#async {
+            write!(fmt, "async ")?;
+        }
+
+        write!(fmt, "fn {}", self.name)?;
+        fmt_generics(&self.generics, fmt)?;
+
+        write!(fmt, "(")?;
+
+        if let Some(ref receiver) = self.receiver {
+            write!(fmt, "{}", receiver)?;
+        }
+
+        for (i, arg) in self.args.iter().enumerate() {
+            if i != 0 || self.receiver.is_some() {
+                write!(fmt, ", ")?;
+            }
+
+            arg.attributes().fmt_inline(fmt)?;
+            write!(fmt, "{}: ", arg.name)?;
+            arg.ty.fmt(fmt)?;
+        }
+
+        write!(fmt, ")")?;
+
+        if let Some(ref ret) = self.ret {
+            write!(fmt, " -> ")?;
+            ret.fmt(fmt)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,68 @@
+use std::fmt;
+
+use crate::formatter::Formatter;
+
+/// Defines a custom kind of item, e.g. a domain-specific macro invocation,
+/// that can be pushed to a scope or module with `push_custom` alongside the
+/// built-in items.
+///
+/// Implementors only need to derive `Clone` and `Debug` and implement `fmt`.
+///
+/// # Examples
+///
+/// ```
+/// use std::fmt::{self, Write};
+///
+/// use rust_codegen::{FormatItem, Formatter, Scope};
+///
+/// #[derive(Debug, Clone)]
+/// struct Routes(Vec<&'static str>);
+///
+/// impl FormatItem for Routes {
+///     fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+///         write!(fmt, "routes!")?;
+///         fmt.block(|fmt| {
+///             for route in &self.0 {
+///                 write!(fmt, "{},\n", route)?;
+///             }
+///             Ok(())
+///         })
+///     }
+/// }
+///
+/// let mut scope = Scope::new();
+/// scope.push_custom(Routes(vec!["index", "login"]));
+///
+/// assert_eq!(scope.to_string(), "routes! {\n    index,\n    login,\n}");
+/// ```
+pub trait FormatItem: FormatItemClone + fmt::Debug + Send + Sync {
+    /// Formats the item using the given formatter. The item should end with
+    /// a newline.
+    ///
+    /// # Arguments
+    ///
+    /// * `fmt` - The formatter to use.
+    fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result;
+}
+
+/// Clones boxed custom items, implemented for every `FormatItem` that is
+/// `Clone`.
+pub trait FormatItemClone {
+    /// Returns a boxed clone of the item.
+    fn clone_box(&self) -> Box<dyn FormatItem>;
+}
+
+impl<T> FormatItemClone for T
+where
+    T: FormatItem + Clone + 'static,
+{
+    fn clone_box(&self) -> Box<dyn FormatItem> {
+        Box::new(self.clone())
+    }
+}
+
+impl Clone for Box<dyn FormatItem> {
+    fn clone(&self) -> Self {
+        self.clone_box()
+    }
+}
@@ -1,11 +1,97 @@
 use std::fmt::{self, Write};
 
 use crate::bound::Bound;
+use crate::render_hook::RenderHook;
+use crate::source_map::SourceMap;
 use crate::r#type::Type;
 
 /// The default value to use for any indentation values.
 const DEFAULT_INDENT: usize = 4;
 
+/// Defines how function bodies are rendered, see [`Scope::body_mode`].
+///
+/// [`Scope::body_mode`]: crate::Scope::body_mode
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum BodyMode {
+    /// Render the bodies as they are.
+    #[default]
+    Keep,
+    /// Replace every body with `unimplemented!()`, e.g. for a mock crate.
+    Unimplemented,
+    /// Omit every body and end the signatures with `;`, e.g. for
+    /// header-like output. The output only compiles in trait and extern
+    /// contexts.
+    Omit,
+}
+
+/// Defines how the visibility of trait functions is handled, since they
+/// can't have one, see [`Formatter::set_trait_fn_vis`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TraitFnVis {
+    /// Render the functions without their visibility, e.g. when the same
+    /// `Function` is pushed to an impl and a trait.
+    #[default]
+    Drop,
+    /// Fail formatting with `fmt::Error`. Use [`Scope::check_trait_fn_vis`]
+    /// to find the functions.
+    ///
+    /// [`Scope::check_trait_fn_vis`]: crate::Scope::check_trait_fn_vis
+    Error,
+}
+
+/// Defines the layout of `where` clauses, see [`Scope::where_style`].
+///
+/// [`Scope::where_style`]: crate::Scope::where_style
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum WhereStyle {
+    /// `where` on a new line with the bounds aligned under the first one,
+    /// e.g. `where T: Clone,` followed by `      U: Send,`.
+    #[default]
+    Aligned,
+    /// `where` on a new line and one indented bound per line, as formatted
+    /// by rustfmt.
+    Rustfmt,
+    /// Every bound on the same line as the signature, e.g.
+    /// `fn foo<T>(t: T) where T: Clone {`.
+    SameLine,
+    /// Every bound on a single line after the signature, e.g.
+    /// `where T: Clone, U: Send`.
+    Compact,
+}
+
+/// Defines the delimiters around an indented group of lines, see
+/// [`Formatter::delimited`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Delimiter {
+    /// `{` and `}`, preceded by a space unless the formatter has
+    /// `space_before_brace` turned off.
+    Brace,
+    /// `[` and `]`.
+    Bracket,
+    /// `(` and `)`.
+    Paren,
+}
+
+impl Delimiter {
+    /// Returns the opening delimiter.
+    fn open(&self) -> char {
+        match *self {
+            Delimiter::Brace => '{',
+            Delimiter::Bracket => '[',
+            Delimiter::Paren => '(',
+        }
+    }
+
+    /// Returns the closing delimiter.
+    fn close(&self) -> char {
+        match *self {
+            Delimiter::Brace => '}',
+            Delimiter::Bracket => ']',
+            Delimiter::Paren => ')',
+        }
+    }
+}
+
 /// Configures how a scope is formatted.
 #[derive(Debug)]
 pub struct Formatter<'a> {
@@ -15,6 +101,26 @@ pub struct Formatter<'a> {
     spaces: usize,
     /// Number of spaces per indentiation.
     indent: usize,
+    /// The source map being recorded, if any.
+    pub(crate) source_map: Option<SourceMap>,
+    /// The hooks called around each item, if any.
+    pub(crate) hook: Option<&'a mut dyn RenderHook>,
+    /// How function bodies are rendered.
+    pub(crate) body_mode: BodyMode,
+    /// How the visibility of trait functions is handled.
+    pub(crate) trait_fn_vis: TraitFnVis,
+    /// The layout of `where` clauses.
+    pub(crate) where_style: WhereStyle,
+    /// Whether the last field, variant, or `where` bound of a list is
+    /// followed by a comma.
+    pub(crate) trailing_comma: bool,
+    /// Whether an opening brace is preceded by a space, e.g. `Foo {`.
+    pub(crate) space_before_brace: bool,
+    /// The width beyond which use trees and derive lists are wrapped, if
+    /// any.
+    pub(crate) max_width: Option<usize>,
+    /// Whether raw items are preceded by `#[rustfmt::skip]`.
+    pub(crate) rustfmt_skip_raw: bool,
 }
 
 impl<'a> Formatter<'a> {
@@ -37,21 +143,382 @@ impl<'a> Formatter<'a> {
             dst,
             spaces: 0,
             indent: DEFAULT_INDENT,
+            source_map: None,
+            hook: None,
+            body_mode: BodyMode::Keep,
+            trait_fn_vis: TraitFnVis::Drop,
+            where_style: WhereStyle::Aligned,
+            trailing_comma: true,
+            space_before_brace: true,
+            max_width: None,
+            rustfmt_skip_raw: false,
+        }
+    }
+
+    /// Set the hooks called before and after each item of a scope and its
+    /// modules is formatted.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `hook` - The hooks to call.
+    pub fn set_hook(&mut self, hook: &'a mut dyn RenderHook) -> &mut Self {
+        self.hook = Some(hook);
+        self
+    }
+
+    /// Set how function bodies are rendered, e.g. to replace them with
+    /// `unimplemented!()`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `mode` - How to render function bodies.
+    pub fn set_body_mode(&mut self, mode: BodyMode) -> &mut Self {
+        self.body_mode = mode;
+        self
+    }
+
+    /// Set how the visibility of trait functions is handled. By default it
+    /// is dropped.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `mode` - How to handle the visibility of trait functions.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Formatter, Scope, TraitFnVis};
+    /// 
+    /// let mut scope = Scope::new();
+    /// scope.new_trait("Foo").new_fn("bar").vis("pub");
+    /// 
+    /// let mut dest = String::new();
+    /// let mut fmt = Formatter::new(&mut dest);
+    /// fmt.set_trait_fn_vis(TraitFnVis::Error);
+    /// 
+    /// assert!(scope.fmt(&mut fmt).is_err());
+    /// ```
+    pub fn set_trait_fn_vis(&mut self, mode: TraitFnVis) -> &mut Self {
+        self.trait_fn_vis = mode;
+        self
+    }
+
+    /// Set the layout of `where` clauses.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `style` - The layout of `where` clauses.
+    pub fn set_where_style(&mut self, style: WhereStyle) -> &mut Self {
+        self.where_style = style;
+        self
+    }
+
+    /// Set whether the last field, variant, or `where` bound of a list is
+    /// followed by a comma. Enabled by default.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `trailing_comma` - Whether to write trailing commas.
+    pub fn set_trailing_comma(&mut self, trailing_comma: bool) -> &mut Self {
+        self.trailing_comma = trailing_comma;
+        self
+    }
+
+    /// Set whether an opening brace is preceded by a space, e.g. `Foo {`
+    /// rather than `Foo{`. Enabled by default.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `space` - Whether to write a space before opening braces.
+    pub fn set_space_before_brace(&mut self, space: bool) -> &mut Self {
+        self.space_before_brace = space;
+        self
+    }
+
+    /// Set the width beyond which use trees and derive lists are wrapped
+    /// with one item per line, including the indentation. They are never
+    /// wrapped by default.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `width` - The maximum width of a line.
+    pub fn set_max_width(&mut self, width: usize) -> &mut Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Set whether raw items are preceded by `#[rustfmt::skip]`, so that
+    /// formatting passes keep their layout. Disabled by default.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `skip` - Whether to protect raw items from rustfmt.
+    pub fn set_rustfmt_skip_raw(&mut self, skip: bool) -> &mut Self {
+        self.rustfmt_skip_raw = skip;
+        self
+    }
+
+    /// Writes the given items between the given opening and closing
+    /// delimiters, on a single line if it fits in the maximum width and with
+    /// one indented item per line otherwise, matching how use trees and
+    /// derive lists are wrapped.
+    /// 
+    /// On a single line the items are separated by the separator and a
+    /// space. On several lines every item is followed by the separator,
+    /// except the last one when the formatter has no trailing commas.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `open` - The text before the items, e.g. `use std::{`.
+    /// * `items` - The items.
+    /// * `sep` - The separator of the items, e.g. `,`.
+    /// * `close` - The text after the items, e.g. `};`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Formatter;
+    ///
+    /// let mut dest = String::new();
+    /// let mut fmt = Formatter::new(&mut dest);
+    /// fmt.set_max_width(20);
+    ///
+    /// fmt.wrap_list("call(", &["first", "second", "third"], ",", ")").unwrap();
+    ///
+    /// assert_eq!(dest, "call(\n    first,\n    second,\n    third,\n)");
+    /// ```
+    pub fn wrap_list<T>(&mut self, open: &str, items: &[T], sep: &str, close: &str) -> fmt::Result
+    where
+        T: AsRef<str>,
+    {
+        let items: Vec<&str> = items.iter().map(|item| item.as_ref()).collect();
+        let inline = format!("{}{}{}", open, items.join(&format!("{} ", sep)), close);
+
+        let fits = match self.max_width {
+            Some(width) => self.column() + inline.len() <= width,
+            None => true,
+        };
+
+        if fits {
+            return write!(self, "{}", inline);
+        }
+
+        write!(self, "{}\n", open)?;
+
+        self.indent(|fmt| {
+            for (i, item) in items.iter().enumerate() {
+                let sep = if fmt.list_comma(i, items.len()).is_empty() { "" } else { sep };
+                write!(fmt, "{}{}\n", item, sep)?;
+            }
+
+            Ok(())
+        })?;
+
+        write!(self, "{}", close)
+    }
+
+    /// Writes the separator between a header and its opening brace, if any.
+    pub(crate) fn fmt_brace_space(&mut self) -> fmt::Result {
+        if !self.is_start_of_line() && !self.dst.ends_with(' ') && self.space_before_brace {
+            write!(self, " ")?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the comma that follows the item at the given index of a list
+    /// of the given length.
+    pub(crate) fn list_comma(&self, i: usize, len: usize) -> &'static str {
+        if i + 1 < len || self.trailing_comma {
+            ","
+        } else {
+            ""
         }
     }
 
     /// Wrap the given function inside a block.
     pub fn block<F>(&mut self, f: F) -> fmt::Result
+    where
+        F: FnOnce(&mut Self) -> fmt::Result,
+    {
+        self.braced("", f)
+    }
+
+    /// Writes the given prefix followed by an indented block of the lines
+    /// written by the given function, e.g. `match value {` and its arms.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `prefix` - The text before the opening brace, e.g. `match value`.
+    /// * `f` - The function writing the contents of the block.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use std::fmt::Write;
+    ///
+    /// use rust_codegen::Formatter;
+    ///
+    /// let mut dest = String::new();
+    /// let mut fmt = Formatter::new(&mut dest);
+    ///
+    /// fmt.braced("match value", |fmt| write!(fmt, "_ => {{}}\n")).unwrap();
+    ///
+    /// assert_eq!(dest, "match value {\n    _ => {}\n}\n");
+    /// ```
+    pub fn braced<F>(&mut self, prefix: &str, f: F) -> fmt::Result
+    where
+        F: FnOnce(&mut Self) -> fmt::Result,
+    {
+        self.delimited(prefix, Delimiter::Brace, "", f)
+    }
+
+    /// Writes the given prefix followed by an indented group of the lines
+    /// written by the given function between brackets, e.g. `vec![`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `prefix` - The text before the opening bracket, e.g. `vec!`.
+    /// * `f` - The function writing the contents of the group.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use std::fmt::Write;
+    ///
+    /// use rust_codegen::Formatter;
+    ///
+    /// let mut dest = String::new();
+    /// let mut fmt = Formatter::new(&mut dest);
+    ///
+    /// fmt.bracketed("vec!", |fmt| write!(fmt, "1,\n2,\n")).unwrap();
+    ///
+    /// assert_eq!(dest, "vec![\n    1,\n    2,\n]\n");
+    /// ```
+    pub fn bracketed<F>(&mut self, prefix: &str, f: F) -> fmt::Result
+    where
+        F: FnOnce(&mut Self) -> fmt::Result,
+    {
+        self.delimited(prefix, Delimiter::Bracket, "", f)
+    }
+
+    /// Writes the given prefix followed by an indented group of the lines
+    /// written by the given function between parentheses, e.g. the
+    /// arguments of a macro invocation.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `prefix` - The text before the opening parenthesis, e.g.
+    ///   `assert_eq!`.
+    /// * `f` - The function writing the contents of the group.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use std::fmt::Write;
+    ///
+    /// use rust_codegen::Formatter;
+    ///
+    /// let mut dest = String::new();
+    /// let mut fmt = Formatter::new(&mut dest);
+    ///
+    /// fmt.parenthesized("assert_eq!", |fmt| write!(fmt, "a,\nb,\n")).unwrap();
+    ///
+    /// assert_eq!(dest, "assert_eq!(\n    a,\n    b,\n)\n");
+    /// ```
+    pub fn parenthesized<F>(&mut self, prefix: &str, f: F) -> fmt::Result
+    where
+        F: FnOnce(&mut Self) -> fmt::Result,
+    {
+        self.delimited(prefix, Delimiter::Paren, "", f)
+    }
+
+    /// Writes the given prefix followed by an indented group of the lines
+    /// written by the given function between the given delimiters, with
+    /// the given punctuation after the closing delimiter, e.g. `,` after a
+    /// match arm or `;` after a statement.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `prefix` - The text before the opening delimiter.
+    /// * `delimiter` - The delimiters around the group.
+    /// * `trailing` - The text after the closing delimiter, if any.
+    /// * `f` - The function writing the contents of the group.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use std::fmt::Write;
+    ///
+    /// use rust_codegen::{Delimiter, Formatter};
+    ///
+    /// let mut dest = String::new();
+    /// let mut fmt = Formatter::new(&mut dest);
+    ///
+    /// fmt.delimited("let primes = ", Delimiter::Bracket, ";", |fmt| write!(fmt, "2,\n3,\n"))
+    ///     .unwrap();
+    ///
+    /// assert_eq!(dest, "let primes = [\n    2,\n    3,\n];\n");
+    /// ```
+    pub fn delimited<F>(
+        &mut self,
+        prefix: &str,
+        delimiter: Delimiter,
+        trailing: &str,
+        f: F,
+    ) -> fmt::Result
+    where
+        F: FnOnce(&mut Self) -> fmt::Result,
+    {
+        write!(self, "{}", prefix)?;
+
+        if delimiter == Delimiter::Brace {
+            self.fmt_brace_space()?;
+        }
+
+        writeln!(self, "{}", delimiter.open())?;
+        self.indented(f)?;
+        writeln!(self, "{}{}", delimiter.close(), trailing)
+    }
+
+    /// Calls the given function with the indentation level incremented by
+    /// one, making sure that what it writes starts and ends on its own
+    /// lines, e.g. for the body of a macro.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `f` - The function writing the indented lines.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use std::fmt::Write;
+    ///
+    /// use rust_codegen::Formatter;
+    ///
+    /// let mut dest = String::new();
+    /// let mut fmt = Formatter::new(&mut dest);
+    ///
+    /// write!(fmt, "a").unwrap();
+    /// fmt.indented(|fmt| write!(fmt, "b")).unwrap();
+    /// write!(fmt, "c").unwrap();
+    ///
+    /// assert_eq!(dest, "a\n    b\nc");
+    /// ```
+    pub fn indented<F>(&mut self, f: F) -> fmt::Result
     where
         F: FnOnce(&mut Self) -> fmt::Result,
     {
         if !self.is_start_of_line() {
-            write!(self, " ")?;
+            self.write_str("\n")?;
         }
 
-        write!(self, "{{\n")?;
         self.indent(f)?;
-        write!(self, "}}\n")?;
+
+        if !self.is_start_of_line() {
+            self.write_str("\n")?;
+        }
+
         Ok(())
     }
 
@@ -66,11 +533,114 @@ impl<'a> Formatter<'a> {
         ret
     }
 
+    /// Writes the given text as whole lines at the current indentation
+    /// level: on a new line if the destination isn't at the start of one,
+    /// with every non-empty line indented, and ending with a newline. Lines
+    /// keep their indentation relative to each other.
+    /// 
+    /// Any write indents the lines after a `\n` of the text, so this is only
+    /// needed to make sure that the text starts and ends on its own lines.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `text` - The text to write, e.g. a snippet of several lines.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use std::fmt::Write;
+    ///
+    /// use rust_codegen::Formatter;
+    ///
+    /// let mut dest = String::new();
+    /// let mut fmt = Formatter::new(&mut dest);
+    ///
+    /// write!(fmt, "routes!").unwrap();
+    /// fmt.block(|fmt| fmt.write_indented("index,\nlogin,")).unwrap();
+    ///
+    /// assert_eq!(dest, "routes! {\n    index,\n    login,\n}\n");
+    /// ```
+    pub fn write_indented(&mut self, text: &str) -> fmt::Result {
+        if !self.is_start_of_line() {
+            self.write_str("\n")?;
+        }
+
+        self.write_str(text)?;
+
+        if !self.is_start_of_line() {
+            self.write_str("\n")?;
+        }
+
+        Ok(())
+    }
+
+    /// Returns the current indentation level, e.g. `1` in the body of a
+    /// top-level function.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Formatter;
+    ///
+    /// let mut dest = String::new();
+    /// let mut fmt = Formatter::new(&mut dest);
+    ///
+    /// assert_eq!(fmt.indent(|fmt| fmt.indent_level()), 1);
+    /// ```
+    pub fn indent_level(&self) -> usize {
+        self.spaces / self.indent
+    }
+
+    /// Returns the column, starting at 0, of the next character written to
+    /// the destination. At the start of a line, this is the indentation
+    /// that the next write is prefixed with.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use std::fmt::Write;
+    ///
+    /// use rust_codegen::Formatter;
+    ///
+    /// let mut dest = String::new();
+    /// let mut fmt = Formatter::new(&mut dest);
+    ///
+    /// fmt.indent(|fmt| {
+    ///     assert_eq!(fmt.column(), 4);
+    ///
+    ///     write!(fmt, "let x = ").unwrap();
+    ///     assert_eq!(fmt.column(), 12);
+    /// });
+    /// ```
+    pub fn column(&self) -> usize {
+        if self.is_start_of_line() {
+            return self.spaces;
+        }
+
+        let line = match self.dst.rfind('\n') {
+            Some(i) => &self.dst[i + 1..],
+            None => &self.dst[..],
+        };
+
+        line.chars().count()
+    }
+
+    /// Returns the width beyond which lists are wrapped, if any, see
+    /// [`Formatter::set_max_width`].
+    pub fn max_width(&self) -> Option<usize> {
+        self.max_width
+    }
+
     /// Check if current destination is the start of a new line.
     pub fn is_start_of_line(&self) -> bool {
         self.dst.is_empty() || self.dst.as_bytes().last() == Some(&b'\n')
     }
 
+    /// Returns the number of lines written to the destination so far.
+    pub(crate) fn line_count(&self) -> usize {
+        self.dst.matches('\n').count()
+    }
+
     /// Pushes the number of spaces defined for a new line.
     fn push_spaces(&mut self) {
         for _ in 0..self.spaces {
@@ -80,6 +650,8 @@ impl<'a> Formatter<'a> {
 }
 
 impl<'a> fmt::Write for Formatter<'a> {
+    /// Writes the given string, indenting every non-empty line that starts
+    /// in the destination, including the lines after a `\n` of the string.
     fn write_str(&mut self, s: &str) -> fmt::Result {
         let mut first = true;
         let mut should_indent = self.is_start_of_line();
@@ -111,6 +683,41 @@ impl<'a> fmt::Write for Formatter<'a> {
     }
 }
 
+/// Formats with a fresh formatter and returns the code without the trailing
+/// newline.
+///
+/// # Arguments
+///
+/// * `f` - The function formatting the code.
+pub(crate) fn fmt_to_code_string<F>(f: F) -> String
+where
+    F: FnOnce(&mut Formatter<'_>) -> fmt::Result,
+{
+    try_fmt_to_code_string(f).unwrap()
+}
+
+/// Formats with a fresh formatter and returns the code without the trailing
+/// newline, or the error of the formatting function.
+///
+/// # Arguments
+///
+/// * `f` - The function formatting the code.
+pub(crate) fn try_fmt_to_code_string<F>(f: F) -> Result<String, fmt::Error>
+where
+    F: FnOnce(&mut Formatter<'_>) -> fmt::Result,
+{
+    let mut ret = String::new();
+
+    f(&mut Formatter::new(&mut ret))?;
+
+    // Remove the trailing newline
+    if ret.as_bytes().last() == Some(&b'\n') {
+        ret.pop();
+    }
+
+    Ok(ret)
+}
+
 /// Format generics.
 pub fn fmt_generics(generics: &[String], fmt: &mut Formatter<'_>) -> fmt::Result {
     if !generics.is_empty() {
@@ -129,21 +736,118 @@ pub fn fmt_generics(generics: &[String], fmt: &mut Formatter<'_>) -> fmt::Result
     Ok(())
 }
 
-/// Format generic bounds.
+/// Format generic bounds in the `where` style of the formatter.
 pub fn fmt_bounds(bounds: &[Bound], fmt: &mut Formatter<'_>) -> fmt::Result {
-    if !bounds.is_empty() {
-        write!(fmt, "\n")?;
+    fmt_where(bounds, None, fmt)
+}
+
+/// Format generic bounds in the `where` style of the formatter and end the
+/// item they belong to with `;`, e.g. a trait function without a body. The
+/// `;` follows the last bound, as formatted by rustfmt.
+pub fn fmt_bounds_end(bounds: &[Bound], fmt: &mut Formatter<'_>) -> fmt::Result {
+    if bounds.is_empty() {
+        return write!(fmt, ";\n");
+    }
+
+    fmt_where(bounds, Some(";"), fmt)
+}
+
+/// Format generic bounds on the same line and end the item they belong to
+/// with `;`, e.g. `type Iter<'a>: Iterator where Self: 'a;`. If the line
+/// would exceed the maximum width of the formatter, the bounds are wrapped
+/// like with [`fmt_bounds_end`] instead.
+pub fn fmt_short_bounds_end(bounds: &[Bound], fmt: &mut Formatter<'_>) -> fmt::Result {
+    let inline = fmt_to_code_string(|fmt| fmt_inline_bounds(bounds, fmt));
+
+    let fits = match fmt.max_width {
+        Some(width) => fmt.column() + inline.len() < width,
+        None => true,
+    };
+
+    if fits || fmt.where_style == WhereStyle::SameLine {
+        return write!(fmt, "{};\n", inline);
+    }
+
+    fmt_bounds_end(bounds, fmt)
+}
+
+/// Format generic bounds in the `where` style of the formatter, followed by
+/// the given end of the item, if any.
+fn fmt_where(bounds: &[Bound], end: Option<&str>, fmt: &mut Formatter<'_>) -> fmt::Result {
+    if bounds.is_empty() {
+        return Ok(());
+    }
+
+    // The end replaces the comma of the last bound.
+    let sep = |fmt: &Formatter<'_>, i: usize| match end {
+        Some(end) if i + 1 == bounds.len() => end,
+        _ => fmt.list_comma(i, bounds.len()),
+    };
+
+    match fmt.where_style {
+        WhereStyle::Aligned => {
+            write!(fmt, "\n")?;
+
+            for (i, bound) in bounds.iter().enumerate() {
+                // The bounds are aligned under the first one.
+                let prefix = if i == 0 { "where " } else { "      " };
 
-        // Write first bound
-        write!(fmt, "where {}: ", bounds[0].name)?;
-        fmt_bound_rhs(&bounds[0].bound, fmt)?;
-        write!(fmt, ",\n")?;
+                write!(fmt, "{}{}: ", prefix, bound.name)?;
+                fmt_bound_rhs(&bound.bound, fmt)?;
+                write!(fmt, "{}\n", sep(fmt, i))?;
+            }
 
-        for bound in &bounds[1..] {
-            write!(fmt, "      {}: ", bound.name)?;
-            fmt_bound_rhs(&bound.bound, fmt)?;
-            write!(fmt, ",\n")?;
+            Ok(())
         }
+        WhereStyle::Rustfmt => {
+            write!(fmt, "\nwhere\n")?;
+
+            fmt.indent(|fmt| {
+                for (i, bound) in bounds.iter().enumerate() {
+                    write!(fmt, "{}: ", bound.name)?;
+                    fmt_bound_rhs(&bound.bound, fmt)?;
+                    write!(fmt, "{}\n", sep(fmt, i))?;
+                }
+
+                Ok(())
+            })
+        }
+        WhereStyle::SameLine => {
+            fmt_inline_bounds(bounds, fmt)?;
+
+            match end {
+                Some(end) => write!(fmt, "{}\n", end),
+                None => Ok(()),
+            }
+        }
+        WhereStyle::Compact => {
+            write!(fmt, "\nwhere ")?;
+
+            for (i, bound) in bounds.iter().enumerate() {
+                if i != 0 {
+                    write!(fmt, ", ")?;
+                }
+
+                write!(fmt, "{}: ", bound.name)?;
+                fmt_bound_rhs(&bound.bound, fmt)?;
+            }
+
+            write!(fmt, "{}\n", end.unwrap_or(""))
+        }
+    }
+}
+
+/// Format generic bounds on the same line, e.g. ` where T: Clone`.
+pub fn fmt_inline_bounds(bounds: &[Bound], fmt: &mut Formatter<'_>) -> fmt::Result {
+    for (i, bound) in bounds.iter().enumerate() {
+        if i == 0 {
+            write!(fmt, " where ")?;
+        } else {
+            write!(fmt, ", ")?;
+        }
+
+        write!(fmt, "{}: ", bound.name)?;
+        fmt_bound_rhs(&bound.bound, fmt)?;
     }
 
     Ok(())
@@ -1,44 +1,90 @@
+use std::borrow::Cow;
 use std::fmt::{self, Write};
+use std::sync::Arc;
 
-use crate::block::Block;
+use crate::asm::Asm;
+use crate::async_variant::AsyncVariant;
+use crate::attributes::Attributes;
+use crate::block::{cfg_line, Block};
 use crate::body::Body;
-use crate::bound::Bound;
-use crate::docs::Docs;
+use crate::body_builder::{BodyBuilder, LazyBody};
+use crate::docs::{DocTest, Docs};
+use crate::ffi::looks_ffi_safe;
 use crate::field::Field;
-use crate::formatter::Formatter;
-use crate::formatter::{fmt_bounds, fmt_generics};
+use crate::fn_sig::FnSig;
+use crate::formatter::{BodyMode, Formatter, TraitFnVis};
+use crate::formatter::{fmt_bounds, fmt_bounds_end, fmt_generics, fmt_inline_bounds, fmt_to_code_string};
+use crate::literal::quote_str;
+use crate::metadata::Metadata;
+use crate::policy::Policy;
+use crate::receiver::Receiver;
+use crate::template::render_template;
+use crate::test_runner::TestRunner;
+use crate::transform::Transform;
+use crate::visibility::{map_vis, Visibility};
+use crate::wasm_bindgen::BindgenAttrs;
 
 use crate::r#type::Type;
 
+/// The attributes that are kept when declaring a function in an `extern`
+/// block, along with `#[cfg(...)]` and `#[allow(...)]`.
+const EXTERN_DECL_ATTRIBUTES: &[&str] = &[
+    "link_name",
+    "link_ordinal",
+    "cfg",
+    "cfg_attr",
+    "doc",
+    "deprecated",
+    "warn",
+    "deny",
+    "expect",
+];
+
+/// The constructs that aren't allowed in a `const fn`, along with their
+/// descriptions. A leading `^` matches the start of a line, and `?` matches
+/// the `?` operator after an expression.
+const NON_CONST_CONSTRUCTS: &[(&str, &str)] = &[
+    (".to_string()", "`to_string`"),
+    (".to_owned()", "`to_owned`"),
+    (".clone()", "`clone`"),
+    ("String::from", "`String::from`"),
+    ("format!", "`format!`"),
+    ("vec!", "`vec!`"),
+    ("println!", "`println!`"),
+    ("Box::new", "`Box::new`"),
+    (".collect", "`collect`"),
+    (".iter()", "`iter`"),
+    (".into_iter()", "`into_iter`"),
+    (".await", "`.await`"),
+    ("?", "the `?` operator"),
+    ("^for ", "a `for` loop"),
+];
+
 /// Defines a function.
 #[derive(Debug, Clone)]
 pub struct Function {
-    /// The name of the function.
-    name: String,
+    /// The signature of the function.
+    sig: FnSig,
     /// The function's documentation.
     docs: Option<Docs>,
-    /// A lint attribute used to suppress a warning or error.
-    allow: Option<String>,
     /// Function visibility.
     vis: Option<String>,
-    /// Function generics.
-    generics: Vec<String>,
-    /// If the function takes `&self` or `&mut self`.
-    arg_self: Option<String>,
-    /// Function arguments.
-    args: Vec<Field>,
-    /// Return type.
-    ret: Option<Type>,
-    /// Where bounds.
-    bounds: Vec<Bound>,
     /// Body contents.
     pub body: Option<Vec<Body>>,
-    /// Function attributes, e.g., `#[no_mangle]`.
-    attributes: Vec<String>,
-    /// Function `extern` ABI.
-    extern_abi: Option<String>,
-    /// Whether or not this function is `async` or not.
-    r#async: bool,
+    /// Function attributes, e.g., `#[no_mangle]`, in the order they were
+    /// added.
+    attributes: Attributes,
+    /// Whether this function is a generated accessor, which a scope's policy
+    /// can inline.
+    accessor: bool,
+    /// The docs that a scope's policy can apply when the function has none,
+    /// e.g. the docs of the field that a getter returns.
+    inherited_docs: Option<Docs>,
+    /// The function's metadata, which is never rendered.
+    metadata: Metadata,
+    /// Whether `Ok(())` is written at the end of the body, see
+    /// `ret_boxed_error`.
+    ok_tail: bool,
 }
 
 impl Function {
@@ -55,21 +101,42 @@ impl Function {
     /// 
     /// let foo_fn = Function::new("foo_fn");
     /// ```
-    pub fn new(name: &str) -> Self {
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        Function::from_sig(FnSig::new(name))
+    }
+
+    /// Return a new function definition with the given signature, e.g. one
+    /// shared with a trait declaring the function.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `sig` - The signature of the function.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{FnSig,Function};
+    /// 
+    /// let mut get_sig = FnSig::new("get");
+    /// get_sig.arg_ref_self().ret("u32");
+    /// 
+    /// let mut get_fn = Function::from_sig(get_sig);
+    /// get_fn.line("self.value");
+    /// ```
+    pub fn from_sig(sig: FnSig) -> Self {
         Function {
-            name: name.to_string(),
+            sig,
             docs: None,
-            allow: None,
             vis: None,
-            generics: vec![],
-            arg_self: None,
-            args: vec![],
-            ret: None,
-            bounds: vec![],
             body: Some(vec![]),
-            attributes: vec![],
-            extern_abi: None,
-            r#async: false,
+            attributes: Attributes::new(),
+            accessor: false,
+            inherited_docs: None,
+            metadata: Metadata::new(),
+            ok_tail: false,
         }
     }
 
@@ -87,16 +154,24 @@ impl Function {
     /// let mut foo_fn = Function::new("foo_fn");
     /// foo_fn.doc("Sample Foo function documentation.");
     /// ```
-    pub fn doc(&mut self, docs: &str) -> &mut Self {
-        self.docs = Some(Docs::new(docs));
+    pub fn doc<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        match self.docs {
+            Some(ref mut existing) => existing.set_text(docs, false),
+            None => self.docs = Some(Docs::new(docs)),
+        }
+
         self
     }
 
-    /// Specify lint attribute to supress a warning or error.
+    /// Set the function documentation, written as is without escaping
+    /// markdown, e.g. for intra-doc links.
     /// 
     /// # Arguments
     /// 
-    /// * `allow` - The lint attribute to add.
+    /// * `docs` - The docs to set for the function.
     /// 
     /// # Examples
     /// 
@@ -104,37 +179,49 @@ impl Function {
     /// use rust_codegen::Function;
     /// 
     /// let mut foo_fn = Function::new("foo_fn");
-    /// foo_fn.allow("dead_code");
+    /// foo_fn.doc_raw("Returns a [`Foo`].");
     /// ```
-    pub fn allow(&mut self, allow: &str) -> &mut Self {
-        self.allow = Some(allow.to_string());
+    pub fn doc_raw<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.docs
+            .get_or_insert_with(|| Docs::new(""))
+            .set_text(docs, true);
         self
     }
 
-    /// Set the function visibility.
+    /// Add an example to the function documentation.
     /// 
     /// # Arguments
     /// 
-    /// * `vis` - The visiblity to set for the function.
+    /// * `code` - The code of the example.
+    /// * `mode` - Whether rustdoc should run, only compile, or ignore it.
     /// 
     /// # Examples
     /// 
     /// ```
-    /// use rust_codegen::Function;
+    /// use rust_codegen::{DocTest,Docs,Function};
     /// 
     /// let mut foo_fn = Function::new("foo_fn");
-    /// foo_fn.vis("pub");
+    /// let example = Docs::example_from_fn_in(&foo_fn, "my_crate", None);
+    /// foo_fn.doc_example(example, DocTest::Run);
     /// ```
-    pub fn vis(&mut self, vis: &str) -> &mut Self {
-        self.vis = Some(vis.to_string());
+    pub fn doc_example<T>(&mut self, code: T, mode: DocTest) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.docs
+            .get_or_insert_with(|| Docs::new(""))
+            .example(code, mode);
         self
     }
 
-    /// Set whether this function is async or not.
+    /// Specify lint attribute to supress a warning or error.
     /// 
     /// # Arguments
     /// 
-    /// * `async` - Indicates whether this function is async or not.
+    /// * `allow` - The lint attribute to add.
     /// 
     /// # Examples
     /// 
@@ -142,18 +229,42 @@ impl Function {
     /// use rust_codegen::Function;
     /// 
     /// let mut foo_fn = Function::new("foo_fn");
-    /// foo_fn.set_async(true);
+    /// foo_fn.allow("dead_code");
     /// ```
-    pub fn set_async(&mut self, r#async: bool) -> &mut Self {
-        self.r#async = r#async;
+    pub fn allow<T>(&mut self, allow: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.allow(allow);
         self
     }
 
-    /// Add a generic to the function.
+    /// Returns the metadata of the function, which is kept when it is cloned
+    /// and never rendered.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns a mutable reference to the metadata of the function, e.g. to tag
+    /// it in a multi-pass generator.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    /// 
+    /// let mut foo_fn = Function::new("foo");
+    /// foo_fn.metadata_mut().set("schema", "foo.json");
+    /// ```
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    /// Set the function visibility.
     /// 
     /// # Arguments
     /// 
-    /// * `name` - The name of the generic to add.
+    /// * `vis` - The visiblity to set for the function.
     /// 
     /// # Examples
     /// 
@@ -161,14 +272,18 @@ impl Function {
     /// use rust_codegen::Function;
     /// 
     /// let mut foo_fn = Function::new("foo_fn");
-    /// foo_fn.generic("T");
+    /// foo_fn.vis("pub");
     /// ```
-    pub fn generic(&mut self, name: &str) -> &mut Self {
-        self.generics.push(name.to_string());
+    pub fn vis(&mut self, vis: &str) -> &mut Self {
+        self.vis = Some(vis.to_string());
         self
     }
 
-    /// Add `self` as a function argument.
+    /// Set whether this function is async or not.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `async` - Indicates whether this function is async or not.
     /// 
     /// # Examples
     /// 
@@ -176,14 +291,20 @@ impl Function {
     /// use rust_codegen::Function;
     /// 
     /// let mut foo_fn = Function::new("foo_fn");
-    /// foo_fn.arg_self();
+    /// foo_fn.set_async(true);
     /// ```
-    pub fn arg_self(&mut self) -> &mut Self {
-        self.arg_self = Some("self".to_string());
+    pub fn set_async(&mut self, r#async: bool) -> &mut Self {
+        self.sig.set_async(r#async);
         self
     }
 
-    /// Add `&self` as a function argument.
+    /// Set whether this function is a `const fn`. `Scope::check_const`
+    /// reports the constructs of its body that aren't allowed in a
+    /// `const fn`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `r#const` - Indicates whether this function is const or not.
     /// 
     /// # Examples
     /// 
@@ -191,14 +312,14 @@ impl Function {
     /// use rust_codegen::Function;
     /// 
     /// let mut foo_fn = Function::new("foo_fn");
-    /// foo_fn.arg_ref_self();
+    /// foo_fn.set_const(true);
     /// ```
-    pub fn arg_ref_self(&mut self) -> &mut Self {
-        self.arg_self = Some("&self".to_string());
+    pub fn set_const(&mut self, r#const: bool) -> &mut Self {
+        self.sig.set_const(r#const);
         self
     }
 
-    /// Add `&mut self` as a function argument.
+    /// Returns the signature of the function.
     /// 
     /// # Examples
     /// 
@@ -206,19 +327,15 @@ impl Function {
     /// use rust_codegen::Function;
     /// 
     /// let mut foo_fn = Function::new("foo_fn");
-    /// foo_fn.arg_mut_self();
+    /// foo_fn.arg_ref_self();
+    /// 
+    /// assert_eq!(foo_fn.sig().to_code_string(), "fn foo_fn(&self)");
     /// ```
-    pub fn arg_mut_self(&mut self) -> &mut Self {
-        self.arg_self = Some("&mut self".to_string());
-        self
+    pub fn sig(&self) -> &FnSig {
+        &self.sig
     }
 
-    /// Add a function argument.
-    /// 
-    /// # Arguments
-    /// 
-    /// * `name` - The name of the argument.
-    /// * `ty` - The type of the argument.
+    /// Returns a mutable reference to the signature of the function.
     /// 
     /// # Examples
     /// 
@@ -226,30 +343,46 @@ impl Function {
     /// use rust_codegen::Function;
     /// 
     /// let mut foo_fn = Function::new("foo_fn");
-    /// foo_fn.arg("name", "&str");
+    /// foo_fn.sig_mut().ret("u32");
     /// ```
-    pub fn arg<T>(&mut self, name: &str, ty: T) -> &mut Self
-    where
-        T: Into<Type>,
-    {
-        self.args.push(Field {
-            name: name.to_string(),
-            ty: ty.into(),
-            // While a `Field` is used here, both `documentation`
-            // and `annotation` does not make sense for function arguments.
-            // Simply use empty strings.
-            documentation: Vec::new(),
-            annotation: Vec::new(),
-        });
+    pub fn sig_mut(&mut self) -> &mut FnSig {
+        &mut self.sig
+    }
 
-        self
+    /// Returns the name of the function.
+    pub(crate) fn name(&self) -> &str {
+        &self.sig.name
     }
 
-    /// Set the function return type.
+    /// Returns the visibility of the function.
+    pub(crate) fn get_vis(&self) -> Option<&str> {
+        self.vis.as_deref()
+    }
+
+    /// Returns whether the function is documented.
+    pub(crate) fn has_docs(&self) -> bool {
+        self.docs.is_some()
+    }
+
+    /// Returns the signature of the function without docs, attributes,
+    /// visibility, or body, e.g. `fn login(token: &str);`.
+    pub(crate) fn signature(&self) -> String {
+        let mut func = self.clone();
+
+        func.docs = None;
+        func.attributes = Attributes::new();
+        func.vis = None;
+        func.body = None;
+
+        fmt_to_code_string(|fmt| func.fmt(true, fmt))
+    }
+
+    /// Returns a copy of the function adjusted by the given function, e.g. to
+    /// emit the same logic as a free function, a method, and a trait default.
     /// 
     /// # Arguments
     /// 
-    /// * `ty` - The return type.
+    /// * `f` - The function adjusting the copy.
     /// 
     /// # Examples
     /// 
@@ -257,221 +390,1579 @@ impl Function {
     /// use rust_codegen::Function;
     /// 
     /// let mut foo_fn = Function::new("foo_fn");
-    /// foo_fn.ret("String");
+    /// foo_fn.line("42");
+    /// 
+    /// let async_fn = foo_fn.clone_with(|f| {
+    ///     f.set_async(true);
+    /// });
     /// ```
-    pub fn ret<T>(&mut self, ty: T) -> &mut Self
+    pub fn clone_with<F>(&self, f: F) -> Function
     where
-        T: Into<Type>,
+        F: FnOnce(&mut Function),
     {
-        self.ret = Some(ty.into());
-        self
+        let mut func = self.clone();
+        f(&mut func);
+        func
     }
 
-    /// Add a `where` bound to the function.
+    /// Returns a copy of the function with the given visibility.
     /// 
-    /// # Arguments 
+    /// # Arguments
     /// 
-    /// * `name ` - The name of the bound.
-    /// * `ty` - The type of the bound.
+    /// * `vis` - The visibility of the copy.
     /// 
     /// # Examples
     /// 
     /// ```
-    /// use rust_codegen::Function;
+    /// use rust_codegen::{Function,Visibility};
     /// 
     /// let mut foo_fn = Function::new("foo_fn");
-    /// foo_fn.bound("A", "TraitA");
+    /// foo_fn.vis("pub");
+    /// 
+    /// let private_fn = foo_fn.with_vis(Visibility::Private);
     /// ```
-    pub fn bound<T>(&mut self, name: &str, ty: T) -> &mut Self
-    where
-        T: Into<Type>,
-    {
-        self.bounds.push(Bound {
-            name: name.to_string(),
-            bound: vec![ty.into()],
-        });
-        self
+    pub fn with_vis(&self, vis: Visibility) -> Function {
+        self.clone_with(|func| func.vis = vis.to_vis())
     }
 
-    /// Push a line to the function implementation.
-    /// 
-    /// # Arguments
-    /// 
-    /// * `line` - The line to add to the function.
+    /// Returns a `pub(crate)` copy of the function.
     /// 
     /// # Examples
     /// 
     /// ```
     /// use rust_codegen::Function;
     /// 
-    /// let mut foo_fn = Function::new("foo_fn");
-    /// foo_fn.line("println!(\"Hello, world!\")");
+    /// let foo_fn = Function::new("foo_fn");
+    /// let crate_fn = foo_fn.as_pub_crate();
     /// ```
-    pub fn line<T>(&mut self, line: T) -> &mut Self
-    where
-        T: ToString,
-    {
-        self.body
-            .get_or_insert(vec![])
-            .push(Body::String(line.to_string()));
-
-        self
+    pub fn as_pub_crate(&self) -> Function {
+        self.with_vis(Visibility::PubCrate)
     }
 
-    /// Add an attribute to the function.
-    /// 
-    /// # Arguments
-    /// 
-    /// * `attribute` - The attribute to add.
+    /// Returns a copy of the function that can be pushed to a trait, i.e.
+    /// without visibility, keeping the body as the default implementation.
     /// 
     /// # Examples
-    ///
+    /// 
     /// ```
-    /// use rust_codegen::Function;
-    ///
+    /// use rust_codegen::{Function,Trait};
+    /// 
     /// let mut foo_fn = Function::new("foo_fn");
-    /// foo_fn.attr("test");
+    /// foo_fn.vis("pub").arg_ref_self().line("42");
+    /// 
+    /// let mut foo_trait = Trait::new("Foo");
+    /// foo_trait.push_fn(foo_fn.as_trait_fn());
     /// ```
-    pub fn attr(&mut self, attribute: &str) -> &mut Self {
-        self.attributes.push(attribute.to_string());
-        self
+    pub fn as_trait_fn(&self) -> Function {
+        self.with_vis(Visibility::Private)
     }
 
-    /// Specify an `extern` ABI for the function.
+    /// Returns a copy of the function taking `self` as the given receiver.
     /// 
     /// # Arguments
     /// 
-    /// * `abi` - The extern ABI to add.
+    /// * `receiver` - How the copy takes `self`.
     /// 
-    /// # Examples 
+    /// # Examples
     /// 
     /// ```
-    /// use rust_codegen::Function;
-    ///
+    /// use rust_codegen::{Function,Receiver};
+    /// 
     /// let mut foo_fn = Function::new("foo_fn");
-    /// foo_fn.extern_abi("C");
+    /// foo_fn.arg_ref_self();
+    /// 
+    /// let mut_fn = foo_fn.with_receiver(Receiver::RefMut);
     /// ```
-    pub fn extern_abi(&mut self, abi: &str) -> &mut Self {
-        self.extern_abi.replace(abi.to_string());
-        self
+    pub fn with_receiver(&self, receiver: Receiver) -> Function {
+        self.clone_with(|func| func.sig.receiver = Some(receiver))
     }
 
-    /// Push a block to the function implementation.
-    /// 
-    /// # Arguments
-    /// 
-    /// * `block` - The block to push to the function.
+    /// Returns a copy of the function that doesn't take `self`, e.g. to emit
+    /// a method as a free function or an associated function.
     /// 
     /// # Examples
     /// 
     /// ```
-    /// use rust_codegen::*;
-    ///
-    /// let mut foo_fn = Function::new("foo_fn");
+    /// use rust_codegen::Function;
     /// 
-    /// let mut block = Block::new("");
-    /// block.line("println!(\"Hello, world!\");");
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.arg_ref_self();
     /// 
-    /// foo_fn.push_block(block);
+    /// let free_fn = foo_fn.without_receiver();
     /// ```
-    pub fn push_block(&mut self, block: Block) -> &mut Self {
-        self.body.get_or_insert(vec![]).push(Body::Block(block));
+    pub fn without_receiver(&self) -> Function {
+        self.clone_with(|func| func.sig.receiver = None)
+    }
 
-        self
+    /// Returns a copy of the function without docs and with an empty body,
+    /// e.g. to implement a trait function.
+    pub(crate) fn to_impl_fn(&self) -> Function {
+        let mut func = self.clone();
+
+        func.docs = None;
+        func.body = Some(vec![]);
+
+        func
     }
 
-    /// Formats the function using the given formatter.
+    /// Returns a call of the function as a method of the given receiver with
+    /// its arguments, awaited if the function is async, e.g.
+    /// `self.inner.get(key).await`, or `None` if the function doesn't take
+    /// `self`.
     /// 
     /// # Arguments
     /// 
-    /// * `is_trait` - Indicates whether it is a trait or not.
-    /// * `fmt` - The formatter to use.
-    /// 
-    /// # Examples
-    /// 
-    /// ```
-    /// use rust_codegen::*;
-    /// 
-    /// let mut dest = String::new();
-    /// let mut fmt = Formatter::new(&mut dest);
-    /// 
-    /// let mut foo_fn = Function::new("foo_fn");
-    /// foo_fn.fmt(false, &mut fmt);
-    /// ```
-    pub fn fmt(&self, is_trait: bool, fmt: &mut Formatter<'_>) -> fmt::Result {
-        if let Some(ref docs) = self.docs {
-            docs.fmt(fmt)?;
-        }
+    /// * `receiver` - The expression to call the method on.
+    pub(crate) fn method_call(&self, receiver: &str) -> Option<String> {
+        self.sig.receiver.as_ref()?;
+
+        let args: Vec<&str> = self.sig.args.iter().map(|arg| &*arg.name).collect();
+        let r#await = if self.sig.r#async { ".await" } else { "" };
+
+        Some(format!(
+            "{}.{}({}){}",
+            receiver,
+            self.sig.name,
+            args.join(", "),
+            r#await
+        ))
+    }
 
-        if let Some(ref allow) = self.allow {
-            write!(fmt, "#[allow({})]\n", allow)?;
+    /// Empties the body of the function, if it has one, keeping its
+    /// signature.
+    pub(crate) fn strip_body(&mut self) {
+        if self.body.is_some() {
+            self.body = Some(vec![]);
         }
+    }
+
+    /// Returns whether this function is async or not.
+    pub(crate) fn is_async(&self) -> bool {
+        self.sig.r#async
+    }
+
+    /// Marks the function as a generated accessor with the given docs to
+    /// inherit, see `Policy`.
+    pub(crate) fn mark_accessor(&mut self, inherited_docs: Option<Docs>) -> &mut Self {
+        self.accessor = true;
+        self.inherited_docs = inherited_docs;
+        self
+    }
 
-        for attr in self.attributes.iter() {
-            write!(fmt, "#[{}]\n", attr)?;
+    /// Applies the conventions of the given policy to the function.
+    pub(crate) fn apply_policy(&mut self, policy: &Policy) {
+        self.attributes.sort_by_kind(policy.get_attribute_order());
+
+        if let Some(exclusion) = policy.get_coverage_exclusion() {
+            self.attributes.marker(exclusion.meta());
         }
 
-        if is_trait {
-            assert!(
-                self.vis.is_none(),
-                "trait fns do not have visibility modifiers"
-            );
+        if !self.accessor {
+            return;
         }
 
-        if let Some(ref vis) = self.vis {
-            write!(fmt, "{} ", vis)?;
+        if policy.get_inline_accessors() && !self.attributes.has_named("inline") {
+            self.attributes.meta("inline");
         }
 
-        if let Some(ref extern_abi) = self.extern_abi {
-            write!(fmt, "extern \"{extern_abi}\" ", extern_abi = extern_abi)?;
+        if policy.get_inherit_field_docs() && self.docs.is_none() {
+            self.docs = self.inherited_docs.clone();
         }
+    }
 
-        if self.r#async {
-            write!(fmt, "async ")?;
+    /// Returns a usage example of the function, see `Docs::example_from_fn`
+    /// and `Docs::example_from_fn_in`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - The path of the module declaring the function, if any.
+    /// * `receiver` - The expression creating the value methods are called
+    ///   on, if any.
+    pub(crate) fn usage_example(&self, path: Option<&str>, receiver: Option<&str>) -> String {
+        let mut lines = vec![];
+
+        if let Some(path) = path {
+            lines.push(format!("# use {}::*;", path));
         }
 
-        write!(fmt, "fn {}", self.name)?;
-        fmt_generics(&self.generics, fmt)?;
+        let setup = match (&self.sig.receiver, receiver) {
+            (Some(r), Some(value)) if r.is_mut() => Some(format!("let mut value = {};", value)),
+            (Some(_), Some(value)) => Some(format!("let value = {};", value)),
+            _ => None,
+        };
 
-        write!(fmt, "(")?;
+        let names: Vec<&str> = self.sig.args.iter().map(|arg| &*arg.name).collect();
 
-        if let Some(ref s) = self.arg_self {
-            write!(fmt, "{}", s)?;
+        let mut call = match self.sig.receiver {
+            Some(_) => format!("value.{}({})", self.sig.name, names.join(", ")),
+            None => format!("{}({})", self.sig.name, names.join(", ")),
+        };
+
+        if self.sig.r#async {
+            call.push_str(".await");
         }
 
-        for (i, arg) in self.args.iter().enumerate() {
-            if i != 0 || self.arg_self.is_some() {
-                write!(fmt, ", ")?;
-            }
+        let call = match self.sig.ret {
+            Some(_) => format!("let result = {};", call),
+            None => format!("{};", call),
+        };
 
-            write!(fmt, "{}: ", arg.name)?;
-            arg.ty.fmt(fmt)?;
+        if self.sig.args.is_empty() && self.sig.generics.is_empty() && !self.sig.r#async {
+            lines.extend(setup);
+            lines.push(call);
+            return lines.join("\n");
         }
 
-        write!(fmt, ")")?;
+        let mut wrapper = String::new();
+        let mut fmt = Formatter::new(&mut wrapper);
 
-        if let Some(ref ret) = self.ret {
-            write!(fmt, " -> ")?;
-            ret.fmt(fmt)?;
+        if self.sig.r#async {
+            write!(fmt, "async ").unwrap();
         }
 
-        fmt_bounds(&self.bounds, fmt)?;
+        write!(fmt, "fn example").unwrap();
+        fmt_generics(&self.sig.generics, &mut fmt).unwrap();
 
-        match self.body {
-            Some(ref body) => fmt.block(|fmt| {
-                for b in body {
-                    b.fmt(fmt)?;
-                }
+        let args: Vec<String> = self
+            .sig
+            .args
+            .iter()
+            .map(|arg| format!("{}: {}", arg.name, arg.ty.fmt_to_string()))
+            .collect();
 
-                Ok(())
-            }),
-            None => {
-                if !is_trait {
-                    panic!("impl blocks must define fn bodies");
-                }
+        write!(fmt, "({})", args.join(", ")).unwrap();
+        fmt_inline_bounds(&self.sig.bounds, &mut fmt).unwrap();
 
-                write!(fmt, ";\n")
-            }
-        }
+        lines.push(format!("# {} {{", wrapper));
+        lines.extend(setup);
+        lines.push(call);
+        lines.push("# }".to_string());
+        lines.join("\n")
+    }
+
+    /// Add a generic to the function.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the generic to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    /// 
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.generic("T");
+    /// ```
+    pub fn generic(&mut self, name: &str) -> &mut Self {
+        self.sig.generic(name);
+        self
     }
+
+    /// Add `self` as a function argument.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    /// 
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.arg_self();
+    /// ```
+    pub fn arg_self(&mut self) -> &mut Self {
+        self.sig.arg_self();
+        self
+    }
+
+    /// Add `&self` as a function argument.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    /// 
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.arg_ref_self();
+    /// ```
+    pub fn arg_ref_self(&mut self) -> &mut Self {
+        self.sig.arg_ref_self();
+        self
+    }
+
+    /// Add `&mut self` as a function argument.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    /// 
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.arg_mut_self();
+    /// ```
+    pub fn arg_mut_self(&mut self) -> &mut Self {
+        self.sig.arg_mut_self();
+        self
+    }
+
+    /// Set how the function takes `self`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `receiver` - The receiver of the function.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Function,Receiver};
+    /// 
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.receiver(Receiver::RefLifetime("'a".to_string()));
+    /// ```
+    pub fn receiver(&mut self, receiver: Receiver) -> &mut Self {
+        self.sig.receiver(receiver);
+        self
+    }
+
+    /// Returns how the function takes `self`, or `None` if it doesn't.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Function,Receiver};
+    /// 
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.arg_ref_self();
+    /// 
+    /// assert!(matches!(foo_fn.get_receiver(), Some(Receiver::Ref)));
+    /// ```
+    pub fn get_receiver(&self) -> Option<&Receiver> {
+        self.sig.get_receiver()
+    }
+
+    /// Add a function argument.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the argument.
+    /// * `ty` - The type of the argument.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    /// 
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.arg("name", "&str");
+    /// ```
+    pub fn arg<N, T>(&mut self, name: N, ty: T) -> &mut Self
+    where
+        N: Into<Cow<'static, str>>,
+        T: Into<Type>,
+    {
+        self.sig.arg(name, ty);
+        self
+    }
+
+    /// Push a function argument, e.g. one with `#[cfg(...)]` or
+    /// `#[allow(...)]` attributes. The documentation and annotations of the
+    /// field are not rendered.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `arg` - The argument to push.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Field,Function};
+    /// 
+    /// let mut trace_arg = Field::new("trace_id", "u64");
+    /// trace_arg.cfg("feature = \"tracing\"");
+    /// 
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.push_arg(trace_arg);
+    /// ```
+    pub fn push_arg(&mut self, arg: Field) -> &mut Self {
+        self.sig.push_arg(arg);
+        self
+    }
+
+    /// Add an `impl Trait` function argument.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the argument.
+    /// * `bounds` - The traits that the argument implements.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    /// 
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.arg_impl("reader", ["Read", "Send"]);
+    /// ```
+    pub fn arg_impl<N, I, T>(&mut self, name: N, bounds: I) -> &mut Self
+    where
+        N: Into<Cow<'static, str>>,
+        I: IntoIterator<Item = T>,
+        T: Into<Type>,
+    {
+        self.arg(name, Type::impl_trait(bounds))
+    }
+
+    /// Set the function return type.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `ty` - The return type.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    /// 
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.ret("String");
+    /// ```
+    pub fn ret<T>(&mut self, ty: T) -> &mut Self
+    where
+        T: Into<Type>,
+    {
+        self.sig.ret(ty);
+        self
+    }
+
+    /// Set the function return type to an `impl Trait` type.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `bounds` - The traits that the return type implements.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    /// 
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.ret_impl(["Iterator<Item = u8>"]);
+    /// ```
+    pub fn ret_impl<I, T>(&mut self, bounds: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Type>,
+    {
+        self.ret(Type::impl_trait(bounds))
+    }
+
+    /// Add a `where` bound to the function.
+    /// 
+    /// # Arguments 
+    /// 
+    /// * `name ` - The name of the bound.
+    /// * `ty` - The type of the bound.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    /// 
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.bound("A", "TraitA");
+    /// ```
+    pub fn bound<T>(&mut self, name: &str, ty: T) -> &mut Self
+    where
+        T: Into<Type>,
+    {
+        self.sig.bound(name, ty);
+        self
+    }
+
+    /// Push a line to the function implementation.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `line` - The line to add to the function.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    /// 
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.line("println!(\"Hello, world!\")");
+    /// ```
+    pub fn line<T>(&mut self, line: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.body
+            .get_or_insert(vec![])
+            .push(Body::String(line.into()));
+
+        self
+    }
+
+    /// Push a line to the function implementation with a `#[cfg(...)]`
+    /// attribute on the line before it.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `cfg` - The configuration predicate, e.g. `feature = "tracing"`.
+    /// * `line` - The line to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    /// 
+    /// let mut foo_fn = Function::new("foo");
+    /// foo_fn.line_cfg("feature = \"tracing\"", "tracing::info!(\"foo\");");
+    /// ```
+    pub fn line_cfg<C, T>(&mut self, cfg: C, line: T) -> &mut Self
+    where
+        C: AsRef<str>,
+        T: AsRef<str>,
+    {
+        self.line(cfg_line(cfg.as_ref(), line.as_ref()))
+    }
+
+    /// Push the lines of a body template to the function implementation.
+    /// 
+    /// Every `{name}` placeholder is replaced by its value and `{{`/`}}` are
+    /// written as literal braces. The template is dedented and the lines of
+    /// multi-line values keep the indentation of their placeholder's line.
+    /// Panics if a placeholder has no value.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template` - The body template.
+    /// * `values` - The values of the placeholders as `(name, value)` pairs.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    /// 
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.body_template(
+    ///     "let {name} = {value};\nprintln!(\"{{}}\", {name});",
+    ///     &[("name", "answer"), ("value", "42")],
+    /// );
+    /// ```
+    pub fn body_template(&mut self, template: &str, values: &[(&str, &str)]) -> &mut Self {
+        for line in render_template(template, values) {
+            self.line(line);
+        }
+
+        self
+    }
+
+    /// Add an attribute to the function.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attribute` - The attribute to add.
+    /// 
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Function;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.attr("test");
+    /// ```
+    pub fn attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.meta(attribute);
+        self
+    }
+
+    /// Add `#[inline]` to the function, replacing any other `#[inline(...)]`.
+    /// 
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Function;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.inline();
+    /// ```
+    pub fn inline(&mut self) -> &mut Self {
+        self.set_inline("inline")
+    }
+
+    /// Add `#[inline(always)]` to the function, replacing any other
+    /// `#[inline(...)]`.
+    /// 
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Function;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.inline_always();
+    /// ```
+    pub fn inline_always(&mut self) -> &mut Self {
+        self.set_inline("inline(always)")
+    }
+
+    /// Add `#[inline(never)]` to the function, replacing any other
+    /// `#[inline(...)]`.
+    /// 
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Function;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.inline_never();
+    /// ```
+    pub fn inline_never(&mut self) -> &mut Self {
+        self.set_inline("inline(never)")
+    }
+
+    /// Replaces the `#[inline(...)]` attribute of the function.
+    fn set_inline(&mut self, meta: &'static str) -> &mut Self {
+        self.attributes.remove_named("inline").meta(meta);
+        self
+    }
+
+    /// Add `#[cold]` to the function, unless it has it already, e.g. for
+    /// error paths that are rarely taken.
+    /// 
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Function;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.cold();
+    /// ```
+    pub fn cold(&mut self) -> &mut Self {
+        if !self.attributes.has_named("cold") {
+            self.attributes.meta("cold");
+        }
+
+        self
+    }
+
+    /// Add `#[must_use]` to the function, replacing any existing one.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `msg` - The message shown when the result is unused, if any.
+    /// 
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Function;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.must_use(Some("the builder does nothing until built"));
+    /// ```
+    pub fn must_use(&mut self, msg: Option<&str>) -> &mut Self {
+        self.attributes.remove_named("must_use");
+
+        match msg {
+            Some(msg) => self.attributes.meta(format!("must_use = {}", quote_str(msg))),
+            None => self.attributes.meta("must_use"),
+        };
+
+        self
+    }
+
+    /// Add `#[track_caller]` to the function, unless it has it already, so
+    /// that panics report the location of the caller.
+    /// 
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Function;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.track_caller();
+    /// ```
+    pub fn track_caller(&mut self) -> &mut Self {
+        if !self.attributes.has_named("track_caller") {
+            self.attributes.meta("track_caller");
+        }
+
+        self
+    }
+
+    /// Mark the function as a test with `#[test]`.
+    /// 
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Function;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.test();
+    /// ```
+    pub fn test(&mut self) -> &mut Self {
+        self.test_with(TestRunner::Std)
+    }
+
+    /// Mark the function as a test run by the given runner, replacing any
+    /// existing runner. Async runners also make the function async.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `runner` - The runner of the test.
+    /// 
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Function, TestRunner};
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.test_with(TestRunner::Tokio);
+    /// ```
+    pub fn test_with(&mut self, runner: TestRunner) -> &mut Self {
+        for other in TestRunner::ALL {
+            self.attributes.remove_named(other.meta());
+        }
+
+        self.attributes.meta(runner.meta());
+
+        if runner.is_async() {
+            self.set_async(true);
+        }
+
+        self
+    }
+
+    /// Add `#[should_panic]` to the test function, replacing any existing
+    /// one. Functions without a runner are marked with `#[test]` first.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `expected` - A substring of the expected panic message, if any.
+    /// 
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Function;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.test().test_should_panic(Some("index out of bounds"));
+    /// ```
+    pub fn test_should_panic(&mut self, expected: Option<&str>) -> &mut Self {
+        self.ensure_test_runner();
+        self.attributes.remove_named("should_panic");
+
+        match expected {
+            Some(expected) => self
+                .attributes
+                .meta(format!("should_panic(expected = {})", quote_str(expected))),
+            None => self.attributes.meta("should_panic"),
+        };
+
+        self
+    }
+
+    /// Add `#[ignore]` to the test function, replacing any existing one.
+    /// Functions without a runner are marked with `#[test]` first.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `reason` - Why the test is ignored, if given.
+    /// 
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Function;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.test().test_ignore(Some("requires a database"));
+    /// ```
+    pub fn test_ignore(&mut self, reason: Option<&str>) -> &mut Self {
+        self.ensure_test_runner();
+        self.attributes.remove_named("ignore");
+
+        match reason {
+            Some(reason) => self.attributes.meta(format!("ignore = {}", quote_str(reason))),
+            None => self.attributes.meta("ignore"),
+        };
+
+        self
+    }
+
+    /// Mark the function with `#[test]` unless it has a runner already.
+    fn ensure_test_runner(&mut self) {
+        let has_runner = TestRunner::ALL
+            .iter()
+            .any(|runner| self.attributes.has_named(runner.meta()));
+
+        if !has_runner {
+            self.test();
+        }
+    }
+
+    /// Add `#[rustfmt::skip]` to the function, unless it has it already, so
+    /// that formatting passes keep the layout of its body.
+    /// 
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Function;
+    ///
+    /// let mut table_fn = Function::new("table");
+    /// table_fn.rustfmt_skip().ret("[u8; 4]").line("[1, 2,\n 3, 4]");
+    /// ```
+    pub fn rustfmt_skip(&mut self) -> &mut Self {
+        if !self.attributes.has_named("rustfmt::skip") {
+            self.attributes.meta("rustfmt::skip");
+        }
+
+        self
+    }
+
+    /// Add a tool attribute to the function, e.g. `rustfmt::skip` to keep a
+    /// generated lookup table in its body as it is. Tool attributes are
+    /// rendered before `#[inline]`, `#[test]`, and every other attribute.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attribute` - The attribute to add, without the surrounding `#[...]`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    /// 
+    /// let mut foo_fn = Function::new("Foo");
+    /// foo_fn.tool_attr("rustfmt::skip");
+    /// ```
+    pub fn tool_attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.marker(attribute);
+        self
+    }
+
+    /// Set the return type to `Result<(), Box<dyn std::error::Error>>`, e.g.
+    /// for a `main` function that uses `?`. `Ok(())` is written at the end
+    /// of the body unless its last line is `Ok(())` already.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Scope;
+    /// 
+    /// let mut scope = Scope::new();
+    /// scope
+    ///     .new_main()
+    ///     .ret_boxed_error()
+    ///     .line("let config = std::fs::read_to_string(\"config.toml\")?;");
+    /// ```
+    pub fn ret_boxed_error(&mut self) -> &mut Self {
+        self.ok_tail = true;
+        self.ret("Result<(), Box<dyn std::error::Error>>")
+    }
+
+    /// Make the function async and run it on the tokio runtime with
+    /// `#[tokio::main]`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Scope;
+    /// 
+    /// let mut scope = Scope::new();
+    /// scope.new_main().tokio_main();
+    /// ```
+    pub fn tokio_main(&mut self) -> &mut Self {
+        self.attributes.meta("tokio::main");
+        self.set_async(true)
+    }
+
+    /// Mark the function as naked with `#[unsafe(naked)]`. The body of a
+    /// naked function has to be a single `naked_asm!` invocation, see
+    /// `Asm::naked`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    /// 
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.naked();
+    /// ```
+    pub fn naked(&mut self) -> &mut Self {
+        self.attributes.meta("unsafe(naked)");
+        self
+    }
+
+    /// Push an `asm!` or `naked_asm!` invocation to the function body. A
+    /// `global_asm!` invocation makes formatting the function fail, as it
+    /// has to be pushed to a scope.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `asm` - The assembly to push.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Asm,Function};
+    /// 
+    /// let mut nop = Asm::new();
+    /// nop.template("nop");
+    /// 
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.line("unsafe {").push_asm(&nop).line("}");
+    /// ```
+    pub fn push_asm(&mut self, asm: &Asm) -> &mut Self {
+        self.body
+            .get_or_insert(vec![])
+            .push(Body::Asm(asm.clone()));
+
+        self
+    }
+
+    /// Specify an `extern` ABI for the function.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `abi` - The extern ABI to add.
+    /// 
+    /// # Examples 
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.extern_abi("C");
+    /// ```
+    pub fn extern_abi(&mut self, abi: &str) -> &mut Self {
+        self.sig.extern_abi(abi);
+        self
+    }
+
+    /// Add `#[no_mangle]` to the function, unless it has it already, so that
+    /// it's exported under its own name. Any `#[export_name = "..."]` is
+    /// replaced, since the two can't be combined.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.no_mangle();
+    /// ```
+    pub fn no_mangle(&mut self) -> &mut Self {
+        self.attributes.no_mangle();
+        self
+    }
+
+    /// Set the symbol name that the function is exported under with
+    /// `#[export_name = "..."]`, replacing any existing one and any
+    /// `#[no_mangle]`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The symbol name.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.export_name("plugin_foo_v1");
+    /// ```
+    pub fn export_name(&mut self, name: &str) -> &mut Self {
+        self.attributes.export_name(name);
+        self
+    }
+
+    /// Set the object file section that the function is placed in with
+    /// `#[link_section = "..."]`, replacing any existing one.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `section` - The name of the section, e.g. `.text.boot`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.link_section(".text.boot");
+    /// ```
+    pub fn link_section(&mut self, section: &str) -> &mut Self {
+        self.attributes.link_section(section);
+        self
+    }
+
+    /// Configure the function as a C ABI export, i.e.
+    /// `#[no_mangle] pub extern "C" fn`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.export_c();
+    /// ```
+    pub fn export_c(&mut self) -> &mut Self {
+        self.no_mangle();
+        self.vis("pub");
+        self.extern_abi("C")
+    }
+
+    /// Returns the ABI of the function, if it has one.
+    pub(crate) fn get_extern_abi(&self) -> Option<&str> {
+        self.sig.extern_abi.as_deref()
+    }
+
+    /// Returns a copy of the function's signature suitable for an `extern`
+    /// block, i.e. without a body, visibility, or ABI, and only with the
+    /// attributes allowed on foreign functions, e.g. `#[link_name = "..."]`
+    /// and `#[cfg(...)]`. An `#[export_name = "..."]` becomes the
+    /// `#[link_name = "..."]` of the declaration.
+    pub(crate) fn to_extern_decl(&self) -> Function {
+        let mut func = self.clone();
+
+        let link_name = self
+            .attributes
+            .get_named("export_name")
+            .map(|meta| meta.replacen("export_name", "link_name", 1));
+
+        func.attributes.retain_named(EXTERN_DECL_ATTRIBUTES);
+
+        if let Some(link_name) = link_name {
+            func.attributes.remove_named("link_name");
+            func.attributes.meta(link_name);
+        }
+        func.vis = None;
+        func.sig.extern_abi = None;
+        func.sig.r#async = false;
+        func.body = None;
+
+        func
+    }
+
+    /// Returns a description of every part of the function's signature that
+    /// prevents calling it on a `dyn` trait object. Functions bound by
+    /// `where Self: Sized` are excluded from trait objects and never
+    /// reported.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `trait_name` - The name of the trait declaring the function.
+    pub(crate) fn object_safety_violations(&self, trait_name: &str) -> Vec<String> {
+        let mut violations = vec![];
+
+        let self_sized = self
+            .sig
+            .bounds
+            .iter()
+            .any(|b| b.name == "Self" && b.bound.iter().any(|ty| ty.name() == "Sized"));
+
+        if self_sized {
+            return violations;
+        }
+
+        if self.sig.receiver.is_none() {
+            violations.push(format!(
+                "`{}::{}` has no `self` receiver",
+                trait_name, self.sig.name
+            ));
+        }
+
+        if !self.sig.generics.is_empty() {
+            violations.push(format!(
+                "`{}::{}` has generic parameters",
+                trait_name, self.sig.name
+            ));
+        }
+
+        if self.sig.r#async {
+            violations.push(format!("`{}::{}` is async", trait_name, self.sig.name));
+        }
+
+        for arg in &self.sig.args {
+            let ty = arg.ty.fmt_to_string();
+
+            if ty.starts_with("impl ") {
+                violations.push(format!(
+                    "`{}::{}` argument `{}` is `{}` which makes it generic",
+                    trait_name, self.sig.name, arg.name, ty
+                ));
+            } else if ty == "Self" {
+                violations.push(format!(
+                    "`{}::{}` argument `{}` takes `Self` by value",
+                    trait_name, self.sig.name, arg.name
+                ));
+            }
+        }
+
+        if let Some(ref ret) = self.sig.ret {
+            let ty = ret.fmt_to_string();
+
+            if ty == "Self" {
+                violations.push(format!(
+                    "`{}::{}` returns `Self` by value",
+                    trait_name, self.sig.name
+                ));
+            } else if ty.starts_with("impl ") {
+                violations.push(format!(
+                    "`{}::{}` returns `{}`",
+                    trait_name, self.sig.name, ty
+                ));
+            }
+        }
+
+        violations
+    }
+
+    /// Returns a description of every construct of the function that isn't
+    /// allowed in a `const fn`, if the function is `const`. Body lines are
+    /// checked heuristically, e.g. for allocations and trait method calls.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - The path of the function used in the descriptions.
+    /// * `in_trait` - Whether the function is part of a trait or a trait impl.
+    pub(crate) fn const_violations(&self, path: &str, in_trait: bool) -> Vec<String> {
+        let mut violations = vec![];
+
+        if !self.sig.r#const {
+            return violations;
+        }
+
+        if in_trait {
+            violations.push(format!("`{}` is `const` but trait functions can't be", path));
+        }
+
+        if self.sig.r#async {
+            violations.push(format!("`{}` is `const` but also async", path));
+        }
+
+        let mut lines = vec![];
+
+        if let Some(ref body) = self.body {
+            for b in body {
+                b.collect_lines(&mut lines);
+            }
+        }
+
+        for (construct, description) in NON_CONST_CONSTRUCTS {
+            let found = lines.iter().any(|line| match construct.strip_prefix('^') {
+                Some(start) => line.trim_start().starts_with(start),
+                None if *construct == "?" => line
+                    .match_indices('?')
+                    .filter_map(|(i, _)| line[..i].chars().last())
+                    .any(|c| c == ')' || c == ']' || c == '_' || c.is_alphanumeric()),
+                None => line.contains(construct),
+            });
+
+            if found {
+                violations.push(format!(
+                    "`{}` is `const` but uses {}, which isn't allowed in a `const fn`",
+                    path, description
+                ));
+            }
+        }
+
+        violations
+    }
+
+    /// Returns a description of every part of the function's signature that
+    /// does not look like it can be passed across the C ABI.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `repr_c` - The names of the known `#[repr(C)]` types.
+    pub(crate) fn ffi_violations(&self, repr_c: &[String]) -> Vec<String> {
+        let mut violations = vec![];
+
+        if self.sig.receiver.is_some() {
+            violations.push(format!("`{}` takes `self` which is not FFI-safe", self.sig.name));
+        }
+
+        if !self.sig.generics.is_empty() {
+            violations.push(format!("`{}` is generic which is not FFI-safe", self.sig.name));
+        }
+
+        if self.sig.r#async {
+            violations.push(format!("`{}` is async which is not FFI-safe", self.sig.name));
+        }
+
+        for arg in &self.sig.args {
+            let ty = arg.ty.fmt_to_string();
+
+            if !looks_ffi_safe(&ty, repr_c) {
+                violations.push(format!(
+                    "`{}` argument `{}` has type `{}` which does not look FFI-safe",
+                    self.sig.name, arg.name, ty
+                ));
+            }
+        }
+
+        if let Some(ref ret) = self.sig.ret {
+            let ty = ret.fmt_to_string();
+
+            if !looks_ffi_safe(&ty, repr_c) {
+                violations.push(format!(
+                    "`{}` returns `{}` which does not look FFI-safe",
+                    self.sig.name, ty
+                ));
+            }
+        }
+
+        violations
+    }
+
+    /// Push a block to the function implementation.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `block` - The block to push to the function.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::*;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// 
+    /// let mut block = Block::new("");
+    /// block.line("println!(\"Hello, world!\");");
+    /// 
+    /// foo_fn.push_block(block);
+    /// ```
+    pub fn push_block(&mut self, block: Block) -> &mut Self {
+        self.body.get_or_insert(vec![]).push(Body::Block(block));
+
+        self
+    }
+
+    /// Push a body built by the given closure each time the function is
+    /// rendered, after any lines and blocks pushed before it. The closure
+    /// isn't called when the body isn't rendered, e.g. with
+    /// `BodyMode::Omit`, so expensive bodies cost nothing in signature-only
+    /// outputs. Transforms apply to the built lines when the body is
+    /// rendered, while checks that inspect the lines of the body, e.g.
+    /// `Scope::check_const`, call the closure to build them.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `f` - The closure building the body.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.ret("u32").body_with(|body| {
+    ///     body.line("42");
+    /// });
+    ///
+    /// assert_eq!(foo_fn.to_code_string(), "fn foo_fn() -> u32 {\n    42\n}");
+    /// ```
+    pub fn body_with<F>(&mut self, f: F) -> &mut Self
+    where
+        F: Fn(&mut BodyBuilder) + Send + Sync + 'static,
+    {
+        self.body
+            .get_or_insert(vec![])
+            .push(Body::Lazy(LazyBody(Arc::new(f))));
+
+        self
+    }
+
+    /// Returns an async version of the function.
+    ///
+    /// By default the function is simply marked as `async`. If the variant
+    /// wraps return types, the function is left synchronous, its return type
+    /// is wrapped, and its body is moved into `Box::pin(async move { ... })`.
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - The configuration of the async variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::*;
+    ///
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.ret("String");
+    ///
+    /// let async_foo_fn = foo_fn.to_async(&AsyncVariant::new());
+    /// ```
+    pub fn to_async(&self, variant: &AsyncVariant) -> Function {
+        let mut func = self.clone();
+
+        let lifetime = if variant.is_boxed_future() {
+            func.name_elided_lifetimes()
+        } else {
+            None
+        };
+
+        let template = match variant.ret_wrapper(lifetime.as_deref()) {
+            Some(template) => template,
+            None => {
+                func.sig.r#async = true;
+                return func;
+            }
+        };
+
+        let mut ret = String::new();
+
+        match self.sig.ret {
+            Some(ref ty) => ty.fmt(&mut Formatter::new(&mut ret)).unwrap(),
+            None => ret.push_str("()"),
+        }
+
+        func.sig.r#async = false;
+        func.sig.ret = Some(Type::new(template.replace("{}", &ret)));
+
+        if let Some(body) = func.body.take() {
+            let mut block = Block::new("Box::pin(async move");
+            block.after(")");
+
+            for b in body {
+                block.push_body(b);
+            }
+
+            func.body = Some(vec![Body::Block(block)]);
+        }
+
+        func
+    }
+
+    /// Names the elided lifetimes of the receiver, the arguments, and the
+    /// return type with a single lifetime, which is declared on the function
+    /// unless it is the lifetime of the receiver. Returns the lifetime, or
+    /// `None` if the function takes no references.
+    fn name_elided_lifetimes(&mut self) -> Option<String> {
+        let declared: Vec<&str> = self
+            .sig
+            .generics
+            .iter()
+            .map(|g| g.split(':').next().unwrap_or(g).trim())
+            .collect();
+
+        let (lifetime, declare) = match self.sig.receiver {
+            Some(Receiver::RefLifetime(ref lifetime))
+            | Some(Receiver::RefMutLifetime(ref lifetime)) => (lifetime.clone(), false),
+            _ => {
+                let lifetime = ('a'..='z')
+                    .map(|c| format!("'{}", c))
+                    .find(|lifetime| !declared.contains(&lifetime.as_str()))?;
+
+                (lifetime, true)
+            }
+        };
+
+        let mut named = match self.sig.receiver {
+            Some(Receiver::Ref) => {
+                self.sig.receiver = Some(Receiver::RefLifetime(lifetime.clone()));
+                true
+            }
+            Some(Receiver::RefMut) => {
+                self.sig.receiver = Some(Receiver::RefMutLifetime(lifetime.clone()));
+                true
+            }
+            Some(ref receiver) => receiver.is_ref(),
+            None => false,
+        };
+
+        for arg in &mut self.sig.args {
+            named |= arg.ty.name_elided_lifetimes(&lifetime);
+        }
+
+        if !named {
+            return None;
+        }
+
+        if let Some(ref mut ret) = self.sig.ret {
+            ret.name_elided_lifetimes(&lifetime);
+        }
+
+        if declare {
+            self.sig.generics.insert(0, lifetime.clone());
+        }
+
+        Some(lifetime)
+    }
+
+    /// Adds a `#[wasm_bindgen]` attribute to the function.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attrs` - The arguments of the attribute.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    /// use rust_codegen::wasm_bindgen::BindgenAttrs;
+    /// 
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.wasm_bindgen(&BindgenAttrs::new());
+    /// ```
+    pub fn wasm_bindgen(&mut self, attrs: &BindgenAttrs) -> &mut Self {
+        self.attributes.meta(attrs.meta());
+        self
+    }
+
+    /// Calls the given function with every type of the arguments,
+    /// return type, and bounds of the function.
+    pub(crate) fn visit_types_mut(&mut self, f: &mut dyn FnMut(&mut Type)) {
+        for arg in &mut self.sig.args {
+            arg.ty.visit_types_mut(f);
+        }
+
+        if let Some(ref mut ret) = self.sig.ret {
+            ret.visit_types_mut(f);
+        }
+
+        for bound in &mut self.sig.bounds {
+            bound.visit_types_mut(f);
+        }
+    }
+
+    /// Maps the visibility of the function with the given function.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `f` - The function mapping the visibility.
+    pub(crate) fn map_visibility(&mut self, f: &dyn Fn(Visibility) -> Visibility) {
+        map_vis(&mut self.vis, f);
+    }
+
+    /// Applies the given transform to the function and its body.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `transform` - The transform to apply.
+    pub(crate) fn transform(&mut self, transform: &Transform) {
+        if transform.strips_async() {
+            self.sig.r#async = false;
+        }
+
+        if let Some(ref mut body) = self.body {
+            for b in body {
+                b.transform(transform);
+            }
+        }
+    }
+
+    /// Returns the function as a string of code formatted with a fresh
+    /// formatter, e.g. to render it without a scope.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Function;
+    /// 
+    /// let mut foo_fn = Function::new("foo");
+    /// foo_fn.line("bar()");
+    /// 
+    /// assert_eq!(foo_fn.to_code_string(), "fn foo() {\n    bar()\n}");
+    /// ```
+    pub fn to_code_string(&self) -> String {
+        fmt_to_code_string(|fmt| self.fmt(false, fmt))
+    }
+
+    /// Returns an estimate of the rendered length of the function.
+    pub(crate) fn estimated_len(&self) -> usize {
+        let docs = self.docs.as_ref().map_or(0, |docs| docs.estimated_len());
+        let args: usize = self.sig.args.iter().map(|arg| arg.name.len() + 16).sum();
+        let body: usize = match self.body {
+            Some(ref body) => body.iter().map(|body| body.estimated_len()).sum(),
+            None => 0,
+        };
+
+        docs + self.attributes.estimated_len() + self.sig.name.len() + args + body + 32
+    }
+
+    /// Formats the function using the given formatter.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `is_trait` - Indicates whether it is a trait or not.
+    /// * `fmt` - The formatter to use.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::*;
+    /// 
+    /// let mut dest = String::new();
+    /// let mut fmt = Formatter::new(&mut dest);
+    /// 
+    /// let mut foo_fn = Function::new("foo_fn");
+    /// foo_fn.fmt(false, &mut fmt);
+    /// ```
+    pub fn fmt(&self, is_trait: bool, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref docs) = self.docs {
+            docs.fmt(fmt)?;
+        }
+
+        self.attributes.fmt(fmt)?;
+
+        match self.vis {
+            Some(_) if is_trait && fmt.trait_fn_vis == TraitFnVis::Error => return Err(fmt::Error),
+            Some(ref vis) if !is_trait => write!(fmt, "{} ", vis)?,
+            _ => {}
+        }
+
+        self.sig.fmt_head(fmt)?;
+
+        match self.body {
+            Some(ref body) => match fmt.body_mode {
+                BodyMode::Keep => {
+                    fmt_bounds(&self.sig.bounds, fmt)?;
+
+                    fmt.block(|fmt| {
+                        for b in body {
+                            b.fmt(fmt)?;
+                        }
+
+                        if self.ok_tail && !ends_with_ok(body) {
+                            writeln!(fmt, "Ok(())")?;
+                        }
+
+                        Ok(())
+                    })
+                }
+                BodyMode::Unimplemented => {
+                    fmt_bounds(&self.sig.bounds, fmt)?;
+                    fmt.block(|fmt| write!(fmt, "unimplemented!()\n"))
+                }
+                BodyMode::Omit => fmt_bounds_end(&self.sig.bounds, fmt),
+            },
+            None => {
+                if !is_trait {
+                    panic!("impl blocks must define fn bodies");
+                }
+
+                fmt_bounds_end(&self.sig.bounds, fmt)
+            }
+        }
+    }
+}
+
+/// Returns whether the last line of the body is `Ok(())`.
+///
+/// # Arguments
+///
+/// * `body` - The body of the function.
+fn ends_with_ok(body: &[Body]) -> bool {
+    matches!(body.last(), Some(Body::String(line)) if line.trim() == "Ok(())")
 }
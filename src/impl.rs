@@ -1,9 +1,19 @@
+use std::borrow::Cow;
 use std::fmt::{self, Write};
 
+use crate::associated_const::AssociatedConst;
+use crate::associated_type_binding::AssociatedTypeBinding;
+use crate::attributes::Attributes;
+use crate::async_variant::{trait_ident, AsyncTraitStrategy, AsyncVariant};
 use crate::bound::Bound;
-use crate::field::Field;
-use crate::formatter::{fmt_bounds, fmt_generics, Formatter};
+use crate::formatter::{fmt_bounds, fmt_generics, fmt_to_code_string, Formatter};
+use crate::fn_sig::FnSig;
 use crate::function::Function;
+use crate::metadata::Metadata;
+use crate::policy::Policy;
+use crate::transform::Transform;
+use crate::visibility::Visibility;
+use crate::wasm_bindgen::BindgenAttrs;
 
 use crate::r#type::Type;
 
@@ -17,13 +27,17 @@ pub struct Impl {
     /// If implementing a trait.
     impl_trait: Option<Type>,
     /// Associated types.
-    assoc_tys: Vec<Field>,
+    assoc_tys: Vec<AssociatedTypeBinding>,
+    /// Associated constants.
+    assoc_consts: Vec<AssociatedConst>,
     /// The bounds of the impl block.
     bounds: Vec<Bound>,
     /// The functions for the impl block.
     fns: Vec<Function>,
-    /// The macros for the impl block.
-    macros: Vec<String>,
+    /// The attributes and macros for the impl block.
+    attributes: Attributes,
+    /// The impl block's metadata, which is never rendered.
+    metadata: Metadata,
 }
 
 impl Impl {
@@ -49,12 +63,49 @@ impl Impl {
             generics: vec![],
             impl_trait: None,
             assoc_tys: vec![],
+            assoc_consts: vec![],
             bounds: vec![],
             fns: vec![],
-            macros: vec![],
+            attributes: Attributes::new(),
+            metadata: Metadata::new(),
         }
     }
 
+    /// Returns a reference to the impl block's target type.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Impl;
+    /// 
+    /// let foo_impl = Impl::new("Foo");
+    /// println!("{:?}", foo_impl.target());
+    /// ```
+    pub fn target(&self) -> &Type {
+        &self.target
+    }
+
+    /// Returns the metadata of the impl block, which is kept when it is cloned
+    /// and never rendered.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns a mutable reference to the metadata of the impl block, e.g. to tag
+    /// it in a multi-pass generator.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Impl;
+    /// 
+    /// let mut foo_impl = Impl::new("Foo");
+    /// foo_impl.metadata_mut().set("schema", "foo.json");
+    /// ```
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
     /// Add a generic to the impl block.
     ///
     /// This adds the generic for the block (`impl<T>`) and not the target 
@@ -121,7 +172,133 @@ impl Impl {
         self
     }
 
-    /// Add a macro to the impl block (e.g. `"#[async_trait]"`)
+    /// Set the trait that the impl block is implementing along with the
+    /// generics of the impl block, e.g. `impl<'a, T: Serialize> Foo<'a, T>
+    /// for Wrapper<T>`.
+    ///
+    /// Each generic may include its bounds (`"T: Serialize"`). Unless the
+    /// target was given generics beforehand with [`Impl::target_generic`],
+    /// it gets every declared type parameter along with the declared
+    /// lifetimes that the trait doesn't use. The generics of the trait are
+    /// added to it beforehand with [`Type::generic`].
+    ///
+    /// The generics are not validated here, use
+    /// [`Scope::check_impl_generics`] to find the generics that are never
+    /// used and the lifetimes that are never declared.
+    ///
+    /// [`Scope::check_impl_generics`]: crate::Scope::check_impl_generics
+    /// 
+    /// # Arguments
+    /// 
+    /// * `generics` - The generics of the impl block.
+    /// * `ty` - The trait that the impl block is implementing.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Impl,Type};
+    /// 
+    /// let mut my_trait = Type::new("MyTrait");
+    /// my_trait.generic("'a").generic("T");
+    /// 
+    /// let mut wrapper_impl = Impl::new("Wrapper");
+    /// wrapper_impl.impl_trait_with_generics(["'a", "T: Serialize"], my_trait);
+    /// ```
+    pub fn impl_trait_with_generics<I, G, T>(&mut self, generics: I, ty: T) -> &mut Self
+    where
+        I: IntoIterator<Item = G>,
+        G: AsRef<str>,
+        T: Into<Type>,
+    {
+        let ty = ty.into();
+
+        let generics: Vec<String> = generics
+            .into_iter()
+            .map(|g| g.as_ref().to_string())
+            .collect();
+
+        if self.target.generics().is_empty() {
+            let mut used = vec![];
+            ty.collect_idents(&mut used);
+
+            for name in generics.iter().map(|g| generic_name(g)) {
+                if !name.starts_with('\'') || !used.iter().any(|u| u == name) {
+                    self.target.generic(name.to_string());
+                }
+            }
+        }
+
+        self.generics.extend(generics);
+        self.impl_trait(ty)
+    }
+
+    /// Returns a description of each generic of the impl block that is used
+    /// by neither the trait nor the target, and of each lifetime that they
+    /// use without it being declared.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the target to use in the descriptions.
+    pub(crate) fn generic_violations(&self, path: &str) -> Vec<String> {
+        let mut violations = vec![];
+
+        let mut used = vec![];
+        self.target.collect_idents(&mut used);
+
+        if let Some(ref ty) = self.impl_trait {
+            ty.collect_idents(&mut used);
+        }
+
+        let declared: Vec<&str> = self.generics.iter().map(|g| generic_name(g)).collect();
+
+        for name in &declared {
+            if !used.iter().any(|u| u == name) {
+                violations.push(format!(
+                    "impl block for `{}` declares generic `{}`, which is used by neither the trait nor the target",
+                    path, name
+                ));
+            }
+        }
+
+        for name in &used {
+            let is_lifetime = name.starts_with('\'') && name != "'static" && name != "'_";
+
+            if is_lifetime && !declared.contains(&name.as_str()) {
+                violations.push(format!(
+                    "impl block for `{}` uses lifetime `{}`, which is not declared",
+                    path, name
+                ));
+            }
+        }
+
+        violations
+    }
+
+    /// Adds an attribute to the impl block, without the surrounding `#[...]`
+    /// (e.g. `"cfg(test)"`).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attribute` - The attribute to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Impl;
+    /// 
+    /// let mut foo_impl = Impl::new("Foo");
+    /// foo_impl.attr("cfg(test)");
+    /// ```
+    pub fn attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.meta(attribute);
+        self
+    }
+
+    /// Add a macro to the impl block that is written as is (e.g.
+    /// `"#[async_trait]"`)
     /// 
     /// # Arguments
     /// 
@@ -133,10 +310,53 @@ impl Impl {
     /// use rust_codegen::Impl;
     /// 
     /// let mut foo_impl = Impl::new("Foo");
-    /// foo_impl.r#macro("async_trait");
+    /// foo_impl.r#macro("#[async_trait]");
+    /// ```
+    pub fn r#macro<T>(&mut self, r#macro: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.raw(r#macro);
+        self
+    }
+
+    /// Add `#[automatically_derived]` to the impl, e.g. for a trait impl that
+    /// is generated in place of a derive, so that lints such as `dead_code`
+    /// treat it like a derived impl. It's rendered before any other attribute.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Impl;
+    /// 
+    /// let mut foo_impl = Impl::new("Foo");
+    /// foo_impl.automatically_derived();
     /// ```
-    pub fn r#macro(&mut self, r#macro: &str) -> &mut Self {
-        self.macros.push(r#macro.to_string());
+    pub fn automatically_derived(&mut self) -> &mut Self {
+        self.attributes.marker("automatically_derived");
+        self
+    }
+
+    /// Add a tool attribute to the impl, rendered before any other attribute
+    /// along with `#[automatically_derived]`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attribute` - The attribute to add, without the surrounding `#[...]`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Impl;
+    /// 
+    /// let mut foo_impl = Impl::new("Foo");
+    /// foo_impl.tool_attr("my_tool::generated(schema = \"user\")");
+    /// ```
+    pub fn tool_attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.marker(attribute);
         self
     }
 
@@ -159,17 +379,109 @@ impl Impl {
     /// 
     /// impl_bar.associate_type("A", "Foo");
     /// ```
-    pub fn associate_type<T>(&mut self, name: &str, ty: T) -> &mut Self
+    pub fn associate_type<N, T>(&mut self, name: N, ty: T) -> &mut Self
     where
+        N: Into<Cow<'static, str>>,
         T: Into<Type>,
     {
-        self.assoc_tys.push(Field {
-            name: name.to_string(),
-            ty: ty.into(),
-            documentation: Vec::new(),
-            annotation: Vec::new(),
-        });
+        self.push_associate_type(AssociatedTypeBinding::new(name, ty))
+    }
+
+    /// Push a new associated type, returning a mutable reference to it.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the associated type.
+    /// * `ty` - The type of the associated type.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Impl;
+    /// 
+    /// let mut impl_bar = Impl::new("Bar");
+    /// 
+    /// impl_bar
+    ///     .new_associate_type("Iter<'a>", "std::slice::Iter<'a, u8>")
+    ///     .bound("Self", "'a");
+    /// ```
+    pub fn new_associate_type<N, T>(&mut self, name: N, ty: T) -> &mut AssociatedTypeBinding
+    where
+        N: Into<Cow<'static, str>>,
+        T: Into<Type>,
+    {
+        self.push_associate_type(AssociatedTypeBinding::new(name, ty));
+        self.assoc_tys.last_mut().unwrap()
+    }
 
+    /// Push an associated type.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `item` - The associated type to push.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{AssociatedTypeBinding,Impl};
+    /// 
+    /// let mut impl_bar = Impl::new("Bar");
+    /// let item_binding = AssociatedTypeBinding::new("Item", "u8");
+    /// 
+    /// impl_bar.push_associate_type(item_binding);
+    /// ```
+    pub fn push_associate_type(&mut self, item: AssociatedTypeBinding) -> &mut Self {
+        self.assoc_tys.push(item);
+        self
+    }
+
+    /// Push a new associated constant, returning a mutable reference to it.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the constant.
+    /// * `ty` - The type of the constant.
+    /// * `value` - The value of the constant.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Impl;
+    /// 
+    /// let mut impl_bar = Impl::new("Bar");
+    /// 
+    /// impl_bar
+    ///     .new_associate_const("MAX", "u8", "255")
+    ///     .vis("pub");
+    /// ```
+    pub fn new_associate_const<N, T, V>(&mut self, name: N, ty: T, value: V) -> &mut AssociatedConst
+    where
+        N: Into<Cow<'static, str>>,
+        T: Into<Type>,
+        V: Into<Cow<'static, str>>,
+    {
+        self.push_associate_const(AssociatedConst::new(name, ty, value));
+        self.assoc_consts.last_mut().unwrap()
+    }
+
+    /// Push an associated constant.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `item` - The associated constant to push.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{AssociatedConst,Impl};
+    /// 
+    /// let mut impl_bar = Impl::new("Bar");
+    /// let max_const = AssociatedConst::new("MAX", "u8", "255");
+    /// 
+    /// impl_bar.push_associate_const(max_const);
+    /// ```
+    pub fn push_associate_const(&mut self, item: AssociatedConst) -> &mut Self {
+        self.assoc_consts.push(item);
         self
     }
 
@@ -211,11 +523,37 @@ impl Impl {
     /// 
     /// let mut foo_impl = Impl::new("Foo");
     /// foo_impl.new_fn("bar_fn");
-    pub fn new_fn(&mut self, name: &str) -> &mut Function {
+    pub fn new_fn<T>(&mut self, name: T) -> &mut Function
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.push_fn(Function::new(name));
         self.fns.last_mut().unwrap()
     }
 
+    /// Push a new function definition with the given signature, returning a
+    /// mutable reference to it to add the body.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `sig` - The signature of the function.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{FnSig,Impl};
+    /// 
+    /// let mut get_sig = FnSig::new("get");
+    /// get_sig.arg_ref_self().ret("u32");
+    /// 
+    /// let mut foo_impl = Impl::new("Foo");
+    /// foo_impl.new_fn_from_sig(get_sig).line("self.value");
+    /// ```
+    pub fn new_fn_from_sig(&mut self, sig: FnSig) -> &mut Function {
+        self.push_fn(Function::from_sig(sig));
+        self.fns.last_mut().unwrap()
+    }
+
     /// Push a function definition.
     /// 
     /// # Arguments
@@ -236,6 +574,258 @@ impl Impl {
         self
     }
 
+    /// Marks every function of the impl block as a generated accessor, see
+    /// `Policy`.
+    pub(crate) fn mark_accessors(&mut self) -> &mut Self {
+        for func in &mut self.fns {
+            func.mark_accessor(None);
+        }
+
+        self
+    }
+
+    /// Applies the conventions of the given policy to the functions of the
+    /// impl block.
+    pub(crate) fn apply_policy(&mut self, policy: &Policy) {
+        self.attributes.sort_by_kind(policy.get_attribute_order());
+
+        for func in &mut self.fns {
+            func.apply_policy(policy);
+        }
+    }
+
+    /// Returns an async version of the impl block where every function has
+    /// been converted with [`Function::to_async`].
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - The configuration of the async variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{AsyncVariant,Impl};
+    ///
+    /// let mut foo_impl = Impl::new("Foo");
+    /// foo_impl.new_fn("bar_fn");
+    ///
+    /// let async_foo_impl = foo_impl.to_async(&AsyncVariant::new());
+    /// ```
+    pub fn to_async(&self, variant: &AsyncVariant) -> Impl {
+        let mut imp = self.clone();
+
+        imp.fns = self.fns.iter().map(|f| f.to_async(variant)).collect();
+
+        if variant.uses_async_trait() && self.impl_trait.is_some() {
+            imp.r#macro("#[async_trait]");
+        }
+
+        imp
+    }
+
+    /// Adds or removes `#[async_trait]` according to the given strategy.
+    ///
+    /// The attribute is added if the impl block contains async functions or
+    /// implements one of the given async traits, matched by the last segment
+    /// of the trait's path.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `strategy` - The async trait strategy to apply.
+    /// * `async_traits` - The names of the traits that contain async functions.
+    pub(crate) fn apply_async_trait_strategy(
+        &mut self,
+        strategy: AsyncTraitStrategy,
+        async_traits: &[String],
+    ) {
+        let impl_trait = match self.impl_trait {
+            Some(ref ty) => trait_ident(ty.name()).to_string(),
+            None => return,
+        };
+
+        match strategy {
+            AsyncTraitStrategy::Native => {
+                self.attributes.remove_raw("#[async_trait]");
+            }
+            AsyncTraitStrategy::AsyncTrait => {
+                let is_async = self.fns.iter().any(|f| f.is_async())
+                    || async_traits.contains(&impl_trait);
+
+                if is_async && !self.attributes.has_raw("#[async_trait]") {
+                    self.attributes.raw("#[async_trait]");
+                }
+            }
+        }
+    }
+
+    /// Adds a `#[wasm_bindgen]` attribute to the impl.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attrs` - The arguments of the attribute.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Impl;
+    /// use rust_codegen::wasm_bindgen::BindgenAttrs;
+    /// 
+    /// let mut foo_impl = Impl::new("Foo");
+    /// foo_impl.wasm_bindgen(&BindgenAttrs::new());
+    /// ```
+    pub fn wasm_bindgen(&mut self, attrs: &BindgenAttrs) -> &mut Self {
+        self.attributes.meta(attrs.meta());
+        self
+    }
+
+    /// Returns whether the impl block implements a trait.
+    pub(crate) fn is_trait_impl(&self) -> bool {
+        self.impl_trait.is_some()
+    }
+
+    /// Returns the functions of the impl block.
+    pub(crate) fn fns(&self) -> &[Function] {
+        &self.fns
+    }
+
+    /// Replaces the signatures of the functions implementing one of the
+    /// given trait functions with the signature declared by the trait,
+    /// keeping their bodies, docs, and attributes.
+    ///
+    /// # Arguments
+    ///
+    /// * `sigs` - The name of each trait, matched by the last segment of
+    ///   its path, along with the signature of one of its functions.
+    pub(crate) fn sync_sigs(&mut self, sigs: &[(String, FnSig)]) {
+        let impl_trait = match self.impl_trait {
+            Some(ref ty) => trait_ident(ty.name()).to_string(),
+            None => return,
+        };
+
+        for func in &mut self.fns {
+            let declared = sigs
+                .iter()
+                .find(|(name, sig)| *name == impl_trait && sig.name() == func.name());
+
+            if let Some((_, sig)) = declared {
+                *func.sig_mut() = sig.clone();
+            }
+        }
+    }
+
+    /// Removes the functions and associated constants that aren't `pub` from
+    /// an inherent impl block and empties the bodies of the functions.
+    /// Returns whether the impl block is still part of the public API, i.e.
+    /// it implements a trait or has public functions or constants.
+    pub(crate) fn retain_public_api(&mut self) -> bool {
+        if !self.is_trait_impl() {
+            self.fns.retain(|func| func.get_vis() == Some("pub"));
+            self.assoc_consts.retain(|r#const| r#const.get_vis() == Some("pub"));
+        }
+
+        for func in &mut self.fns {
+            func.strip_body();
+        }
+
+        self.is_trait_impl() || !self.fns.is_empty() || !self.assoc_consts.is_empty()
+    }
+
+    /// Calls the given function with every type of the impl block, its
+    /// associated types and constants, and its functions.
+    pub(crate) fn visit_types_mut(&mut self, f: &mut dyn FnMut(&mut Type)) {
+        self.target.visit_types_mut(f);
+
+        if let Some(ref mut impl_trait) = self.impl_trait {
+            impl_trait.visit_types_mut(f);
+        }
+
+        for assoc in &mut self.assoc_tys {
+            assoc.visit_types_mut(f);
+        }
+
+        for r#const in &mut self.assoc_consts {
+            r#const.visit_types_mut(f);
+        }
+
+        for bound in &mut self.bounds {
+            bound.visit_types_mut(f);
+        }
+
+        for func in &mut self.fns {
+            func.visit_types_mut(f);
+        }
+    }
+
+    /// Applies the given transform to the functions of the impl block.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `transform` - The transform to apply.
+    pub(crate) fn transform(&mut self, transform: &Transform) {
+        for func in &mut self.fns {
+            func.transform(transform);
+        }
+    }
+
+    /// Maps the visibility of the functions and associated constants of the
+    /// impl block with the given function. The items of trait impls have no
+    /// visibility and are left as is.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `f` - The function mapping the visibility.
+    pub(crate) fn map_visibility(&mut self, f: &dyn Fn(Visibility) -> Visibility) {
+        if self.impl_trait.is_some() {
+            return;
+        }
+
+        for r#const in &mut self.assoc_consts {
+            r#const.map_visibility(f);
+        }
+
+        for func in &mut self.fns {
+            func.map_visibility(f);
+        }
+    }
+
+    /// Returns the name of the impl block in a source map, e.g.
+    /// `Display for Foo`.
+    pub(crate) fn source_name(&self) -> String {
+        match self.impl_trait {
+            Some(ref impl_trait) => format!(
+                "{} for {}",
+                impl_trait.fmt_to_string(),
+                self.target.fmt_to_string()
+            ),
+            None => self.target.fmt_to_string(),
+        }
+    }
+
+    /// Returns the impl block as a string of code formatted with a fresh
+    /// formatter, e.g. to render it without a scope.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Impl;
+    /// 
+    /// let mut foo_impl = Impl::new("Foo");
+    /// foo_impl.new_fn("bar").line("1");
+    /// 
+    /// assert_eq!(foo_impl.to_code_string(), "impl Foo {\n    fn bar() {\n        1\n    }\n}");
+    /// ```
+    pub fn to_code_string(&self) -> String {
+        fmt_to_code_string(|fmt| self.fmt(fmt))
+    }
+
+    /// Returns an estimate of the rendered length of the impl block.
+    pub(crate) fn estimated_len(&self) -> usize {
+        let fns: usize = self.fns.iter().map(|func| func.estimated_len() + 5).sum();
+        let assoc = (self.assoc_tys.len() + self.assoc_consts.len()) * 32;
+
+        self.attributes.estimated_len() + self.target.name().len() + fns + assoc + 32
+    }
+
     /// Formats the impl block using the given formatter.
     /// 
     /// # Arguments
@@ -254,9 +844,8 @@ impl Impl {
     /// foo_impl.fmt( &mut fmt);
     /// ```
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        for m in self.macros.iter() {
-            write!(fmt, "{}\n", m)?;
-        }
+        self.attributes.fmt(fmt)?;
+
         write!(fmt, "impl")?;
         fmt_generics(&self.generics[..], fmt)?;
 
@@ -275,14 +864,16 @@ impl Impl {
             // format associated types
             if !self.assoc_tys.is_empty() {
                 for ty in &self.assoc_tys {
-                    write!(fmt, "type {} = ", ty.name)?;
-                    ty.ty.fmt(fmt)?;
-                    write!(fmt, ";\n")?;
+                    ty.fmt(fmt)?;
                 }
             }
 
+            for r#const in &self.assoc_consts {
+                r#const.fmt(fmt)?;
+            }
+
             for (i, func) in self.fns.iter().enumerate() {
-                if i != 0 || !self.assoc_tys.is_empty() {
+                if i != 0 || !self.assoc_tys.is_empty() || !self.assoc_consts.is_empty() {
                     write!(fmt, "\n")?;
                 }
 
@@ -293,3 +884,9 @@ impl Impl {
         })
     }
 }
+
+/// Returns the name of the given generic without its bounds, e.g. `T` for
+/// `T: Clone`.
+fn generic_name(generic: &str) -> &str {
+    generic.split(':').next().unwrap_or(generic).trim()
+}
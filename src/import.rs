@@ -6,6 +6,9 @@ pub struct Import {
     line: String,
     /// Import visibility.
     pub vis: Option<String>,
+    /// The name the type is imported as when it conflicts with another
+    /// import.
+    alias: Option<String>,
 }
 
 impl Import {
@@ -25,6 +28,7 @@ impl Import {
         Import {
             line: format!("{}::{}", path, ty),
             vis: None,
+            alias: None,
         }
     }
 
@@ -45,4 +49,30 @@ impl Import {
         self.vis = Some(vis.to_string());
         self
     }
+
+    /// Returns the name the type is imported as when it conflicts with
+    /// another import of the scope, e.g. `BFoo` for `use b::Foo as BFoo;`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Scope;
+    /// 
+    /// let mut scope = Scope::new();
+    /// scope.import("a", "Foo");
+    /// 
+    /// assert_eq!(scope.import("b", "Foo").alias(), Some("BFoo"));
+    /// ```
+    pub fn alias(&self) -> Option<&str> {
+        self.alias.as_deref()
+    }
+
+    /// Sets the name the type is imported as.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `alias` - The name the type is imported as.
+    pub(crate) fn set_alias(&mut self, alias: String) {
+        self.alias = Some(alias);
+    }
 }
@@ -1,8 +1,16 @@
+use std::borrow::Cow;
+
+use crate::asm::Asm;
+use crate::extern_block::ExternBlock;
+use crate::feature_layout::FEATURE_KEY;
+use crate::format_item::FormatItem;
 use crate::function::Function;
+use crate::metadata::Metadata;
 use crate::module::Module;
 
 use crate::r#enum::Enum;
 use crate::r#impl::Impl;
+use crate::r#static::Static;
 use crate::r#struct::Struct;
 use crate::r#trait::Trait;
 
@@ -15,5 +23,125 @@ pub enum Item {
     Trait(Trait),
     Enum(Enum),
     Impl(Impl),
-    Raw(String),
+    Static(Static),
+    ExternBlock(ExternBlock),
+    Asm(Asm),
+    Raw(Cow<'static, str>),
+    Custom(Box<dyn FormatItem>),
+}
+
+impl Item {
+    /// Returns the kind of the item, e.g. `struct` or `fn`.
+    pub(crate) fn kind(&self) -> &'static str {
+        match *self {
+            Item::Module(..) => "mod",
+            Item::Struct(..) => "struct",
+            Item::Function(..) => "fn",
+            Item::Trait(..) => "trait",
+            Item::Enum(..) => "enum",
+            Item::Impl(..) => "impl",
+            Item::Static(..) => "static",
+            Item::ExternBlock(..) => "extern",
+            Item::Asm(..) => "asm",
+            Item::Raw(..) => "raw",
+            Item::Custom(..) => "custom",
+        }
+    }
+
+    /// Returns the name of the item, e.g. `Display for Foo` for an impl
+    /// block, or an empty string for assembly, raw code, and custom items.
+    pub(crate) fn name(&self) -> String {
+        match *self {
+            Item::Module(ref v) => v.name.to_string(),
+            Item::Struct(ref v) => v.ty().name().to_string(),
+            Item::Function(ref v) => v.name().to_string(),
+            Item::Trait(ref v) => v.ty().name().to_string(),
+            Item::Enum(ref v) => v.ty().name().to_string(),
+            Item::Impl(ref v) => v.source_name(),
+            Item::Static(ref v) => v.name().to_string(),
+            Item::ExternBlock(ref v) => format!("\"{}\"", v.abi()),
+            Item::Asm(..) | Item::Raw(..) | Item::Custom(..) => String::new(),
+        }
+    }
+
+    /// Returns an estimate of the rendered length of the item, see
+    /// `Scope::estimated_len`.
+    pub(crate) fn estimated_len(&self) -> usize {
+        match *self {
+            Item::Module(ref v) => v.estimated_len(),
+            Item::Struct(ref v) => v.estimated_len(),
+            Item::Function(ref v) => v.estimated_len(),
+            Item::Trait(ref v) => v.estimated_len(),
+            Item::Enum(ref v) => v.estimated_len(),
+            Item::Impl(ref v) => v.estimated_len(),
+            Item::Static(ref v) => v.estimated_len(),
+            Item::ExternBlock(ref v) => v.estimated_len(),
+            Item::Asm(ref v) => v.estimated_len(),
+            Item::Raw(ref v) => v.len() + 1,
+            // Custom items are opaque, so a typical macro invocation is
+            // assumed.
+            Item::Custom(..) => 64,
+        }
+    }
+
+    /// Returns the cargo feature the item is tagged with, if any.
+    pub(crate) fn feature(&self) -> Option<&str> {
+        let metadata = match *self {
+            Item::Module(ref v) => v.metadata(),
+            Item::Struct(ref v) => v.metadata(),
+            Item::Function(ref v) => v.metadata(),
+            Item::Trait(ref v) => v.metadata(),
+            Item::Enum(ref v) => v.metadata(),
+            Item::Impl(ref v) => v.metadata(),
+            _ => return None,
+        };
+
+        metadata.get(FEATURE_KEY)
+    }
+
+    /// Adds the given attribute to the item, e.g. `cfg(test)`. Items
+    /// without attributes are left as is.
+    pub(crate) fn push_attr(&mut self, attribute: String) {
+        match *self {
+            Item::Module(ref mut v) => {
+                v.attr(attribute);
+            }
+            Item::Struct(ref mut v) => {
+                v.attr(attribute);
+            }
+            Item::Function(ref mut v) => {
+                v.attr(attribute);
+            }
+            Item::Enum(ref mut v) => {
+                v.attr(attribute);
+            }
+            Item::Trait(ref mut v) => {
+                v.attr(attribute);
+            }
+            Item::Impl(ref mut v) => {
+                v.attr(attribute);
+            }
+            Item::Static(ref mut v) => {
+                v.attr(attribute);
+            }
+            _ => {}
+        }
+    }
+
+    /// Returns the kind, name, and metadata of the item for a source map, or
+    /// `None` for assembly, raw code, and custom items.
+    pub(crate) fn source_info(&self) -> Option<(&'static str, String, Option<&Metadata>)> {
+        let metadata = match *self {
+            Item::Module(ref v) => Some(v.metadata()),
+            Item::Struct(ref v) => Some(v.metadata()),
+            Item::Function(ref v) => Some(v.metadata()),
+            Item::Trait(ref v) => Some(v.metadata()),
+            Item::Enum(ref v) => Some(v.metadata()),
+            Item::Impl(ref v) => Some(v.metadata()),
+            Item::Static(..) | Item::ExternBlock(..) => None,
+            Item::Asm(..) | Item::Raw(..) | Item::Custom(..) => return None,
+        };
+
+        Some((self.kind(), self.name(), metadata))
+    }
 }
@@ -0,0 +1,270 @@
+use std::borrow::Cow;
+
+use crate::field::Field;
+use crate::function::Function;
+use crate::scope::Scope;
+
+use crate::r#impl::Impl;
+use crate::r#struct::Struct;
+use crate::r#type::Type;
+
+/// Generates a custom iterator: a struct holding the state of the iteration,
+/// its `impl Iterator`, and optionally `ExactSizeIterator` and
+/// `DoubleEndedIterator` impls.
+///
+/// Functions without a body are generated with a `todo!()` stub.
+#[derive(Debug, Clone)]
+pub struct IteratorAdapter {
+    /// The name of the iterator struct.
+    name: Cow<'static, str>,
+    /// The visibility of the iterator struct.
+    vis: Option<String>,
+    /// The type of the items.
+    item: Type,
+    /// The state fields of the iterator struct.
+    fields: Vec<Field>,
+    /// The `next` function.
+    next: Function,
+    /// The `size_hint` function, if the iterator has an exact size.
+    size_hint: Option<Function>,
+    /// The `next_back` function, if the iterator is double-ended.
+    next_back: Option<Function>,
+}
+
+impl IteratorAdapter {
+    /// Returns a new iterator generator with the given name and item type.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the iterator struct.
+    /// * `item` - The type of the items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::IteratorAdapter;
+    ///
+    /// let pages = IteratorAdapter::new("Pages", "Page");
+    /// ```
+    pub fn new<N, T>(name: N, item: T) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+        T: Into<Type>,
+    {
+        let mut next = Function::new("next");
+        next.arg_mut_self().ret("Option<Self::Item>");
+
+        IteratorAdapter {
+            name: name.into(),
+            vis: None,
+            item: item.into(),
+            fields: vec![],
+            next,
+            size_hint: None,
+            next_back: None,
+        }
+    }
+
+    /// Set the visibility of the iterator struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `vis` - The visibility to set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::IteratorAdapter;
+    ///
+    /// let mut pages = IteratorAdapter::new("Pages", "Page");
+    /// pages.vis("pub");
+    /// ```
+    pub fn vis(&mut self, vis: &str) -> &mut Self {
+        self.vis = Some(vis.to_string());
+        self
+    }
+
+    /// Add a state field to the iterator struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the field.
+    /// * `ty` - The type of the field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::IteratorAdapter;
+    ///
+    /// let mut pages = IteratorAdapter::new("Pages", "Page");
+    /// pages.field("cursor", "Option<String>");
+    /// ```
+    pub fn field<N, T>(&mut self, name: N, ty: T) -> &mut Self
+    where
+        N: Into<Cow<'static, str>>,
+        T: Into<Type>,
+    {
+        self.fields.push(Field::new(name, ty));
+        self
+    }
+
+    /// Returns the `next` function to add its body to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::IteratorAdapter;
+    ///
+    /// let mut pages = IteratorAdapter::new("Pages", "Page");
+    /// pages.next_fn().line("self.buffer.pop()");
+    /// ```
+    pub fn next_fn(&mut self) -> &mut Function {
+        &mut self.next
+    }
+
+    /// Generate an `ExactSizeIterator` impl, returning the `size_hint`
+    /// function of the `Iterator` impl, which must return the exact number of
+    /// remaining items, to add its body to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::IteratorAdapter;
+    ///
+    /// let mut pages = IteratorAdapter::new("Pages", "Page");
+    /// pages.exact_size().line("(self.buffer.len(), Some(self.buffer.len()))");
+    /// ```
+    pub fn exact_size(&mut self) -> &mut Function {
+        self.size_hint.get_or_insert_with(|| {
+            let mut size_hint = Function::new("size_hint");
+            size_hint.arg_ref_self().ret("(usize, Option<usize>)");
+            size_hint
+        })
+    }
+
+    /// Generate a `DoubleEndedIterator` impl, returning its `next_back`
+    /// function to add its body to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::IteratorAdapter;
+    ///
+    /// let mut pages = IteratorAdapter::new("Pages", "Page");
+    /// pages.double_ended().line("self.buffer.pop_front()");
+    /// ```
+    pub fn double_ended(&mut self) -> &mut Function {
+        self.next_back.get_or_insert_with(|| {
+            let mut next_back = Function::new("next_back");
+            next_back.arg_mut_self().ret("Option<Self::Item>");
+            next_back
+        })
+    }
+
+    /// Returns the iterator struct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::IteratorAdapter;
+    ///
+    /// let pages = IteratorAdapter::new("Pages", "Page");
+    /// let pages_struct = pages.to_struct();
+    /// ```
+    pub fn to_struct(&self) -> Struct {
+        let mut ret = Struct::new(self.name.clone());
+
+        if let Some(ref vis) = self.vis {
+            ret.vis(vis);
+        }
+
+        for field in &self.fields {
+            ret.push_field(field.clone());
+        }
+
+        ret
+    }
+
+    /// Returns the `impl Iterator` block followed by the
+    /// `ExactSizeIterator` and `DoubleEndedIterator` impls, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::IteratorAdapter;
+    ///
+    /// let pages = IteratorAdapter::new("Pages", "Page");
+    /// let impls = pages.to_impls();
+    /// ```
+    pub fn to_impls(&self) -> Vec<Impl> {
+        let mut iterator = Impl::new(self.name.clone());
+        iterator
+            .impl_trait("Iterator")
+            .associate_type("Item", &self.item)
+            .push_fn(stub(&self.next));
+
+        if let Some(ref size_hint) = self.size_hint {
+            iterator.push_fn(stub(size_hint));
+        }
+
+        let mut impls = vec![iterator];
+
+        if self.size_hint.is_some() {
+            let mut exact_size = Impl::new(self.name.clone());
+            exact_size.impl_trait("ExactSizeIterator");
+
+            impls.push(exact_size);
+        }
+
+        if let Some(ref next_back) = self.next_back {
+            let mut double_ended = Impl::new(self.name.clone());
+            double_ended
+                .impl_trait("DoubleEndedIterator")
+                .push_fn(stub(next_back));
+
+            impls.push(double_ended);
+        }
+
+        impls
+    }
+
+    /// Push the iterator struct and its impls to the given scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - The scope to push the iterator to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{IteratorAdapter,Scope};
+    ///
+    /// let mut scope = Scope::new();
+    /// let pages = IteratorAdapter::new("Pages", "Page");
+    ///
+    /// pages.push_to(&mut scope);
+    /// ```
+    pub fn push_to(&self, scope: &mut Scope) {
+        scope.push_struct(self.to_struct());
+
+        for item in self.to_impls() {
+            scope.push_impl(item);
+        }
+    }
+}
+
+/// Returns the given function with a `todo!()` body if it has none.
+fn stub(func: &Function) -> Function {
+    let mut func = func.clone();
+
+    let empty = match func.body {
+        Some(ref body) => body.is_empty(),
+        None => true,
+    };
+
+    if empty {
+        func.line("todo!()");
+    }
+
+    func
+}
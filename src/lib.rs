@@ -24,41 +24,136 @@
 //! println!("{}", scope.to_string());
 //! ```
 
+mod asm;
+mod associated_const;
 mod associated_type;
+mod associated_type_binding;
+mod async_variant;
+mod attribute;
+mod attributes;
 mod block;
 mod body;
+mod body_builder;
+mod call_chain;
 mod bound;
+mod case;
+mod deterministic;
+mod diff;
 mod docs;
+mod env_loader;
+mod error_type;
+mod expr;
+mod extern_block;
+mod feature_layout;
+mod ffi;
 mod field;
+mod flag_set;
+mod format_item;
 mod fields;
+mod fn_sig;
 mod formatter;
 mod function;
 mod import;
 mod item;
+mod iterator_adapter;
+mod literal;
+mod merge;
+mod metadata;
 mod module;
+mod path_rewrite;
+mod platform_modules;
+mod policy;
+mod prelude;
+mod receiver;
+mod region;
+mod registry;
+mod render_hook;
 mod scope;
+mod semver;
+mod source_map;
+mod source_tree;
+mod state_machine;
+mod stats;
+mod struct_literal;
+mod template;
+mod test_runner;
+mod transform;
 mod type_def;
+mod type_map;
+mod type_pool;
+mod validator;
 mod variant;
+mod visibility;
+mod visitor;
 
 mod r#enum;
 mod r#impl;
+mod r#static;
 mod r#struct;
 mod r#trait;
 mod r#type;
 
+pub mod clap;
+pub mod pyo3;
+pub mod scaffold;
+pub mod serde;
+pub mod wasm_bindgen;
 
+pub use asm::*;
+pub use associated_const::*;
 pub use associated_type::*;
+pub use associated_type_binding::*;
+pub use async_variant::*;
+pub use attribute::*;
 pub use block::*;
+pub use body_builder::*;
+pub use call_chain::*;
+pub use diff::*;
+pub use docs::*;
+pub use env_loader::*;
+pub use error_type::*;
+pub use expr::*;
+pub use extern_block::*;
+pub use feature_layout::*;
 pub use field::*;
+pub use flag_set::*;
+pub use fn_sig::*;
+pub use format_item::*;
 pub use formatter::*;
 pub use function::*;
 pub use import::*;
+pub use iterator_adapter::*;
+pub use literal::*;
+pub use merge::*;
+pub use metadata::*;
 pub use module::*;
+pub use path_rewrite::*;
+pub use platform_modules::*;
+pub use policy::*;
+pub use prelude::*;
+pub use receiver::*;
+pub use region::*;
+pub use registry::*;
+pub use render_hook::*;
 pub use scope::*;
+pub use semver::*;
+pub use source_map::*;
+pub use source_tree::*;
+pub use state_machine::*;
+pub use stats::*;
+pub use struct_literal::*;
+pub use test_runner::*;
+pub use transform::*;
+pub use type_map::*;
+pub use type_pool::*;
+pub use validator::*;
 pub use variant::*;
+pub use visibility::*;
+pub use visitor::*;
 
 pub use r#enum::*;
 pub use r#impl::*;
+pub use r#static::*;
 pub use r#struct::*;
 pub use r#trait::*;
 pub use r#type::*;
@@ -0,0 +1,241 @@
+/// Returns the given string as a Rust string literal.
+///
+/// Quotes, backslashes, and control characters are escaped. If the string
+/// contains quotes or backslashes but no control characters, a raw string is
+/// used instead when it is shorter. Newlines are always escaped so that the
+/// literal stays on one line and isn't affected by indentation.
+///
+/// # Arguments
+///
+/// * `s` - The string to quote.
+///
+/// # Examples
+///
+/// ```
+/// use rust_codegen::quote_str;
+///
+/// assert_eq!(quote_str("tab\there"), r#""tab\there""#);
+/// assert_eq!(quote_str(r#"{"a": "b"}"#), r###"r#"{"a": "b"}"#"###);
+/// ```
+pub fn quote_str(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len() + 2);
+    escaped.push('"');
+
+    for c in s.chars() {
+        match c {
+            '"' => escaped.push_str("\\\""),
+            '\\' => escaped.push_str("\\\\"),
+            '\n' => escaped.push_str("\\n"),
+            '\r' => escaped.push_str("\\r"),
+            '\t' => escaped.push_str("\\t"),
+            '\0' => escaped.push_str("\\0"),
+            c if c.is_control() => escaped.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => escaped.push(c),
+        }
+    }
+
+    escaped.push('"');
+
+    if (s.contains('"') || s.contains('\\')) && !s.chars().any(|c| c.is_control()) {
+        let raw = quote_raw_str(s, 0);
+
+        if raw.len() < escaped.len() {
+            return raw;
+        }
+    }
+
+    escaped
+}
+
+/// Returns the given string as a Rust raw string literal with at least the
+/// given number of hashes. More hashes are used if the string contains a
+/// quote followed by that many hashes.
+///
+/// Raw strings can't contain escapes, so control characters such as
+/// newlines are written as is.
+///
+/// # Arguments
+///
+/// * `s` - The string to quote.
+/// * `hashes` - The minimum number of hashes.
+///
+/// # Examples
+///
+/// ```
+/// use rust_codegen::quote_raw_str;
+///
+/// assert_eq!(quote_raw_str(r"C:\dir", 0), r#"r"C:\dir""#);
+/// assert_eq!(quote_raw_str(r#"say "hi""#, 0), r###"r#"say "hi""#"###);
+/// ```
+pub fn quote_raw_str(s: &str, hashes: usize) -> String {
+    let mut hashes = hashes;
+
+    while s.contains(&format!("\"{}", "#".repeat(hashes))) {
+        hashes += 1;
+    }
+
+    let hashes = "#".repeat(hashes);
+    format!("r{}\"{}\"{}", hashes, s, hashes)
+}
+
+/// Returns the given bytes as a Rust byte string literal, e.g. `b"GIF89a"`.
+///
+/// Quotes, backslashes, and any byte that isn't printable ASCII are escaped.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes to quote.
+///
+/// # Examples
+///
+/// ```
+/// use rust_codegen::byte_str;
+///
+/// assert_eq!(byte_str(b"GIF\x00\"\n"), r#"b"GIF\x00\"\n""#);
+/// ```
+pub fn byte_str(bytes: &[u8]) -> String {
+    let mut ret = String::with_capacity(bytes.len() + 3);
+    ret.push_str("b\"");
+
+    for &b in bytes {
+        match b {
+            b'"' => ret.push_str("\\\""),
+            b'\\' => ret.push_str("\\\\"),
+            b'\n' => ret.push_str("\\n"),
+            b'\r' => ret.push_str("\\r"),
+            b'\t' => ret.push_str("\\t"),
+            0x20..=0x7e => ret.push(b as char),
+            _ => ret.push_str(&format!("\\x{:02x}", b)),
+        }
+    }
+
+    ret.push('"');
+    ret
+}
+
+/// Returns the given bytes as a `&[u8]` array literal with the given number
+/// of bytes per line, written in hex, e.g. for embedding binary assets.
+///
+/// # Arguments
+///
+/// * `bytes` - The bytes of the array.
+/// * `per_line` - The number of bytes per line.
+///
+/// # Examples
+///
+/// ```
+/// use rust_codegen::byte_array;
+///
+/// assert_eq!(
+///     byte_array(&[1, 2, 3], 2),
+///     "&[\n    0x01, 0x02,\n    0x03,\n]"
+/// );
+/// ```
+pub fn byte_array(bytes: &[u8], per_line: usize) -> String {
+    assert!(per_line > 0, "byte arrays need at least one byte per line");
+
+    if bytes.is_empty() {
+        return "&[]".to_string();
+    }
+
+    let mut ret = String::from("&[\n");
+
+    for chunk in bytes.chunks(per_line) {
+        let line: Vec<String> = chunk.iter().map(|b| format!("0x{:02x}", b)).collect();
+
+        ret.push_str("    ");
+        ret.push_str(&line.join(", "));
+        ret.push_str(",\n");
+    }
+
+    ret.push(']');
+    ret
+}
+
+/// Returns the given integer as a literal with `_` separators every three
+/// digits followed by the given type suffix, e.g. `1_000_000u64`.
+///
+/// # Arguments
+///
+/// * `value` - The value of the literal.
+/// * `suffix` - The type suffix, or an empty string for none.
+///
+/// # Examples
+///
+/// ```
+/// use rust_codegen::int_literal;
+///
+/// assert_eq!(int_literal(1_000_000, "u64"), "1_000_000u64");
+/// assert_eq!(int_literal(-1234, ""), "-1_234");
+/// ```
+pub fn int_literal<T>(value: T, suffix: &str) -> String
+where
+    T: Into<i128>,
+{
+    let value = value.into();
+    let digits = value.unsigned_abs().to_string();
+
+    let mut ret = String::new();
+
+    if value < 0 {
+        ret.push('-');
+    }
+
+    ret.push_str(&group_digits(&digits, 3));
+    ret.push_str(suffix);
+    ret
+}
+
+/// Returns the given integer as a hex literal with `_` separators every four
+/// digits, zero-padded to the given number of digits, followed by the given
+/// type suffix, e.g. `0xdead_beef_u32`.
+///
+/// # Arguments
+///
+/// * `value` - The value of the literal.
+/// * `width` - The minimum number of hex digits.
+/// * `suffix` - The type suffix, or an empty string for none.
+///
+/// # Examples
+///
+/// ```
+/// use rust_codegen::hex_literal;
+///
+/// assert_eq!(hex_literal(0xdeadbeef_u32, 8, "u32"), "0xdead_beef_u32");
+/// assert_eq!(hex_literal(0xff_u8, 4, ""), "0x00ff");
+/// ```
+pub fn hex_literal<T>(value: T, width: usize, suffix: &str) -> String
+where
+    T: Into<u128>,
+{
+    let digits = format!("{:0width$x}", value.into(), width = width);
+    let mut ret = format!("0x{}", group_digits(&digits, 4));
+
+    if !suffix.is_empty() {
+        // Hex digits include `a` to `f`, so separate the suffix to keep it
+        // readable and unambiguous.
+        ret.push('_');
+        ret.push_str(suffix);
+    }
+
+    ret
+}
+
+/// Inserts `_` between every group of the given size, counted from the end.
+fn group_digits(digits: &str, size: usize) -> String {
+    let mut ret = String::with_capacity(digits.len() + digits.len() / size);
+
+    let first = match digits.len() % size {
+        0 => size,
+        n => n,
+    };
+
+    ret.push_str(&digits[..first]);
+
+    for group in digits.as_bytes()[first..].chunks(size) {
+        ret.push('_');
+        ret.push_str(std::str::from_utf8(group).unwrap());
+    }
+
+    ret
+}
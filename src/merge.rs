@@ -0,0 +1,209 @@
+/// Defines a region that both the current file and the newly generated
+/// output changed differently, see [`three_way_merge`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeConflict {
+    /// The line of the merged output where the conflict markers start,
+    /// starting at 1.
+    pub line: usize,
+    /// The lines of the previously generated output.
+    pub previous: Vec<String>,
+    /// The lines of the current file, e.g. with hand edits.
+    pub current: Vec<String>,
+    /// The lines of the newly generated output.
+    pub generated: Vec<String>,
+}
+
+/// Defines the result of a three-way merge, returned by
+/// [`three_way_merge`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct MergeResult {
+    /// The merged file, with git-style conflict markers around every
+    /// conflict.
+    pub merged: String,
+    /// The conflicts, in the order they appear in the merged file.
+    pub conflicts: Vec<MergeConflict>,
+}
+
+impl MergeResult {
+    /// Returns whether the merge has no conflicts, i.e. whether the merged
+    /// file can be written without review.
+    pub fn is_clean(&self) -> bool {
+        self.conflicts.is_empty()
+    }
+}
+
+/// Merges the changes between the previously and the newly generated output
+/// into the current file, e.g. to regenerate a file that was edited by hand
+/// without losing the edits.
+///
+/// The merge is line-based. Regions that only one side changed take that
+/// side's lines, and regions that both sides changed differently are
+/// reported as conflicts and surrounded by `<<<<<<< current`, `=======`, and
+/// `>>>>>>> generated` markers.
+///
+/// # Arguments
+///
+/// * `previous` - The output of the previous generation, i.e. the common
+///   ancestor.
+/// * `generated` - The output of the new generation.
+/// * `current` - The current contents of the file.
+///
+/// # Examples
+///
+/// ```
+/// use rust_codegen::three_way_merge;
+///
+/// let previous = "struct User {\n    id: u64,\n}\n";
+/// let current = "#[derive(Debug)]\nstruct User {\n    id: u64,\n}\n";
+/// let generated = "struct User {\n    id: u64,\n    name: String,\n}\n";
+///
+/// let result = three_way_merge(previous, generated, current);
+///
+/// assert!(result.is_clean());
+/// assert_eq!(
+///     result.merged,
+///     "#[derive(Debug)]\nstruct User {\n    id: u64,\n    name: String,\n}\n"
+/// );
+/// ```
+pub fn three_way_merge(previous: &str, generated: &str, current: &str) -> MergeResult {
+    let base: Vec<&str> = previous.split_inclusive('\n').collect();
+    let ours: Vec<&str> = current.split_inclusive('\n').collect();
+    let theirs: Vec<&str> = generated.split_inclusive('\n').collect();
+
+    let ours_matches = match_lines(&base, &ours);
+    let theirs_matches = match_lines(&base, &theirs);
+
+    let mut result = MergeResult {
+        merged: String::new(),
+        conflicts: vec![],
+    };
+
+    let (mut b, mut o, mut t) = (0, 0, 0);
+
+    loop {
+        // The next base line kept by both sides, or the end of the files.
+        let stable = (b..base.len()).find_map(|i| match (ours_matches[i], theirs_matches[i]) {
+            (Some(oi), Some(ti)) if oi >= o && ti >= t => Some((i, oi, ti)),
+            _ => None,
+        });
+
+        let (b_end, o_end, t_end) = stable.unwrap_or((base.len(), ours.len(), theirs.len()));
+
+        merge_chunk(
+            &base[b..b_end],
+            &ours[o..o_end],
+            &theirs[t..t_end],
+            &mut result,
+        );
+
+        match stable {
+            Some(_) => {
+                result.merged.push_str(base[b_end]);
+                b = b_end + 1;
+                o = o_end + 1;
+                t = t_end + 1;
+            }
+            None => break,
+        }
+    }
+
+    result
+}
+
+/// Pushes the merge of a chunk between two stable lines to the result.
+fn merge_chunk(base: &[&str], ours: &[&str], theirs: &[&str], result: &mut MergeResult) {
+    if ours == base || ours == theirs {
+        result.merged.extend(theirs.iter().copied());
+        return;
+    }
+
+    if theirs == base {
+        result.merged.extend(ours.iter().copied());
+        return;
+    }
+
+    result.conflicts.push(MergeConflict {
+        line: result.merged.matches('\n').count() + 1,
+        previous: base.iter().map(|line| line.to_string()).collect(),
+        current: ours.iter().map(|line| line.to_string()).collect(),
+        generated: theirs.iter().map(|line| line.to_string()).collect(),
+    });
+
+    result.merged.push_str("<<<<<<< current\n");
+    push_lines(&mut result.merged, ours);
+    result.merged.push_str("=======\n");
+    push_lines(&mut result.merged, theirs);
+    result.merged.push_str(">>>>>>> generated\n");
+}
+
+/// Pushes the given lines, ending the last one with a newline if it has
+/// none so that the next conflict marker starts a line.
+fn push_lines(merged: &mut String, lines: &[&str]) {
+    merged.extend(lines.iter().copied());
+
+    if !merged.is_empty() && !merged.ends_with('\n') {
+        merged.push('\n');
+    }
+}
+
+/// Returns, for every line of `base`, the index of the matching line of
+/// `other` in a longest common subsequence of the two, if any.
+fn match_lines(base: &[&str], other: &[&str]) -> Vec<Option<usize>> {
+    let mut matches = vec![None; base.len()];
+
+    // The common prefix and suffix are matched directly to keep the table
+    // small, since regenerated files mostly stay the same.
+    let prefix = base
+        .iter()
+        .zip(other)
+        .take_while(|(a, b)| a == b)
+        .count();
+    let suffix = base[prefix..]
+        .iter()
+        .rev()
+        .zip(other[prefix..].iter().rev())
+        .take_while(|(a, b)| a == b)
+        .count();
+
+    for (i, m) in matches.iter_mut().enumerate().take(prefix) {
+        *m = Some(i);
+    }
+
+    for i in 0..suffix {
+        matches[base.len() - 1 - i] = Some(other.len() - 1 - i);
+    }
+
+    let base_mid = &base[prefix..base.len() - suffix];
+    let other_mid = &other[prefix..other.len() - suffix];
+    let (n, m) = (base_mid.len(), other_mid.len());
+
+    // `lcs[i][j]` is the length of the LCS of `base_mid[i..]` and
+    // `other_mid[j..]`.
+    let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+
+    for i in (0..n).rev() {
+        for j in (0..m).rev() {
+            lcs[i][j] = if base_mid[i] == other_mid[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+
+    while i < n && j < m {
+        if base_mid[i] == other_mid[j] {
+            matches[prefix + i] = Some(prefix + j);
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            i += 1;
+        } else {
+            j += 1;
+        }
+    }
+
+    matches
+}
@@ -0,0 +1,131 @@
+use std::borrow::Cow;
+
+use indexmap::IndexMap;
+
+use crate::feature_layout::FEATURE_KEY;
+
+/// Defines arbitrary key-value metadata attached to an item, e.g. to tag the
+/// items of a multi-pass generator with the schema they came from.
+///
+/// Metadata is kept when the item is cloned and is never rendered.
+#[derive(Debug, Clone, Default)]
+pub struct Metadata {
+    /// The entries, in the order they were first set.
+    entries: IndexMap<Cow<'static, str>, Cow<'static, str>>,
+}
+
+impl Metadata {
+    /// Returns new, empty metadata.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Metadata;
+    ///
+    /// let metadata = Metadata::new();
+    /// ```
+    pub fn new() -> Self {
+        Metadata {
+            entries: IndexMap::new(),
+        }
+    }
+
+    /// Sets the value of the given key, replacing the previous value.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to set.
+    /// * `value` - The value of the key.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Metadata;
+    ///
+    /// let mut metadata = Metadata::new();
+    /// metadata.set("schema", "user.json");
+    /// ```
+    pub fn set<K, V>(&mut self, key: K, value: V) -> &mut Self
+    where
+        K: Into<Cow<'static, str>>,
+        V: Into<Cow<'static, str>>,
+    {
+        self.entries.insert(key.into(), value.into());
+        self
+    }
+
+    /// Returns the value of the given key.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to look up.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Metadata;
+    ///
+    /// let mut metadata = Metadata::new();
+    /// metadata.set("schema", "user.json");
+    ///
+    /// assert_eq!(metadata.get("schema"), Some("user.json"));
+    /// ```
+    pub fn get(&self, key: &str) -> Option<&str> {
+        self.entries.get(key).map(|value| &**value)
+    }
+
+    /// Tags the item with the cargo feature it belongs to, used to lay out
+    /// the item by [`Scope::render_features`].
+    ///
+    /// # Arguments
+    ///
+    /// * `feature` - The name of the feature.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Metadata;
+    ///
+    /// let mut metadata = Metadata::new();
+    /// metadata.feature("serde");
+    ///
+    /// assert_eq!(metadata.get("feature"), Some("serde"));
+    /// ```
+    ///
+    /// [`Scope::render_features`]: crate::Scope::render_features
+    pub fn feature<T>(&mut self, feature: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.set(FEATURE_KEY, feature)
+    }
+
+    /// Returns whether the given key is set.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to look up.
+    pub fn contains(&self, key: &str) -> bool {
+        self.entries.contains_key(key)
+    }
+
+    /// Removes the given key, returning its value.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key to remove.
+    pub fn remove(&mut self, key: &str) -> Option<Cow<'static, str>> {
+        self.entries.shift_remove(key)
+    }
+
+    /// Returns whether no key is set.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Returns an iterator over the keys and values, in the order they were
+    /// first set.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.entries.iter().map(|(key, value)| (&**key, &**value))
+    }
+}
@@ -1,9 +1,15 @@
+use std::borrow::Cow;
 use std::fmt::{self, Write};
 
+use crate::attributes::Attributes;
 use crate::docs::Docs;
-use crate::formatter::Formatter;
+use crate::format_item::FormatItem;
+use crate::formatter::{fmt_to_code_string, Formatter};
 use crate::function::Function;
+use crate::metadata::Metadata;
+use crate::policy::Policy;
 use crate::scope::Scope;
+use crate::visibility::{map_vis, Visibility};
 
 use crate::r#enum::Enum;
 use crate::r#impl::Impl;
@@ -15,13 +21,18 @@ use crate::r#trait::Trait;
 #[allow(dead_code)]
 pub struct Module {
     /// The module's name.
-    pub name: String,
+    pub name: Cow<'static, str>,
     /// The module's visibility.
     vis: Option<String>,
     /// Module documentation.
     docs: Option<Docs>,
+    /// Module attributes, e.g., `#[cfg(test)]`, in the order they were
+    /// added.
+    attributes: Attributes,
     /// Contents of the module.
-    scope: Scope,
+    pub(crate) scope: Scope,
+    /// The module's metadata, which is never rendered.
+    metadata: Metadata,
 }
 
 impl Module {
@@ -38,12 +49,17 @@ impl Module {
     /// 
     /// let foo_module = Module::new("Foo");
     /// ```
-    pub fn new(name: &str) -> Self {
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
         Module {
-            name: name.to_string(),
+            name: name.into(),
             vis: None,
             docs: None,
+            attributes: Attributes::new(),
             scope: Scope::new(),
+            metadata: Metadata::new(),
         }
     }
 
@@ -61,6 +77,48 @@ impl Module {
         &mut self.scope
     }
 
+    /// Returns the metadata of the module, which is kept when it is cloned
+    /// and never rendered.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns a mutable reference to the metadata of the module, e.g. to tag
+    /// it in a multi-pass generator.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Module;
+    /// 
+    /// let mut foo_module = Module::new("foo");
+    /// foo_module.metadata_mut().set("schema", "foo.json");
+    /// ```
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    /// Returns the visibility of the module.
+    pub(crate) fn get_vis(&self) -> Option<&str> {
+        self.vis.as_deref()
+    }
+
+    /// Returns whether the module is documented.
+    pub(crate) fn has_docs(&self) -> bool {
+        self.docs.is_some()
+    }
+
+    /// Maps the visibility of the module and its items with the given
+    /// function.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `f` - The function mapping the visibility.
+    pub(crate) fn map_visibility(&mut self, f: &dyn Fn(Visibility) -> Visibility) {
+        map_vis(&mut self.vis, f);
+        self.scope.map_visibility(f);
+    }
+
     /// Set the module visibility.
     /// 
     /// # Arguments
@@ -80,6 +138,75 @@ impl Module {
         self
     }
 
+    /// Set the module documentation.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `docs` - The documentation to set for the module.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Module;
+    /// 
+    /// let mut foo_module = Module::new("foo");
+    /// foo_module.doc("The foo module.");
+    /// ```
+    pub fn doc<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        match self.docs {
+            Some(ref mut existing) => existing.set_text(docs, false),
+            None => self.docs = Some(Docs::new(docs)),
+        }
+        self
+    }
+
+    /// Specify lint attribute to supress a warning or error.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `allow` - The lint attribute to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Module;
+    /// 
+    /// let mut foo_module = Module::new("Foo");
+    /// foo_module.allow("dead_code");
+    /// ```
+    pub fn allow<T>(&mut self, allow: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.allow(allow);
+        self
+    }
+
+    /// Add an attribute to the module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attribute` - The attribute to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Module;
+    /// 
+    /// let mut foo_module = Module::new("Foo");
+    /// foo_module.attr("cfg(test)");
+    /// ```
+    pub fn attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.meta(attribute);
+        self
+    }
+
     /// Import a type into the module's scope.
     ///
     /// This results in a new `use` statement bein added to the beginning of 
@@ -128,7 +255,10 @@ impl Module {
     /// let mut foo_module = Module::new("Foo");
     /// foo_module.new_module("Bar");
     /// ```
-    pub fn new_module(&mut self, name: &str) -> &mut Module {
+    pub fn new_module<T>(&mut self, name: T) -> &mut Module
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.scope.new_module(name)
     }
 
@@ -150,7 +280,7 @@ impl Module {
     /// ```
     pub fn get_module<Q: ?Sized>(&self, name: &Q) -> Option<&Module>
     where
-        String: PartialEq<Q>,
+        Cow<'static, str>: PartialEq<Q>,
     {
         self.scope.get_module(name)
     }
@@ -173,7 +303,7 @@ impl Module {
     /// ```
     pub fn get_module_mut<Q: ?Sized>(&mut self, name: &Q) -> Option<&mut Module>
     where
-        String: PartialEq<Q>,
+        Cow<'static, str>: PartialEq<Q>,
     {
         self.scope.get_module_mut(name)
     }
@@ -193,7 +323,10 @@ impl Module {
     /// let mut foo_module = Module::new("Foo");
     /// foo_module.get_or_new_module("Bar");
     /// ```
-    pub fn get_or_new_module(&mut self, name: &str) -> &mut Module {
+    pub fn get_or_new_module<T>(&mut self, name: T) -> &mut Module
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.scope.get_or_new_module(name)
     }
 
@@ -243,7 +376,10 @@ impl Module {
     /// let mut foo_module = Module::new("Foo");
     /// foo_module.new_struct("Bar");
     /// ```
-    pub fn new_struct(&mut self, name: &str) -> &mut Struct {
+    pub fn new_struct<T>(&mut self, name: T) -> &mut Struct
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.scope.new_struct(name)
     }
 
@@ -282,7 +418,10 @@ impl Module {
     /// let mut foo_module = Module::new("Foo");
     /// foo_module.new_fn("bar_fn");
     /// ```
-    pub fn new_fn(&mut self, name: &str) -> &mut Function {
+    pub fn new_fn<T>(&mut self, name: T) -> &mut Function
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.scope.new_fn(name)
     }
 
@@ -321,7 +460,10 @@ impl Module {
     /// let mut foo_module = Module::new("Foo");
     /// foo_module.new_enum("Bar");
     /// ```
-    pub fn new_enum(&mut self, name: &str) -> &mut Enum {
+    pub fn new_enum<T>(&mut self, name: T) -> &mut Enum
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.scope.new_enum(name)
     }
 
@@ -385,6 +527,257 @@ impl Module {
         self
     }
 
+    /// Returns a reference to a struct if it exists in this module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the struct to get.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Module;
+    /// 
+    /// let mut foo_module = Module::new("Foo");
+    /// foo_module.new_struct("Bar");
+    /// 
+    /// foo_module.get_struct("Bar");
+    /// ```
+    pub fn get_struct<Q: ?Sized>(&self, name: &Q) -> Option<&Struct>
+    where
+        str: PartialEq<Q>,
+    {
+        self.scope.get_struct(name)
+    }
+
+    /// Returns a mutable reference to a struct if it exists in this module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the struct to get.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Module;
+    /// 
+    /// let mut foo_module = Module::new("Foo");
+    /// foo_module.new_struct("Bar");
+    /// 
+    /// foo_module.get_struct_mut("Bar");
+    /// ```
+    pub fn get_struct_mut<Q: ?Sized>(&mut self, name: &Q) -> Option<&mut Struct>
+    where
+        str: PartialEq<Q>,
+    {
+        self.scope.get_struct_mut(name)
+    }
+
+    /// Returns a reference to a function if it exists in this module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the function to get.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Module;
+    /// 
+    /// let mut foo_module = Module::new("Foo");
+    /// foo_module.new_fn("bar_fn");
+    /// 
+    /// foo_module.get_fn("bar_fn");
+    /// ```
+    pub fn get_fn<Q: ?Sized>(&self, name: &Q) -> Option<&Function>
+    where
+        str: PartialEq<Q>,
+    {
+        self.scope.get_fn(name)
+    }
+
+    /// Returns a mutable reference to a function if it exists in this module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the function to get.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Module;
+    /// 
+    /// let mut foo_module = Module::new("Foo");
+    /// foo_module.new_fn("bar_fn");
+    /// 
+    /// foo_module.get_fn_mut("bar_fn");
+    /// ```
+    pub fn get_fn_mut<Q: ?Sized>(&mut self, name: &Q) -> Option<&mut Function>
+    where
+        str: PartialEq<Q>,
+    {
+        self.scope.get_fn_mut(name)
+    }
+
+    /// Returns a reference to a trait if it exists in this module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the trait to get.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Module;
+    /// 
+    /// let mut foo_module = Module::new("Foo");
+    /// foo_module.new_trait("Bar");
+    /// 
+    /// foo_module.get_trait("Bar");
+    /// ```
+    pub fn get_trait<Q: ?Sized>(&self, name: &Q) -> Option<&Trait>
+    where
+        str: PartialEq<Q>,
+    {
+        self.scope.get_trait(name)
+    }
+
+    /// Returns a mutable reference to a trait if it exists in this module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the trait to get.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Module;
+    /// 
+    /// let mut foo_module = Module::new("Foo");
+    /// foo_module.new_trait("Bar");
+    /// 
+    /// foo_module.get_trait_mut("Bar");
+    /// ```
+    pub fn get_trait_mut<Q: ?Sized>(&mut self, name: &Q) -> Option<&mut Trait>
+    where
+        str: PartialEq<Q>,
+    {
+        self.scope.get_trait_mut(name)
+    }
+
+    /// Returns a reference to an enum if it exists in this module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the enum to get.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Module;
+    /// 
+    /// let mut foo_module = Module::new("Foo");
+    /// foo_module.new_enum("Bar");
+    /// 
+    /// foo_module.get_enum("Bar");
+    /// ```
+    pub fn get_enum<Q: ?Sized>(&self, name: &Q) -> Option<&Enum>
+    where
+        str: PartialEq<Q>,
+    {
+        self.scope.get_enum(name)
+    }
+
+    /// Returns a mutable reference to an enum if it exists in this module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the enum to get.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Module;
+    /// 
+    /// let mut foo_module = Module::new("Foo");
+    /// foo_module.new_enum("Bar");
+    /// 
+    /// foo_module.get_enum_mut("Bar");
+    /// ```
+    pub fn get_enum_mut<Q: ?Sized>(&mut self, name: &Q) -> Option<&mut Enum>
+    where
+        str: PartialEq<Q>,
+    {
+        self.scope.get_enum_mut(name)
+    }
+
+    /// Returns a reference to an `impl` block if it exists in this module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `target` - The name of the target of the `impl` block to get.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Module;
+    /// 
+    /// let mut foo_module = Module::new("Foo");
+    /// foo_module.new_impl("Bar");
+    /// 
+    /// foo_module.get_impl("Bar");
+    /// ```
+    pub fn get_impl<Q: ?Sized>(&self, target: &Q) -> Option<&Impl>
+    where
+        str: PartialEq<Q>,
+    {
+        self.scope.get_impl(target)
+    }
+
+    /// Returns a mutable reference to an `impl` block if it exists in this module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `target` - The name of the target of the `impl` block to get.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Module;
+    /// 
+    /// let mut foo_module = Module::new("Foo");
+    /// foo_module.new_impl("Bar");
+    /// 
+    /// foo_module.get_impl_mut("Bar");
+    /// ```
+    pub fn get_impl_mut<Q: ?Sized>(&mut self, target: &Q) -> Option<&mut Impl>
+    where
+        str: PartialEq<Q>,
+    {
+        self.scope.get_impl_mut(target)
+    }
+
+    /// Push a new trait definition, returning a mutable reference to it.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the trait.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Module;
+    /// 
+    /// let mut foo_module = Module::new("Foo");
+    /// foo_module.new_trait("Bar");
+    /// ```
+    pub fn new_trait<T>(&mut self, name: T) -> &mut Trait
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.scope.new_trait(name)
+    }
+
     /// Push a trait definition.
     /// 
     /// # Arguments
@@ -406,6 +799,68 @@ impl Module {
         self
     }
 
+    /// Push a custom item to the module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `item` - The custom item to push.
+    pub fn push_custom<T>(&mut self, item: T) -> &mut Self
+    where
+        T: FormatItem + 'static,
+    {
+        self.scope.push_custom(item);
+        self
+    }
+
+    /// Push raw source code to the module, after the items added so far.
+    /// 
+    /// The text is included verbatim, with every line indented to the
+    /// level of the module.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `text` - The source code to push.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Module;
+    /// 
+    /// let mut foo_module = Module::new("Foo");
+    /// foo_module.raw("const BAR: u8 = 1;");
+    /// ```
+    pub fn raw<T>(&mut self, text: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.scope.raw(text);
+        self
+    }
+
+    /// Returns the module as a string of code formatted with a fresh
+    /// formatter, e.g. to render it without a scope.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Module;
+    /// 
+    /// let mut foo_module = Module::new("foo");
+    /// foo_module.new_struct("Bar");
+    /// 
+    /// assert_eq!(foo_module.to_code_string(), "mod foo {\n    struct Bar;\n}");
+    /// ```
+    pub fn to_code_string(&self) -> String {
+        fmt_to_code_string(|fmt| self.fmt(fmt))
+    }
+
+    /// Returns an estimate of the rendered length of the module.
+    pub(crate) fn estimated_len(&self) -> usize {
+        let docs = self.docs.as_ref().map_or(0, |docs| docs.estimated_len());
+
+        docs + self.attributes.estimated_len() + self.name.len() + self.scope.estimated_len() + 16
+    }
+
     /// Formats the module using the given formatter.
     /// 
     /// # Arguments
@@ -424,11 +879,27 @@ impl Module {
     /// foo_module.fmt(&mut fmt);
     /// ```
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_with_policy(None, fmt)
+    }
+
+    /// Formats the module, applying the given policy of the parent scope
+    /// unless the module has its own.
+    pub(crate) fn fmt_with_policy(
+        &self,
+        policy: Option<&Policy>,
+        fmt: &mut Formatter<'_>,
+    ) -> fmt::Result {
+        if let Some(ref docs) = self.docs {
+            docs.fmt(fmt)?;
+        }
+
+        self.attributes.fmt(fmt)?;
+
         if let Some(ref vis) = self.vis {
             write!(fmt, "{} ", vis)?;
         }
 
         write!(fmt, "mod {}", self.name)?;
-        fmt.block(|fmt| self.scope.fmt(fmt))
+        fmt.block(|fmt| self.scope.fmt_with_policy(policy, fmt))
     }
 }
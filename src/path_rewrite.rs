@@ -0,0 +1,99 @@
+use std::borrow::Cow;
+
+/// Defines a mapping of path prefixes used to rewrite the type paths and
+/// imports of a scope with [`Scope::rewrite_paths`], e.g. to vendor generated
+/// code into another crate.
+///
+/// [`Scope::rewrite_paths`]: crate::Scope::rewrite_paths
+#[derive(Debug, Clone, Default)]
+pub struct PathRewrite {
+    /// The `(from, to)` prefixes, in the order they were added.
+    rules: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+}
+
+impl PathRewrite {
+    /// Returns a new, empty path rewrite.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::PathRewrite;
+    ///
+    /// let rewrite = PathRewrite::new();
+    /// ```
+    pub fn new() -> Self {
+        PathRewrite { rules: vec![] }
+    }
+
+    /// Rewrite paths starting with the given prefix to start with another
+    /// prefix instead. Prefixes only match whole path segments, so
+    /// `my_models` doesn't match `my_models_v2::User`. When several prefixes
+    /// match, the one added first is used.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The prefix to replace, e.g. `my_models::`.
+    /// * `to` - The prefix to replace it with, e.g. `crate::models::`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::PathRewrite;
+    ///
+    /// let mut rewrite = PathRewrite::new();
+    /// rewrite.rule("my_models::", "crate::models::");
+    /// ```
+    pub fn rule<F, T>(&mut self, from: F, to: T) -> &mut Self
+    where
+        F: Into<Cow<'static, str>>,
+        T: Into<Cow<'static, str>>,
+    {
+        let from = from.into();
+        let to = to.into();
+
+        assert!(!from.trim_end_matches("::").is_empty(), "path prefixes can't be empty");
+
+        self.rules.push((
+            from.trim_end_matches("::").to_string().into(),
+            to.trim_end_matches("::").to_string().into(),
+        ));
+        self
+    }
+
+    /// Returns the given type or path with every path that starts with one
+    /// of the prefixes rewritten, e.g. in `&Vec<my_models::User>`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The type or path to rewrite.
+    pub(crate) fn apply(&self, path: &str) -> String {
+        let mut ret = String::with_capacity(path.len());
+        let mut rest = path;
+
+        'outer: while let Some(c) = rest.chars().next() {
+            // Only rewrite at the start of a path, not in the middle of a
+            // name or after another path segment.
+            let at_start = match ret.chars().last() {
+                Some(prev) => !(prev.is_alphanumeric() || prev == '_' || prev == ':'),
+                None => true,
+            };
+
+            if at_start {
+                for (from, to) in &self.rules {
+                    if let Some(after) = rest.strip_prefix(&**from) {
+                        if after.is_empty() || after.starts_with("::") {
+                            ret.push_str(to);
+                            rest = after;
+                            continue 'outer;
+                        }
+                    }
+                }
+            }
+
+            ret.push(c);
+            rest = &rest[c.len_utf8()..];
+        }
+
+        ret
+    }
+}
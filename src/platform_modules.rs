@@ -0,0 +1,264 @@
+use crate::literal::quote_str;
+use crate::module::Module;
+use crate::scope::Scope;
+
+/// Defines the module of a platform.
+#[derive(Debug, Clone)]
+struct Platform {
+    /// The name of the module.
+    name: String,
+    /// The `cfg` predicate of the platform, e.g. `unix`.
+    cfg: String,
+    /// The contents of the module.
+    scope: Scope,
+}
+
+/// Generates the platform-split pattern: a module per platform gated by its
+/// `cfg` predicate, followed by a glob `pub use` of each module under the
+/// same predicate, e.g.:
+///
+/// ```text
+/// #[cfg(unix)]
+/// mod unix { .. }
+///
+/// #[cfg(windows)]
+/// mod windows { .. }
+///
+/// #[cfg(unix)]
+/// pub use self::unix::*;
+/// #[cfg(windows)]
+/// pub use self::windows::*;
+/// ```
+///
+/// The platform modules should define the same items so that the crate
+/// sees the same names on every platform.
+#[derive(Debug, Clone)]
+pub struct PlatformModules {
+    /// The platforms, in order.
+    platforms: Vec<Platform>,
+    /// The visibility of the re-exports.
+    vis: String,
+    /// The message of the `compile_error!` emitted on other platforms, if
+    /// any.
+    unsupported: Option<String>,
+}
+
+impl PlatformModules {
+    /// Returns a new generator without platforms, re-exporting with `pub`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::PlatformModules;
+    ///
+    /// let platforms = PlatformModules::new();
+    /// ```
+    pub fn new() -> Self {
+        PlatformModules {
+            platforms: vec![],
+            vis: "pub".to_string(),
+            unsupported: None,
+        }
+    }
+
+    /// Add the module of a platform.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the module.
+    /// * `cfg` - The `cfg` predicate of the platform, e.g. `unix` or
+    ///   `all(unix, not(target_os = "macos"))`.
+    /// * `scope` - The contents of the module.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{PlatformModules,Scope};
+    ///
+    /// let mut platforms = PlatformModules::new();
+    /// platforms
+    ///     .platform("unix", "unix", Scope::new())
+    ///     .platform("windows", "windows", Scope::new());
+    /// ```
+    pub fn platform(&mut self, name: &str, cfg: &str, scope: Scope) -> &mut Self {
+        assert!(
+            !self.platforms.iter().any(|platform| platform.name == name),
+            "the `{}` platform module was already added",
+            name
+        );
+
+        self.platforms.push(Platform {
+            name: name.to_string(),
+            cfg: cfg.to_string(),
+            scope,
+        });
+        self
+    }
+
+    /// Add the module of an operating system, named after it and gated by
+    /// `target_os`.
+    ///
+    /// # Arguments
+    ///
+    /// * `os` - The operating system, e.g. `linux`.
+    /// * `scope` - The contents of the module.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{PlatformModules,Scope};
+    ///
+    /// let mut platforms = PlatformModules::new();
+    /// platforms.target_os("linux", Scope::new());
+    /// ```
+    pub fn target_os(&mut self, os: &str, scope: Scope) -> &mut Self {
+        self.platform(os, &format!("target_os = {}", quote_str(os)), scope)
+    }
+
+    /// Add the module of an architecture, named after it and gated by
+    /// `target_arch`.
+    ///
+    /// # Arguments
+    ///
+    /// * `arch` - The architecture, e.g. `x86_64`.
+    /// * `scope` - The contents of the module.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{PlatformModules,Scope};
+    ///
+    /// let mut platforms = PlatformModules::new();
+    /// platforms.target_arch("x86_64", Scope::new());
+    /// ```
+    pub fn target_arch(&mut self, arch: &str, scope: Scope) -> &mut Self {
+        self.platform(arch, &format!("target_arch = {}", quote_str(arch)), scope)
+    }
+
+    /// Set the visibility of the re-exports, `pub` by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `vis` - The visibility to set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::PlatformModules;
+    ///
+    /// let mut platforms = PlatformModules::new();
+    /// platforms.vis("pub(crate)");
+    /// ```
+    pub fn vis(&mut self, vis: &str) -> &mut Self {
+        self.vis = vis.to_string();
+        self
+    }
+
+    /// Emit a `compile_error!` with the given message on the platforms that
+    /// have no module.
+    ///
+    /// # Arguments
+    ///
+    /// * `message` - The message of the error.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::PlatformModules;
+    ///
+    /// let mut platforms = PlatformModules::new();
+    /// platforms.unsupported("this platform isn't supported");
+    /// ```
+    pub fn unsupported(&mut self, message: &str) -> &mut Self {
+        self.unsupported = Some(message.to_string());
+        self
+    }
+
+    /// Returns the platform modules, each with its `cfg` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{PlatformModules,Scope};
+    ///
+    /// let mut platforms = PlatformModules::new();
+    /// platforms.platform("unix", "unix", Scope::new());
+    ///
+    /// let modules = platforms.to_modules();
+    /// ```
+    pub fn to_modules(&self) -> Vec<Module> {
+        self.platforms
+            .iter()
+            .map(|platform| {
+                let mut module = Module::new(platform.name.clone());
+                module.attr(format!("cfg({})", platform.cfg));
+                module.scope = platform.scope.clone();
+                module
+            })
+            .collect()
+    }
+
+    /// Returns the `cfg`-gated re-exports of the platform modules, followed
+    /// by the `compile_error!` of the other platforms, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{PlatformModules,Scope};
+    ///
+    /// let mut platforms = PlatformModules::new();
+    /// platforms.platform("unix", "unix", Scope::new());
+    ///
+    /// assert_eq!(platforms.to_reexports(), "#[cfg(unix)]\npub use self::unix::*;");
+    /// ```
+    pub fn to_reexports(&self) -> String {
+        let mut lines = vec![];
+
+        for platform in &self.platforms {
+            lines.push(format!("#[cfg({})]", platform.cfg));
+            lines.push(format!("{} use self::{}::*;", self.vis, platform.name));
+        }
+
+        if let Some(ref message) = self.unsupported {
+            let cfgs: Vec<&str> = self.platforms.iter().map(|p| &p.cfg[..]).collect();
+
+            lines.push(format!("#[cfg(not(any({})))]", cfgs.join(", ")));
+            lines.push(format!("compile_error!({});", quote_str(message)));
+        }
+
+        lines.join("\n")
+    }
+
+    /// Push the platform modules and their re-exports to the given scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - The scope to push the modules to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{PlatformModules,Scope};
+    ///
+    /// let mut scope = Scope::new();
+    /// let mut platforms = PlatformModules::new();
+    /// platforms.platform("unix", "unix", Scope::new());
+    ///
+    /// platforms.push_to(&mut scope);
+    /// ```
+    pub fn push_to(&self, scope: &mut Scope) {
+        for module in self.to_modules() {
+            scope.push_module(module);
+        }
+
+        if !self.platforms.is_empty() || self.unsupported.is_some() {
+            scope.raw(self.to_reexports());
+        }
+    }
+}
+
+impl Default for PlatformModules {
+    fn default() -> Self {
+        PlatformModules::new()
+    }
+}
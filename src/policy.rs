@@ -0,0 +1,243 @@
+use std::borrow::Cow;
+
+use crate::attribute::AttributeKind;
+
+/// Defines the attribute that excludes generated functions from coverage
+/// reports, see [`Policy::exclude_from_coverage`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CoverageExclusion {
+    /// `#[cfg_attr(coverage_nightly, coverage(off))]`, e.g. for
+    /// `cargo llvm-cov`, which sets `coverage_nightly` on nightly toolchains.
+    CoverageOff,
+    /// `#[no_coverage]`, for toolchains predating `coverage(off)`.
+    NoCoverage,
+    /// Any other attribute, without the surrounding `#[...]`.
+    Custom(Cow<'static, str>),
+}
+
+impl CoverageExclusion {
+    /// Returns the attribute without the surrounding `#[...]`.
+    pub(crate) fn meta(&self) -> Cow<'static, str> {
+        match *self {
+            CoverageExclusion::CoverageOff => "cfg_attr(coverage_nightly, coverage(off))".into(),
+            CoverageExclusion::NoCoverage => "no_coverage".into(),
+            CoverageExclusion::Custom(ref meta) => meta.clone(),
+        }
+    }
+}
+
+/// Defines the conventions that a scope applies to its items when it is
+/// rendered, so that they don't have to be applied item by item.
+///
+/// The policy of a scope also applies to its modules unless they set their
+/// own.
+#[derive(Debug, Clone, Default)]
+pub struct Policy {
+    /// Whether to add `#[inline]` to generated accessors.
+    inline_accessors: bool,
+    /// Whether generated getters inherit the documentation of their fields.
+    inherit_field_docs: bool,
+    /// The types that every struct and enum derives.
+    derives: Vec<Cow<'static, str>>,
+    /// The types that every struct derives.
+    struct_derives: Vec<Cow<'static, str>>,
+    /// The order of the attributes of every item by kind.
+    attribute_order: Vec<AttributeKind>,
+    /// The attribute that excludes every function from coverage reports.
+    coverage_exclusion: Option<CoverageExclusion>,
+}
+
+impl Policy {
+    /// Returns a new policy which doesn't change any items.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Policy;
+    ///
+    /// let policy = Policy::new();
+    /// ```
+    pub fn new() -> Self {
+        Policy {
+            inline_accessors: false,
+            inherit_field_docs: false,
+            derives: vec![],
+            struct_derives: vec![],
+            attribute_order: vec![],
+            coverage_exclusion: None,
+        }
+    }
+
+    /// Set whether to add `#[inline]` to generated accessors, e.g. the ones
+    /// from `Struct::getters` and `Enum::accessors`, which don't have it
+    /// already.
+    ///
+    /// # Arguments
+    ///
+    /// * `inline` - Indicates whether to inline accessors or not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Policy;
+    ///
+    /// let mut policy = Policy::new();
+    /// policy.inline_accessors(true);
+    /// ```
+    pub fn inline_accessors(&mut self, inline: bool) -> &mut Self {
+        self.inline_accessors = inline;
+        self
+    }
+
+    /// Set whether getters generated with `Struct::getters` which have no
+    /// documentation of their own use the documentation of their field.
+    ///
+    /// # Arguments
+    ///
+    /// * `inherit` - Indicates whether to inherit field docs or not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Policy;
+    ///
+    /// let mut policy = Policy::new();
+    /// policy.inherit_field_docs(true);
+    /// ```
+    pub fn inherit_field_docs(&mut self, inherit: bool) -> &mut Self {
+        self.inherit_field_docs = inherit;
+        self
+    }
+
+    /// Add a type that every struct and enum derives, unless it opts out
+    /// with `no_default_derives`. The defaults are rendered before the
+    /// item's own derives, and types that the item already derives aren't
+    /// repeated.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the type to derive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Policy;
+    ///
+    /// let mut policy = Policy::new();
+    /// policy.derive("Debug");
+    /// ```
+    pub fn derive<T>(&mut self, name: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.derives.push(name.into());
+        self
+    }
+
+    /// Add a type that every struct derives, but not enums, see `derive`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the type to derive.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Policy;
+    ///
+    /// let mut policy = Policy::new();
+    /// policy.struct_derive("Debug").struct_derive("Clone");
+    /// ```
+    pub fn struct_derive<T>(&mut self, name: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.struct_derives.push(name.into());
+        self
+    }
+
+    /// Set the order in which the attributes of every struct, enum, impl
+    /// block, and function are rendered by kind, instead of the order in
+    /// which they were added. Attributes of the same kind keep the order in
+    /// which they were added, and the kinds that aren't in the order follow
+    /// the others. Docs are always rendered before the attributes.
+    ///
+    /// # Arguments
+    ///
+    /// * `order` - The kinds of attributes in the order to render them.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{AttributeKind, Policy};
+    ///
+    /// let mut policy = Policy::new();
+    /// policy.attribute_order(&[
+    ///     AttributeKind::Cfg,
+    ///     AttributeKind::Derive,
+    ///     AttributeKind::Meta,
+    ///     AttributeKind::Repr,
+    /// ]);
+    /// ```
+    pub fn attribute_order(&mut self, order: &[AttributeKind]) -> &mut Self {
+        self.attribute_order = order.to_vec();
+        self
+    }
+
+    /// Set the attribute that excludes every function, including the ones
+    /// of impl blocks, from coverage reports, e.g. so that generated code
+    /// doesn't count towards the coverage of a crate.
+    ///
+    /// # Arguments
+    ///
+    /// * `exclusion` - The attribute to add to every function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{CoverageExclusion, Policy};
+    ///
+    /// let mut policy = Policy::new();
+    /// policy.exclude_from_coverage(CoverageExclusion::CoverageOff);
+    /// ```
+    pub fn exclude_from_coverage(&mut self, exclusion: CoverageExclusion) -> &mut Self {
+        self.coverage_exclusion = Some(exclusion);
+        self
+    }
+
+    /// Returns whether to add `#[inline]` to generated accessors.
+    pub(crate) fn get_inline_accessors(&self) -> bool {
+        self.inline_accessors
+    }
+
+    /// Returns whether generated getters inherit the docs of their field.
+    pub(crate) fn get_inherit_field_docs(&self) -> bool {
+        self.inherit_field_docs
+    }
+
+    /// Returns the types that every struct derives, including the ones that
+    /// every enum derives as well.
+    pub(crate) fn get_struct_derives(&self) -> Vec<Cow<'static, str>> {
+        self.derives
+            .iter()
+            .chain(&self.struct_derives)
+            .cloned()
+            .collect()
+    }
+
+    /// Returns the order of the attributes of every item by kind.
+    pub(crate) fn get_attribute_order(&self) -> &[AttributeKind] {
+        &self.attribute_order
+    }
+
+    /// Returns the attribute that excludes every function from coverage
+    /// reports, if any.
+    pub(crate) fn get_coverage_exclusion(&self) -> Option<&CoverageExclusion> {
+        self.coverage_exclusion.as_ref()
+    }
+
+    /// Returns the types that every enum derives.
+    pub(crate) fn get_enum_derives(&self) -> &[Cow<'static, str>] {
+        &self.derives
+    }
+}
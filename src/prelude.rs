@@ -0,0 +1,134 @@
+use std::borrow::Cow;
+
+/// Defines a reusable set of imports, inner attributes, and allowed lints
+/// that can be applied to any scope with [`Scope::use_prelude`].
+///
+/// [`Scope::use_prelude`]: crate::Scope::use_prelude
+#[derive(Debug, Clone, Default)]
+pub struct Prelude {
+    /// The imports as `(path, type)` pairs.
+    pub(crate) imports: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+    /// The inner attributes, e.g. `#![no_std]`.
+    pub(crate) inner_attributes: Vec<Cow<'static, str>>,
+    /// The lints to allow for the whole scope.
+    pub(crate) allow: Vec<Cow<'static, str>>,
+}
+
+impl Prelude {
+    /// Returns a new, empty prelude.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Prelude;
+    ///
+    /// let prelude = Prelude::new();
+    /// ```
+    pub fn new() -> Self {
+        Prelude {
+            imports: vec![],
+            inner_attributes: vec![],
+            allow: vec![],
+        }
+    }
+
+    /// Returns a prelude that imports serde's `Serialize` and `Deserialize`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Prelude;
+    ///
+    /// let prelude = Prelude::serde();
+    /// ```
+    pub fn serde() -> Self {
+        let mut prelude = Prelude::new();
+        prelude
+            .import("serde", "Deserialize")
+            .import("serde", "Serialize");
+        prelude
+    }
+
+    /// Returns a prelude that imports `wasm_bindgen::prelude::*`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Prelude;
+    ///
+    /// let prelude = Prelude::wasm_bindgen();
+    /// ```
+    pub fn wasm_bindgen() -> Self {
+        let mut prelude = Prelude::new();
+        prelude.import("wasm_bindgen::prelude", "*");
+        prelude
+    }
+
+    /// Add an import to the prelude.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the type to import.
+    /// * `ty` - The type to import.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Prelude;
+    ///
+    /// let mut prelude = Prelude::new();
+    /// prelude.import("std::collections", "HashMap");
+    /// ```
+    pub fn import<P, T>(&mut self, path: P, ty: T) -> &mut Self
+    where
+        P: Into<Cow<'static, str>>,
+        T: Into<Cow<'static, str>>,
+    {
+        self.imports.push((path.into(), ty.into()));
+        self
+    }
+
+    /// Add an inner attribute to the prelude (e.g. `"no_std"` for `#![no_std]`).
+    ///
+    /// # Arguments
+    ///
+    /// * `attribute` - The attribute to add.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Prelude;
+    ///
+    /// let mut prelude = Prelude::new();
+    /// prelude.inner_attr("no_std");
+    /// ```
+    pub fn inner_attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.inner_attributes.push(attribute.into());
+        self
+    }
+
+    /// Add a lint to allow for the whole scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `allow` - The lint to allow.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Prelude;
+    ///
+    /// let mut prelude = Prelude::new();
+    /// prelude.allow("dead_code");
+    /// ```
+    pub fn allow<T>(&mut self, allow: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.allow.push(allow.into());
+        self
+    }
+}
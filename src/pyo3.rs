@@ -0,0 +1,195 @@
+//! Scaffolding for PyO3 extension modules.
+//!
+//! ```
+//! use rust_codegen::pyo3::PyModule;
+//! use rust_codegen::{Function, Impl, Scope, Struct};
+//!
+//! let mut counter = Struct::new("Counter");
+//! counter.field("count", "u32");
+//!
+//! let mut add = Function::new("add");
+//! add.arg("a", "i64").arg("b", "i64").ret("i64").line("a + b");
+//!
+//! let mut py_module = PyModule::new("my_module");
+//! py_module.push_class(counter).push_function(add);
+//!
+//! let mut scope = Scope::new();
+//! py_module.push_to(&mut scope);
+//! ```
+
+use std::borrow::Cow;
+
+use crate::function::Function;
+use crate::scope::Scope;
+
+use crate::r#impl::Impl;
+use crate::r#struct::Struct;
+
+/// Defines a PyO3 extension module along with the classes and functions that
+/// it exports.
+#[derive(Debug, Clone)]
+pub struct PyModule {
+    /// The name of the Python module.
+    name: Cow<'static, str>,
+    /// The exported classes.
+    classes: Vec<Struct>,
+    /// The `#[pymethods]` blocks of the classes.
+    methods: Vec<Impl>,
+    /// The exported functions.
+    fns: Vec<Function>,
+}
+
+impl PyModule {
+    /// Return a new PyO3 module with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the Python module.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::pyo3::PyModule;
+    ///
+    /// let py_module = PyModule::new("my_module");
+    /// ```
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        PyModule {
+            name: name.into(),
+            classes: vec![],
+            methods: vec![],
+            fns: vec![],
+        }
+    }
+
+    /// Export the struct as a class, adding `#[pyclass]` to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - The struct to export.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::pyo3::PyModule;
+    /// use rust_codegen::Struct;
+    ///
+    /// let mut py_module = PyModule::new("my_module");
+    /// py_module.push_class(Struct::new("Counter"));
+    /// ```
+    pub fn push_class(&mut self, mut item: Struct) -> &mut Self {
+        item.attr("pyclass");
+        self.classes.push(item);
+        self
+    }
+
+    /// Export the methods of a class, adding `#[pymethods]` to the impl
+    /// block.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - The impl block of the class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::pyo3::PyModule;
+    /// use rust_codegen::Impl;
+    ///
+    /// let mut py_module = PyModule::new("my_module");
+    /// py_module.push_methods(Impl::new("Counter"));
+    /// ```
+    pub fn push_methods(&mut self, mut item: Impl) -> &mut Self {
+        item.attr("pymethods");
+        self.methods.push(item);
+        self
+    }
+
+    /// Export the function, adding `#[pyfunction]` to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - The function to export.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::pyo3::PyModule;
+    /// use rust_codegen::Function;
+    ///
+    /// let mut py_module = PyModule::new("my_module");
+    /// py_module.push_function(Function::new("add"));
+    /// ```
+    pub fn push_function(&mut self, mut item: Function) -> &mut Self {
+        item.attr("pyfunction");
+        self.fns.push(item);
+        self
+    }
+
+    /// Returns the `#[pymodule]` function which registers every class and
+    /// function with the Python module.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::pyo3::PyModule;
+    ///
+    /// let py_module = PyModule::new("my_module");
+    /// let init_fn = py_module.module_fn();
+    /// ```
+    pub fn module_fn(&self) -> Function {
+        let mut func = Function::new(self.name.clone());
+        func.attr("pymodule")
+            .arg("_py", "Python<'_>")
+            .arg("m", "&PyModule")
+            .ret("PyResult<()>");
+
+        for class in &self.classes {
+            func.line(format!("m.add_class::<{}>()?;", class.ty().name()));
+        }
+
+        for f in &self.fns {
+            func.line(format!("m.add_function(wrap_pyfunction!({}, m)?)?;", f.name()));
+        }
+
+        func.line("Ok(())");
+        func
+    }
+
+    /// Push the `use pyo3::prelude::*;` import, the classes, their methods,
+    /// the functions, and the `#[pymodule]` function to the given scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - The scope to push the module to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::pyo3::PyModule;
+    /// use rust_codegen::Scope;
+    ///
+    /// let mut scope = Scope::new();
+    /// PyModule::new("my_module").push_to(&mut scope);
+    /// ```
+    pub fn push_to(&self, scope: &mut Scope) {
+        scope.import("pyo3::prelude", "*");
+
+        for class in &self.classes {
+            scope.push_struct(class.clone());
+        }
+
+        for methods in &self.methods {
+            scope.push_impl(methods.clone());
+        }
+
+        for f in &self.fns {
+            scope.push_fn(f.clone());
+        }
+
+        scope.push_fn(self.module_fn());
+    }
+}
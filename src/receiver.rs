@@ -0,0 +1,73 @@
+use std::fmt;
+
+use crate::r#type::Type;
+
+/// Defines how a method takes `self`, e.g. to know whether a generated mock
+/// or delegating method needs mutable access.
+#[derive(Debug, Clone)]
+pub enum Receiver {
+    /// `self`.
+    Owned,
+    /// `&self`.
+    Ref,
+    /// `&mut self`.
+    RefMut,
+    /// `&'a self`, with the lifetime, e.g. `'a`.
+    RefLifetime(String),
+    /// `&'a mut self`, with the lifetime, e.g. `'a`.
+    RefMutLifetime(String),
+    /// `self: T`, with the wrapper type, e.g. `Box<Self>` or
+    /// `Pin<&mut Self>`.
+    Typed(Type),
+}
+
+impl Receiver {
+    /// Returns whether the receiver borrows `self` rather than taking
+    /// ownership of it. Typed receivers are considered owned.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Receiver;
+    ///
+    /// assert!(Receiver::RefLifetime("'a".to_string()).is_ref());
+    /// assert!(!Receiver::Owned.is_ref());
+    /// ```
+    pub fn is_ref(&self) -> bool {
+        matches!(
+            *self,
+            Receiver::Ref
+                | Receiver::RefMut
+                | Receiver::RefLifetime(..)
+                | Receiver::RefMutLifetime(..)
+        )
+    }
+
+    /// Returns whether the receiver borrows `self` mutably.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Receiver;
+    ///
+    /// assert!(Receiver::RefMut.is_mut());
+    /// assert!(!Receiver::Ref.is_mut());
+    /// ```
+    pub fn is_mut(&self) -> bool {
+        matches!(*self, Receiver::RefMut | Receiver::RefMutLifetime(..))
+    }
+}
+
+impl fmt::Display for Receiver {
+    /// Formats the receiver as a function argument, e.g. `&mut self`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Receiver::Owned => f.write_str("self"),
+            Receiver::Ref => f.write_str("&self"),
+            Receiver::RefMut => f.write_str("&mut self"),
+            Receiver::RefLifetime(ref lifetime) => write!(f, "&{} self", lifetime),
+            Receiver::RefMutLifetime(ref lifetime) => write!(f, "&{} mut self", lifetime),
+            Receiver::Typed(ref ty) => write!(f, "self: {}", ty.fmt_to_string()),
+        }
+    }
+}
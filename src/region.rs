@@ -0,0 +1,168 @@
+use std::error::Error;
+use std::fmt;
+use std::io;
+use std::path::Path;
+
+use crate::scope::Scope;
+
+/// The marker at the end of every anchored region.
+const END_MARKER: &str = "// codegen:end";
+
+/// Defines why a scope couldn't be rendered into an anchored region, see
+/// [`Scope::render_region`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RegionError {
+    /// The source has no `// codegen:begin(name)` marker, with the name.
+    MissingBegin(String),
+    /// The region isn't closed by a `// codegen:end` marker, with the name.
+    MissingEnd(String),
+    /// The source has several `// codegen:begin(name)` markers, with the
+    /// name.
+    Duplicate(String),
+}
+
+impl fmt::Display for RegionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            RegionError::MissingBegin(ref name) => {
+                write!(f, "missing `// codegen:begin({})` marker", name)
+            }
+            RegionError::MissingEnd(ref name) => {
+                write!(f, "region `{}` is not closed by `{}`", name, END_MARKER)
+            }
+            RegionError::Duplicate(ref name) => {
+                write!(f, "region `{}` is defined more than once", name)
+            }
+        }
+    }
+}
+
+impl Error for RegionError {}
+
+impl Scope {
+    /// Returns the given source with the contents of the region with the
+    /// given name replaced by the rendered scope, keeping everything outside
+    /// of the region as is, e.g. for hand-maintained files with generated
+    /// sections.
+    ///
+    /// A region starts with a `// codegen:begin(name)` line and ends with
+    /// the next `// codegen:end` line. The rendered scope is indented like
+    /// the begin marker.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source containing the region.
+    /// * `name` - The name of the region.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Scope;
+    ///
+    /// let source = "\
+    /// pub struct Handwritten;
+    ///
+    /// // codegen:begin(ids)
+    /// // codegen:end
+    /// ";
+    ///
+    /// let mut scope = Scope::new();
+    /// scope.new_struct("UserId").tuple_field("u64");
+    ///
+    /// assert_eq!(
+    ///     scope.render_region(source, "ids").unwrap(),
+    ///     "pub struct Handwritten;\n\n// codegen:begin(ids)\nstruct UserId(u64);\n// codegen:end\n"
+    /// );
+    /// ```
+    pub fn render_region(&self, source: &str, name: &str) -> Result<String, RegionError> {
+        let begin_marker = format!("// codegen:begin({})", name);
+
+        let mut begin = None;
+        let mut end = None;
+        let mut offset = 0;
+
+        for line in source.split_inclusive('\n') {
+            let trimmed = line.trim();
+
+            if trimmed == begin_marker {
+                if begin.is_some() {
+                    return Err(RegionError::Duplicate(name.to_string()));
+                }
+
+                let indent = &line[..line.len() - line.trim_start().len()];
+                begin = Some((offset + line.len(), indent));
+            } else if trimmed == END_MARKER && begin.is_some() && end.is_none() {
+                end = Some(offset);
+            }
+
+            offset += line.len();
+        }
+
+        let (start, indent) = match begin {
+            Some(begin) => begin,
+            None => return Err(RegionError::MissingBegin(name.to_string())),
+        };
+
+        let end = match end {
+            Some(end) => end,
+            None => return Err(RegionError::MissingEnd(name.to_string())),
+        };
+
+        let mut ret = String::with_capacity(source.len());
+        ret.push_str(&source[..start]);
+
+        // A begin marker on the last line has no newline to end it.
+        if !ret.ends_with('\n') {
+            ret.push('\n');
+        }
+
+        for line in self.to_string().lines() {
+            if !line.is_empty() {
+                ret.push_str(indent);
+                ret.push_str(line);
+            }
+
+            ret.push('\n');
+        }
+
+        ret.push_str(&source[end..]);
+
+        Ok(ret)
+    }
+
+    /// Renders the scope into the region with the given name of the file at
+    /// the given path, see [`Scope::render_region`]. The file is only
+    /// written if its contents change.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file containing the region.
+    /// * `name` - The name of the region.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rust_codegen::Scope;
+    ///
+    /// let mut scope = Scope::new();
+    /// scope.new_struct("UserId").tuple_field("u64");
+    ///
+    /// scope.write_region("src/ids.rs", "ids").unwrap();
+    /// ```
+    pub fn write_region<P>(&self, path: P, name: &str) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let source = std::fs::read_to_string(&path)?;
+
+        let rendered = self
+            .render_region(&source, name)
+            .map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err))?;
+
+        if rendered != source {
+            std::fs::write(path, rendered)?;
+        }
+
+        Ok(())
+    }
+}
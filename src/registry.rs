@@ -0,0 +1,285 @@
+use std::borrow::Cow;
+
+use crate::block::Block;
+use crate::case::to_snake_case;
+use crate::literal::quote_str;
+use crate::scope::Scope;
+
+use crate::r#type::Type;
+
+/// Defines how a [`Registry`] is generated.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RegistryStyle {
+    /// An `all` function returning a boxed instance of every entry and a
+    /// `get` function returning the entry with a given name.
+    Factory,
+    /// A `static` per entry and a `REGISTRY` slice of the entries by name.
+    /// The constructors must be usable in a `static`, e.g. `const fn`s, and
+    /// the types must be `Sync`.
+    Static,
+}
+
+/// Defines an entry of a registry.
+#[derive(Debug, Clone)]
+struct RegistryEntry {
+    /// The name of the entry, e.g. `login`.
+    name: Cow<'static, str>,
+    /// The type of the entry.
+    ty: Type,
+    /// The expression building the entry, e.g. `LoginHandler::new()`.
+    constructor: Cow<'static, str>,
+}
+
+/// Generates a registry of the types implementing a trait, e.g. to wire
+/// generated handlers into a dispatch table, along with the imports that it
+/// requires.
+#[derive(Debug, Clone)]
+pub struct Registry {
+    /// The trait implemented by the entries.
+    r#trait: Type,
+    /// How the registry is generated.
+    style: RegistryStyle,
+    /// The visibility of the generated items.
+    vis: Option<String>,
+    /// The imports as `(path, type)` pairs.
+    imports: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+    /// The entries, in the order they were added.
+    entries: Vec<RegistryEntry>,
+}
+
+impl Registry {
+    /// Returns a new registry generator of the types implementing the given
+    /// trait, generated in the `Factory` style.
+    ///
+    /// # Arguments
+    ///
+    /// * `r#trait` - The trait implemented by the entries.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Registry;
+    ///
+    /// let registry = Registry::new("Handler");
+    /// ```
+    pub fn new<T>(r#trait: T) -> Self
+    where
+        T: Into<Type>,
+    {
+        Registry {
+            r#trait: r#trait.into(),
+            style: RegistryStyle::Factory,
+            vis: None,
+            imports: vec![],
+            entries: vec![],
+        }
+    }
+
+    /// Set how the registry is generated.
+    ///
+    /// # Arguments
+    ///
+    /// * `style` - The style of the registry.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Registry, RegistryStyle};
+    ///
+    /// let mut registry = Registry::new("Handler");
+    /// registry.style(RegistryStyle::Static);
+    /// ```
+    pub fn style(&mut self, style: RegistryStyle) -> &mut Self {
+        self.style = style;
+        self
+    }
+
+    /// Set the visibility of the generated items.
+    ///
+    /// # Arguments
+    ///
+    /// * `vis` - The visibility to set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Registry;
+    ///
+    /// let mut registry = Registry::new("Handler");
+    /// registry.vis("pub");
+    /// ```
+    pub fn vis(&mut self, vis: &str) -> &mut Self {
+        self.vis = Some(vis.to_string());
+        self
+    }
+
+    /// Add an import that the registry requires, e.g. the trait or the
+    /// types of the entries.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the type to import.
+    /// * `ty` - The type to import.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Registry;
+    ///
+    /// let mut registry = Registry::new("Handler");
+    /// registry.import("crate::handlers", "Handler");
+    /// ```
+    pub fn import<P, T>(&mut self, path: P, ty: T) -> &mut Self
+    where
+        P: Into<Cow<'static, str>>,
+        T: Into<Cow<'static, str>>,
+    {
+        self.imports.push((path.into(), ty.into()));
+        self
+    }
+
+    /// Add an entry to the registry.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the entry, e.g. `login`.
+    /// * `ty` - The type of the entry.
+    /// * `constructor` - The expression building the entry, e.g.
+    ///   `LoginHandler::new()`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Registry;
+    ///
+    /// let mut registry = Registry::new("Handler");
+    /// registry.entry("login", "LoginHandler", "LoginHandler::new()");
+    /// ```
+    pub fn entry<N, T, C>(&mut self, name: N, ty: T, constructor: C) -> &mut Self
+    where
+        N: Into<Cow<'static, str>>,
+        T: Into<Type>,
+        C: Into<Cow<'static, str>>,
+    {
+        self.entries.push(RegistryEntry {
+            name: name.into(),
+            ty: ty.into(),
+            constructor: constructor.into(),
+        });
+        self
+    }
+
+    /// Push the imports and the items of the registry to the given scope,
+    /// e.g. the scope of a `registry` module.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - The scope to push the registry to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Registry, Scope};
+    ///
+    /// let mut scope = Scope::new();
+    ///
+    /// let mut registry = Registry::new("Handler");
+    /// registry.entry("login", "LoginHandler", "LoginHandler::new()");
+    ///
+    /// registry.push_to(&mut scope);
+    /// ```
+    pub fn push_to(&self, scope: &mut Scope) {
+        for (path, ty) in &self.imports {
+            scope.import(path, ty);
+        }
+
+        match self.style {
+            RegistryStyle::Factory => self.push_factory(scope),
+            RegistryStyle::Static => self.push_static(scope),
+        }
+    }
+
+    /// Pushes the `all` and `get` functions to the given scope.
+    fn push_factory(&self, scope: &mut Scope) {
+        let r#trait = self.r#trait.fmt_to_string();
+        let boxed = format!("Box<dyn {}>", r#trait);
+
+        let all = scope
+            .new_fn("all")
+            .doc(format!("Returns every registered `{}`.", r#trait))
+            .ret(format!("Vec<{}>", boxed));
+
+        if let Some(ref vis) = self.vis {
+            all.vis(vis);
+        }
+
+        all.line("vec![");
+
+        for entry in &self.entries {
+            all.line(format!("    Box::new({}),", entry.constructor));
+        }
+
+        all.line("]");
+
+        let get = scope
+            .new_fn("get")
+            .doc(format!("Returns the registered `{}` with the given name.", r#trait))
+            .arg("name", "&str")
+            .ret(format!("Option<{}>", boxed));
+
+        if let Some(ref vis) = self.vis {
+            get.vis(vis);
+        }
+
+        let mut arms = Block::new("match name");
+
+        for entry in &self.entries {
+            arms.line(format!(
+                "{} => Some(Box::new({})),",
+                quote_str(&entry.name),
+                entry.constructor
+            ));
+        }
+
+        arms.line("_ => None,");
+        get.push_block(arms);
+    }
+
+    /// Pushes a `static` per entry and the `REGISTRY` slice to the given
+    /// scope.
+    fn push_static(&self, scope: &mut Scope) {
+        let vis = match self.vis {
+            Some(ref vis) => format!("{} ", vis),
+            None => String::new(),
+        };
+
+        let mut statics = vec![];
+        let mut registry = vec![];
+
+        for entry in &self.entries {
+            let name = to_snake_case(&entry.name).to_uppercase();
+
+            statics.push(format!(
+                "static {}: {} = {};",
+                name,
+                entry.ty.fmt_to_string(),
+                entry.constructor
+            ));
+            registry.push(format!("    ({}, &{}),", quote_str(&entry.name), name));
+        }
+
+        if !statics.is_empty() {
+            scope.raw(statics.join("\n"));
+        }
+
+        let r#trait = self.r#trait.fmt_to_string();
+
+        scope.raw(format!(
+            "/// Every registered `{}` by name.\n{}static REGISTRY: &[(&str, &(dyn {} + Sync))] = &[\n{}\n];",
+            r#trait,
+            vis,
+            r#trait,
+            registry.join("\n")
+        ));
+    }
+}
@@ -0,0 +1,59 @@
+use std::fmt;
+
+use crate::formatter::Formatter;
+
+/// Defines hooks called before and after each item of a scope and its
+/// modules is formatted, e.g. to write separators or markers between the
+/// items. Set them with `Formatter::set_hook` or use
+/// `Scope::to_string_with_hook`.
+///
+/// Both hooks do nothing by default.
+///
+/// # Examples
+///
+/// ```
+/// use std::fmt::{self, Write};
+///
+/// use rust_codegen::{Formatter, RenderHook, Scope};
+///
+/// #[derive(Debug)]
+/// struct Markers;
+///
+/// impl RenderHook for Markers {
+///     fn before_item(&mut self, kind: &str, name: &str, fmt: &mut Formatter<'_>) -> fmt::Result {
+///         write!(fmt, "// {} {}\n", kind, name)
+///     }
+/// }
+///
+/// let mut scope = Scope::new();
+/// scope.new_struct("Foo");
+///
+/// assert_eq!(scope.to_string_with_hook(&mut Markers), "// struct Foo\nstruct Foo;");
+/// ```
+pub trait RenderHook: fmt::Debug {
+    /// Called before the item is formatted.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of the item, e.g. `struct` or `fn`.
+    /// * `name` - The name of the item, or an empty string for assembly,
+    ///   raw code, and custom items.
+    /// * `fmt` - The formatter the item is formatted with.
+    fn before_item(&mut self, kind: &str, name: &str, fmt: &mut Formatter<'_>) -> fmt::Result {
+        let _ = (kind, name, fmt);
+        Ok(())
+    }
+
+    /// Called after the item is formatted.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of the item, e.g. `struct` or `fn`.
+    /// * `name` - The name of the item, or an empty string for assembly,
+    ///   raw code, and custom items.
+    /// * `fmt` - The formatter the item is formatted with.
+    fn after_item(&mut self, kind: &str, name: &str, fmt: &mut Formatter<'_>) -> fmt::Result {
+        let _ = (kind, name, fmt);
+        Ok(())
+    }
+}
@@ -0,0 +1,620 @@
+//! Scaffolding for whole generated crates: a minimal `Cargo.toml` along with
+//! the `src/lib.rs` and `src/main.rs` entry points, examples, criterion
+//! benchmarks, and integration tests.
+//!
+//! ```
+//! use rust_codegen::scaffold::Crate;
+//! use rust_codegen::Scope;
+//!
+//! let mut lib = Scope::new();
+//! lib.new_fn("answer").vis("pub").ret("u32").line("42");
+//!
+//! let mut generated = Crate::new("answers");
+//! generated.dependency("serde", r#"{ version = "1", features = ["derive"] }"#);
+//! generated.lib(lib);
+//!
+//! let tree = generated.to_source_tree();
+//! assert!(tree.get("Cargo.toml").is_some());
+//! ```
+
+use std::borrow::Cow;
+
+use crate::function::Function;
+use crate::literal::quote_str;
+use crate::scope::Scope;
+use crate::source_tree::SourceTree;
+
+/// Defines a generated crate.
+#[derive(Debug, Clone)]
+pub struct Crate {
+    /// The name of the package.
+    name: Cow<'static, str>,
+    /// The version of the package.
+    version: Cow<'static, str>,
+    /// The edition of the package.
+    edition: Cow<'static, str>,
+    /// The dependencies along with their TOML specifications, in order.
+    dependencies: Vec<(String, String)>,
+    /// The dev-dependencies along with their TOML specifications, in order.
+    dev_dependencies: Vec<(String, String)>,
+    /// The contents of `src/lib.rs`, if any.
+    lib: Option<Scope>,
+    /// The contents of `src/main.rs`, if any.
+    bin: Option<Scope>,
+    /// The examples, by name, in order.
+    examples: Vec<(String, Scope)>,
+    /// The benchmarks, by name, in order.
+    benches: Vec<(String, Scope)>,
+    /// The integration tests, in order.
+    tests: Vec<IntegrationTest>,
+}
+
+/// Defines an integration test file, `tests/{name}.rs`, importing everything
+/// from the crate under test.
+#[derive(Debug, Clone)]
+pub struct IntegrationTest {
+    /// The name of the test file.
+    name: String,
+    /// The contents of the test file.
+    scope: Scope,
+}
+
+impl Crate {
+    /// Return a new crate with the given package name, at version `0.1.0`
+    /// and edition 2021.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the package.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::scaffold::Crate;
+    ///
+    /// let generated = Crate::new("answers");
+    /// ```
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let name = name.into();
+        assert!(!name.is_empty(), "the name of a crate must not be empty");
+
+        Crate {
+            name,
+            version: "0.1.0".into(),
+            edition: "2021".into(),
+            dependencies: vec![],
+            dev_dependencies: vec![],
+            lib: None,
+            bin: None,
+            examples: vec![],
+            benches: vec![],
+            tests: vec![],
+        }
+    }
+
+    /// Set the version of the package.
+    ///
+    /// # Arguments
+    ///
+    /// * `version` - The version, e.g. `1.2.0`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::scaffold::Crate;
+    ///
+    /// let mut generated = Crate::new("answers");
+    /// generated.version("1.2.0");
+    /// ```
+    pub fn version<T>(&mut self, version: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.version = version.into();
+        self
+    }
+
+    /// Set the edition of the package.
+    ///
+    /// # Arguments
+    ///
+    /// * `edition` - The edition, e.g. `2018`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::scaffold::Crate;
+    ///
+    /// let mut generated = Crate::new("answers");
+    /// generated.edition("2018");
+    /// ```
+    pub fn edition<T>(&mut self, edition: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.edition = edition.into();
+        self
+    }
+
+    /// Add a dependency, replacing the dependency with the same name if
+    /// any.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the dependency.
+    /// * `spec` - The TOML specification of the dependency, written as is,
+    ///   e.g. `"1.0"` or `{ version = "1", features = ["derive"] }`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::scaffold::Crate;
+    ///
+    /// let mut generated = Crate::new("answers");
+    /// generated.dependency("indexmap", r#""1.9.3""#);
+    /// ```
+    pub fn dependency(&mut self, name: &str, spec: &str) -> &mut Self {
+        set_dependency(&mut self.dependencies, name, spec);
+        self
+    }
+
+    /// Add a dev-dependency, replacing the dev-dependency with the same name
+    /// if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the dev-dependency.
+    /// * `spec` - The TOML specification of the dev-dependency, written as
+    ///   is, e.g. `"0.5"`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::scaffold::Crate;
+    ///
+    /// let mut generated = Crate::new("answers");
+    /// generated.dev_dependency("criterion", r#""0.5""#);
+    /// ```
+    pub fn dev_dependency(&mut self, name: &str, spec: &str) -> &mut Self {
+        set_dependency(&mut self.dev_dependencies, name, spec);
+        self
+    }
+
+    /// Set the contents of `src/lib.rs`.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - The contents of the library.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::scaffold::Crate;
+    /// use rust_codegen::Scope;
+    ///
+    /// let mut generated = Crate::new("answers");
+    /// generated.lib(Scope::new());
+    /// ```
+    pub fn lib(&mut self, scope: Scope) -> &mut Self {
+        self.lib = Some(scope);
+        self
+    }
+
+    /// Set the contents of `src/main.rs`. An empty `main` function is added
+    /// when the scope has none.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - The contents of the binary.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::scaffold::Crate;
+    /// use rust_codegen::Scope;
+    ///
+    /// let mut generated = Crate::new("answers");
+    /// generated.bin(Scope::new());
+    /// ```
+    pub fn bin(&mut self, mut scope: Scope) -> &mut Self {
+        ensure_main(&mut scope);
+
+        self.bin = Some(scope);
+        self
+    }
+
+    /// Add an example, written to `examples/{name}.rs`. An empty `main`
+    /// function is added when the scope has none.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the example.
+    /// * `scope` - The contents of the example.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::scaffold::Crate;
+    /// use rust_codegen::Scope;
+    ///
+    /// let mut scope = Scope::new();
+    /// scope.new_fn("main").line("println!(\"{}\", answers::answer());");
+    ///
+    /// let mut generated = Crate::new("answers");
+    /// generated.example("print", scope);
+    /// ```
+    pub fn example(&mut self, name: &str, mut scope: Scope) -> &mut Self {
+        ensure_main(&mut scope);
+
+        self.examples.push((name.to_string(), scope));
+        self
+    }
+
+    /// Add a criterion benchmark, written to `benches/{name}.rs` and
+    /// declared in the manifest without the default harness. Every function
+    /// of the scope is a benchmark function taking a `&mut Criterion`, and a
+    /// skeleton function is added when the scope has none. A `criterion`
+    /// dev-dependency is added unless one was declared.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the benchmark.
+    /// * `scope` - The benchmark functions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::scaffold::Crate;
+    /// use rust_codegen::Scope;
+    ///
+    /// let mut generated = Crate::new("answers");
+    /// generated.bench("answer", Scope::new());
+    /// ```
+    pub fn bench(&mut self, name: &str, mut scope: Scope) -> &mut Self {
+        if scope.fn_names().is_empty() {
+            push_bench_skeleton(&mut scope, name);
+        }
+
+        finish_bench(&mut scope);
+
+        if !self.dev_dependencies.iter().any(|(n, _)| n == "criterion") {
+            self.dev_dependency("criterion", "\"0.5\"");
+        }
+
+        self.benches.push((name.to_string(), scope));
+        self
+    }
+
+    /// Add a criterion benchmark with a skeleton `bench_{name}` function
+    /// for each of the given names, see [`Crate::bench`].
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the benchmark.
+    /// * `fns` - The names of the benchmark functions, e.g. `encode`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::scaffold::Crate;
+    ///
+    /// let mut generated = Crate::new("codecs");
+    /// generated.bench_skeletons("json", ["encode", "decode"]);
+    /// ```
+    pub fn bench_skeletons<I, T>(&mut self, name: &str, fns: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let mut scope = Scope::new();
+
+        for f in fns {
+            push_bench_skeleton(&mut scope, f.as_ref());
+        }
+
+        self.bench(name, scope)
+    }
+
+    /// Add an integration test file importing everything from the crate,
+    /// returning it to add tests to.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the test file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::scaffold::Crate;
+    ///
+    /// let mut generated = Crate::new("answers");
+    /// generated
+    ///     .new_test("answer")
+    ///     .new_test("is_42")
+    ///     .line("assert_eq!(answer(), 42);");
+    /// ```
+    pub fn new_test(&mut self, name: &str) -> &mut IntegrationTest {
+        self.tests.push(IntegrationTest::new(&self.name, name));
+        self.tests.last_mut().unwrap()
+    }
+
+    /// Returns the `Cargo.toml` of the crate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::scaffold::Crate;
+    ///
+    /// let generated = Crate::new("answers");
+    ///
+    /// assert_eq!(
+    ///     generated.to_manifest(),
+    ///     "[package]\nname = \"answers\"\nversion = \"0.1.0\"\nedition = \"2021\"\n"
+    /// );
+    /// ```
+    pub fn to_manifest(&self) -> String {
+        let mut ret = String::new();
+
+        ret.push_str("[package]\n");
+        ret.push_str(&format!("name = {}\n", quote_toml(&self.name)));
+        ret.push_str(&format!("version = {}\n", quote_toml(&self.version)));
+        ret.push_str(&format!("edition = {}\n", quote_toml(&self.edition)));
+
+        for (table, dependencies) in [
+            ("dependencies", &self.dependencies),
+            ("dev-dependencies", &self.dev_dependencies),
+        ] {
+            if !dependencies.is_empty() {
+                ret.push_str(&format!("\n[{}]\n", table));
+
+                for (name, spec) in dependencies {
+                    ret.push_str(&format!("{} = {}\n", name, spec));
+                }
+            }
+        }
+
+        for (name, _) in &self.benches {
+            ret.push_str("\n[[bench]]\n");
+            ret.push_str(&format!("name = {}\n", quote_toml(name)));
+            ret.push_str("harness = false\n");
+        }
+
+        ret
+    }
+
+    /// Push the manifest and the entry points of the crate to the given
+    /// source tree. An empty `src/lib.rs` is written when the crate has
+    /// neither a library nor a binary, so that the crate always compiles.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The source tree to push the files to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::scaffold::Crate;
+    /// use rust_codegen::SourceTree;
+    ///
+    /// let mut tree = SourceTree::new();
+    /// Crate::new("answers").push_to(&mut tree);
+    ///
+    /// assert_eq!(tree.get("src/lib.rs"), Some(""));
+    /// ```
+    pub fn push_to(&self, tree: &mut SourceTree) {
+        tree.file("Cargo.toml", self.to_manifest());
+
+        match (&self.lib, &self.bin) {
+            (None, None) => {
+                tree.file("src/lib.rs", "");
+            }
+            (lib, bin) => {
+                if let Some(lib) = lib {
+                    tree.scope("src/lib.rs", lib);
+                }
+
+                if let Some(bin) = bin {
+                    tree.scope("src/main.rs", bin);
+                }
+            }
+        }
+
+        for (name, scope) in &self.examples {
+            tree.scope(&format!("examples/{}.rs", name), scope);
+        }
+
+        for (name, scope) in &self.benches {
+            tree.scope(&format!("benches/{}.rs", name), scope);
+        }
+
+        for test in &self.tests {
+            test.push_to(tree);
+        }
+    }
+
+    /// Returns a new source tree with the manifest and the entry points of
+    /// the crate.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::scaffold::Crate;
+    ///
+    /// let tree = Crate::new("answers").to_source_tree();
+    /// ```
+    pub fn to_source_tree(&self) -> SourceTree {
+        let mut tree = SourceTree::new();
+        self.push_to(&mut tree);
+        tree
+    }
+}
+
+impl IntegrationTest {
+    /// Return a new integration test file importing everything from the
+    /// given crate.
+    ///
+    /// # Arguments
+    ///
+    /// * `crate_name` - The name of the package under test, e.g.
+    ///   `my-crate`.
+    /// * `name` - The name of the test file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::scaffold::IntegrationTest;
+    ///
+    /// let test = IntegrationTest::new("my-crate", "conformance");
+    /// ```
+    pub fn new(crate_name: &str, name: &str) -> Self {
+        let mut scope = Scope::new();
+        scope.import(&crate_name.replace('-', "_"), "*");
+
+        IntegrationTest {
+            name: name.to_string(),
+            scope,
+        }
+    }
+
+    /// Returns a mutable reference to the contents of the test file, e.g. to
+    /// add imports or helpers.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::scaffold::IntegrationTest;
+    ///
+    /// let mut test = IntegrationTest::new("my-crate", "conformance");
+    /// test.scope().import("std::collections", "HashMap");
+    /// ```
+    pub fn scope(&mut self) -> &mut Scope {
+        &mut self.scope
+    }
+
+    /// Push a new `#[test]` function, returning a mutable reference to it.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the test function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::scaffold::IntegrationTest;
+    ///
+    /// let mut test = IntegrationTest::new("my-crate", "conformance");
+    /// test.new_test("round_trip").line("assert!(true);");
+    /// ```
+    pub fn new_test(&mut self, name: &str) -> &mut Function {
+        self.scope.new_fn(name.to_string()).test()
+    }
+
+    /// Push the test file to the given source tree.
+    ///
+    /// # Arguments
+    ///
+    /// * `tree` - The source tree to push the file to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::scaffold::IntegrationTest;
+    /// use rust_codegen::SourceTree;
+    ///
+    /// let mut tree = SourceTree::new();
+    /// IntegrationTest::new("my-crate", "conformance").push_to(&mut tree);
+    ///
+    /// assert_eq!(tree.get("tests/conformance.rs"), Some("use my_crate::*;\n"));
+    /// ```
+    pub fn push_to(&self, tree: &mut SourceTree) {
+        tree.scope(&format!("tests/{}.rs", self.name), &self.scope);
+    }
+}
+
+/// Adds the given dependency to the list, replacing the dependency with the
+/// same name if any.
+fn set_dependency(dependencies: &mut Vec<(String, String)>, name: &str, spec: &str) {
+    match dependencies.iter_mut().find(|(n, _)| n == name) {
+        Some(dependency) => dependency.1 = spec.to_string(),
+        None => dependencies.push((name.to_string(), spec.to_string())),
+    }
+}
+
+/// Returns the contents of a criterion benchmark file with a skeleton
+/// `bench_{name}` function taking a `&mut Criterion` for each of the given
+/// names, along with the `criterion_group!` and `criterion_main!`
+/// invocations, e.g. for a `benches/{name}.rs` file of an existing crate.
+///
+/// # Arguments
+///
+/// * `names` - The names of the benchmark functions, e.g. `encode`.
+///
+/// # Examples
+///
+/// ```
+/// use rust_codegen::scaffold::criterion_bench;
+///
+/// let bench = criterion_bench(["encode"]);
+///
+/// assert_eq!(
+///     bench.to_string(),
+///     "use criterion::{criterion_group, criterion_main, Criterion};\n\n\
+///      fn bench_encode(c: &mut Criterion) {\n    \
+///      c.bench_function(\"encode\", |b| b.iter(|| {}));\n}\n\n\
+///      criterion_group!(benches, bench_encode);\ncriterion_main!(benches);"
+/// );
+/// ```
+pub fn criterion_bench<I, T>(names: I) -> Scope
+where
+    I: IntoIterator<Item = T>,
+    T: AsRef<str>,
+{
+    let mut scope = Scope::new();
+
+    for name in names {
+        push_bench_skeleton(&mut scope, name.as_ref());
+    }
+
+    finish_bench(&mut scope);
+    scope
+}
+
+/// Pushes a skeleton `bench_{name}` function to the given scope.
+fn push_bench_skeleton(scope: &mut Scope, name: &str) {
+    scope
+        .new_fn(format!("bench_{}", name))
+        .arg("c", "&mut Criterion")
+        .line(format!(
+            "c.bench_function({}, |b| b.iter(|| {{}}));",
+            quote_str(name)
+        ));
+}
+
+/// Adds the criterion imports and the `criterion_group!` and
+/// `criterion_main!` invocations of every function to the given scope.
+fn finish_bench(scope: &mut Scope) {
+    let group = format!(
+        "criterion_group!(benches, {});\ncriterion_main!(benches);",
+        scope.fn_names().join(", ")
+    );
+
+    scope.import("criterion", "criterion_group");
+    scope.import("criterion", "criterion_main");
+    scope.import("criterion", "Criterion");
+    scope.raw(group);
+}
+
+/// Adds an empty `main` function to the given scope if it has none.
+fn ensure_main(scope: &mut Scope) {
+    if scope.get_fn("main").is_none() {
+        scope.new_fn("main");
+    }
+}
+
+/// Returns the given value as a TOML basic string.
+fn quote_toml(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
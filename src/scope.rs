@@ -1,31 +1,111 @@
+use std::borrow::Cow;
+use std::collections::HashSet;
 use std::fmt::{self, Write};
 
 use indexmap::IndexMap;
 
+use crate::asm::Asm;
+use crate::async_variant::{trait_ident, AsyncTraitStrategy};
+use crate::case::to_pascal_case;
+use crate::deterministic::normalize;
+use crate::diff::{ChangeKind, ItemChange, ScopeDiff};
 use crate::docs::Docs;
-use crate::formatter::Formatter;
+use crate::extern_block::ExternBlock;
+use crate::feature_layout::{cfg_feature, feature_module, module_dir, FeatureLayout};
+use crate::format_item::FormatItem;
+use crate::fn_sig::FnSig;
+use crate::formatter::{fmt_to_code_string, try_fmt_to_code_string, BodyMode, Formatter, WhereStyle};
 use crate::function::Function;
 use crate::import::Import;
 use crate::item::Item;
 use crate::module::Module;
+use crate::path_rewrite::PathRewrite;
+use crate::policy::Policy;
+use crate::prelude::Prelude;
+use crate::render_hook::RenderHook;
+use crate::semver::ApiShape;
+use crate::source_map::SourceMap;
+use crate::source_tree::SourceTree;
+use crate::stats::ScopeStats;
+use crate::transform::Transform;
+use crate::visibility::{map_vis, Visibility};
 
 use crate::r#enum::Enum;
 use crate::r#impl::Impl;
+use crate::r#static::Static;
 use crate::r#struct::Struct;
 use crate::r#trait::Trait;
+use crate::r#type::Type;
+
+/// The lints that machine-generated code commonly triggers, allowed by
+/// `Scope::allow_generated_lints`.
+pub const GENERATED_LINTS: &[&str] = &[
+    "dead_code",
+    "unused_imports",
+    "unused_variables",
+    "unused_mut",
+    "unused_parens",
+    "unreachable_patterns",
+    "non_camel_case_types",
+    "non_snake_case",
+    "non_upper_case_globals",
+    "clippy::all",
+    "clippy::pedantic",
+];
+
+/// The attribute preceding raw items protected from rustfmt, see
+/// `Scope::rustfmt_skip_raw`.
+const RUSTFMT_SKIP: &str = "#[rustfmt::skip]";
+
+// Fails to compile if an item stops being shareable between threads, e.g.
+// because of interior mutability.
+const _: fn() = || {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<Scope>();
+};
 
 /// Defines a scope.
 ///
 /// A scope contains modules, types, etc.
+///
+/// The output is deterministic: imports are rendered grouped by path in the
+/// order their paths were first imported, and items, including modules, in
+/// the order they were added, on every platform. Use
+/// [`Scope::reorder_modules`] to change the order of the modules.
+///
+/// Rendering only borrows the scope and its configuration lives in the
+/// formatter, so a scope is `Send + Sync` and can be rendered several times,
+/// or from several threads at once, with different configurations, see
+/// [`Scope::to_string_with`].
 #[allow(dead_code)]
 #[derive(Debug, Clone)]
 pub struct Scope {
     /// Scope documentation.
     docs: Option<Docs>,
+    /// Inner attributes, e.g. `#![no_std]`.
+    inner_attributes: Vec<Cow<'static, str>>,
     /// Imports.
     imports: IndexMap<String, IndexMap<String, Import>>,
     /// Contents of the documentation,.
     items: Vec<Item>,
+    /// The conventions applied to the items when rendering.
+    policy: Option<Policy>,
+    /// How function bodies are rendered, if set on this scope.
+    body_mode: Option<BodyMode>,
+    /// The layout of `where` clauses, if set on this scope.
+    where_style: Option<WhereStyle>,
+    /// Whether lists end with a trailing comma, if set on this scope.
+    trailing_comma: Option<bool>,
+    /// Whether opening braces are preceded by a space, if set on this scope.
+    space_before_brace: Option<bool>,
+    /// The width beyond which use trees and derive lists are wrapped, if
+    /// set on this scope.
+    max_width: Option<usize>,
+    /// Whether raw items are preceded by `#[rustfmt::skip]`, if set on this
+    /// scope.
+    rustfmt_skip_raw: Option<bool>,
+    /// Whether the output is normalized for reproducible builds.
+    deterministic: bool,
 }
 
 impl Scope {
@@ -33,8 +113,17 @@ impl Scope {
     pub fn new() -> Self {
         Scope {
             docs: None,
+            inner_attributes: vec![],
             imports: IndexMap::new(),
             items: vec![],
+            policy: None,
+            body_mode: None,
+            where_style: None,
+            trailing_comma: None,
+            space_before_brace: None,
+            max_width: None,
+            rustfmt_skip_raw: None,
+            deterministic: false,
         }
     }
 
@@ -42,15 +131,519 @@ impl Scope {
     ///
     /// This results in a new `use` statement being added to the beginning of
     /// the scope.
+    ///
+    /// If another import already brings a type with the same name into the
+    /// scope, the type is imported under an alias made of the last segment of
+    /// its path, e.g. `use b::Foo as BFoo;`. The alias is returned by
+    /// `Import::alias` and listed by `Scope::import_aliases`.
     pub fn import(&mut self, path: &str, ty: &str) -> &mut Import {
         // handle cases where the caller wants to refer to a type namespaced
         // within the containing namespace, like "a::B".
         let ty = ty.split("::").next().unwrap_or(ty);
+
+        let existing = self
+            .imports
+            .get(path)
+            .and_then(|imports| imports.keys().find(|key| key.split(' ').next() == Some(ty)))
+            .cloned();
+
+        let key = match existing {
+            Some(key) => key,
+            None if ty != "*" && ty != "self" && self.is_imported(ty) => {
+                format!("{} as {}", ty, self.import_alias(path, ty))
+            }
+            None => ty.to_string(),
+        };
+
         self.imports
             .entry(path.to_string())
             .or_insert(IndexMap::new())
-            .entry(ty.to_string())
-            .or_insert_with(|| Import::new(path, ty))
+            .entry(key)
+            .or_insert_with_key(|key| {
+                let mut import = Import::new(path, ty);
+
+                if let Some(alias) = key.strip_prefix(ty).and_then(|k| k.strip_prefix(" as ")) {
+                    import.set_alias(alias.to_string());
+                }
+
+                import
+            })
+    }
+
+    /// Returns the path and alias of every import that was renamed because
+    /// it conflicted with another import, e.g. `("b::Foo", "BFoo")`.
+    pub fn import_aliases(&self) -> Vec<(String, String)> {
+        let mut aliases = vec![];
+
+        for (path, imports) in &self.imports {
+            for (ty, import) in imports {
+                if let Some(alias) = import.alias() {
+                    let name = ty.split(' ').next().unwrap();
+                    aliases.push((format!("{}::{}", path, name), alias.to_string()));
+                }
+            }
+        }
+
+        aliases
+    }
+
+    /// Returns whether an import brings the given name into the scope.
+    fn is_imported(&self, name: &str) -> bool {
+        self.imports
+            .values()
+            .flat_map(|imports| imports.keys())
+            .any(|key| key.rsplit(' ').next() == Some(name))
+    }
+
+    /// Returns an alias for the type that isn't imported yet.
+    fn import_alias(&self, path: &str, ty: &str) -> String {
+        let prefix = path.rsplit("::").next().unwrap();
+
+        let base = if ty.starts_with(char::is_uppercase) {
+            format!("{}{}", to_pascal_case(prefix), ty)
+        } else {
+            format!("{}_{}", prefix, ty)
+        };
+
+        let mut alias = base.clone();
+        let mut i = 2;
+
+        while self.is_imported(&alias) {
+            alias = format!("{}{}", base, i);
+            i += 1;
+        }
+
+        alias
+    }
+
+    /// Removes the imports whose name doesn't appear in any item of the scope
+    /// or of its modules, e.g. the stale imports of merged scopes. Nested
+    /// modules are pruned as well.
+    ///
+    /// This is a best-effort scan of the rendered items: glob imports and
+    /// imports with a visibility (re-exports) are always kept, and traits
+    /// that are only imported for their methods are removed.
+    pub fn prune_unused_imports(&mut self) -> &mut Self {
+        for item in self.items.iter_mut() {
+            if let Item::Module(ref mut v) = *item {
+                v.scope.prune_unused_imports();
+            }
+        }
+
+        let mut items_only = self.clone();
+        items_only.imports.clear();
+        let rendered = items_only.to_string();
+
+        let idents: HashSet<&str> = rendered
+            .split(|c: char| !(c.is_alphanumeric() || c == '_'))
+            .collect();
+
+        for (path, imports) in self.imports.iter_mut() {
+            imports.retain(|ty, import| {
+                // `use a::b::{self}` and `use a::B as C` are referred to by
+                // the last path segment and the alias respectively.
+                let name = match ty.rsplit(' ').next().unwrap() {
+                    "self" => path.rsplit("::").next().unwrap(),
+                    name => name,
+                };
+
+                name == "*" || import.vis.is_some() || idents.contains(name)
+            });
+        }
+
+        self.imports.retain(|_, imports| !imports.is_empty());
+        self
+    }
+
+    /// Rewrites the paths of the types and imports of the scope and its
+    /// modules with the given prefixes, e.g. `my_models::` to
+    /// `crate::models::`. Function bodies and raw code are left as is.
+    pub fn rewrite_paths(&mut self, rewrite: &PathRewrite) -> &mut Self {
+        let imports = std::mem::take(&mut self.imports);
+
+        for (path, tys) in imports {
+            let rewritten = self
+                .imports
+                .entry(rewrite.apply(&path))
+                .or_default();
+
+            for (ty, import) in tys {
+                rewritten.entry(ty).or_insert(import);
+            }
+        }
+
+        let f = &mut |ty: &mut Type| ty.rewrite_path(rewrite);
+
+        for item in self.items.iter_mut() {
+            match *item {
+                Item::Module(ref mut v) => {
+                    v.scope.rewrite_paths(rewrite);
+                }
+                Item::Struct(ref mut v) => v.visit_types_mut(f),
+                Item::Function(ref mut v) => v.visit_types_mut(f),
+                Item::Trait(ref mut v) => v.visit_types_mut(f),
+                Item::Enum(ref mut v) => v.visit_types_mut(f),
+                Item::Impl(ref mut v) => v.visit_types_mut(f),
+                Item::Static(ref mut v) => v.visit_types_mut(f),
+                Item::ExternBlock(ref mut v) => v.visit_types_mut(f),
+                Item::Asm(..) | Item::Raw(..) | Item::Custom(..) => {}
+            }
+        }
+
+        self
+    }
+
+    /// Returns a copy of the scope with the visibility of every item
+    /// mapped with the given function, e.g. to turn `pub` into `pub(crate)`
+    /// for an internal version of a public API. The visibility written before
+    /// struct fields, e.g. `pub id`, and of imports is mapped as well, while
+    /// the items of traits and trait impls are left as is.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `f` - The function mapping the visibility of each item.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Scope, Visibility};
+    /// 
+    /// let mut scope = Scope::new();
+    /// scope.new_struct("User").vis("pub");
+    /// 
+    /// let internal = scope.with_visibility_map(|vis| match vis {
+    ///     Visibility::Pub => Visibility::PubCrate,
+    ///     vis => vis,
+    /// });
+    /// 
+    /// assert_eq!(internal.to_string(), "pub(crate) struct User;");
+    /// ```
+    pub fn with_visibility_map<F>(&self, f: F) -> Scope
+    where
+        F: Fn(Visibility) -> Visibility,
+    {
+        let mut ret = self.clone();
+        ret.map_visibility(&f);
+        ret
+    }
+
+    /// Maps the visibility of the items of the scope and its modules with the
+    /// given function.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `f` - The function mapping the visibility.
+    pub(crate) fn map_visibility(&mut self, f: &dyn Fn(Visibility) -> Visibility) {
+        for import in self.imports.values_mut().flat_map(|tys| tys.values_mut()) {
+            map_vis(&mut import.vis, f);
+        }
+
+        for item in self.items.iter_mut() {
+            match *item {
+                Item::Module(ref mut v) => v.map_visibility(f),
+                Item::Struct(ref mut v) => v.map_visibility(f),
+                Item::Function(ref mut v) => v.map_visibility(f),
+                Item::Trait(ref mut v) => v.map_visibility(f),
+                Item::Enum(ref mut v) => v.map_visibility(f),
+                Item::Impl(ref mut v) => v.map_visibility(f),
+                Item::Static(ref mut v) => v.map_visibility(f),
+                Item::ExternBlock(ref mut v) => v.map_visibility(f),
+                Item::Asm(..) | Item::Raw(..) | Item::Custom(..) => {}
+            }
+        }
+    }
+
+    /// Returns a copy of the scope with the given transform applied to the
+    /// functions of the scope and its modules, e.g. to generate a sync or a
+    /// panic-free version of the same code.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `transform` - The transform to apply.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Scope, Transform};
+    /// 
+    /// let mut scope = Scope::new();
+    /// scope.new_fn("load").set_async(true).line("fetch().await.unwrap()");
+    /// 
+    /// let mut transform = Transform::new();
+    /// transform.strip_async().unwrap_to_try();
+    /// 
+    /// assert_eq!(scope.transformed(&transform).to_string(), "fn load() {\n    fetch()?\n}");
+    /// ```
+    pub fn transformed(&self, transform: &Transform) -> Scope {
+        let mut ret = self.clone();
+        ret.transform(transform);
+        ret
+    }
+
+    /// Applies the given transform to the functions of the scope and its
+    /// modules.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `transform` - The transform to apply.
+    fn transform(&mut self, transform: &Transform) {
+        for item in self.items.iter_mut() {
+            match *item {
+                Item::Module(ref mut v) => v.scope.transform(transform),
+                Item::Function(ref mut v) => v.transform(transform),
+                Item::Trait(ref mut v) => v.transform(transform),
+                Item::Impl(ref mut v) => v.transform(transform),
+                Item::Struct(..)
+                | Item::Enum(..)
+                | Item::Static(..)
+                | Item::ExternBlock(..)
+                | Item::Asm(..)
+                | Item::Raw(..)
+                | Item::Custom(..) => {}
+            }
+        }
+    }
+
+    /// Returns a copy of the scope with only its public API, e.g. to generate
+    /// an interface summary or an API stub crate: the `pub` items of the
+    /// scope and its `pub` modules with their `pub` fields and methods, trait
+    /// impls, and imports. Functions keep their signatures with empty
+    /// bodies. Raw code, custom items, assembly, and extern blocks are
+    /// removed.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Scope;
+    /// 
+    /// let mut scope = Scope::new();
+    /// scope.new_struct("Client")
+    ///     .vis("pub")
+    ///     .field("pub url", "String")
+    ///     .field("retries", "u32");
+    /// scope.new_fn("connect").vis("pub").ret("Client").line("todo!()");
+    /// scope.new_fn("helper");
+    /// 
+    /// assert_eq!(
+    ///     scope.public_api().to_string(),
+    ///     "pub struct Client {\n    pub url: String,\n}\n\npub fn connect() -> Client {\n}"
+    /// );
+    /// ```
+    pub fn public_api(&self) -> Scope {
+        let mut ret = self.clone();
+        ret.retain_public_api();
+        ret
+    }
+
+    /// Removes the items of the scope and its modules that aren't part of the
+    /// public API and empties the bodies of the functions.
+    fn retain_public_api(&mut self) {
+        let public = |vis: Option<&str>| vis == Some("pub");
+
+        for mut item in std::mem::take(&mut self.items) {
+            let keep = match item {
+                Item::Module(ref mut v) => {
+                    v.scope.retain_public_api();
+                    public(v.get_vis())
+                }
+                Item::Struct(ref mut v) => {
+                    v.retain_public_fields();
+                    public(v.type_def().get_vis())
+                }
+                Item::Function(ref mut v) => {
+                    v.strip_body();
+                    public(v.get_vis())
+                }
+                Item::Trait(ref mut v) => {
+                    v.strip_bodies();
+                    public(v.type_def().get_vis())
+                }
+                Item::Enum(ref v) => public(v.type_def().get_vis()),
+                Item::Impl(ref mut v) => v.retain_public_api(),
+                Item::Static(ref v) => public(v.get_vis()),
+                Item::ExternBlock(..) | Item::Asm(..) | Item::Raw(..) | Item::Custom(..) => false,
+            };
+
+            if keep {
+                self.items.push(item);
+            }
+        }
+    }
+
+    /// Renders the scope to the given path of a new source tree, laying out
+    /// the items tagged with a cargo feature with [`Metadata::feature`]
+    /// according to the given layout.
+    ///
+    /// With [`FeatureLayout::Files`], the tagged items of the scope are moved
+    /// to a file per feature, e.g. `src/serde.rs` for `src/lib.rs`, which
+    /// imports everything from its parent. Tagged items of nested modules
+    /// get a `cfg` attribute in both layouts.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `path` - The path of the main file, e.g. `src/lib.rs`.
+    /// * `layout` - How to lay out the tagged items.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{FeatureLayout, Scope};
+    /// 
+    /// let mut scope = Scope::new();
+    /// scope.new_fn("to_json").metadata_mut().feature("json");
+    /// 
+    /// let tree = scope.render_features("src/lib.rs", FeatureLayout::Cfg);
+    /// assert_eq!(
+    ///     tree.get("src/lib.rs"),
+    ///     Some("#[cfg(feature = \"json\")]\nfn to_json() {\n}\n")
+    /// );
+    /// ```
+    ///
+    /// [`Metadata::feature`]: crate::Metadata::feature
+    pub fn render_features(&self, path: &str, layout: FeatureLayout) -> SourceTree {
+        let mut root = self.clone();
+        let mut features: IndexMap<String, Scope> = IndexMap::new();
+
+        if layout == FeatureLayout::Files {
+            for item in std::mem::take(&mut root.items) {
+                match item.feature().map(str::to_string) {
+                    Some(feature) => features
+                        .entry(feature)
+                        .or_insert_with(|| {
+                            let mut scope = Scope::new();
+                            scope.import("super", "*");
+                            scope
+                        })
+                        .items
+                        .push(item),
+                    None => root.items.push(item),
+                }
+            }
+
+            let mut declarations = vec![];
+
+            for feature in features.keys() {
+                let module = feature_module(feature);
+                let cfg = cfg_feature(feature);
+
+                declarations.push(format!("#[{}]\nmod {};", cfg, module));
+                declarations.push(format!("#[{}]\npub use self::{}::*;", cfg, module));
+            }
+
+            if !declarations.is_empty() {
+                root.raw(declarations.join("\n"));
+            }
+        }
+
+        let mut tree = SourceTree::new();
+        let dir = module_dir(path);
+
+        root.cfg_features(None);
+        tree.scope(path, &root);
+
+        for (feature, scope) in features.iter_mut() {
+            let file = match &dir[..] {
+                "" => format!("{}.rs", feature_module(feature)),
+                _ => format!("{}/{}.rs", dir, feature_module(feature)),
+            };
+
+            scope.cfg_features(Some(feature));
+            tree.scope(&file, scope);
+        }
+
+        tree
+    }
+
+    /// Adds a `cfg` attribute to the items of the scope and its modules that
+    /// are tagged with a cargo feature other than the given enabled one.
+    fn cfg_features(&mut self, enabled: Option<&str>) {
+        for item in self.items.iter_mut() {
+            match item.feature() {
+                Some(feature) if Some(feature) != enabled => {
+                    let cfg = cfg_feature(feature);
+                    item.push_attr(cfg);
+                }
+                _ => {}
+            }
+
+            if let Item::Module(ref mut v) = *item {
+                v.scope.cfg_features(enabled);
+            }
+        }
+    }
+
+    /// Returns the policy of the scope, which applies conventions such as
+    /// default derives to the items of the scope and its modules when it is
+    /// rendered. A module's own policy replaces the policy of its parent.
+    pub fn policy(&mut self) -> &mut Policy {
+        self.policy.get_or_insert_with(Policy::new)
+    }
+
+    /// Derive the given types on every struct and enum of the scope and its
+    /// modules that doesn't opt out with `no_default_derives`.
+    pub fn default_derives<I, T>(&mut self, names: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Cow<'static, str>>,
+    {
+        let policy = self.policy();
+
+        for name in names {
+            policy.derive(name);
+        }
+
+        self
+    }
+
+    /// Add an inner attribute to the scope (e.g. `"no_std"` for `#![no_std]`).
+    ///
+    /// Inner attributes are rendered at the very beginning of the scope.
+    pub fn inner_attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.inner_attributes.push(attribute.into());
+        self
+    }
+
+    /// Allow the lints that machine-generated code commonly triggers, see
+    /// `GENERATED_LINTS`, with a single `#![allow(...)]` inner attribute.
+    pub fn allow_generated_lints(&mut self) -> &mut Self {
+        self.allow_lints(GENERATED_LINTS.iter().copied())
+    }
+
+    /// Allow the given lints with a single `#![allow(...)]` inner attribute,
+    /// e.g. to customize `GENERATED_LINTS`.
+    pub fn allow_lints<I, T>(&mut self, lints: I) -> &mut Self
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let lints: Vec<String> = lints.into_iter().map(|l| l.as_ref().to_string()).collect();
+
+        if !lints.is_empty() {
+            self.inner_attr(format!("allow({})", lints.join(", ")));
+        }
+
+        self
+    }
+
+    /// Apply a prelude to the scope, adding its imports, inner attributes,
+    /// and allowed lints.
+    pub fn use_prelude(&mut self, prelude: &Prelude) -> &mut Self {
+        for attr in &prelude.inner_attributes {
+            self.inner_attr(attr.clone());
+        }
+
+        for allow in &prelude.allow {
+            self.inner_attr(format!("allow({})", allow));
+        }
+
+        for (path, ty) in &prelude.imports {
+            self.import(path, ty);
+        }
+
+        self
     }
 
     /// Push a new module definition, returning a mutable reference to it.
@@ -65,7 +658,10 @@ impl Scope {
     /// will return the existing definition instead.
     ///
     /// [`get_or_new_module`]: #method.get_or_new_module
-    pub fn new_module(&mut self, name: &str) -> &mut Module {
+    pub fn new_module<T>(&mut self, name: T) -> &mut Module
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.push_module(Module::new(name));
 
         match *self.items.last_mut().unwrap() {
@@ -77,7 +673,7 @@ impl Scope {
     /// Returns a mutable reference to a module if it is exists in this scope.
     pub fn get_module_mut<Q: ?Sized>(&mut self, name: &Q) -> Option<&mut Module>
     where
-        String: PartialEq<Q>,
+        Cow<'static, str>: PartialEq<Q>,
     {
         self.items
             .iter_mut()
@@ -91,7 +687,7 @@ impl Scope {
     /// Returns a mutable reference to a module if it is exists in this scope.
     pub fn get_module<Q: ?Sized>(&self, name: &Q) -> Option<&Module>
     where
-        String: PartialEq<Q>,
+        Cow<'static, str>: PartialEq<Q>,
     {
         self.items
             .iter()
@@ -102,11 +698,124 @@ impl Scope {
             .next()
     }
 
+    /// Returns the modules of this scope, in the order they are rendered.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Scope;
+    /// 
+    /// let mut scope = Scope::new();
+    /// scope.new_module("b");
+    /// scope.new_module("a");
+    /// 
+    /// let names: Vec<&str> = scope.modules().map(|module| &*module.name).collect();
+    /// assert_eq!(names, vec!["b", "a"]);
+    /// ```
+    pub fn modules(&self) -> impl Iterator<Item = &Module> {
+        self.items.iter().filter_map(|item| match *item {
+            Item::Module(ref module) => Some(module),
+            _ => None,
+        })
+    }
+
+    /// Returns the kind and name of every item of this scope, e.g.
+    /// `("struct", "Foo")`, in the order they are rendered. Raw code, custom
+    /// items, and assembly have an empty name.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Scope;
+    /// 
+    /// let mut scope = Scope::new();
+    /// scope.new_struct("Foo");
+    /// scope.new_fn("foo");
+    /// 
+    /// assert_eq!(
+    ///     scope.outline(),
+    ///     vec![("struct", "Foo".to_string()), ("fn", "foo".to_string())]
+    /// );
+    /// ```
+    pub fn outline(&self) -> Vec<(&'static str, String)> {
+        self.items
+            .iter()
+            .map(|item| (item.kind(), item.name()))
+            .collect()
+    }
+
+    /// Reorders the modules of this scope. The given modules come first, in
+    /// the given order, followed by the other modules in their current
+    /// order. Modules keep their positions relative to the other items.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `order` - The names of the modules to put first.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Scope;
+    /// 
+    /// let mut scope = Scope::new();
+    /// scope.new_module("b");
+    /// scope.new_module("c");
+    /// scope.new_module("a");
+    /// scope.reorder_modules(&["a", "b"]);
+    /// 
+    /// let names: Vec<&str> = scope.modules().map(|module| &*module.name).collect();
+    /// assert_eq!(names, vec!["a", "b", "c"]);
+    /// ```
+    pub fn reorder_modules(&mut self, order: &[&str]) -> &mut Self {
+        for name in order {
+            if self.get_module(*name).is_none() {
+                panic!("unknown module `{}` in the module order", name);
+            }
+        }
+
+        let mut slots = vec![];
+        let mut modules = vec![];
+
+        for (i, item) in self.items.iter_mut().enumerate() {
+            if let Item::Module(..) = *item {
+                slots.push(i);
+                modules.push(std::mem::replace(item, Item::Raw("".into())));
+            }
+        }
+
+        let mut reordered = Vec::with_capacity(modules.len());
+
+        for name in order {
+            let position = modules.iter().position(|item| match *item {
+                Item::Module(ref module) => module.name == *name,
+                _ => false,
+            });
+
+            // Modules listed twice keep their first position.
+            if let Some(i) = position {
+                reordered.push(modules.remove(i));
+            }
+        }
+
+        reordered.extend(modules);
+
+        for (slot, module) in slots.into_iter().zip(reordered) {
+            self.items[slot] = module;
+        }
+
+        self
+    }
+
     /// Returns a mutable reference to a module, creating it if it does
     /// not exist.
-    pub fn get_or_new_module(&mut self, name: &str) -> &mut Module {
-        if self.get_module(name).is_some() {
-            self.get_module_mut(name).unwrap()
+    pub fn get_or_new_module<T>(&mut self, name: T) -> &mut Module
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        let name = name.into();
+
+        if self.get_module(&*name).is_some() {
+            self.get_module_mut(&*name).unwrap()
         } else {
             self.new_module(name)
         }
@@ -131,7 +840,10 @@ impl Scope {
     }
 
     /// Push a new struct definition, returning a mutable reference to it.
-    pub fn new_struct(&mut self, name: &str) -> &mut Struct {
+    pub fn new_struct<T>(&mut self, name: T) -> &mut Struct
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.push_struct(Struct::new(name));
 
         match *self.items.last_mut().unwrap() {
@@ -146,8 +858,39 @@ impl Scope {
         self
     }
 
+    /// Returns a reference to a struct if it exists in this scope.
+    pub fn get_struct<Q: ?Sized>(&self, name: &Q) -> Option<&Struct>
+    where
+        str: PartialEq<Q>,
+    {
+        self.items
+            .iter()
+            .filter_map(|item| match *item {
+                Item::Struct(ref v) if *v.ty().name() == *name => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Returns a mutable reference to a struct if it exists in this scope.
+    pub fn get_struct_mut<Q: ?Sized>(&mut self, name: &Q) -> Option<&mut Struct>
+    where
+        str: PartialEq<Q>,
+    {
+        self.items
+            .iter_mut()
+            .filter_map(|item| match *item {
+                Item::Struct(ref mut v) if *v.ty().name() == *name => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
     /// Push a new function definition, returning a mutable reference to it.
-    pub fn new_fn(&mut self, name: &str) -> &mut Function {
+    pub fn new_fn<T>(&mut self, name: T) -> &mut Function
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.push_fn(Function::new(name));
 
         match *self.items.last_mut().unwrap() {
@@ -156,14 +899,63 @@ impl Scope {
         }
     }
 
+    /// Push a new `fn main()` definition, returning a mutable reference to
+    /// it. Use `Function::ret_boxed_error` to return errors from it and
+    /// `Function::tokio_main` to run it on the tokio runtime.
+    pub fn new_main(&mut self) -> &mut Function {
+        self.new_fn("main")
+    }
+
     /// Push a function definition
     pub fn push_fn(&mut self, item: Function) -> &mut Self {
         self.items.push(Item::Function(item));
         self
     }
 
+    /// Returns a reference to a function if it exists in this scope.
+    pub fn get_fn<Q: ?Sized>(&self, name: &Q) -> Option<&Function>
+    where
+        str: PartialEq<Q>,
+    {
+        self.items
+            .iter()
+            .filter_map(|item| match *item {
+                Item::Function(ref v) if *v.name() == *name => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Returns the names of the functions of this scope, in order.
+    pub(crate) fn fn_names(&self) -> Vec<&str> {
+        self.items
+            .iter()
+            .filter_map(|item| match *item {
+                Item::Function(ref v) => Some(v.name()),
+                _ => None,
+            })
+            .collect()
+    }
+
+    /// Returns a mutable reference to a function if it exists in this scope.
+    pub fn get_fn_mut<Q: ?Sized>(&mut self, name: &Q) -> Option<&mut Function>
+    where
+        str: PartialEq<Q>,
+    {
+        self.items
+            .iter_mut()
+            .filter_map(|item| match *item {
+                Item::Function(ref mut v) if *v.name() == *name => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
     /// Push a new trait definition, returning a mutable reference to it.
-    pub fn new_trait(&mut self, name: &str) -> &mut Trait {
+    pub fn new_trait<T>(&mut self, name: T) -> &mut Trait
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.push_trait(Trait::new(name));
 
         match *self.items.last_mut().unwrap() {
@@ -178,8 +970,39 @@ impl Scope {
         self
     }
 
+    /// Returns a reference to a trait if it exists in this scope.
+    pub fn get_trait<Q: ?Sized>(&self, name: &Q) -> Option<&Trait>
+    where
+        str: PartialEq<Q>,
+    {
+        self.items
+            .iter()
+            .filter_map(|item| match *item {
+                Item::Trait(ref v) if *v.ty().name() == *name => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Returns a mutable reference to a trait if it exists in this scope.
+    pub fn get_trait_mut<Q: ?Sized>(&mut self, name: &Q) -> Option<&mut Trait>
+    where
+        str: PartialEq<Q>,
+    {
+        self.items
+            .iter_mut()
+            .filter_map(|item| match *item {
+                Item::Trait(ref mut v) if *v.ty().name() == *name => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
     /// Push a new struct definition, returning a mutable reference to it.
-    pub fn new_enum(&mut self, name: &str) -> &mut Enum {
+    pub fn new_enum<T>(&mut self, name: T) -> &mut Enum
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.push_enum(Enum::new(name));
 
         match *self.items.last_mut().unwrap() {
@@ -194,10 +1017,38 @@ impl Scope {
         self
     }
 
-    /// Push a new `impl` block, returning a mutable reference to it.
-    pub fn new_impl(&mut self, target: &str) -> &mut Impl {
-        self.push_impl(Impl::new(target));
-
+    /// Returns a reference to an enum if it exists in this scope.
+    pub fn get_enum<Q: ?Sized>(&self, name: &Q) -> Option<&Enum>
+    where
+        str: PartialEq<Q>,
+    {
+        self.items
+            .iter()
+            .filter_map(|item| match *item {
+                Item::Enum(ref v) if *v.ty().name() == *name => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Returns a mutable reference to an enum if it exists in this scope.
+    pub fn get_enum_mut<Q: ?Sized>(&mut self, name: &Q) -> Option<&mut Enum>
+    where
+        str: PartialEq<Q>,
+    {
+        self.items
+            .iter_mut()
+            .filter_map(|item| match *item {
+                Item::Enum(ref mut v) if *v.ty().name() == *name => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Push a new `impl` block, returning a mutable reference to it.
+    pub fn new_impl(&mut self, target: &str) -> &mut Impl {
+        self.push_impl(Impl::new(target));
+
         match *self.items.last_mut().unwrap() {
             Item::Impl(ref mut v) => v,
             _ => unreachable!(),
@@ -210,30 +1061,1104 @@ impl Scope {
         self
     }
 
-    /// Push a raw string to the scope.
+    /// Returns a reference to an `impl` block if it exists in this scope.
+    ///
+    /// If there are multiple `impl` blocks for the target, the first one is
+    /// returned.
+    pub fn get_impl<Q: ?Sized>(&self, target: &Q) -> Option<&Impl>
+    where
+        str: PartialEq<Q>,
+    {
+        self.items
+            .iter()
+            .filter_map(|item| match *item {
+                Item::Impl(ref v) if *v.target().name() == *target => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Returns a mutable reference to an `impl` block if it exists in this scope.
+    ///
+    /// If there are multiple `impl` blocks for the target, the first one is
+    /// returned.
+    pub fn get_impl_mut<Q: ?Sized>(&mut self, target: &Q) -> Option<&mut Impl>
+    where
+        str: PartialEq<Q>,
+    {
+        self.items
+            .iter_mut()
+            .filter_map(|item| match *item {
+                Item::Impl(ref mut v) if *v.target().name() == *target => Some(v),
+                _ => None,
+            })
+            .next()
+    }
+
+    /// Push a new static, returning a mutable reference to it.
+    pub fn new_static<N, T, V>(&mut self, name: N, ty: T, value: V) -> &mut Static
+    where
+        N: Into<Cow<'static, str>>,
+        T: Into<Type>,
+        V: Into<Cow<'static, str>>,
+    {
+        self.push_static(Static::new(name, ty, value));
+
+        match *self.items.last_mut().unwrap() {
+            Item::Static(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Push a static.
+    pub fn push_static(&mut self, item: Static) -> &mut Self {
+        self.items.push(Item::Static(item));
+        self
+    }
+
+    /// Push a new `extern` block, returning a mutable reference to it.
+    pub fn new_extern_block<T>(&mut self, abi: T) -> &mut ExternBlock
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.push_extern_block(ExternBlock::new(abi));
+
+        match *self.items.last_mut().unwrap() {
+            Item::ExternBlock(ref mut v) => v,
+            _ => unreachable!(),
+        }
+    }
+
+    /// Push an `extern` block.
+    pub fn push_extern_block(&mut self, item: ExternBlock) -> &mut Self {
+        self.items.push(Item::ExternBlock(item));
+        self
+    }
+
+    /// Push raw source code to the scope, after the items added so far.
+    ///
+    /// The text is included verbatim, with every line indented to the
+    /// level of the scope (e.g. when the scope belongs to a module). Like
+    /// other items, it's separated from its neighbours by a blank line.
+    ///
+    /// # Arguments
+    ///
+    /// * `val` - The source code to push.
+    ///
+    /// # Examples
     ///
-    /// This string will be included verbatim in the formatted string.
-    pub fn raw(&mut self, val: &str) -> &mut Self {
-        self.items.push(Item::Raw(val.to_string()));
+    /// ```
+    /// use rust_codegen::Scope;
+    ///
+    /// let mut scope = Scope::new();
+    /// scope.new_struct("Foo");
+    /// scope.raw("const BAR: u8 = 1;");
+    ///
+    /// assert_eq!(scope.to_string(), "struct Foo;\n\nconst BAR: u8 = 1;");
+    /// ```
+    pub fn raw<T>(&mut self, val: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.items.push(Item::Raw(val.into()));
         self
     }
 
+    /// Push a `global_asm!` invocation to the scope. An `asm!` or
+    /// `naked_asm!` invocation makes formatting the scope fail, as it has to
+    /// be pushed to a function.
+    pub fn push_asm(&mut self, item: Asm) -> &mut Self {
+        self.items.push(Item::Asm(item));
+        self
+    }
+
+    /// Push a custom item to the scope, after the items added so far.
+    pub fn push_custom<T>(&mut self, item: T) -> &mut Self
+    where
+        T: FormatItem + 'static,
+    {
+        self.items.push(Item::Custom(Box::new(item)));
+        self
+    }
+
+    /// Applies the given async trait strategy to every trait and impl block
+    /// in the scope, including those in nested modules.
+    ///
+    /// With [`AsyncTraitStrategy::AsyncTrait`], `#[async_trait]` is added to
+    /// every trait containing async functions and to every impl block
+    /// implementing such a trait. With [`AsyncTraitStrategy::Native`], any
+    /// existing `#[async_trait]` is removed instead.
+    ///
+    /// Traits are matched by the last segment of their path, so an impl
+    /// block of `storage::Store` is rewritten along with a `Store` trait
+    /// from any module. Function signatures and bodies are left unchanged,
+    /// as both strategies accept the same `async fn` bodies.
+    ///
+    /// This should be called once all of the items have been added.
+    pub fn apply_async_trait_strategy(&mut self, strategy: AsyncTraitStrategy) -> &mut Self {
+        let mut async_traits = vec![];
+        self.collect_async_traits(&mut async_traits);
+        self.apply_async_trait_strategy_with(strategy, &async_traits);
+        self
+    }
+
+    /// Collects the names of every trait that contains async functions.
+    fn collect_async_traits(&self, async_traits: &mut Vec<String>) {
+        for item in &self.items {
+            match *item {
+                Item::Module(ref v) => v.scope.collect_async_traits(async_traits),
+                Item::Trait(ref v) if v.has_async_fns() => {
+                    async_traits.push(trait_ident(v.ty().name()).to_string());
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Applies the async trait strategy using the collected async traits.
+    fn apply_async_trait_strategy_with(
+        &mut self,
+        strategy: AsyncTraitStrategy,
+        async_traits: &[String],
+    ) {
+        for item in self.items.iter_mut() {
+            match *item {
+                Item::Module(ref mut v) => {
+                    v.scope.apply_async_trait_strategy_with(strategy, async_traits)
+                }
+                Item::Trait(ref mut v) => v.apply_async_trait_strategy(strategy),
+                Item::Impl(ref mut v) => v.apply_async_trait_strategy(strategy, async_traits),
+                _ => {}
+            }
+        }
+    }
+
+    /// Copies the signature of every trait function in the scope, including
+    /// those in nested modules, to the functions implementing it, so that a
+    /// signature shared with [`FnSig`] only has to be edited on the trait.
+    /// The bodies, docs, and attributes of the impl functions are kept.
+    ///
+    /// Impl blocks are matched to traits by the last segment of the trait's
+    /// path, and signatures are only copied to functions of the same name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{FnSig, Scope};
+    ///
+    /// let mut get_sig = FnSig::new("get");
+    /// get_sig.arg_ref_self().ret("u32");
+    ///
+    /// let mut scope = Scope::new();
+    /// scope.new_trait("Store").push_sig(get_sig.clone());
+    /// scope.new_impl("Memory").impl_trait("Store").new_fn_from_sig(get_sig).line("0");
+    ///
+    /// scope.get_trait_mut("Store").unwrap().get_fn_mut("get").unwrap().ret("u64");
+    /// scope.sync_trait_sigs();
+    ///
+    /// assert!(scope.to_string().contains("fn get(&self) -> u64 {"));
+    /// ```
+    pub fn sync_trait_sigs(&mut self) -> &mut Self {
+        let mut sigs = vec![];
+        self.collect_trait_sigs(&mut sigs);
+        self.sync_trait_sigs_with(&sigs);
+        self
+    }
+
+    /// Collects the signatures of every trait function along with the name
+    /// of its trait.
+    fn collect_trait_sigs(&self, sigs: &mut Vec<(String, FnSig)>) {
+        for item in &self.items {
+            match *item {
+                Item::Module(ref v) => v.scope.collect_trait_sigs(sigs),
+                Item::Trait(ref v) => {
+                    let name = trait_ident(v.ty().name());
+
+                    for func in v.fns() {
+                        sigs.push((name.to_string(), func.sig().clone()));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Copies the collected trait signatures to the impl functions.
+    fn sync_trait_sigs_with(&mut self, sigs: &[(String, FnSig)]) {
+        for item in self.items.iter_mut() {
+            match *item {
+                Item::Module(ref mut v) => v.scope.sync_trait_sigs_with(sigs),
+                Item::Impl(ref mut v) => v.sync_sigs(sigs),
+                _ => {}
+            }
+        }
+    }
+
+    /// Checks every `extern "C"` function in the scope, including those in
+    /// nested modules, and returns a description of each argument or return
+    /// type that does not look FFI-safe.
+    ///
+    /// Primitives, `c_*` types, raw pointers, `extern "C"` function pointers,
+    /// `NonNull`, nullable pointer-like `Option`s, and the structs and enums
+    /// of the scope marked `#[repr(C)]` or `#[repr(transparent)]` are
+    /// considered FFI-safe. An empty list means that no problems were found.
+    pub fn check_ffi(&self) -> Vec<String> {
+        let mut repr_c = vec![];
+        self.collect_repr_c(&mut repr_c);
+
+        let mut violations = vec![];
+
+        for func in self.extern_c_fns() {
+            violations.extend(func.ffi_violations(&repr_c));
+        }
+
+        violations
+    }
+
+    /// Checks every trait in the scope marked with [`Trait::dyn_compatible`],
+    /// including those in nested modules, and returns a description of each
+    /// construct that prevents using it as a `dyn` trait object, e.g.
+    /// generic methods, methods returning `Self`, and associated constants.
+    /// An empty list means that no problems were found.
+    pub fn check_object_safety(&self) -> Vec<String> {
+        let mut violations = vec![];
+
+        for item in &self.items {
+            match *item {
+                Item::Module(ref v) => violations.extend(v.scope.check_object_safety()),
+                Item::Trait(ref v) if v.is_dyn_compatible() => {
+                    violations.extend(v.object_safety_violations())
+                }
+                _ => {}
+            }
+        }
+
+        violations
+    }
+
+    /// Checks every function in the scope marked with
+    /// [`Function::set_const`], including those in impls and nested modules,
+    /// and returns a description of each construct that isn't allowed in a
+    /// `const fn`, e.g. `to_string` calls and heap allocations. The body is
+    /// checked heuristically, so an empty list means that no problems were
+    /// found rather than that the functions compile.
+    pub fn check_const(&self) -> Vec<String> {
+        let mut violations = vec![];
+        self.collect_const_violations("", &mut violations);
+        violations
+    }
+
+    /// Collects the `const fn` violations of the functions, prefixing their
+    /// paths with the given module path.
+    fn collect_const_violations(&self, prefix: &str, violations: &mut Vec<String>) {
+        for item in &self.items {
+            match *item {
+                Item::Module(ref v) => v
+                    .scope
+                    .collect_const_violations(&format!("{}{}::", prefix, v.name), violations),
+                Item::Function(ref v) => {
+                    let path = format!("{}{}", prefix, v.name());
+                    violations.extend(v.const_violations(&path, false));
+                }
+                Item::Impl(ref v) => {
+                    for func in v.fns() {
+                        let path = format!("{}{}::{}", prefix, v.target().name(), func.name());
+                        violations.extend(func.const_violations(&path, v.is_trait_impl()));
+                    }
+                }
+                Item::Trait(ref v) => {
+                    for func in v.fns() {
+                        let path = format!("{}{}::{}", prefix, v.type_def().ty.name(), func.name());
+                        violations.extend(func.const_violations(&path, true));
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Checks every impl block in the scope, including those in nested
+    /// modules, and returns a description of each generic that is used by
+    /// neither the trait nor the target, and of each lifetime that they use
+    /// without it being declared. An empty list means that no problems were
+    /// found.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Scope;
+    ///
+    /// let mut scope = Scope::new();
+    /// scope
+    ///     .new_impl("Wrapper")
+    ///     .generic("T")
+    ///     .impl_trait("Display");
+    ///
+    /// assert_eq!(scope.check_impl_generics().len(), 1);
+    /// ```
+    pub fn check_impl_generics(&self) -> Vec<String> {
+        let mut violations = vec![];
+        self.collect_impl_generics("", &mut violations);
+        violations
+    }
+
+    /// Collects the generic violations of the impl blocks, prefixing their
+    /// paths with the given module path.
+    fn collect_impl_generics(&self, prefix: &str, violations: &mut Vec<String>) {
+        for item in &self.items {
+            match *item {
+                Item::Module(ref v) => v
+                    .scope
+                    .collect_impl_generics(&format!("{}{}::", prefix, v.name), violations),
+                Item::Impl(ref v) => {
+                    let path = format!("{}{}", prefix, v.target().name());
+                    violations.extend(v.generic_violations(&path));
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Checks every trait in the scope, including those in nested modules,
+    /// and returns a description of each function that has a visibility,
+    /// which is dropped when rendered or fails formatting with
+    /// [`TraitFnVis::Error`]. An empty list means that no problems were
+    /// found.
+    ///
+    /// [`TraitFnVis::Error`]: crate::TraitFnVis::Error
+    pub fn check_trait_fn_vis(&self) -> Vec<String> {
+        let mut violations = vec![];
+        self.collect_trait_fn_vis("", &mut violations);
+        violations
+    }
+
+    /// Collects the trait functions with a visibility, prefixing their paths
+    /// with the given module path.
+    fn collect_trait_fn_vis(&self, prefix: &str, violations: &mut Vec<String>) {
+        for item in &self.items {
+            match *item {
+                Item::Module(ref v) => v
+                    .scope
+                    .collect_trait_fn_vis(&format!("{}{}::", prefix, v.name), violations),
+                Item::Trait(ref v) => {
+                    for func in v.fns() {
+                        if let Some(vis) = func.get_vis() {
+                            violations.push(format!(
+                                "`{}{}::{}` has visibility `{}` but trait fns can't have one",
+                                prefix,
+                                v.type_def().ty.name(),
+                                func.name(),
+                                vis
+                            ));
+                        }
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns an `extern "C"` block declaring every `extern "C"` function
+    /// in the scope, including those in nested modules, e.g. for a generated
+    /// C consumer stub.
+    pub fn ffi_extern_block(&self) -> ExternBlock {
+        let mut block = ExternBlock::new("C");
+
+        for func in self.extern_c_fns() {
+            block.push_fn(func.clone());
+        }
+
+        block
+    }
+
+    /// Collects the names of every `#[repr(C)]` struct and enum.
+    fn collect_repr_c(&self, repr_c: &mut Vec<String>) {
+        for item in &self.items {
+            match *item {
+                Item::Module(ref v) => v.scope.collect_repr_c(repr_c),
+                Item::Struct(ref v) if v.is_repr_c() => repr_c.push(v.ty().name().to_string()),
+                Item::Enum(ref v) if v.is_repr_c() => repr_c.push(v.ty().name().to_string()),
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns every `extern "C"` function.
+    fn extern_c_fns(&self) -> Vec<&Function> {
+        let mut fns = vec![];
+
+        for item in &self.items {
+            match *item {
+                Item::Module(ref v) => fns.extend(v.scope.extern_c_fns()),
+                Item::Function(ref v) if v.get_extern_abi() == Some("C") => fns.push(v),
+                _ => {}
+            }
+        }
+
+        fns
+    }
+
+    /// Normalize the output of the scope for reproducible builds: line
+    /// endings, e.g. of raw code written on Windows, are rendered as `\n`,
+    /// trailing whitespace is removed, and the absolute paths of the build
+    /// in comments, e.g. `OUT_DIR` or the manifest directory in a provenance
+    /// comment, are made relative. Rendering panics when a comment contains
+    /// a timestamp.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Scope;
+    /// 
+    /// let mut scope = Scope::new();
+    /// scope.deterministic();
+    /// scope.raw("// Generated from schema.json  \r\nconst VERSION: u32 = 1;");
+    /// 
+    /// assert_eq!(scope.to_string(), "// Generated from schema.json\nconst VERSION: u32 = 1;");
+    /// ```
+    pub fn deterministic(&mut self) -> &mut Self {
+        self.deterministic = true;
+        self
+    }
+
+    /// Set how the function bodies of the scope and its modules are rendered,
+    /// e.g. to generate a mock crate with `unimplemented!()` bodies or
+    /// header-like output without bodies. A module's own mode replaces the
+    /// mode of its parent.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `mode` - How to render function bodies.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{BodyMode, Scope};
+    /// 
+    /// let mut scope = Scope::new();
+    /// scope.new_fn("load").ret("String").line("std::fs::read_to_string(\"a\").unwrap()");
+    /// scope.body_mode(BodyMode::Unimplemented);
+    /// 
+    /// assert_eq!(scope.to_string(), "fn load() -> String {\n    unimplemented!()\n}");
+    /// 
+    /// scope.body_mode(BodyMode::Omit);
+    /// assert_eq!(scope.to_string(), "fn load() -> String;");
+    /// ```
+    pub fn body_mode(&mut self, mode: BodyMode) -> &mut Self {
+        self.body_mode = Some(mode);
+        self
+    }
+
+    /// Set the layout of the `where` clauses of the scope and its modules,
+    /// e.g. to match the output of rustfmt. A module's own style replaces
+    /// the style of its parent.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `style` - The layout of `where` clauses.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Scope, WhereStyle};
+    /// 
+    /// let mut scope = Scope::new();
+    /// scope.new_fn("show").generic("T").arg("t", "T").bound("T", "Display");
+    /// scope.where_style(WhereStyle::SameLine);
+    /// 
+    /// assert_eq!(scope.to_string(), "fn show<T>(t: T) where T: Display {\n}");
+    /// ```
+    pub fn where_style(&mut self, style: WhereStyle) -> &mut Self {
+        self.where_style = Some(style);
+        self
+    }
+
+    /// Set whether the last field, variant, or `where` bound of a list in the
+    /// scope and its modules is followed by a comma, which is the default.
+    /// A module's own setting replaces the setting of its parent.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `trailing_comma` - Whether to write trailing commas.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Scope;
+    /// 
+    /// let mut scope = Scope::new();
+    /// scope.new_struct("Point").field("x", "i32").field("y", "i32");
+    /// scope.trailing_comma(false);
+    /// 
+    /// assert_eq!(scope.to_string(), "struct Point {\n    x: i32,\n    y: i32\n}");
+    /// ```
+    pub fn trailing_comma(&mut self, trailing_comma: bool) -> &mut Self {
+        self.trailing_comma = Some(trailing_comma);
+        self
+    }
+
+    /// Set whether the opening braces of the scope and its modules are
+    /// preceded by a space, e.g. `Foo {` rather than `Foo{`, which is the
+    /// default. A module's own setting replaces the setting of its parent.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `space` - Whether to write a space before opening braces.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Scope;
+    /// 
+    /// let mut scope = Scope::new();
+    /// scope.new_struct("Point").field("x", "i32");
+    /// scope.space_before_brace(false);
+    /// 
+    /// assert_eq!(scope.to_string(), "struct Point{\n    x: i32,\n}");
+    /// ```
+    pub fn space_before_brace(&mut self, space: bool) -> &mut Self {
+        self.space_before_brace = Some(space);
+        self
+    }
+
+    /// Set the width beyond which the use trees and derive lists of the scope
+    /// and its modules are wrapped with one item per line, including the
+    /// indentation. They are never wrapped by default. A module's own width
+    /// replaces the width of its parent.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `width` - The maximum width of a line, e.g. `100` like rustfmt.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Scope;
+    /// 
+    /// let mut scope = Scope::new();
+    /// scope.import("std::collections", "HashMap");
+    /// scope.import("std::collections", "BTreeMap");
+    /// scope.max_width(30);
+    /// 
+    /// assert!(scope
+    ///     .to_string()
+    ///     .starts_with("use std::collections::{\n    HashMap,\n    BTreeMap,\n};"));
+    /// ```
+    pub fn max_width(&mut self, width: usize) -> &mut Self {
+        self.max_width = Some(width);
+        self
+    }
+
+    /// Set whether the raw items of the scope and its modules are preceded by
+    /// `#[rustfmt::skip]`, so that formatting passes keep the layout of e.g.
+    /// generated tables. Each raw item must then be a single item, and the
+    /// ones that already start with `#[rustfmt::skip]` are kept as is. A
+    /// module's own setting replaces the setting of its parent.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `skip` - Whether to protect raw items from rustfmt.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Scope;
+    /// 
+    /// let mut scope = Scope::new();
+    /// scope.raw("const PRIMES: [u8; 4] = [\n    2,  3,\n    5,  7,\n];");
+    /// scope.rustfmt_skip_raw(true);
+    /// 
+    /// assert_eq!(
+    ///     scope.to_string(),
+    ///     "#[rustfmt::skip]\nconst PRIMES: [u8; 4] = [\n    2,  3,\n    5,  7,\n];"
+    /// );
+    /// ```
+    pub fn rustfmt_skip_raw(&mut self, skip: bool) -> &mut Self {
+        self.rustfmt_skip_raw = Some(skip);
+        self
+    }
+
+    /// Returns the given rendered output of the scope, normalized if the
+    /// scope is deterministic.
+    fn finish(&self, rendered: String) -> String {
+        if self.deterministic {
+            normalize(&rendered)
+        } else {
+            rendered
+        }
+    }
+
     /// Return a string representation of the scope.
     pub fn to_string(&self) -> String {
+        let mut ret = String::with_capacity(self.estimated_len());
+        self.render_into(&mut ret);
+        ret
+    }
+
+    /// Renders the scope into the given buffer, replacing its contents, so
+    /// that a buffer can be reused to render many scopes without allocating
+    /// again once it is large enough.
+    ///
+    /// # Arguments
+    ///
+    /// * `buf` - The buffer to render the scope into.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Scope;
+    ///
+    /// let mut buf = String::new();
+    ///
+    /// for name in ["Foo", "Bar"] {
+    ///     let mut scope = Scope::new();
+    ///     scope.new_struct(name);
+    ///
+    ///     scope.render_into(&mut buf);
+    ///     assert_eq!(buf, format!("struct {};", name));
+    /// }
+    /// ```
+    pub fn render_into(&self, buf: &mut String) {
+        buf.clear();
+
+        self.fmt(&mut Formatter::new(buf)).unwrap();
+
+        // Remove the trailing newline
+        if buf.as_bytes().last() == Some(&b'\n') {
+            buf.pop();
+        }
+
+        if self.deterministic {
+            let normalized = normalize(buf);
+            buf.clear();
+            buf.push_str(&normalized);
+        }
+    }
+
+    /// Returns an estimate of the length of the rendered scope, computed
+    /// from the contents of its items without rendering them, e.g. to
+    /// reserve the capacity of a buffer. `to_string` reserves it already.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Scope;
+    ///
+    /// let mut scope = Scope::new();
+    /// scope.new_fn("answer").ret("u32").line("42");
+    ///
+    /// assert!(scope.estimated_len() >= scope.to_string().len());
+    /// ```
+    pub fn estimated_len(&self) -> usize {
+        let docs = self.docs.as_ref().map_or(0, |docs| docs.estimated_len());
+        let inner_attributes: usize = self.inner_attributes.iter().map(|attr| attr.len() + 5).sum();
+
+        let imports: usize = self
+            .imports
+            .iter()
+            .map(|(path, tys)| {
+                let tys: usize = tys.keys().map(|ty| ty.len() + 2).sum();
+                path.len() + tys + 12
+            })
+            .sum();
+
+        // Every item is separated from the previous one by a blank line.
+        let items: usize = self.items.iter().map(|item| item.estimated_len() + 1).sum();
+
+        docs + inner_attributes + imports + items
+    }
+
+    /// Return a string representation of the scope rendered with a fresh
+    /// formatter configured by the given function, e.g. to render the same
+    /// scope for several targets without cloning it. The scope's own style
+    /// options still take precedence over the formatter's.
+    ///
+    /// # Arguments
+    ///
+    /// * `configure` - The function configuring the formatter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{BodyMode, Scope};
+    ///
+    /// let mut scope = Scope::new();
+    /// scope.new_fn("answer").ret("u32").line("42");
+    ///
+    /// let stubs = scope.to_string_with(|fmt| {
+    ///     fmt.set_body_mode(BodyMode::Unimplemented);
+    /// });
+    ///
+    /// assert_eq!(stubs, "fn answer() -> u32 {\n    unimplemented!()\n}");
+    /// assert_eq!(scope.to_string(), "fn answer() -> u32 {\n    42\n}");
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics if formatting fails, e.g. with [`TraitFnVis::Error`] when a
+    /// trait function has a visibility. Use [`Scope::try_to_string_with`] to
+    /// get the error instead.
+    ///
+    /// [`TraitFnVis::Error`]: crate::TraitFnVis::Error
+    pub fn to_string_with<F>(&self, configure: F) -> String
+    where
+        F: FnOnce(&mut Formatter<'_>),
+    {
+        self.finish(fmt_to_code_string(|fmt| {
+            configure(fmt);
+            self.fmt(fmt)
+        }))
+    }
+
+    /// Return a string representation of the scope rendered with a fresh
+    /// formatter configured by the given function, or the error if the
+    /// formatting fails, e.g. with [`TraitFnVis::Error`].
+    ///
+    /// [`TraitFnVis::Error`]: crate::TraitFnVis::Error
+    ///
+    /// # Arguments
+    ///
+    /// * `configure` - The function configuring the formatter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Scope, TraitFnVis};
+    ///
+    /// let mut scope = Scope::new();
+    /// scope.new_trait("Foo").new_fn("bar").vis("pub");
+    ///
+    /// let rendered = scope.try_to_string_with(|fmt| {
+    ///     fmt.set_trait_fn_vis(TraitFnVis::Error);
+    /// });
+    ///
+    /// assert!(rendered.is_err());
+    /// ```
+    pub fn try_to_string_with<F>(&self, configure: F) -> Result<String, fmt::Error>
+    where
+        F: FnOnce(&mut Formatter<'_>),
+    {
+        let rendered = try_fmt_to_code_string(|fmt| {
+            configure(fmt);
+            self.fmt(fmt)
+        })?;
+
+        Ok(self.finish(rendered))
+    }
+
+    /// Returns statistics about the items of the scope and its modules: the
+    /// number of items per kind, the rendered length, the number of public
+    /// and private items, and the public items without documentation.
+    pub fn stats(&self) -> ScopeStats {
+        let mut stats = ScopeStats::default();
+        self.collect_stats("", &mut stats);
+        stats.rendered_len = self.to_string().len();
+        stats
+    }
+
+    /// Collects the statistics of the items, prefixing their paths with the
+    /// given module path.
+    fn collect_stats(&self, prefix: &str, stats: &mut ScopeStats) {
+        for item in &self.items {
+            *stats.items.entry(item.kind()).or_insert(0) += 1;
+
+            match *item {
+                Item::Module(ref v) => {
+                    let path = format!("{}{}", prefix, v.name);
+
+                    stats.record_vis(path.clone(), v.get_vis(), v.has_docs());
+                    v.scope.collect_stats(&format!("{}::", path), stats);
+                }
+                Item::Struct(ref v) => {
+                    let def = v.type_def();
+                    let path = format!("{}{}", prefix, def.ty.name());
+                    stats.record_vis(path, def.get_vis(), def.has_docs());
+                }
+                Item::Enum(ref v) => {
+                    let def = v.type_def();
+                    let path = format!("{}{}", prefix, def.ty.name());
+                    stats.record_vis(path, def.get_vis(), def.has_docs());
+                }
+                Item::Trait(ref v) => {
+                    let def = v.type_def();
+                    let path = format!("{}{}", prefix, def.ty.name());
+                    stats.record_vis(path, def.get_vis(), def.has_docs());
+                }
+                Item::Function(ref v) => {
+                    let path = format!("{}{}", prefix, v.name());
+                    stats.record_vis(path, v.get_vis(), v.has_docs());
+                }
+                // Trait impl methods are documented by the trait.
+                Item::Impl(ref v) if !v.is_trait_impl() => {
+                    for func in v.fns() {
+                        let path = format!("{}{}::{}", prefix, v.target().name(), func.name());
+                        stats.record_vis(path, func.get_vis(), func.has_docs());
+                    }
+                }
+                _ => {}
+            }
+        }
+    }
+
+    /// Returns the item-level changes from this scope to the given one, e.g.
+    /// between the outputs of two versions of a schema. Items are matched by
+    /// kind and path, including the items of nested modules, and are changed
+    /// when they render differently. Modules are compared by their
+    /// declarations, their items are compared separately. Imports, raw code,
+    /// custom items, and assembly are not compared.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The new scope.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{ChangeKind, Scope};
+    ///
+    /// let mut old = Scope::new();
+    /// old.new_struct("User").field("name", "String");
+    ///
+    /// let mut new = Scope::new();
+    /// new.new_struct("User").field("name", "String").field("age", "u32");
+    /// new.new_fn("login");
+    ///
+    /// let diff = old.diff(&new);
+    /// let changes: Vec<(ChangeKind, &str, &str)> = diff
+    ///     .changes
+    ///     .iter()
+    ///     .map(|c| (c.change, c.kind, &c.path[..]))
+    ///     .collect();
+    ///
+    /// assert_eq!(
+    ///     changes,
+    ///     vec![
+    ///         (ChangeKind::Changed, "struct", "User"),
+    ///         (ChangeKind::Added, "fn", "login"),
+    ///     ]
+    /// );
+    /// ```
+    pub fn diff(&self, other: &Scope) -> ScopeDiff {
+        let mut before = vec![];
+        self.collect_diff_items("", true, None, &mut before);
+
+        let mut after = vec![];
+        other.collect_diff_items("", true, None, &mut after);
+
+        let mut diff = ScopeDiff::default();
+
+        for old in &before {
+            let new = after
+                .iter()
+                .find(|new| new.kind == old.kind && new.path == old.path && new.nth == old.nth);
+
+            match new {
+                Some(new) if new.rendered == old.rendered => {}
+                Some(new) => diff.changes.push(ItemChange {
+                    change: ChangeKind::Changed,
+                    kind: old.kind,
+                    path: old.path.clone(),
+                    before: Some(old.rendered.clone()),
+                    after: Some(new.rendered.clone()),
+                    before_api: Some(old.api.clone()),
+                    after_api: Some(new.api.clone()),
+                }),
+                None => diff.changes.push(ItemChange {
+                    change: ChangeKind::Removed,
+                    kind: old.kind,
+                    path: old.path.clone(),
+                    before: Some(old.rendered.clone()),
+                    after: None,
+                    before_api: Some(old.api.clone()),
+                    after_api: None,
+                }),
+            }
+        }
+
+        for new in after {
+            let exists = before
+                .iter()
+                .any(|old| old.kind == new.kind && old.path == new.path && old.nth == new.nth);
+
+            if !exists {
+                diff.changes.push(ItemChange {
+                    change: ChangeKind::Added,
+                    kind: new.kind,
+                    path: new.path,
+                    before: None,
+                    after: Some(new.rendered),
+                    before_api: None,
+                    after_api: Some(new.api),
+                });
+            }
+        }
+
+        diff
+    }
+
+    /// Collects the named items of the scope and its modules rendered on
+    /// their own, prefixing their paths with the given module path.
+    fn collect_diff_items(
+        &self,
+        prefix: &str,
+        public: bool,
+        inherited: Option<&Policy>,
+        items: &mut Vec<DiffItem>,
+    ) {
+        let policy = self.policy.as_ref().or(inherited);
+
+        for item in &self.items {
+            let name = item.name();
+
+            if name.is_empty() {
+                continue;
+            }
+
+            let path = format!("{}{}", prefix, name);
+            let nth = items
+                .iter()
+                .filter(|other| other.kind == item.kind() && other.path == path)
+                .count();
+
+            let mut single = Scope::new();
+            single.policy = policy.cloned();
+
+            match *item {
+                Item::Module(ref v) => {
+                    let mut declaration = v.clone();
+                    declaration.scope = Scope::new();
+                    single.items.push(Item::Module(declaration));
+                }
+                _ => single.items.push(item.clone()),
+            }
+
+            let api = ApiShape::of(item, public);
+            let module_public = api.is_public();
+
+            items.push(DiffItem {
+                kind: item.kind(),
+                path: path.clone(),
+                nth,
+                rendered: single.to_string(),
+                api,
+            });
+
+            if let Item::Module(ref v) = *item {
+                let prefix = format!("{}::", path);
+                v.scope.collect_diff_items(&prefix, module_public, policy, items);
+            }
+        }
+    }
+
+    /// Return a string representation of the scope, calling the given hooks
+    /// before and after each item of the scope and its modules.
+    pub fn to_string_with_hook(&self, hook: &mut dyn RenderHook) -> String {
         let mut ret = String::new();
 
-        self.fmt(&mut Formatter::new(&mut ret)).unwrap();
+        {
+            let mut fmt = Formatter::new(&mut ret);
+            fmt.set_hook(hook);
+
+            self.fmt(&mut fmt).unwrap();
+        }
 
         // Remove the trailing newline
         if ret.as_bytes().last() == Some(&b'\n') {
             ret.pop();
         }
 
-        ret
+        self.finish(ret)
+    }
+
+    /// Return a string representation of the scope along with a source map
+    /// of the lines produced by each item, including the items of nested
+    /// modules, e.g. to translate the lines of compiler errors back to the
+    /// items that produced them.
+    pub fn to_string_with_source_map(&self) -> (String, SourceMap) {
+        let mut ret = String::new();
+
+        let source_map = {
+            let mut fmt = Formatter::new(&mut ret);
+            fmt.source_map = Some(SourceMap::new());
+
+            self.fmt(&mut fmt).unwrap();
+            fmt.source_map.take().unwrap()
+        };
+
+        // Remove the trailing newline
+        if ret.as_bytes().last() == Some(&b'\n') {
+            ret.pop();
+        }
+
+        (self.finish(ret), source_map)
     }
 
     /// Formats the scope using the given formatter.
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_with_policy(None, fmt)
+    }
+
+    /// Formats the scope, applying its own policy or else the given policy of
+    /// the parent scope.
+    pub(crate) fn fmt_with_policy(
+        &self,
+        inherited: Option<&Policy>,
+        fmt: &mut Formatter<'_>,
+    ) -> fmt::Result {
+        let policy = self.policy.as_ref().or(inherited);
+        let inherited_body_mode = fmt.body_mode;
+        let inherited_where_style = fmt.where_style;
+        let inherited_trailing_comma = fmt.trailing_comma;
+        let inherited_space_before_brace = fmt.space_before_brace;
+        let inherited_max_width = fmt.max_width;
+        let inherited_rustfmt_skip_raw = fmt.rustfmt_skip_raw;
+
+        if let Some(mode) = self.body_mode {
+            fmt.body_mode = mode;
+        }
+
+        if let Some(style) = self.where_style {
+            fmt.where_style = style;
+        }
+
+        if let Some(trailing_comma) = self.trailing_comma {
+            fmt.trailing_comma = trailing_comma;
+        }
+
+        if let Some(space) = self.space_before_brace {
+            fmt.space_before_brace = space;
+        }
+
+        if self.max_width.is_some() {
+            fmt.max_width = self.max_width;
+        }
+
+        if let Some(skip) = self.rustfmt_skip_raw {
+            fmt.rustfmt_skip_raw = skip;
+        }
+
+        let ret = self.fmt_items(policy, fmt);
+        fmt.body_mode = inherited_body_mode;
+        fmt.where_style = inherited_where_style;
+        fmt.trailing_comma = inherited_trailing_comma;
+        fmt.space_before_brace = inherited_space_before_brace;
+        fmt.max_width = inherited_max_width;
+        fmt.rustfmt_skip_raw = inherited_rustfmt_skip_raw;
+        ret
+    }
+
+    /// Formats the inner attributes, imports, and items of the scope.
+    fn fmt_items(&self, policy: Option<&Policy>, fmt: &mut Formatter<'_>) -> fmt::Result {
+        for attr in &self.inner_attributes {
+            write!(fmt, "#![{}]\n", attr)?;
+        }
+
+        if !self.inner_attributes.is_empty()
+            && (!self.imports.is_empty() || !self.items.is_empty())
+        {
+            write!(fmt, "\n")?;
+        }
+
         self.fmt_imports(fmt)?;
 
         if !self.imports.is_empty() {
@@ -245,16 +2170,65 @@ impl Scope {
                 write!(fmt, "\n")?;
             }
 
+            if let Some(hook) = fmt.hook.take() {
+                let ret = hook.before_item(item.kind(), &item.name(), fmt);
+                fmt.hook = Some(hook);
+                ret?;
+            }
+
+            let span = match (fmt.source_map.is_some(), item.source_info()) {
+                (true, Some((kind, name, metadata))) => {
+                    let start_line = fmt.line_count() + 1;
+                    let metadata = metadata.cloned().unwrap_or_default();
+                    let map = fmt.source_map.as_mut().unwrap();
+
+                    Some(map.start(kind, &name, metadata, start_line))
+                }
+                _ => None,
+            };
+
             match *item {
-                Item::Module(ref v) => v.fmt(fmt)?,
-                Item::Struct(ref v) => v.fmt(fmt)?,
-                Item::Function(ref v) => v.fmt(false, fmt)?,
+                Item::Module(ref v) => {
+                    if let Some(ref mut map) = fmt.source_map {
+                        map.enter_module(&v.name);
+                    }
+
+                    v.fmt_with_policy(policy, fmt)?;
+
+                    if let Some(ref mut map) = fmt.source_map {
+                        map.leave_module();
+                    }
+                }
+                Item::Struct(ref v) => with_policy(v, policy, Struct::apply_policy).fmt(fmt)?,
+                Item::Function(ref v) => {
+                    with_policy(v, policy, Function::apply_policy).fmt(false, fmt)?
+                }
                 Item::Trait(ref v) => v.fmt(fmt)?,
-                Item::Enum(ref v) => v.fmt(fmt)?,
-                Item::Impl(ref v) => v.fmt(fmt)?,
+                Item::Enum(ref v) => with_policy(v, policy, Enum::apply_policy).fmt(fmt)?,
+                Item::Impl(ref v) => with_policy(v, policy, Impl::apply_policy).fmt(fmt)?,
+                Item::Static(ref v) => v.fmt(fmt)?,
+                Item::ExternBlock(ref v) => v.fmt(fmt)?,
+                Item::Asm(ref v) if !v.is_global() => return Err(fmt::Error),
+                Item::Asm(ref v) => v.fmt(fmt)?,
                 Item::Raw(ref v) => {
+                    if fmt.rustfmt_skip_raw && !v.starts_with(RUSTFMT_SKIP) {
+                        write!(fmt, "{}\n", RUSTFMT_SKIP)?;
+                    }
+
                     write!(fmt, "{}\n", v)?;
                 }
+                Item::Custom(ref v) => FormatItem::fmt(&**v, fmt)?,
+            }
+
+            if let Some(index) = span {
+                let end_line = fmt.line_count();
+                fmt.source_map.as_mut().unwrap().end(index, end_line);
+            }
+
+            if let Some(hook) = fmt.hook.take() {
+                let ret = hook.after_item(item.kind(), &item.name(), fmt);
+                fmt.hook = Some(hook);
+                ret?;
             }
         }
 
@@ -287,25 +2261,17 @@ impl Scope {
                 }
 
                 if !tys.is_empty() {
-                    if let Some(ref vis) = *vis {
-                        write!(fmt, "{} ", vis)?;
-                    }
-
-                    write!(fmt, "use {}::", path)?;
+                    let head = match *vis {
+                        Some(ref vis) => format!("{} use {}::", vis, path),
+                        None => format!("use {}::", path),
+                    };
 
                     if tys.len() > 1 {
-                        write!(fmt, "{{")?;
-
-                        for (i, ty) in tys.iter().enumerate() {
-                            if i != 0 {
-                                write!(fmt, ", ")?;
-                            }
-                            write!(fmt, "{}", ty)?;
-                        }
-
-                        write!(fmt, "}};\n")?;
-                    } else if tys.len() == 1 {
-                        write!(fmt, "{};\n", tys[0])?;
+                        // Long use trees are wrapped with one type per line.
+                        fmt.wrap_list(&format!("{}{{", head), &tys, ",", "};")?;
+                        write!(fmt, "\n")?;
+                    } else {
+                        write!(fmt, "{}{};\n", head, tys[0])?;
                     }
                 }
             }
@@ -314,3 +2280,38 @@ impl Scope {
         Ok(())
     }
 }
+
+/// Returns the item with the given policy applied, or the item itself if
+/// there is no policy.
+fn with_policy<'a, T>(
+    item: &'a T,
+    policy: Option<&Policy>,
+    apply: fn(&mut T, &Policy),
+) -> Cow<'a, T>
+where
+    T: Clone,
+{
+    match policy {
+        Some(policy) => {
+            let mut item = item.clone();
+            apply(&mut item, policy);
+            Cow::Owned(item)
+        }
+        None => Cow::Borrowed(item),
+    }
+}
+
+/// An item of a scope rendered on its own, compared by `Scope::diff`.
+struct DiffItem {
+    /// The kind of the item, e.g. `struct` or `fn`.
+    kind: &'static str,
+    /// The path of the item from the root of the scope.
+    path: String,
+    /// The number of earlier items with the same kind and path, e.g. for
+    /// several inherent impls of a type.
+    nth: usize,
+    /// The rendered item.
+    rendered: String,
+    /// The public API of the item.
+    api: ApiShape,
+}
@@ -0,0 +1,402 @@
+use std::fmt;
+
+use crate::diff::ScopeDiff;
+use crate::fields::Fields;
+use crate::item::Item;
+use crate::visibility::strip_vis;
+
+/// Defines the impact of a change on the version of a crate.
+///
+/// The impacts are ordered from the least to the most severe, so the impact
+/// of several changes is the maximum of their impacts.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SemverImpact {
+    /// The change doesn't affect the public API, e.g. a changed function
+    /// body or a private item.
+    Patch,
+    /// The change adds to the public API, e.g. a new public item.
+    Minor,
+    /// The change may break users of the public API, e.g. a removed public
+    /// item or a changed function signature.
+    Major,
+}
+
+impl fmt::Display for SemverImpact {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = match *self {
+            SemverImpact::Patch => "patch",
+            SemverImpact::Minor => "minor",
+            SemverImpact::Major => "major",
+        };
+
+        f.write_str(name)
+    }
+}
+
+/// Defines the impact of a single item change, see [`SemverReport`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SemverChange {
+    /// The kind of the item, e.g. `struct` or `fn`.
+    pub kind: &'static str,
+    /// The path of the item from the root of the scope, e.g.
+    /// `handlers::login`.
+    pub path: String,
+    /// The impact of the change.
+    pub impact: SemverImpact,
+    /// Why the change has its impact, e.g. `removed public item`.
+    pub reason: String,
+}
+
+/// Defines the semver impact of every change of a [`ScopeDiff`], returned
+/// by [`ScopeDiff::semver`].
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SemverReport {
+    /// The classified changes, in the order of the changes of the diff.
+    pub changes: Vec<SemverChange>,
+}
+
+impl SemverReport {
+    /// Returns the most severe impact of the changes, or `Patch` when there
+    /// are none, i.e. the part of the version to bump.
+    pub fn impact(&self) -> SemverImpact {
+        self.changes
+            .iter()
+            .map(|change| change.impact)
+            .max()
+            .unwrap_or(SemverImpact::Patch)
+    }
+
+    /// Returns the changes that may break users of the public API.
+    pub fn breaking(&self) -> impl Iterator<Item = &SemverChange> {
+        self.changes
+            .iter()
+            .filter(|change| change.impact == SemverImpact::Major)
+    }
+}
+
+impl fmt::Display for SemverReport {
+    /// Formats the report as one line per change, e.g.
+    /// `major fn api::login: changed signature`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for change in &self.changes {
+            writeln!(
+                f,
+                "{} {} {}: {}",
+                change.impact, change.kind, change.path, change.reason
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Defines the parts of an item that make up the public API, compared to
+/// classify a change.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct ApiShape {
+    /// Whether the item and its parent modules are `pub`.
+    public: bool,
+    /// The signature of the item without docs, attributes, or body, e.g.
+    /// `fn login(token: &str);`.
+    signature: String,
+    /// The name and signature of the public members of the item: the `pub`
+    /// fields of a struct, the variants of an enum, the methods of a trait,
+    /// and the `pub` methods of an inherent impl.
+    members: Vec<(String, String)>,
+    /// The names of the members that users must provide, i.e. the trait
+    /// methods without a default body.
+    required: Vec<String>,
+    /// Whether the struct or enum is `#[non_exhaustive]`.
+    non_exhaustive: bool,
+    /// Whether users can build the struct with a literal, i.e. every field is
+    /// `pub` and it isn't `#[non_exhaustive]`.
+    constructible: bool,
+}
+
+impl ApiShape {
+    /// Returns whether the item and its parent modules are `pub`.
+    pub(crate) fn is_public(&self) -> bool {
+        self.public
+    }
+
+    /// Returns the public API shape of the given item.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - The item.
+    /// * `parent_public` - Whether the parent modules of the item are `pub`.
+    pub(crate) fn of(item: &Item, parent_public: bool) -> ApiShape {
+        let mut shape = ApiShape {
+            public: parent_public,
+            signature: String::new(),
+            members: vec![],
+            required: vec![],
+            non_exhaustive: false,
+            constructible: false,
+        };
+
+        match *item {
+            Item::Module(ref v) => {
+                shape.public &= v.get_vis() == Some("pub");
+            }
+            Item::Function(ref v) => {
+                shape.public &= v.get_vis() == Some("pub");
+                shape.signature = v.signature();
+            }
+            Item::Static(ref v) => {
+                shape.public &= v.get_vis() == Some("pub");
+                shape.signature = v.signature();
+            }
+            Item::Struct(ref v) => {
+                let def = v.type_def();
+
+                shape.public &= def.get_vis() == Some("pub");
+                shape.signature = v.signature();
+                shape.non_exhaustive = def.attributes.has_named("non_exhaustive");
+
+                let (members, private) = match *v.fields() {
+                    Fields::Empty => (vec![], 0),
+                    Fields::Tuple(ref tys) => {
+                        let rendered: Vec<String> =
+                            tys.iter().map(|ty| ty.fmt_to_string()).collect();
+                        let members: Vec<(String, String)> = rendered
+                            .iter()
+                            .enumerate()
+                            .filter(|(_, ty)| ty.starts_with("pub "))
+                            .map(|(i, ty)| (i.to_string(), ty.clone()))
+                            .collect();
+                        let private = rendered.len() - members.len();
+
+                        (members, private)
+                    }
+                    Fields::Named(ref fields) => {
+                        let members: Vec<(String, String)> = fields
+                            .iter()
+                            .filter(|field| field.name.starts_with("pub "))
+                            .map(|field| {
+                                let name = strip_vis(&field.name).trim().to_string();
+                                let ty = field.ty.fmt_to_string();
+                                (name.clone(), format!("{}: {}", name, ty))
+                            })
+                            .collect();
+                        let private = fields.len() - members.len();
+
+                        (members, private)
+                    }
+                };
+
+                shape.members = members;
+                shape.constructible = private == 0 && !shape.non_exhaustive;
+            }
+            Item::Enum(ref v) => {
+                let def = v.type_def();
+
+                shape.public &= def.get_vis() == Some("pub");
+                shape.signature = v.signature();
+                shape.non_exhaustive = def.attributes.has_named("non_exhaustive");
+                shape.members = v
+                    .variants()
+                    .iter()
+                    .map(|variant| {
+                        let fields = fields_signature(variant.fields());
+                        (variant.name().to_string(), format!("{}{}", variant.name(), fields))
+                    })
+                    .collect();
+            }
+            Item::Trait(ref v) => {
+                shape.public &= v.type_def().get_vis() == Some("pub");
+                shape.signature = v.signature();
+                shape.members = v
+                    .fns()
+                    .iter()
+                    .map(|func| (func.name().to_string(), func.signature()))
+                    .collect();
+                shape.required = v
+                    .fns()
+                    .iter()
+                    .filter(|func| func.body.is_none())
+                    .map(|func| func.name().to_string())
+                    .collect();
+            }
+            Item::Impl(ref v) => {
+                // The methods of a trait impl are part of the trait's API.
+                if !v.is_trait_impl() {
+                    shape.members = v
+                        .fns()
+                        .iter()
+                        .filter(|func| func.get_vis() == Some("pub"))
+                        .map(|func| (func.name().to_string(), func.signature()))
+                        .collect();
+                }
+            }
+            Item::ExternBlock(..) | Item::Asm(..) | Item::Raw(..) | Item::Custom(..) => {}
+        }
+
+        shape
+    }
+}
+
+/// Returns the fields of a variant without their docs and attributes, e.g.
+/// `(u32, String)` or ` { id: u32 }`.
+fn fields_signature(fields: &Fields) -> String {
+    match *fields {
+        Fields::Empty => String::new(),
+        Fields::Tuple(ref tys) => {
+            let tys: Vec<String> = tys.iter().map(|ty| ty.fmt_to_string()).collect();
+            format!("({})", tys.join(", "))
+        }
+        Fields::Named(ref fields) => {
+            let fields: Vec<String> = fields
+                .iter()
+                .map(|field| format!("{}: {}", field.name, field.ty.fmt_to_string()))
+                .collect();
+            format!(" {{ {} }}", fields.join(", "))
+        }
+    }
+}
+
+/// Returns the impact of a change and why it has it.
+///
+/// # Arguments
+///
+/// * `kind` - The kind of the item, e.g. `struct` or `fn`.
+/// * `before` - The shape of the item in the old scope, if it exists there.
+/// * `after` - The shape of the item in the new scope, if it exists there.
+fn classify(
+    kind: &str,
+    before: Option<&ApiShape>,
+    after: Option<&ApiShape>,
+) -> (SemverImpact, String) {
+    use self::SemverImpact::*;
+
+    let (before, after) = match (before, after) {
+        (None, Some(after)) if after.public => return (Minor, "added public item".into()),
+        (Some(before), None) if before.public => return (Major, "removed public item".into()),
+        (Some(before), Some(after)) => (before, after),
+        _ => return (Patch, "private item".into()),
+    };
+
+    match (before.public, after.public) {
+        (true, false) => return (Major, "item is no longer public".into()),
+        (false, true) => return (Minor, "item is now public".into()),
+        (false, false) => return (Patch, "private item".into()),
+        (true, true) => {}
+    }
+
+    if before.signature != after.signature {
+        return (Major, "changed signature".into());
+    }
+
+    for (name, signature) in &before.members {
+        match after.members.iter().find(|(other, _)| other == name) {
+            None => return (Major, format!("removed `{}`", name)),
+            Some((_, other)) if other != signature => {
+                return (Major, format!("changed `{}`", name))
+            }
+            _ => {}
+        }
+    }
+
+    if !before.non_exhaustive && after.non_exhaustive {
+        return (Major, "added `#[non_exhaustive]`".into());
+    }
+
+    if before.constructible && !after.constructible {
+        return (Major, "struct can no longer be built with a literal".into());
+    }
+
+    let added: Vec<&String> = after
+        .members
+        .iter()
+        .map(|(name, _)| name)
+        .filter(|name| !before.members.iter().any(|(other, _)| other == *name))
+        .collect();
+
+    for name in &added {
+        if kind == "enum" && !before.non_exhaustive {
+            return (Major, format!("added variant `{}` to exhaustive enum", name));
+        }
+
+        if kind == "struct" && before.constructible {
+            return (Major, format!("added field `{}` to constructible struct", name));
+        }
+
+        if after.required.contains(name) {
+            return (Major, format!("added required method `{}`", name));
+        }
+    }
+
+    if let Some(name) = after
+        .required
+        .iter()
+        .find(|name| !before.required.contains(name) && !added.contains(name))
+    {
+        return (Major, format!("removed the default body of `{}`", name));
+    }
+
+    if let Some(name) = added.first() {
+        return (Minor, format!("added `{}`", name));
+    }
+
+    if before.non_exhaustive && !after.non_exhaustive {
+        return (Minor, "removed `#[non_exhaustive]`".into());
+    }
+
+    (Patch, "changed implementation".into())
+}
+
+impl ScopeDiff {
+    /// Classifies every change of the diff by its impact on the version of
+    /// the crate, e.g. to pick the next version of a generated SDK.
+    ///
+    /// Removing public items, changing signatures, adding variants to enums
+    /// that aren't `#[non_exhaustive]`, adding fields to structs that can be
+    /// built with a literal, and adding required trait methods are breaking.
+    /// Adding public items or members is additive. Every other change, e.g.
+    /// to docs, bodies, or private items, is a patch. The classification is
+    /// heuristic: e.g. changing a private type used in a public signature
+    /// isn't considered breaking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Scope, SemverImpact};
+    ///
+    /// let mut old = Scope::new();
+    /// old.new_enum("Role").vis("pub").new_variant("Admin");
+    ///
+    /// let mut new = Scope::new();
+    /// let role = new.new_enum("Role").vis("pub");
+    /// role.new_variant("Admin");
+    /// role.new_variant("Guest");
+    ///
+    /// let report = old.diff(&new).semver();
+    /// assert_eq!(report.impact(), SemverImpact::Major);
+    /// assert_eq!(
+    ///     report.to_string(),
+    ///     "major enum Role: added variant `Guest` to exhaustive enum\n"
+    /// );
+    /// ```
+    pub fn semver(&self) -> SemverReport {
+        let changes = self
+            .changes
+            .iter()
+            .map(|change| {
+                let (impact, reason) = classify(
+                    change.kind,
+                    change.before_api.as_ref(),
+                    change.after_api.as_ref(),
+                );
+
+                SemverChange {
+                    kind: change.kind,
+                    path: change.path.clone(),
+                    impact,
+                    reason,
+                }
+            })
+            .collect();
+
+        SemverReport { changes }
+    }
+}
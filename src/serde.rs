@@ -0,0 +1,477 @@
+//! Typed builders for the most common `serde` attributes.
+//!
+//! The builders render the correct `#[serde(...)]` syntax so that container,
+//! variant, and field attributes don't have to be written out by hand.
+//!
+//! ```
+//! use rust_codegen::serde::{ContainerAttrs, FieldAttrs, RenameRule};
+//! use rust_codegen::{Field, Scope};
+//!
+//! let mut scope = Scope::new();
+//! let mut name_field = Field::new("first_name", "String");
+//! name_field.serde(FieldAttrs::new().rename("firstName"));
+//!
+//! scope
+//!     .new_struct("Foo")
+//!     .derive("serde::Deserialize")
+//!     .serde(ContainerAttrs::new().rename_all(RenameRule::CamelCase))
+//!     .push_field(name_field);
+//! ```
+
+use std::borrow::Cow;
+
+/// The case conventions accepted by `rename_all`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RenameRule {
+    /// `lowercase`
+    Lowercase,
+    /// `UPPERCASE`
+    Uppercase,
+    /// `PascalCase`
+    PascalCase,
+    /// `camelCase`
+    CamelCase,
+    /// `snake_case`
+    SnakeCase,
+    /// `SCREAMING_SNAKE_CASE`
+    ScreamingSnakeCase,
+    /// `kebab-case`
+    KebabCase,
+    /// `SCREAMING-KEBAB-CASE`
+    ScreamingKebabCase,
+}
+
+impl RenameRule {
+    /// Returns the name of the rule as serde expects it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::RenameRule;
+    ///
+    /// assert_eq!(RenameRule::CamelCase.as_str(), "camelCase");
+    /// ```
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            RenameRule::Lowercase => "lowercase",
+            RenameRule::Uppercase => "UPPERCASE",
+            RenameRule::PascalCase => "PascalCase",
+            RenameRule::CamelCase => "camelCase",
+            RenameRule::SnakeCase => "snake_case",
+            RenameRule::ScreamingSnakeCase => "SCREAMING_SNAKE_CASE",
+            RenameRule::KebabCase => "kebab-case",
+            RenameRule::ScreamingKebabCase => "SCREAMING-KEBAB-CASE",
+        }
+    }
+}
+
+/// The arguments of a `#[serde(...)]` attribute in the order they were added.
+#[derive(Debug, Clone, Default)]
+struct Args(Vec<String>);
+
+impl Args {
+    /// Adds a flag argument, e.g. `flatten`.
+    fn flag(&mut self, name: &str) {
+        self.0.push(name.to_string());
+    }
+
+    /// Adds a key-value argument, quoting and escaping the value.
+    fn value(&mut self, name: &str, value: &str) {
+        self.0.push(format!("{} = {:?}", name, value));
+    }
+
+    /// Returns the attribute without the surrounding `#[...]`, or `None` if
+    /// there are no arguments.
+    fn meta(&self) -> Option<Cow<'static, str>> {
+        if self.0.is_empty() {
+            None
+        } else {
+            Some(format!("serde({})", self.0.join(", ")).into())
+        }
+    }
+}
+
+/// Defines the serde attributes of a struct or enum.
+#[derive(Debug, Clone, Default)]
+pub struct ContainerAttrs {
+    /// The attribute arguments.
+    args: Args,
+    /// Whether a `tag` has been set.
+    tagged: bool,
+    /// Whether `untagged` has been set.
+    untagged: bool,
+    /// Whether a `content` has been set.
+    content: bool,
+}
+
+impl ContainerAttrs {
+    /// Returns an empty set of container attributes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::ContainerAttrs;
+    ///
+    /// let attrs = ContainerAttrs::new();
+    /// ```
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Serialize and deserialize the container with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name to use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::ContainerAttrs;
+    ///
+    /// let mut attrs = ContainerAttrs::new();
+    /// attrs.rename("foo");
+    /// ```
+    pub fn rename(&mut self, name: &str) -> &mut Self {
+        self.args.value("rename", name);
+        self
+    }
+
+    /// Rename all of the fields or variants according to the given rule.
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - The case convention to use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::{ContainerAttrs, RenameRule};
+    ///
+    /// let mut attrs = ContainerAttrs::new();
+    /// attrs.rename_all(RenameRule::SnakeCase);
+    /// ```
+    pub fn rename_all(&mut self, rule: RenameRule) -> &mut Self {
+        self.args.value("rename_all", rule.as_str());
+        self
+    }
+
+    /// Use the internally tagged enum representation with the given tag.
+    ///
+    /// # Arguments
+    ///
+    /// * `tag` - The name of the tag field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::ContainerAttrs;
+    ///
+    /// let mut attrs = ContainerAttrs::new();
+    /// attrs.tag("type");
+    /// ```
+    pub fn tag(&mut self, tag: &str) -> &mut Self {
+        assert!(!self.untagged, "a serde container cannot be both tagged and untagged");
+
+        self.args.value("tag", tag);
+        self.tagged = true;
+        self
+    }
+
+    /// Use the adjacently tagged enum representation with the given content
+    /// field. Requires a `tag` to be set.
+    ///
+    /// # Arguments
+    ///
+    /// * `content` - The name of the content field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::ContainerAttrs;
+    ///
+    /// let mut attrs = ContainerAttrs::new();
+    /// attrs.tag("t").content("c");
+    /// ```
+    pub fn content(&mut self, content: &str) -> &mut Self {
+        assert!(!self.untagged, "a serde container cannot be both untagged and have content");
+
+        self.args.value("content", content);
+        self.content = true;
+        self
+    }
+
+    /// Use the untagged enum representation.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::ContainerAttrs;
+    ///
+    /// let mut attrs = ContainerAttrs::new();
+    /// attrs.untagged();
+    /// ```
+    pub fn untagged(&mut self) -> &mut Self {
+        assert!(
+            !self.tagged && !self.content,
+            "a serde container cannot be both tagged and untagged"
+        );
+
+        self.args.flag("untagged");
+        self.untagged = true;
+        self
+    }
+
+    /// Use the container's `Default` implementation for missing fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::ContainerAttrs;
+    ///
+    /// let mut attrs = ContainerAttrs::new();
+    /// attrs.default();
+    /// ```
+    pub fn default(&mut self) -> &mut Self {
+        self.args.flag("default");
+        self
+    }
+
+    /// Error when deserializing unknown fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::ContainerAttrs;
+    ///
+    /// let mut attrs = ContainerAttrs::new();
+    /// attrs.deny_unknown_fields();
+    /// ```
+    pub fn deny_unknown_fields(&mut self) -> &mut Self {
+        self.args.flag("deny_unknown_fields");
+        self
+    }
+
+    /// Returns the attribute without the surrounding `#[...]`, or `None` if
+    /// no attributes were set.
+    pub(crate) fn meta(&self) -> Option<Cow<'static, str>> {
+        assert!(
+            !self.content || self.tagged,
+            "a serde `content` requires a `tag` to be set"
+        );
+
+        self.args.meta()
+    }
+}
+
+/// Defines the serde attributes of an enum variant.
+#[derive(Debug, Clone, Default)]
+pub struct VariantAttrs {
+    /// The attribute arguments.
+    args: Args,
+}
+
+impl VariantAttrs {
+    /// Returns an empty set of variant attributes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::VariantAttrs;
+    ///
+    /// let attrs = VariantAttrs::new();
+    /// ```
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Serialize and deserialize the variant with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name to use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::VariantAttrs;
+    ///
+    /// let mut attrs = VariantAttrs::new();
+    /// attrs.rename("foo");
+    /// ```
+    pub fn rename(&mut self, name: &str) -> &mut Self {
+        self.args.value("rename", name);
+        self
+    }
+
+    /// Rename all of the variant's fields according to the given rule.
+    ///
+    /// # Arguments
+    ///
+    /// * `rule` - The case convention to use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::{RenameRule, VariantAttrs};
+    ///
+    /// let mut attrs = VariantAttrs::new();
+    /// attrs.rename_all(RenameRule::CamelCase);
+    /// ```
+    pub fn rename_all(&mut self, rule: RenameRule) -> &mut Self {
+        self.args.value("rename_all", rule.as_str());
+        self
+    }
+
+    /// Never serialize or deserialize the variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::VariantAttrs;
+    ///
+    /// let mut attrs = VariantAttrs::new();
+    /// attrs.skip();
+    /// ```
+    pub fn skip(&mut self) -> &mut Self {
+        self.args.flag("skip");
+        self
+    }
+
+    /// Returns the attribute without the surrounding `#[...]`, or `None` if
+    /// no attributes were set.
+    pub(crate) fn meta(&self) -> Option<Cow<'static, str>> {
+        self.args.meta()
+    }
+}
+
+/// Defines the serde attributes of a field.
+#[derive(Debug, Clone, Default)]
+pub struct FieldAttrs {
+    /// The attribute arguments.
+    args: Args,
+}
+
+impl FieldAttrs {
+    /// Returns an empty set of field attributes.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::FieldAttrs;
+    ///
+    /// let attrs = FieldAttrs::new();
+    /// ```
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Serialize and deserialize the field with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name to use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::FieldAttrs;
+    ///
+    /// let mut attrs = FieldAttrs::new();
+    /// attrs.rename("fooBar");
+    /// ```
+    pub fn rename(&mut self, name: &str) -> &mut Self {
+        self.args.value("rename", name);
+        self
+    }
+
+    /// Never serialize or deserialize the field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::FieldAttrs;
+    ///
+    /// let mut attrs = FieldAttrs::new();
+    /// attrs.skip();
+    /// ```
+    pub fn skip(&mut self) -> &mut Self {
+        self.args.flag("skip");
+        self
+    }
+
+    /// Skip serializing the field when the given predicate returns `true`.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the predicate, e.g. `Option::is_none`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::FieldAttrs;
+    ///
+    /// let mut attrs = FieldAttrs::new();
+    /// attrs.skip_serializing_if("Option::is_none");
+    /// ```
+    pub fn skip_serializing_if(&mut self, path: &str) -> &mut Self {
+        self.args.value("skip_serializing_if", path);
+        self
+    }
+
+    /// Use the field type's `Default` implementation when the field is
+    /// missing.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::FieldAttrs;
+    ///
+    /// let mut attrs = FieldAttrs::new();
+    /// attrs.default();
+    /// ```
+    pub fn default(&mut self) -> &mut Self {
+        self.args.flag("default");
+        self
+    }
+
+    /// Call the given function to get the value when the field is missing.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the function.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::FieldAttrs;
+    ///
+    /// let mut attrs = FieldAttrs::new();
+    /// attrs.default_with("default_port");
+    /// ```
+    pub fn default_with(&mut self, path: &str) -> &mut Self {
+        self.args.value("default", path);
+        self
+    }
+
+    /// Flatten the field's contents into the containing struct.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::serde::FieldAttrs;
+    ///
+    /// let mut attrs = FieldAttrs::new();
+    /// attrs.flatten();
+    /// ```
+    pub fn flatten(&mut self) -> &mut Self {
+        self.args.flag("flatten");
+        self
+    }
+
+    /// Returns the attribute without the surrounding `#[...]`, or `None` if
+    /// no attributes were set.
+    pub(crate) fn meta(&self) -> Option<Cow<'static, str>> {
+        self.args.meta()
+    }
+}
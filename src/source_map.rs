@@ -0,0 +1,114 @@
+use crate::metadata::Metadata;
+
+/// Defines the range of output lines produced by an item of a scope.
+#[derive(Debug, Clone)]
+pub struct SourceSpan {
+    /// The kind of the item, e.g. `struct` or `fn`.
+    pub kind: &'static str,
+    /// The path of the item from the root of the scope, e.g.
+    /// `handlers::login`.
+    pub path: String,
+    /// The first line of the item, starting at 1.
+    pub start_line: usize,
+    /// The last line of the item, inclusive.
+    pub end_line: usize,
+    /// The metadata of the item, e.g. the schema it was generated from.
+    pub metadata: Metadata,
+}
+
+/// Defines which item produced each range of output lines, returned by
+/// [`Scope::to_string_with_source_map`].
+///
+/// [`Scope::to_string_with_source_map`]: crate::Scope::to_string_with_source_map
+#[derive(Debug, Clone, Default)]
+pub struct SourceMap {
+    /// The spans of the items, in the order the items start.
+    spans: Vec<SourceSpan>,
+    /// The names of the modules being formatted.
+    modules: Vec<String>,
+}
+
+impl SourceMap {
+    /// Returns a new, empty source map.
+    pub fn new() -> Self {
+        SourceMap {
+            spans: vec![],
+            modules: vec![],
+        }
+    }
+
+    /// Returns the spans of every item, including the items of nested
+    /// modules, in the order the items start.
+    pub fn spans(&self) -> &[SourceSpan] {
+        &self.spans
+    }
+
+    /// Returns the span of the innermost item that produced the given line,
+    /// e.g. to translate the line of a compiler error. Lines start at 1.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The line to look up.
+    pub fn lookup(&self, line: usize) -> Option<&SourceSpan> {
+        // Nested items start after, and end before, the module that contains
+        // them, so the last matching span is the innermost one.
+        self.spans
+            .iter()
+            .rev()
+            .find(|span| span.start_line <= line && line <= span.end_line)
+    }
+
+    /// Starts the span of an item, returning its index to end it with.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of the item.
+    /// * `name` - The name of the item.
+    /// * `metadata` - The metadata of the item.
+    /// * `start_line` - The first line of the item.
+    pub(crate) fn start(
+        &mut self,
+        kind: &'static str,
+        name: &str,
+        metadata: Metadata,
+        start_line: usize,
+    ) -> usize {
+        let mut path = self.modules.join("::");
+
+        if !path.is_empty() {
+            path.push_str("::");
+        }
+
+        path.push_str(name);
+
+        self.spans.push(SourceSpan {
+            kind,
+            path,
+            start_line,
+            end_line: start_line,
+            metadata,
+        });
+
+        self.spans.len() - 1
+    }
+
+    /// Ends the span with the given index.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index returned by `start`.
+    /// * `end_line` - The last line of the item.
+    pub(crate) fn end(&mut self, index: usize, end_line: usize) {
+        self.spans[index].end_line = end_line;
+    }
+
+    /// Enters the module with the given name.
+    pub(crate) fn enter_module(&mut self, name: &str) {
+        self.modules.push(name.to_string());
+    }
+
+    /// Leaves the innermost module.
+    pub(crate) fn leave_module(&mut self) {
+        self.modules.pop();
+    }
+}
@@ -0,0 +1,160 @@
+use std::io;
+use std::path::Path;
+
+use indexmap::IndexMap;
+
+use crate::scope::Scope;
+
+/// Defines a tree of generated source files, by path relative to its root,
+/// e.g. `src/lib.rs`.
+#[derive(Debug, Clone, Default)]
+pub struct SourceTree {
+    /// The contents of the files, by path, in the order they were added.
+    files: IndexMap<String, String>,
+}
+
+impl SourceTree {
+    /// Returns a new, empty source tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::SourceTree;
+    ///
+    /// let tree = SourceTree::new();
+    /// ```
+    pub fn new() -> Self {
+        SourceTree {
+            files: IndexMap::new(),
+        }
+    }
+
+    /// Add a file with the rendered contents of the given scope, ending with
+    /// a single newline, replacing the file at the same path if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file relative to the root of the tree.
+    /// * `scope` - The contents of the file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Scope,SourceTree};
+    ///
+    /// let mut scope = Scope::new();
+    /// scope.new_struct("Foo");
+    ///
+    /// let mut tree = SourceTree::new();
+    /// tree.scope("src/lib.rs", &scope);
+    ///
+    /// assert_eq!(tree.get("src/lib.rs"), Some("struct Foo;\n"));
+    /// ```
+    pub fn scope(&mut self, path: &str, scope: &Scope) -> &mut Self {
+        let rendered = scope.to_string();
+        self.file(path, format!("{}\n", rendered.trim_end_matches('\n')))
+    }
+
+    /// Add a file with the given contents, replacing the file at the same
+    /// path if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file relative to the root of the tree.
+    /// * `contents` - The contents of the file.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::SourceTree;
+    ///
+    /// let mut tree = SourceTree::new();
+    /// tree.file("README.md", "# Generated\n");
+    /// ```
+    pub fn file<T>(&mut self, path: &str, contents: T) -> &mut Self
+    where
+        T: Into<String>,
+    {
+        assert!(
+            !path.is_empty() && !path.starts_with('/'),
+            "the path of a file in a source tree must be relative, got `{}`",
+            path
+        );
+
+        self.files.insert(path.to_string(), contents.into());
+        self
+    }
+
+    /// Returns the contents of the file at the given path, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the file relative to the root of the tree.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::SourceTree;
+    ///
+    /// let tree = SourceTree::new();
+    /// assert!(tree.get("src/lib.rs").is_none());
+    /// ```
+    pub fn get(&self, path: &str) -> Option<&str> {
+        self.files.get(path).map(|contents| &contents[..])
+    }
+
+    /// Returns the paths and contents of the files, in the order they were
+    /// added.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::SourceTree;
+    ///
+    /// let mut tree = SourceTree::new();
+    /// tree.file("README.md", "# Generated\n");
+    ///
+    /// for (path, contents) in tree.files() {
+    ///     println!("{}: {} bytes", path, contents.len());
+    /// }
+    /// ```
+    pub fn files(&self) -> impl Iterator<Item = (&str, &str)> {
+        self.files
+            .iter()
+            .map(|(path, contents)| (&path[..], &contents[..]))
+    }
+
+    /// Writes the files under the given directory, creating the directories
+    /// they are in as needed.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The directory to write the tree to.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// use rust_codegen::SourceTree;
+    ///
+    /// let mut tree = SourceTree::new();
+    /// tree.file("README.md", "# Generated\n");
+    ///
+    /// tree.write_to("generated").unwrap();
+    /// ```
+    pub fn write_to<P>(&self, root: P) -> io::Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        for (path, contents) in &self.files {
+            let path = root.as_ref().join(path);
+
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+
+            std::fs::write(path, contents)?;
+        }
+
+        Ok(())
+    }
+}
@@ -0,0 +1,411 @@
+use std::borrow::Cow;
+
+use crate::block::Block;
+use crate::field::Field;
+use crate::scope::Scope;
+
+use crate::r#enum::Enum;
+use crate::r#impl::Impl;
+use crate::r#type::Type;
+
+/// Defines a transition of a state machine.
+#[derive(Debug, Clone)]
+struct Transition {
+    /// The state the transition starts from.
+    from: String,
+    /// The event triggering the transition.
+    event: String,
+    /// The state the transition leads to.
+    to: String,
+    /// The condition that must hold for the transition to happen, if any.
+    guard: Option<String>,
+}
+
+/// Generates a state enum, an event enum, and a `transition` method on the
+/// state enum that moves to the next state when a transition matches the
+/// current state and the event.
+///
+/// `transition` returns whether a transition happened. Transitions are
+/// matched in the order they were added, so a guarded transition can be
+/// followed by a fallback for the same state and event.
+#[derive(Debug, Clone)]
+pub struct StateMachine {
+    /// The name of the state enum.
+    state: Cow<'static, str>,
+    /// The name of the event enum.
+    event: Cow<'static, str>,
+    /// The visibility of the generated items.
+    vis: Option<String>,
+    /// The names of the states, in order.
+    states: Vec<String>,
+    /// The names of the events, in order.
+    events: Vec<String>,
+    /// The transitions, in order.
+    transitions: Vec<Transition>,
+    /// The extra arguments of the `transition` method, e.g. a context used by
+    /// the guards.
+    args: Vec<Field>,
+}
+
+impl StateMachine {
+    /// Returns a new state machine generator with the given enum names.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The name of the state enum.
+    /// * `event` - The name of the event enum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::StateMachine;
+    ///
+    /// let machine = StateMachine::new("State", "Event");
+    /// ```
+    pub fn new<S, E>(state: S, event: E) -> Self
+    where
+        S: Into<Cow<'static, str>>,
+        E: Into<Cow<'static, str>>,
+    {
+        StateMachine {
+            state: state.into(),
+            event: event.into(),
+            vis: None,
+            states: vec![],
+            events: vec![],
+            transitions: vec![],
+            args: vec![],
+        }
+    }
+
+    /// Set the visibility of the enums and the `transition` method.
+    ///
+    /// # Arguments
+    ///
+    /// * `vis` - The visibility to set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::StateMachine;
+    ///
+    /// let mut machine = StateMachine::new("State", "Event");
+    /// machine.vis("pub");
+    /// ```
+    pub fn vis(&mut self, vis: &str) -> &mut Self {
+        self.vis = Some(vis.to_string());
+        self
+    }
+
+    /// Add a state. The first state added is usually the initial state.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the state variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::StateMachine;
+    ///
+    /// let mut machine = StateMachine::new("State", "Event");
+    /// machine.state("Idle").state("Connected");
+    /// ```
+    pub fn state(&mut self, name: &str) -> &mut Self {
+        assert!(
+            !self.states.iter().any(|state| state == name),
+            "the `{}` state was already added",
+            name
+        );
+
+        self.states.push(name.to_string());
+        self
+    }
+
+    /// Add an event.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the event variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::StateMachine;
+    ///
+    /// let mut machine = StateMachine::new("State", "Event");
+    /// machine.event("Connect");
+    /// ```
+    pub fn event(&mut self, name: &str) -> &mut Self {
+        assert!(
+            !self.events.iter().any(|event| event == name),
+            "the `{}` event was already added",
+            name
+        );
+
+        self.events.push(name.to_string());
+        self
+    }
+
+    /// Add an argument to the `transition` method, e.g. a context used by
+    /// the guards.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the argument.
+    /// * `ty` - The type of the argument.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::StateMachine;
+    ///
+    /// let mut machine = StateMachine::new("State", "Event");
+    /// machine.arg("ctx", "&Context");
+    /// ```
+    pub fn arg<T>(&mut self, name: &str, ty: T) -> &mut Self
+    where
+        T: Into<Type>,
+    {
+        self.args.push(Field::new(name.to_string(), ty));
+        self
+    }
+
+    /// Add a transition from a state to another when the given event occurs.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The state the transition starts from.
+    /// * `event` - The event triggering the transition.
+    /// * `to` - The state the transition leads to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::StateMachine;
+    ///
+    /// let mut machine = StateMachine::new("State", "Event");
+    /// machine
+    ///     .state("Idle")
+    ///     .state("Connected")
+    ///     .event("Connect")
+    ///     .transition("Idle", "Connect", "Connected");
+    /// ```
+    pub fn transition(&mut self, from: &str, event: &str, to: &str) -> &mut Self {
+        self.push_transition(from, event, to, None)
+    }
+
+    /// Add a transition that only happens when the given condition holds.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The state the transition starts from.
+    /// * `event` - The event triggering the transition.
+    /// * `to` - The state the transition leads to.
+    /// * `guard` - The condition, e.g. `ctx.retries < 3`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::StateMachine;
+    ///
+    /// let mut machine = StateMachine::new("State", "Event");
+    /// machine
+    ///     .state("Connecting")
+    ///     .event("Timeout")
+    ///     .arg("ctx", "&Context")
+    ///     .guarded_transition("Connecting", "Timeout", "Connecting", "ctx.retries < 3");
+    /// ```
+    pub fn guarded_transition(
+        &mut self,
+        from: &str,
+        event: &str,
+        to: &str,
+        guard: &str,
+    ) -> &mut Self {
+        self.push_transition(from, event, to, Some(guard.to_string()))
+    }
+
+    /// Returns the state enum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::StateMachine;
+    ///
+    /// let mut machine = StateMachine::new("State", "Event");
+    /// machine.state("Idle");
+    ///
+    /// let state = machine.to_state_enum();
+    /// ```
+    pub fn to_state_enum(&self) -> Enum {
+        self.unit_enum(&self.state, &self.states)
+    }
+
+    /// Returns the event enum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::StateMachine;
+    ///
+    /// let mut machine = StateMachine::new("State", "Event");
+    /// machine.event("Connect");
+    ///
+    /// let event = machine.to_event_enum();
+    /// ```
+    pub fn to_event_enum(&self) -> Enum {
+        self.unit_enum(&self.event, &self.events)
+    }
+
+    /// Returns the impl block of the state enum with the `transition` method.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::StateMachine;
+    ///
+    /// let mut machine = StateMachine::new("State", "Event");
+    /// machine.state("Idle").event("Connect");
+    ///
+    /// let transitions = machine.to_impl();
+    /// ```
+    pub fn to_impl(&self) -> Impl {
+        for t in &self.transitions {
+            for state in [&t.from, &t.to] {
+                if !self.states.contains(state) {
+                    panic!("unknown state `{}` in transition", state);
+                }
+            }
+
+            if !self.events.contains(&t.event) {
+                panic!("unknown event `{}` in transition", t.event);
+            }
+        }
+
+        let mut body = Block::new("match *self");
+
+        for state in &self.states {
+            let transitions: Vec<&Transition> = self
+                .transitions
+                .iter()
+                .filter(|t| &t.from == state)
+                .collect();
+
+            if transitions.is_empty() {
+                body.line(format!("{}::{} => false,", self.state, state));
+                continue;
+            }
+
+            let mut arm = Block::new(format!("{}::{} => match event", self.state, state));
+
+            for t in &transitions {
+                let pattern = match t.guard {
+                    Some(ref guard) => format!("{}::{} if {}", self.event, t.event, guard),
+                    None => format!("{}::{}", self.event, t.event),
+                };
+
+                let mut then = Block::new(format!("{} =>", pattern));
+                then.line(format!("*self = {}::{};", self.state, t.to))
+                    .line("true")
+                    .after(",");
+
+                arm.push_block(then);
+            }
+
+            // Leave out the fallback when every event is always handled.
+            let handles_all = self.events.iter().all(|event| {
+                transitions
+                    .iter()
+                    .any(|t| &t.event == event && t.guard.is_none())
+            });
+
+            if !handles_all {
+                arm.line("_ => false,");
+            }
+
+            arm.after(",");
+            body.push_block(arm);
+        }
+
+        let mut imp = Impl::new(self.state.clone());
+        let func = imp
+            .new_fn("transition")
+            .doc("Moves to the next state for the given event, returning whether a transition happened.")
+            .arg_mut_self()
+            .arg("event", self.event.clone());
+
+        if let Some(ref vis) = self.vis {
+            func.vis(vis);
+        }
+
+        for arg in &self.args {
+            func.push_arg(arg.clone());
+        }
+
+        func.ret("bool").push_block(body);
+
+        imp
+    }
+
+    /// Push the enums and the impl block to the given scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - The scope to push the state machine to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Scope,StateMachine};
+    ///
+    /// let mut scope = Scope::new();
+    /// let mut machine = StateMachine::new("State", "Event");
+    /// machine.state("Idle").event("Connect");
+    ///
+    /// machine.push_to(&mut scope);
+    /// ```
+    pub fn push_to(&self, scope: &mut Scope) {
+        scope.push_enum(self.to_state_enum());
+        scope.push_enum(self.to_event_enum());
+        scope.push_impl(self.to_impl());
+    }
+
+    /// Returns an enum with the given unit variants.
+    fn unit_enum(&self, name: &str, variants: &[String]) -> Enum {
+        let mut ret = Enum::new(name.to_string());
+
+        if let Some(ref vis) = self.vis {
+            ret.vis(vis);
+        }
+
+        ret.derive("Debug")
+            .derive("Clone")
+            .derive("Copy")
+            .derive("PartialEq")
+            .derive("Eq");
+
+        for variant in variants {
+            ret.new_variant(variant.clone());
+        }
+
+        ret
+    }
+
+    /// Adds a transition.
+    fn push_transition(
+        &mut self,
+        from: &str,
+        event: &str,
+        to: &str,
+        guard: Option<String>,
+    ) -> &mut Self {
+        self.transitions.push(Transition {
+            from: from.to_string(),
+            event: event.to_string(),
+            to: to.to_string(),
+            guard,
+        });
+        self
+    }
+}
@@ -0,0 +1,280 @@
+use std::borrow::Cow;
+use std::fmt::{self, Write};
+
+use crate::attributes::Attributes;
+use crate::docs::Docs;
+use crate::formatter::Formatter;
+use crate::visibility::{map_vis, Visibility};
+
+use crate::r#type::Type;
+
+/// Defines a static item (`static NAME: T = V;`), e.g. a symbol exported to
+/// C or a value placed in a specific link section.
+#[derive(Debug, Clone)]
+pub struct Static {
+    /// The name of the static.
+    name: Cow<'static, str>,
+    /// The type of the static.
+    ty: Type,
+    /// The value of the static.
+    value: Cow<'static, str>,
+    /// Whether the static is `static mut`.
+    mutable: bool,
+    /// The visibility of the static.
+    vis: Option<String>,
+    /// The documentation of the static.
+    docs: Option<Docs>,
+    /// The attributes of the static.
+    attributes: Attributes,
+}
+
+impl Static {
+    /// Returns a new static.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the static.
+    /// * `ty` - The type of the static.
+    /// * `value` - The value of the static.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Static;
+    ///
+    /// let version_static = Static::new("VERSION", "u32", "1");
+    /// ```
+    pub fn new<N, T, V>(name: N, ty: T, value: V) -> Self
+    where
+        N: Into<Cow<'static, str>>,
+        T: Into<Type>,
+        V: Into<Cow<'static, str>>,
+    {
+        Static {
+            name: name.into(),
+            ty: ty.into(),
+            value: value.into(),
+            mutable: false,
+            vis: None,
+            docs: None,
+            attributes: Attributes::new(),
+        }
+    }
+
+    /// Returns the name of the static.
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the visibility of the static.
+    pub(crate) fn get_vis(&self) -> Option<&str> {
+        self.vis.as_deref()
+    }
+
+    /// Set the visibility of the static.
+    ///
+    /// # Arguments
+    ///
+    /// * `vis` - The visibility to set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Static;
+    ///
+    /// let mut version_static = Static::new("VERSION", "u32", "1");
+    /// version_static.vis("pub");
+    /// ```
+    pub fn vis(&mut self, vis: &str) -> &mut Self {
+        self.vis = Some(vis.to_string());
+        self
+    }
+
+    /// Set whether the static is `static mut`.
+    ///
+    /// # Arguments
+    ///
+    /// * `mutable` - Indicates whether the static is mutable or not.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Static;
+    ///
+    /// let mut counter_static = Static::new("COUNTER", "u32", "0");
+    /// counter_static.set_mut(true);
+    /// ```
+    pub fn set_mut(&mut self, mutable: bool) -> &mut Self {
+        self.mutable = mutable;
+        self
+    }
+
+    /// Set the documentation of the static.
+    ///
+    /// # Arguments
+    ///
+    /// * `docs` - The documentation to set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Static;
+    ///
+    /// let mut version_static = Static::new("VERSION", "u32", "1");
+    /// version_static.doc("The version of the plugin API.");
+    /// ```
+    pub fn doc<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.docs = Some(Docs::new(docs));
+        self
+    }
+
+    /// Add an attribute to the static.
+    ///
+    /// # Arguments
+    ///
+    /// * `attribute` - The attribute to add, without the surrounding `#[...]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Static;
+    ///
+    /// let mut version_static = Static::new("VERSION", "u32", "1");
+    /// version_static.attr("used");
+    /// ```
+    pub fn attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.meta(attribute);
+        self
+    }
+
+    /// Add `#[no_mangle]` to the static so that it's exported under its own
+    /// name, replacing any `#[export_name = "..."]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Static;
+    ///
+    /// let mut version_static = Static::new("VERSION", "u32", "1");
+    /// version_static.no_mangle();
+    /// ```
+    pub fn no_mangle(&mut self) -> &mut Self {
+        self.attributes.no_mangle();
+        self
+    }
+
+    /// Set the symbol name that the static is exported under with
+    /// `#[export_name = "..."]`, replacing any existing one and any
+    /// `#[no_mangle]`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The symbol name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Static;
+    ///
+    /// let mut version_static = Static::new("VERSION", "u32", "1");
+    /// version_static.export_name("plugin_version");
+    /// ```
+    pub fn export_name(&mut self, name: &str) -> &mut Self {
+        self.attributes.export_name(name);
+        self
+    }
+
+    /// Set the object file section that the static is placed in with
+    /// `#[link_section = "..."]`, replacing any existing one.
+    ///
+    /// # Arguments
+    ///
+    /// * `section` - The name of the section, e.g. `.init_array`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Static;
+    ///
+    /// let mut init_static = Static::new("INIT", "extern \"C\" fn()", "init");
+    /// init_static.link_section(".init_array");
+    /// ```
+    pub fn link_section(&mut self, section: &str) -> &mut Self {
+        self.attributes.link_section(section);
+        self
+    }
+
+    /// Returns the signature of the static without its value, e.g.
+    /// `static VERSION: u32`.
+    pub(crate) fn signature(&self) -> String {
+        let mutable = if self.mutable { "mut " } else { "" };
+
+        format!("static {}{}: {}", mutable, self.name, self.ty.fmt_to_string())
+    }
+
+    /// Calls the given function with the type of the static.
+    pub(crate) fn visit_types_mut(&mut self, f: &mut dyn FnMut(&mut Type)) {
+        self.ty.visit_types_mut(f);
+    }
+
+    /// Maps the visibility of the static with the given function.
+    ///
+    /// # Arguments
+    ///
+    /// * `f` - The function mapping the visibility.
+    pub(crate) fn map_visibility(&mut self, f: &dyn Fn(Visibility) -> Visibility) {
+        map_vis(&mut self.vis, f);
+    }
+
+    /// Returns an estimate of the rendered length of the static.
+    pub(crate) fn estimated_len(&self) -> usize {
+        let docs = self.docs.as_ref().map_or(0, |docs| docs.estimated_len());
+
+        docs + self.attributes.estimated_len() + self.name.len() + self.value.len() + 32
+    }
+
+    /// Formats the static using the given formatter.
+    ///
+    /// # Arguments
+    ///
+    /// * `fmt` - The formatter to use.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::*;
+    ///
+    /// let mut dest = String::new();
+    /// let mut fmt = Formatter::new(&mut dest);
+    ///
+    /// let version_static = Static::new("VERSION", "u32", "1");
+    /// version_static.fmt(&mut fmt);
+    /// ```
+    pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref docs) = self.docs {
+            docs.fmt(fmt)?;
+        }
+
+        self.attributes.fmt(fmt)?;
+
+        if let Some(ref vis) = self.vis {
+            write!(fmt, "{} ", vis)?;
+        }
+
+        write!(fmt, "static ")?;
+
+        if self.mutable {
+            write!(fmt, "mut ")?;
+        }
+
+        write!(fmt, "{}: ", self.name)?;
+        self.ty.fmt(fmt)?;
+        writeln!(fmt, " = {};", self.value)
+    }
+}
@@ -0,0 +1,53 @@
+use std::collections::BTreeMap;
+
+/// Defines statistics about the items of a scope and its modules, returned by
+/// [`Scope::stats`].
+///
+/// [`Scope::stats`]: crate::Scope::stats
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ScopeStats {
+    /// The number of items per kind, e.g. `struct` or `fn`, including the
+    /// items of nested modules.
+    pub items: BTreeMap<&'static str, usize>,
+    /// The length of the rendered scope in bytes.
+    pub rendered_len: usize,
+    /// The number of `pub` structs, enums, traits, functions, modules, and
+    /// inherent methods.
+    pub public: usize,
+    /// The number of structs, enums, traits, functions, modules, and inherent
+    /// methods that aren't `pub`, including `pub(crate)` ones.
+    pub private: usize,
+    /// The paths of the `pub` items without documentation, e.g.
+    /// `models::User::new`.
+    pub undocumented_public: Vec<String>,
+}
+
+impl ScopeStats {
+    /// Returns the number of items of the given kind.
+    ///
+    /// # Arguments
+    ///
+    /// * `kind` - The kind of item, e.g. `struct` or `fn`.
+    pub fn count(&self, kind: &str) -> usize {
+        self.items.get(kind).copied().unwrap_or(0)
+    }
+
+    /// Records an item with the given visibility.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the item.
+    /// * `vis` - The visibility of the item.
+    /// * `has_docs` - Whether the item is documented.
+    pub(crate) fn record_vis(&mut self, path: String, vis: Option<&str>, has_docs: bool) {
+        if vis == Some("pub") {
+            self.public += 1;
+
+            if !has_docs {
+                self.undocumented_public.push(path);
+            }
+        } else {
+            self.private += 1;
+        }
+    }
+}
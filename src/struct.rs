@@ -1,10 +1,20 @@
+use std::borrow::Cow;
 use std::fmt::{self, Write};
 
+use crate::docs::{DocTest, Docs};
 use crate::field::Field;
 use crate::fields::Fields;
-use crate::formatter::Formatter;
+use crate::formatter::{fmt_to_code_string, Formatter};
+use crate::function::Function;
+use crate::metadata::Metadata;
+use crate::policy::Policy;
+use crate::serde::ContainerAttrs;
+use crate::template::field_format_args;
 use crate::type_def::TypeDef;
+use crate::visibility::{strip_vis, Visibility};
+use crate::wasm_bindgen::BindgenAttrs;
 
+use crate::r#impl::Impl;
 use crate::r#type::Type;
 
 /// Defines a struct.
@@ -13,8 +23,6 @@ pub struct Struct {
     type_def: TypeDef,
     /// Struct fields
     fields: Fields,
-    /// The attributes for this struct.
-    attributes: Vec<String>,
 }
 
 impl Struct {
@@ -31,11 +39,13 @@ impl Struct {
     /// 
     /// let foo_struct = Struct::new("Foo");
     /// ```
-    pub fn new(name: &str) -> Self {
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
         Struct {
             type_def: TypeDef::new(name),
             fields: Fields::Empty,
-            attributes: vec![],
         }
     }
 
@@ -53,6 +63,27 @@ impl Struct {
         &self.type_def.ty
     }
 
+    /// Returns the metadata of the struct, which is kept when it is cloned
+    /// and never rendered.
+    pub fn metadata(&self) -> &Metadata {
+        self.type_def.metadata()
+    }
+
+    /// Returns a mutable reference to the metadata of the struct, e.g. to tag
+    /// it in a multi-pass generator.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Struct;
+    /// 
+    /// let mut foo_struct = Struct::new("Foo");
+    /// foo_struct.metadata_mut().set("schema", "foo.json");
+    /// ```
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        self.type_def.metadata_mut()
+    }
+
     /// Set the structure visibility.
     /// 
     /// # Arguments
@@ -128,11 +159,76 @@ impl Struct {
     /// let mut foo_struct = Struct::new("Foo");
     /// foo_struct.doc("Sample struct documentation.");
     /// ```
-    pub fn doc(&mut self, docs: &str) -> &mut Self {
+    pub fn doc<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.type_def.doc(docs);
         self
     }
 
+    /// Set the structure documentation, written as is without escaping
+    /// markdown, e.g. for intra-doc links.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `docs` - The documentation to set for the struct.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Struct;
+    /// 
+    /// let mut foo_struct = Struct::new("Foo");
+    /// foo_struct.doc_raw("See [`Bar`].");
+    /// ```
+    pub fn doc_raw<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.type_def.doc_raw(docs);
+        self
+    }
+
+    /// Add an example to the struct documentation.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `code` - The code of the example.
+    /// * `mode` - Whether rustdoc should run, only compile, or ignore it.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{DocTest,Struct};
+    /// 
+    /// let mut foo_struct = Struct::new("Foo");
+    /// foo_struct.doc_example("let foo = Foo::default();", DocTest::Run);
+    /// ```
+    pub fn doc_example<T>(&mut self, code: T, mode: DocTest) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.type_def.doc_example(code, mode);
+        self
+    }
+
+    /// Opt out of the default derives of the scope, see
+    /// `Scope::default_derives`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Struct;
+    /// 
+    /// let mut foo_struct = Struct::new("Foo");
+    /// foo_struct.no_default_derives();
+    /// ```
+    pub fn no_default_derives(&mut self) -> &mut Self {
+        self.type_def.no_default_derives();
+        self
+    }
+
     /// Add a new type that the struct should derive.
     /// 
     /// # Arguments
@@ -147,11 +243,41 @@ impl Struct {
     /// let mut foo_struct = Struct::new("Foo");
     /// foo_struct.derive("Debug");
     /// ```
-    pub fn derive(&mut self, name: &str) -> &mut Self {
+    pub fn derive<T>(&mut self, name: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.type_def.derive(name);
         self
     }
 
+    /// Add a new type that the struct should derive along with the helper
+    /// attributes of its derive macro, which are kept directly after the
+    /// `#[derive(...)]` attribute.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the type to derive.
+    /// * `helpers` - The helper attributes, without the surrounding `#[...]`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Struct;
+    /// 
+    /// let mut foo_struct = Struct::new("Foo");
+    /// foo_struct.derive_with("serde::Serialize", ["serde(rename_all = \"camelCase\")"]);
+    /// ```
+    pub fn derive_with<T, I, H>(&mut self, name: T, helpers: I) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+        I: IntoIterator<Item = H>,
+        H: Into<Cow<'static, str>>,
+    {
+        self.type_def.derive_with(name, helpers);
+        self
+    }
+
     /// Specify lint attribute to supress a warning or error.
     /// 
     /// # Arguments
@@ -166,7 +292,10 @@ impl Struct {
     /// let mut foo_struct = Struct::new("Foo");
     /// foo_struct.allow("dead_code");
     /// ```
-    pub fn allow(&mut self, allow: &str) -> &mut Self {
+    pub fn allow<T>(&mut self, allow: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.type_def.allow(allow);
         self
     }
@@ -183,7 +312,10 @@ impl Struct {
     /// let mut foo_struct = Struct::new("Foo");
     /// foo_struct.repr("C");
     /// ```
-    pub fn repr(&mut self, repr: &str) -> &mut Self {
+    pub fn repr<T>(&mut self, repr: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.type_def.repr(repr);
         self
     }
@@ -230,8 +362,9 @@ impl Struct {
     /// let mut foo_struct = Struct::new("Foo");
     /// foo_struct.field("bar", "i32");
     /// ```
-    pub fn field<T>(&mut self, name: &str, ty: T) -> &mut Self
+    pub fn field<N, T>(&mut self, name: N, ty: T) -> &mut Self
     where
+        N: Into<Cow<'static, str>>,
         T: Into<Type>,
     {
         self.fields.named(name, ty);
@@ -265,7 +398,9 @@ impl Struct {
         self
     }
 
-    /// Adds an attribute to the struct (e.g. `"#[some_attribute]"`)
+    /// Adds an attribute to the struct, without the surrounding `#[...]`
+    /// (e.g. `"non_exhaustive"`). An attribute that is already wrapped, e.g.
+    /// `"#[non_exhaustive]"`, is accepted as well.
     /// 
     /// # Arguments
     /// 
@@ -277,13 +412,541 @@ impl Struct {
     /// use rust_codegen::Struct;
     /// 
     /// let mut foo_struct = Struct::new("Foo");
-    /// foo_struct.attr("some_attribute");
+    /// foo_struct.attr("non_exhaustive");
     /// ```
-    pub fn attr(&mut self, attribute: &str) -> &mut Self {
-        self.attributes.push(attribute.to_string());
+    pub fn attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.type_def.attr(attribute);
+        self
+    }
+
+    /// Add a macro or attribute to the struct that is written as is (e.g.
+    /// `"#[pyclass]"`).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `r#macro` - The macro to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Struct;
+    /// 
+    /// let mut foo_struct = Struct::new("Foo");
+    /// foo_struct.r#macro("#[pyclass]");
+    /// ```
+    pub fn r#macro<T>(&mut self, r#macro: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.type_def.r#macro(r#macro);
+        self
+    }
+
+    /// Add a tool attribute to the struct, e.g. to record the schema that it
+    /// was generated from with `my_tool::generated(schema = "user")`. Tool
+    /// attributes are rendered before the derives and every other attribute.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attribute` - The attribute to add, without the surrounding `#[...]`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Struct;
+    /// 
+    /// let mut foo_struct = Struct::new("Foo");
+    /// foo_struct.tool_attr("my_tool::generated(schema = \"user\")");
+    /// ```
+    pub fn tool_attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.type_def.attributes.marker(attribute);
+        self
+    }
+
+    /// Adds the given serde container attributes to the struct.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attrs` - The serde attributes to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Struct;
+    /// use rust_codegen::serde::ContainerAttrs;
+    /// 
+    /// let mut foo_struct = Struct::new("Foo");
+    /// foo_struct.serde(ContainerAttrs::new().deny_unknown_fields());
+    /// ```
+    pub fn serde(&mut self, attrs: &ContainerAttrs) -> &mut Self {
+        if let Some(meta) = attrs.meta() {
+            self.type_def.attributes.meta(meta);
+        }
         self
     }
 
+    /// Adds a `#[wasm_bindgen]` attribute to the struct.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attrs` - The arguments of the attribute.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Struct;
+    /// use rust_codegen::wasm_bindgen::BindgenAttrs;
+    /// 
+    /// let mut foo_struct = Struct::new("Foo");
+    /// foo_struct.wasm_bindgen(&BindgenAttrs::new());
+    /// ```
+    pub fn wasm_bindgen(&mut self, attrs: &BindgenAttrs) -> &mut Self {
+        self.type_def.attributes.meta(attrs.meta());
+        self
+    }
+
+    /// Returns an impl block with a getter per named field returning a
+    /// reference to it, e.g. `pub fn name(&self) -> &String`. The getters
+    /// are generated accessors, so a scope's `Policy` can inline them and
+    /// give them the docs of their fields.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Scope,Struct};
+    /// 
+    /// let mut scope = Scope::new();
+    /// let mut user_struct = Struct::new("User");
+    /// user_struct.field("name", "String");
+    /// 
+    /// scope.push_impl(user_struct.getters());
+    /// scope.push_struct(user_struct);
+    /// ```
+    pub fn getters(&self) -> Impl {
+        let mut getters = Impl::new(&self.type_def.ty);
+
+        for generic in self.type_def.ty.generics() {
+            getters.generic(generic.name());
+        }
+
+        if let Fields::Named(ref fields) = self.fields {
+            for field in fields {
+                let docs = if field.documentation.is_empty() {
+                    None
+                } else {
+                    Some(Docs::new(field.documentation.join("\n")))
+                };
+
+                let name = strip_vis(&field.name);
+
+                let mut getter = Function::new(name.to_string());
+                getter
+                    .vis("pub")
+                    .arg_ref_self()
+                    .ret(format!("&{}", field.ty.fmt_to_string()))
+                    .line(format!("&self.{}", name))
+                    .mark_accessor(docs);
+
+                getters.push_fn(getter);
+            }
+        }
+
+        getters
+    }
+
+    /// Returns an `impl std::fmt::Display` block writing the given template,
+    /// where `{field}` and `{field:spec}` placeholders are replaced with the
+    /// fields of the struct, e.g. `{name} <{email}>`. Tuple fields are named
+    /// by their index, e.g. `{0}`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template` - The template to write.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Scope,Struct};
+    /// 
+    /// let mut scope = Scope::new();
+    /// let mut user_struct = Struct::new("User");
+    /// user_struct.field("name", "String");
+    /// 
+    /// scope.push_impl(user_struct.display_impl("user {name}"));
+    /// scope.push_struct(user_struct);
+    /// ```
+    pub fn display_impl(&self, template: &str) -> Impl {
+        self.write_impl("std::fmt::Display", template)
+    }
+
+    /// Returns an `impl std::fmt::Debug` block writing the given template,
+    /// where `{field}` and `{field:spec}` placeholders are replaced with the
+    /// fields of the struct, e.g. `User({id:?})`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `template` - The template to write.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Scope,Struct};
+    /// 
+    /// let mut scope = Scope::new();
+    /// let mut user_struct = Struct::new("User");
+    /// user_struct.field("id", "u64");
+    /// 
+    /// scope.push_impl(user_struct.debug_impl("User({id:?})"));
+    /// scope.push_struct(user_struct);
+    /// ```
+    pub fn debug_impl(&self, template: &str) -> Impl {
+        self.write_impl("std::fmt::Debug", template)
+    }
+
+    /// Returns an `impl std::fmt::Debug` block using the `debug_struct`
+    /// builder with the given named fields. When fields are left out, e.g.
+    /// to hide a secret, the output ends with `..` through
+    /// `finish_non_exhaustive`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `fields` - The names of the fields to write, in order.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Scope,Struct};
+    /// 
+    /// let mut scope = Scope::new();
+    /// let mut user_struct = Struct::new("User");
+    /// user_struct.field("name", "String").field("password", "String");
+    /// 
+    /// scope.push_impl(user_struct.debug_struct_impl(["name"]));
+    /// scope.push_struct(user_struct);
+    /// ```
+    pub fn debug_struct_impl<I, T>(&self, fields: I) -> Impl
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let names = match self.fields {
+            Fields::Named(..) => self.field_names(),
+            _ => panic!("`debug_struct_impl` requires named fields"),
+        };
+
+        let mut body = format!("f.debug_struct({:?})", self.type_def.ty.name());
+        let mut used = 0;
+
+        for field in fields {
+            let field = field.as_ref();
+
+            if !names.iter().any(|name| name == field) {
+                panic!("unknown field `{}` in `debug_struct_impl`", field);
+            }
+
+            body.push_str(&format!("\n    .field({:?}, &self.{})", field, field));
+            used += 1;
+        }
+
+        if used < names.len() {
+            body.push_str("\n    .finish_non_exhaustive()");
+        } else {
+            body.push_str("\n    .finish()");
+        }
+
+        self.fmt_impl("std::fmt::Debug", body)
+    }
+
+    /// Returns `impl PartialEq` and `impl Eq` blocks comparing only the given
+    /// fields, e.g. to compare entities by their `id`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `fields` - The names of the fields to compare, in order.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Scope,Struct};
+    /// 
+    /// let mut scope = Scope::new();
+    /// let mut user_struct = Struct::new("User");
+    /// user_struct.field("id", "u64").field("name", "String");
+    /// 
+    /// for imp in user_struct.eq_impls(["id"]) {
+    ///     scope.push_impl(imp);
+    /// }
+    /// ```
+    pub fn eq_impls<I, T>(&self, fields: I) -> Vec<Impl>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let fields = self.key_fields(fields, "eq_impls");
+
+        let mut partial_eq = self.trait_impl("PartialEq");
+        partial_eq
+            .new_fn("eq")
+            .arg_ref_self()
+            .arg("other", "&Self")
+            .ret("bool")
+            .line(
+                fields
+                    .iter()
+                    .map(|field| format!("self.{} == other.{}", field, field))
+                    .collect::<Vec<_>>()
+                    .join("\n    && "),
+            );
+
+        vec![partial_eq, self.trait_impl("Eq")]
+    }
+
+    /// Returns `impl PartialOrd` and `impl Ord` blocks ordering by only the
+    /// given fields, compared in order.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `fields` - The names of the fields to compare, in order.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Scope,Struct};
+    /// 
+    /// let mut scope = Scope::new();
+    /// let mut user_struct = Struct::new("User");
+    /// user_struct.field("id", "u64").field("name", "String");
+    /// 
+    /// for imp in user_struct.ord_impls(["id"]) {
+    ///     scope.push_impl(imp);
+    /// }
+    /// ```
+    pub fn ord_impls<I, T>(&self, fields: I) -> Vec<Impl>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let fields = self.key_fields(fields, "ord_impls");
+
+        let mut partial_ord = self.trait_impl("PartialOrd");
+        partial_ord
+            .new_fn("partial_cmp")
+            .arg_ref_self()
+            .arg("other", "&Self")
+            .ret("Option<std::cmp::Ordering>")
+            .line("Some(self.cmp(other))");
+
+        let mut body = format!("self.{}.cmp(&other.{})", fields[0], fields[0]);
+
+        for field in &fields[1..] {
+            body.push_str(&format!(
+                "\n    .then_with(|| self.{}.cmp(&other.{}))",
+                field, field
+            ));
+        }
+
+        let mut ord = self.trait_impl("Ord");
+        ord.new_fn("cmp")
+            .arg_ref_self()
+            .arg("other", "&Self")
+            .ret("std::cmp::Ordering")
+            .line(body);
+
+        vec![partial_ord, ord]
+    }
+
+    /// Returns an `impl std::hash::Hash` block hashing only the given
+    /// fields, which should be the fields used by `eq_impls`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `fields` - The names of the fields to hash, in order.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Scope,Struct};
+    /// 
+    /// let mut scope = Scope::new();
+    /// let mut user_struct = Struct::new("User");
+    /// user_struct.field("id", "u64").field("name", "String");
+    /// 
+    /// scope.push_impl(user_struct.hash_impl(["id"]));
+    /// ```
+    pub fn hash_impl<I, T>(&self, fields: I) -> Impl
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let fields = self.key_fields(fields, "hash_impl");
+
+        let mut hash = self.trait_impl("std::hash::Hash");
+        let func = hash
+            .new_fn("hash")
+            .generic("H: std::hash::Hasher")
+            .arg_ref_self()
+            .arg("state", "&mut H");
+
+        for field in fields {
+            func.line(format!("self.{}.hash(state);", field));
+        }
+
+        hash
+    }
+
+    /// Returns the given fields, checking that they exist.
+    fn key_fields<I, T>(&self, fields: I, method: &str) -> Vec<String>
+    where
+        I: IntoIterator<Item = T>,
+        T: AsRef<str>,
+    {
+        let names = self.field_names();
+        let fields: Vec<String> = fields.into_iter().map(|f| f.as_ref().to_string()).collect();
+
+        assert!(!fields.is_empty(), "`{}` requires at least one field", method);
+
+        for field in &fields {
+            if !names.contains(field) {
+                panic!("unknown field `{}` in `{}`", field, method);
+            }
+        }
+
+        fields
+    }
+
+    /// Returns the fields of the struct.
+    pub(crate) fn fields(&self) -> &Fields {
+        &self.fields
+    }
+
+    /// Removes the named fields that aren't `pub`.
+    pub(crate) fn retain_public_fields(&mut self) {
+        if let Fields::Named(ref mut fields) = self.fields {
+            fields.retain(|field| field.name.starts_with("pub "));
+        }
+    }
+
+    /// Returns the names of the fields, with tuple fields named by their
+    /// index.
+    pub(crate) fn field_names(&self) -> Vec<String> {
+        match self.fields {
+            Fields::Empty => vec![],
+            Fields::Tuple(ref tys) => (0..tys.len()).map(|i| i.to_string()).collect(),
+            Fields::Named(ref fields) => fields
+                .iter()
+                .map(|field| strip_vis(&field.name).to_string())
+                .collect(),
+        }
+    }
+
+    /// Returns an impl block of the given formatting trait writing the given
+    /// field template.
+    fn write_impl(&self, r#trait: &str, template: &str) -> Impl {
+        let (format, args) = field_format_args(template, &self.field_names());
+
+        let mut body = format!("write!(f, \"{}\"", format);
+
+        for arg in args {
+            body.push_str(&format!(", self.{}", arg));
+        }
+
+        body.push(')');
+
+        self.fmt_impl(r#trait, body)
+    }
+
+    /// Returns an empty impl block of the given trait for the struct.
+    fn trait_impl(&self, r#trait: &str) -> Impl {
+        let mut imp = Impl::new(&self.type_def.ty);
+        imp.impl_trait(r#trait);
+
+        for generic in self.type_def.ty.generics() {
+            imp.generic(generic.name());
+        }
+
+        imp
+    }
+
+    /// Returns an impl block of the given formatting trait with the given
+    /// body in its `fmt` function.
+    fn fmt_impl(&self, r#trait: &str, body: String) -> Impl {
+        let mut imp = self.trait_impl(r#trait);
+
+        imp.new_fn("fmt")
+            .arg_ref_self()
+            .arg("f", "&mut std::fmt::Formatter<'_>")
+            .ret("std::fmt::Result")
+            .line(body);
+
+        imp
+    }
+
+    /// Applies the conventions of the given policy to the struct.
+    pub(crate) fn apply_policy(&mut self, policy: &Policy) {
+        self.type_def.derive_defaults(&policy.get_struct_derives());
+        self.type_def.attributes.sort_by_kind(policy.get_attribute_order());
+    }
+
+    /// Returns whether the struct has a C compatible layout.
+    pub(crate) fn is_repr_c(&self) -> bool {
+        self.type_def.is_repr_c()
+    }
+
+    /// Returns the head of the struct without docs, attributes, or
+    /// visibility, e.g. `struct Foo<T>`.
+    pub(crate) fn signature(&self) -> String {
+        self.type_def.signature("struct", &[])
+    }
+
+    /// Returns the type definition of the struct.
+    pub(crate) fn type_def(&self) -> &TypeDef {
+        &self.type_def
+    }
+
+    /// Calls the given function with every type of the struct and its fields.
+    pub(crate) fn visit_types_mut(&mut self, f: &mut dyn FnMut(&mut Type)) {
+        self.type_def.visit_types_mut(f);
+        self.fields.visit_types_mut(f);
+    }
+
+    /// Maps the visibility of the struct and its fields with the given function.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `f` - The function mapping the visibility.
+    pub(crate) fn map_visibility(&mut self, f: &dyn Fn(Visibility) -> Visibility) {
+        self.type_def.map_visibility(f);
+        self.fields.map_visibility(f);
+    }
+
+    /// Returns the struct as a string of code formatted with a fresh
+    /// formatter, e.g. to render it without a scope.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Struct;
+    /// 
+    /// let mut foo_struct = Struct::new("Foo");
+    /// foo_struct.field("bar", "u32");
+    /// 
+    /// assert_eq!(foo_struct.to_code_string(), "struct Foo {\n    bar: u32,\n}");
+    /// ```
+    pub fn to_code_string(&self) -> String {
+        fmt_to_code_string(|fmt| self.fmt(fmt))
+    }
+
+    /// Returns an estimate of the rendered length of the struct.
+    pub(crate) fn estimated_len(&self) -> usize {
+        self.type_def.estimated_len() + self.fields.estimated_len()
+    }
+
     /// Formats the struct using the given formatter.
     /// 
     /// # Arguments
@@ -301,10 +964,6 @@ impl Struct {
     /// let mut foo_struct = Struct::new("Foo");
     /// foo_struct.fmt(&mut fmt);
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        for m in self.attributes.iter() {
-            write!(fmt, "{}\n", m)?;
-        }
-        
         self.type_def.fmt_head("struct", &[], fmt)?;
         self.fields.fmt(fmt)?;
 
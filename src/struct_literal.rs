@@ -0,0 +1,191 @@
+use std::borrow::Cow;
+
+/// The maximum width of the fields of a struct literal rendered on a single
+/// line, matching the `struct_lit_width` default of rustfmt.
+const STRUCT_LIT_WIDTH: usize = 18;
+
+/// Defines a struct literal expression, e.g. `User { name, age: 3 }`.
+#[derive(Debug, Clone)]
+pub struct StructLiteral {
+    /// The path of the struct, e.g. `User` or `Self`.
+    path: Cow<'static, str>,
+    /// The fields, as `(name, value)` with no value for shorthand fields.
+    fields: Vec<(Cow<'static, str>, Option<Cow<'static, str>>)>,
+    /// The expression the remaining fields are taken from, e.g.
+    /// `Default::default()`.
+    spread: Option<Cow<'static, str>>,
+}
+
+impl StructLiteral {
+    /// Returns a new struct literal of the given struct.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path of the struct, e.g. `User` or `Self`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::StructLiteral;
+    ///
+    /// let literal = StructLiteral::new("User");
+    /// ```
+    pub fn new<T>(path: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        StructLiteral {
+            path: path.into(),
+            fields: vec![],
+            spread: None,
+        }
+    }
+
+    /// Set a field to the given expression. Fields set to a variable of the
+    /// same name use the shorthand `name` instead of `name: name`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the field.
+    /// * `value` - The expression of the field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::StructLiteral;
+    ///
+    /// let mut literal = StructLiteral::new("User");
+    /// literal.field("age", "3");
+    /// ```
+    pub fn field<N, V>(&mut self, name: N, value: V) -> &mut Self
+    where
+        N: Into<Cow<'static, str>>,
+        V: Into<Cow<'static, str>>,
+    {
+        let name = name.into();
+        let value = value.into();
+
+        if name == value {
+            self.fields.push((name, None));
+        } else {
+            self.fields.push((name, Some(value)));
+        }
+        self
+    }
+
+    /// Set a field with the shorthand `name`, taking the value of the
+    /// variable with the same name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the field and the variable.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::StructLiteral;
+    ///
+    /// let mut literal = StructLiteral::new("User");
+    /// literal.shorthand("name");
+    /// ```
+    pub fn shorthand<T>(&mut self, name: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.fields.push((name.into(), None));
+        self
+    }
+
+    /// Take the remaining fields from the given expression, rendered as
+    /// `..other` after the fields.
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - The expression to take the remaining fields from.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::StructLiteral;
+    ///
+    /// let mut literal = StructLiteral::new("User");
+    /// literal.field("age", "3").spread("other");
+    /// ```
+    pub fn spread<T>(&mut self, expr: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.spread = Some(expr.into());
+        self
+    }
+
+    /// Take the remaining fields from the default value of the struct,
+    /// rendered as `..Default::default()` after the fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::StructLiteral;
+    ///
+    /// let mut literal = StructLiteral::new("Config");
+    /// literal.field("port", "8080").spread_default();
+    /// ```
+    pub fn spread_default(&mut self) -> &mut Self {
+        self.spread("Default::default()")
+    }
+
+    /// Returns the struct literal as code, on a single line when the fields
+    /// are short and with a field per line otherwise, like rustfmt.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::StructLiteral;
+    ///
+    /// let mut literal = StructLiteral::new("User");
+    /// literal.shorthand("name").field("age", "3");
+    ///
+    /// assert_eq!(literal.to_code_string(), "User { name, age: 3 }");
+    /// ```
+    pub fn to_code_string(&self) -> String {
+        let mut parts: Vec<String> = self
+            .fields
+            .iter()
+            .map(|(name, value)| match value {
+                Some(value) => format!("{}: {}", name, value),
+                None => name.to_string(),
+            })
+            .collect();
+
+        if let Some(ref spread) = self.spread {
+            parts.push(format!("..{}", spread));
+        }
+
+        if parts.is_empty() {
+            return format!("{} {{}}", self.path);
+        }
+
+        let inline = parts.join(", ");
+
+        if inline.len() <= STRUCT_LIT_WIDTH && !inline.contains('\n') {
+            return format!("{} {{ {} }}", self.path, inline);
+        }
+
+        let mut ret = format!("{} {{\n", self.path);
+
+        for part in &parts {
+            ret.push_str("    ");
+            ret.push_str(&part.replace('\n', "\n    "));
+
+            // The spread can't have a trailing comma.
+            if !part.starts_with("..") {
+                ret.push(',');
+            }
+
+            ret.push('\n');
+        }
+
+        ret.push('}');
+        ret
+    }
+}
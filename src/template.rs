@@ -0,0 +1,158 @@
+/// Renders a body template, substituting every `{name}` placeholder with its
+/// value, and returns the resulting lines.
+///
+/// `{{` and `}}` are written as literal braces. The common indentation of the
+/// template is removed, as are leading and trailing blank lines, and the
+/// continuation lines of multi-line values are indented to match the line of
+/// their placeholder.
+///
+/// # Arguments
+///
+/// * `template` - The template to render.
+/// * `values` - The values of the placeholders as `(name, value)` pairs.
+pub(crate) fn render_template(template: &str, values: &[(&str, &str)]) -> Vec<String> {
+    let mut rendered = String::new();
+
+    for line in template.lines() {
+        let indent: String = line.chars().take_while(|c| c.is_whitespace()).collect();
+        let mut chars = line.chars().peekable();
+
+        while let Some(c) = chars.next() {
+            match c {
+                '{' if chars.peek() == Some(&'{') => {
+                    chars.next();
+                    rendered.push('{');
+                }
+                '}' if chars.peek() == Some(&'}') => {
+                    chars.next();
+                    rendered.push('}');
+                }
+                '{' => {
+                    let mut name = String::new();
+
+                    loop {
+                        match chars.next() {
+                            Some('}') => break,
+                            Some(c) => name.push(c),
+                            None => {
+                                panic!("unclosed placeholder `{{{}` in body template", name)
+                            }
+                        }
+                    }
+
+                    let value = values
+                        .iter()
+                        .find(|(n, _)| *n == name.trim())
+                        .map(|(_, v)| *v)
+                        .unwrap_or_else(|| {
+                            panic!("unknown placeholder `{{{}}}` in body template", name)
+                        });
+
+                    for (i, value_line) in value.lines().enumerate() {
+                        if i != 0 {
+                            rendered.push('\n');
+
+                            if !value_line.is_empty() {
+                                rendered.push_str(&indent);
+                            }
+                        }
+
+                        rendered.push_str(value_line);
+                    }
+                }
+                '}' => panic!("unmatched closing brace in body template, double it for a literal brace"),
+                c => rendered.push(c),
+            }
+        }
+
+        rendered.push('\n');
+    }
+
+    let lines: Vec<&str> = rendered.lines().collect();
+
+    let start = lines.iter().position(|l| !l.trim().is_empty());
+    let end = lines.iter().rposition(|l| !l.trim().is_empty());
+
+    let lines = match (start, end) {
+        (Some(start), Some(end)) => &lines[start..=end],
+        _ => return vec![],
+    };
+
+    let common_indent = lines
+        .iter()
+        .filter(|l| !l.trim().is_empty())
+        .map(|l| l.len() - l.trim_start().len())
+        .min()
+        .unwrap_or(0);
+
+    lines
+        .iter()
+        .map(|l| {
+            if l.trim().is_empty() {
+                String::new()
+            } else {
+                l[common_indent..].trim_end().to_string()
+            }
+        })
+        .collect()
+}
+
+/// Converts a format template with `{field}` or `{field:spec}` placeholders
+/// into a format string with positional `{}` or `{:spec}` placeholders,
+/// returning it along with the fields in the order they are used.
+///
+/// `{{` and `}}` are kept as literal braces.
+///
+/// # Arguments
+///
+/// * `template` - The template to convert.
+/// * `fields` - The names of the fields that can be used.
+pub(crate) fn field_format_args(template: &str, fields: &[String]) -> (String, Vec<String>) {
+    let mut format = String::new();
+    let mut args = vec![];
+    let mut chars = template.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '{' if chars.peek() == Some(&'{') => {
+                chars.next();
+                format.push_str("{{");
+            }
+            '}' if chars.peek() == Some(&'}') => {
+                chars.next();
+                format.push_str("}}");
+            }
+            '{' => {
+                let mut placeholder = String::new();
+
+                loop {
+                    match chars.next() {
+                        Some('}') => break,
+                        Some(c) => placeholder.push(c),
+                        None => panic!("unclosed placeholder `{{{}` in format template", placeholder),
+                    }
+                }
+
+                let (name, spec) = match placeholder.find(':') {
+                    Some(i) => (placeholder[..i].trim(), &placeholder[i..]),
+                    None => (placeholder.trim(), ""),
+                };
+
+                if !fields.iter().any(|field| field == name) {
+                    panic!("unknown field `{}` in format template", name);
+                }
+
+                format.push('{');
+                format.push_str(spec);
+                format.push('}');
+                args.push(name.to_string());
+            }
+            '"' => format.push_str("\\\""),
+            '\\' => format.push_str("\\\\"),
+            '\n' => format.push_str("\\n"),
+            c => format.push(c),
+        }
+    }
+
+    (format, args)
+}
@@ -0,0 +1,50 @@
+/// Defines the attribute that runs a test function, see
+/// [`Function::test_with`].
+///
+/// [`Function::test_with`]: crate::Function::test_with
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TestRunner {
+    /// `#[test]`.
+    Std,
+    /// `#[tokio::test]`, for async tests.
+    Tokio,
+    /// `#[async_std::test]`, for async tests.
+    AsyncStd,
+}
+
+impl TestRunner {
+    /// Every test runner, e.g. to replace the runner of a test.
+    pub(crate) const ALL: [TestRunner; 3] =
+        [TestRunner::Std, TestRunner::Tokio, TestRunner::AsyncStd];
+
+    /// Returns the attribute of the runner, without the surrounding `#[...]`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::TestRunner;
+    ///
+    /// assert_eq!(TestRunner::Tokio.meta(), "tokio::test");
+    /// ```
+    pub fn meta(&self) -> &'static str {
+        match *self {
+            TestRunner::Std => "test",
+            TestRunner::Tokio => "tokio::test",
+            TestRunner::AsyncStd => "async_std::test",
+        }
+    }
+
+    /// Returns whether the runner runs async test functions.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::TestRunner;
+    ///
+    /// assert!(TestRunner::AsyncStd.is_async());
+    /// assert!(!TestRunner::Std.is_async());
+    /// ```
+    pub fn is_async(&self) -> bool {
+        !matches!(*self, TestRunner::Std)
+    }
+}
@@ -1,11 +1,22 @@
+use std::borrow::Cow;
 use std::fmt::{self, Write};
 
+use crate::associated_const::AssociatedConst;
 use crate::associated_type::AssociatedType;
-use crate::bound::Bound;
-use crate::formatter::{fmt_bound_rhs, Formatter};
+use crate::async_variant::{AsyncTraitStrategy, AsyncVariant};
+use crate::block::Block;
+use crate::docs::DocTest;
+use crate::fields::Fields;
+use crate::formatter::{fmt_to_code_string, Formatter};
+use crate::fn_sig::FnSig;
 use crate::function::Function;
+use crate::transform::Transform;
+use crate::metadata::Metadata;
 use crate::type_def::TypeDef;
+use crate::visibility::Visibility;
 
+use crate::r#enum::Enum;
+use crate::r#impl::Impl;
 use crate::r#type::Type;
 
 /// Defines a trait.
@@ -18,10 +29,12 @@ pub struct Trait {
     parents: Vec<Type>,
     /// The trait's associated types.
     associated_tys: Vec<AssociatedType>,
+    /// The trait's associated constants.
+    assoc_consts: Vec<AssociatedConst>,
     /// The trait's functions.
     fns: Vec<Function>,
-    /// The trait's macros.
-    macros: Vec<String>,
+    /// Whether the trait is intended to be used as a `dyn` trait object.
+    dyn_compatible: bool,
 }
 
 impl Trait {
@@ -38,13 +51,17 @@ impl Trait {
     /// 
     /// let foo_trait = Trait::new("Foo");
     /// ```
-    pub fn new(name: &str) -> Self {
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
         Trait {
             type_def: TypeDef::new(name),
             parents: vec![],
             associated_tys: vec![],
+            assoc_consts: vec![],
             fns: vec![],
-            macros: vec![],
+            dyn_compatible: false,
         }
     }
 
@@ -62,6 +79,27 @@ impl Trait {
         &self.type_def.ty
     }
 
+    /// Returns the metadata of the trait, which is kept when it is cloned
+    /// and never rendered.
+    pub fn metadata(&self) -> &Metadata {
+        self.type_def.metadata()
+    }
+
+    /// Returns a mutable reference to the metadata of the trait, e.g. to tag
+    /// it in a multi-pass generator.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Trait;
+    /// 
+    /// let mut foo_trait = Trait::new("Foo");
+    /// foo_trait.metadata_mut().set("schema", "foo.json");
+    /// ```
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        self.type_def.metadata_mut()
+    }
+
     /// Set the trait visibility.
     /// 
     /// # Arguments
@@ -119,7 +157,31 @@ impl Trait {
         self
     }
 
-    /// Add a macro to the trait def (e.g. `"#[async_trait]"`).
+    /// Adds an attribute to the trait, without the surrounding `#[...]`
+    /// (e.g. `"must_use"`).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attribute` - The attribute to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Trait;
+    /// 
+    /// let mut foo_trait = Trait::new("Foo");
+    /// foo_trait.attr("must_use");
+    /// ```
+    pub fn attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.type_def.attr(attribute);
+        self
+    }
+
+    /// Add a macro to the trait def that is written as is (e.g.
+    /// `"#[async_trait]"`).
     /// 
     /// # Arguments 
     /// 
@@ -131,13 +193,40 @@ impl Trait {
     /// use rust_codegen::Trait;
     /// 
     /// let mut foo_trait = Trait::new("Foo");
-    /// foo_trait.r#macro("async_trait");
+    /// foo_trait.r#macro("#[async_trait]");
     /// ```
-    pub fn r#macro(&mut self, r#macro: &str) -> &mut Self {
+    pub fn r#macro<T>(&mut self, r#macro: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.type_def.r#macro(r#macro);
         self
     }
 
+    /// Add a tool attribute to the trait, e.g. `my_tool::interface` to mark a
+    /// generated interface. Tool attributes are rendered before every other
+    /// attribute, including macros such as `#[async_trait]`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attribute` - The attribute to add, without the surrounding `#[...]`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Trait;
+    /// 
+    /// let mut foo_trait = Trait::new("Foo");
+    /// foo_trait.tool_attr("my_tool::interface");
+    /// ```
+    pub fn tool_attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.type_def.attributes.marker(attribute);
+        self
+    }
+
     /// Add a parent trait.
     /// 
     /// # Arguments
@@ -174,11 +263,60 @@ impl Trait {
     /// let mut foo_trait = Trait::new("Foo");
     /// foo_trait.doc("Sample trait documentation.");
     /// ```
-    pub fn doc(&mut self, docs: &str) -> &mut Self {
+    pub fn doc<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
         self.type_def.doc(docs);
         self
     }
 
+    /// Set the trait documentation, written as is without escaping
+    /// markdown, e.g. for intra-doc links.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `docs` - The documentation to set for the trait.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Trait;
+    /// 
+    /// let mut foo_trait = Trait::new("Foo");
+    /// foo_trait.doc_raw("See [`Bar`].");
+    /// ```
+    pub fn doc_raw<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.type_def.doc_raw(docs);
+        self
+    }
+
+    /// Add an example to the trait documentation.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `code` - The code of the example.
+    /// * `mode` - Whether rustdoc should run, only compile, or ignore it.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{DocTest,Trait};
+    /// 
+    /// let mut foo_trait = Trait::new("Foo");
+    /// foo_trait.doc_example("let foo = Foo::default();", DocTest::Run);
+    /// ```
+    pub fn doc_example<T>(&mut self, code: T, mode: DocTest) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.type_def.doc_example(code, mode);
+        self
+    }
+
     /// Add an associated type. Returns a mutable reference to the new
     /// associated type for futher configuration.
     /// 
@@ -195,14 +333,80 @@ impl Trait {
     /// foo_trait.associated_type("A");
     /// ```
     pub fn associated_type(&mut self, name: &str) -> &mut AssociatedType {
-        self.associated_tys.push(AssociatedType(Bound {
-            name: name.to_string(),
-            bound: vec![],
-        }));
-
+        self.push_associated_type(AssociatedType::new(name.to_string()));
         self.associated_tys.last_mut().unwrap()
     }
 
+    /// Push an associated type.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `item` - The associated type to push.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{AssociatedType,Trait};
+    /// 
+    /// let mut foo_trait = Trait::new("Foo");
+    /// let mut item_type = AssociatedType::new("Item");
+    /// item_type.bound("Clone");
+    /// 
+    /// foo_trait.push_associated_type(item_type);
+    /// ```
+    pub fn push_associated_type(&mut self, item: AssociatedType) -> &mut Self {
+        self.associated_tys.push(item);
+        self
+    }
+
+    /// Push a new associated constant with a default value, returning a
+    /// mutable reference to it.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the associated constant.
+    /// * `ty` - The type of the associated constant.
+    /// * `value` - The default value of the associated constant.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Trait;
+    /// 
+    /// let mut foo_trait = Trait::new("Foo");
+    /// foo_trait.new_associate_const("MAX", "u8", "255");
+    /// ```
+    pub fn new_associate_const<N, T, V>(&mut self, name: N, ty: T, value: V) -> &mut AssociatedConst
+    where
+        N: Into<Cow<'static, str>>,
+        T: Into<Type>,
+        V: Into<Cow<'static, str>>,
+    {
+        self.push_associate_const(AssociatedConst::new(name, ty, value));
+        self.assoc_consts.last_mut().unwrap()
+    }
+
+    /// Push an associated constant.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `item` - The associated constant to push.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{AssociatedConst,Trait};
+    /// 
+    /// let mut foo_trait = Trait::new("Foo");
+    /// let max_const = AssociatedConst::new("MAX", "u8", "255");
+    /// 
+    /// foo_trait.push_associate_const(max_const);
+    /// ```
+    pub fn push_associate_const(&mut self, item: AssociatedConst) -> &mut Self {
+        self.assoc_consts.push(item);
+        self
+    }
+
     /// Push a new function definition, returning a mutable reference to it.
     /// 
     /// # Arguments
@@ -217,7 +421,10 @@ impl Trait {
     /// let mut foo_trait = Trait::new("Foo");
     /// foo_trait.new_fn("bar_fn");
     /// ```
-    pub fn new_fn(&mut self, name: &str) -> &mut Function {
+    pub fn new_fn<T>(&mut self, name: T) -> &mut Function
+    where
+        T: Into<Cow<'static, str>>,
+    {
         let mut func = Function::new(name);
         func.body = None;
 
@@ -225,6 +432,58 @@ impl Trait {
         self.fns.last_mut().unwrap()
     }
 
+    /// Returns a mutable reference to a function of the trait if it exists,
+    /// e.g. to edit its signature before [`Scope::sync_trait_sigs`].
+    ///
+    /// [`Scope::sync_trait_sigs`]: crate::Scope::sync_trait_sigs
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the function.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Trait;
+    /// 
+    /// let mut foo_trait = Trait::new("Foo");
+    /// foo_trait.new_fn("get");
+    /// 
+    /// foo_trait.get_fn_mut("get").unwrap().ret("u32");
+    /// ```
+    pub fn get_fn_mut<Q: ?Sized>(&mut self, name: &Q) -> Option<&mut Function>
+    where
+        str: PartialEq<Q>,
+    {
+        self.fns.iter_mut().find(|func| *func.name() == *name)
+    }
+
+    /// Push a function declaration with the given signature and no default
+    /// body, returning a mutable reference to it, e.g. to add docs.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `sig` - The signature of the function.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{FnSig,Trait};
+    /// 
+    /// let mut get_sig = FnSig::new("get");
+    /// get_sig.arg_ref_self().ret("u32");
+    /// 
+    /// let mut foo_trait = Trait::new("Foo");
+    /// foo_trait.push_sig(get_sig);
+    /// ```
+    pub fn push_sig(&mut self, sig: FnSig) -> &mut Function {
+        let mut func = Function::from_sig(sig);
+        func.body = None;
+
+        self.push_fn(func);
+        self.fns.last_mut().unwrap()
+    }
+
     /// Push a function definition.
     /// 
     /// # Arguments
@@ -246,6 +505,343 @@ impl Trait {
         self
     }
 
+    /// Marks the trait as intended to be used as a `dyn` trait object, so
+    /// that [`Scope::check_object_safety`] reports the constructs that
+    /// prevent it.
+    /// 
+    /// [`Scope::check_object_safety`]: crate::Scope::check_object_safety
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Trait;
+    /// 
+    /// let mut foo_trait = Trait::new("Foo");
+    /// foo_trait.dyn_compatible();
+    /// ```
+    pub fn dyn_compatible(&mut self) -> &mut Self {
+        self.dyn_compatible = true;
+        self
+    }
+
+    /// Returns whether the trait is intended to be used as a `dyn` trait
+    /// object.
+    pub(crate) fn is_dyn_compatible(&self) -> bool {
+        self.dyn_compatible
+    }
+
+    /// Returns a description of every construct of the trait that prevents
+    /// using it as a `dyn` trait object: a `Sized` supertrait, associated
+    /// constants, and functions that can't be called on a trait object.
+    pub(crate) fn object_safety_violations(&self) -> Vec<String> {
+        let name = self.type_def.ty.name();
+        let mut violations = vec![];
+
+        if self.parents.iter().any(|ty| ty.name() == "Sized") {
+            violations.push(format!("`{}` requires `Self: Sized`", name));
+        }
+
+        for r#const in &self.assoc_consts {
+            violations.push(format!(
+                "`{}` has the associated constant `{}`",
+                name,
+                r#const.name()
+            ));
+        }
+
+        for func in &self.fns {
+            violations.extend(func.object_safety_violations(name));
+        }
+
+        violations
+    }
+
+    /// Returns an async version of the trait where every function has been
+    /// converted with [`Function::to_async`].
+    ///
+    /// # Arguments
+    ///
+    /// * `variant` - The configuration of the async variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{AsyncVariant,Trait};
+    ///
+    /// let mut foo_trait = Trait::new("Foo");
+    /// foo_trait.new_fn("bar_fn");
+    ///
+    /// let mut variant = AsyncVariant::new();
+    /// variant.async_trait(true);
+    ///
+    /// let async_foo_trait = foo_trait.to_async(&variant);
+    /// ```
+    pub fn to_async(&self, variant: &AsyncVariant) -> Trait {
+        let mut r#trait = self.clone();
+
+        r#trait.fns = self.fns.iter().map(|f| f.to_async(variant)).collect();
+
+        if variant.uses_async_trait() {
+            r#trait.type_def.r#macro("#[async_trait]");
+        }
+
+        r#trait
+    }
+
+    /// Returns whether any of the trait's functions are async.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Trait;
+    /// 
+    /// let mut foo_trait = Trait::new("Foo");
+    /// foo_trait.new_fn("bar_fn").set_async(true);
+    /// 
+    /// assert!(foo_trait.has_async_fns());
+    /// ```
+    pub fn has_async_fns(&self) -> bool {
+        self.fns.iter().any(|f| f.is_async())
+    }
+
+    /// Returns an impl block of the trait for the given wrapper type that
+    /// delegates every function to the given field, e.g.
+    /// `self.inner.get(key)`, awaiting async functions. The field is moved
+    /// out of the wrapper by functions taking `self`. Functions without a
+    /// `self` argument get a `todo!()` body, and the associated types of the
+    /// trait have to be added to the impl block.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `target` - The wrapper type.
+    /// * `field` - The field of the wrapper to delegate to, e.g. `inner` or
+    ///   `0`.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Trait;
+    /// 
+    /// let mut store = Trait::new("Store");
+    /// store.new_fn("get").arg_ref_self().arg("key", "&str").ret("Option<String>");
+    /// 
+    /// let delegate = store.delegate_impl("Cached", "inner");
+    /// 
+    /// assert_eq!(
+    ///     delegate.to_code_string(),
+    ///     "impl Store for Cached {\n    fn get(&self, key: &str) -> Option<String> {\n        self.inner.get(key)\n    }\n}"
+    /// );
+    /// ```
+    pub fn delegate_impl<T>(&self, target: T, field: &str) -> Impl
+    where
+        T: Into<Type>,
+    {
+        let mut delegate = Impl::new(target);
+        delegate.impl_trait(&self.type_def.ty);
+
+        if self.type_def.has_macro("#[async_trait]") {
+            delegate.r#macro("#[async_trait]");
+        }
+
+        let receiver = format!("self.{}", field);
+
+        for func in &self.fns {
+            let mut delegated = func.to_impl_fn();
+
+            match func.method_call(&receiver) {
+                Some(call) => delegated.line(call),
+                None => delegated.line("todo!()"),
+            };
+
+            delegate.push_fn(delegated);
+        }
+
+        delegate
+    }
+
+    /// Returns an impl block of the trait for the given enum that matches on
+    /// the variants and forwards every function to the value wrapped by the
+    /// variant, awaiting async functions, for static dispatch over the types
+    /// implementing the trait. Functions without a `self` argument get a
+    /// `todo!()` body, and the associated types of the trait have to be
+    /// added to the impl block.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `target` - The enum, whose variants each wrap a single type
+    ///   implementing the trait, e.g. `Http(HttpHandler)`.
+    /// 
+    /// # Panics
+    /// 
+    /// If a variant of the enum doesn't wrap a single type.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Enum, Trait};
+    /// 
+    /// let mut handler = Trait::new("Handler");
+    /// handler.new_fn("name").arg_ref_self().ret("&str");
+    /// 
+    /// let mut plugin = Enum::new("Plugin");
+    /// plugin.new_variant("Http").tuple("HttpHandler");
+    /// 
+    /// let dispatch = handler.dispatch_impl(&plugin);
+    /// ```
+    pub fn dispatch_impl(&self, target: &Enum) -> Impl {
+        let mut dispatch = Impl::new(target.ty());
+
+        for generic in target.ty().generics() {
+            dispatch.generic(generic.name());
+        }
+
+        dispatch.impl_trait(&self.type_def.ty);
+
+        if self.type_def.has_macro("#[async_trait]") {
+            dispatch.r#macro("#[async_trait]");
+        }
+
+        for variant in target.variants() {
+            match *variant.fields() {
+                Fields::Tuple(ref tys) if tys.len() == 1 => {}
+                _ => panic!(
+                    "variant `{}` must wrap a single type to dispatch `{}`",
+                    variant.name(),
+                    self.type_def.ty.name()
+                ),
+            }
+        }
+
+        for func in &self.fns {
+            let mut dispatched = func.to_impl_fn();
+
+            match func.method_call("inner") {
+                Some(call) => {
+                    let mut arms = Block::new("match self");
+
+                    for variant in target.variants() {
+                        arms.line(format!("Self::{}(inner) => {},", variant.name(), call));
+                    }
+
+                    dispatched.push_block(arms);
+                }
+                None => {
+                    dispatched.line("todo!()");
+                }
+            }
+
+            dispatch.push_fn(dispatched);
+        }
+
+        dispatch
+    }
+
+    /// Adds or removes `#[async_trait]` according to the given strategy.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `strategy` - The async trait strategy to apply.
+    pub(crate) fn apply_async_trait_strategy(&mut self, strategy: AsyncTraitStrategy) {
+        match strategy {
+            AsyncTraitStrategy::Native => self.type_def.remove_macro("#[async_trait]"),
+            AsyncTraitStrategy::AsyncTrait => {
+                if self.has_async_fns() && !self.type_def.has_macro("#[async_trait]") {
+                    self.type_def.r#macro("#[async_trait]");
+                }
+            }
+        }
+    }
+
+    /// Returns the head of the trait without docs, attributes, or
+    /// visibility, e.g. `trait Foo<T>`.
+    pub(crate) fn signature(&self) -> String {
+        self.type_def.signature("trait", &self.parents)
+    }
+
+    /// Returns the type definition of the trait.
+    pub(crate) fn type_def(&self) -> &TypeDef {
+        &self.type_def
+    }
+
+    /// Empties the default bodies of the trait's functions.
+    pub(crate) fn strip_bodies(&mut self) {
+        for func in &mut self.fns {
+            func.strip_body();
+        }
+    }
+
+    /// Returns the functions of the trait.
+    pub(crate) fn fns(&self) -> &[Function] {
+        &self.fns
+    }
+
+    /// Calls the given function with every type of the trait, its parents,
+    /// associated types and constants, and its functions.
+    pub(crate) fn visit_types_mut(&mut self, f: &mut dyn FnMut(&mut Type)) {
+        self.type_def.visit_types_mut(f);
+
+        for ty in &mut self.parents {
+            ty.visit_types_mut(f);
+        }
+
+        for assoc in &mut self.associated_tys {
+            assoc.visit_types_mut(f);
+        }
+
+        for r#const in &mut self.assoc_consts {
+            r#const.visit_types_mut(f);
+        }
+
+        for func in &mut self.fns {
+            func.visit_types_mut(f);
+        }
+    }
+
+    /// Applies the given transform to the functions of the trait.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `transform` - The transform to apply.
+    pub(crate) fn transform(&mut self, transform: &Transform) {
+        for func in &mut self.fns {
+            func.transform(transform);
+        }
+    }
+
+    /// Maps the visibility of the trait with the given function.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `f` - The function mapping the visibility.
+    pub(crate) fn map_visibility(&mut self, f: &dyn Fn(Visibility) -> Visibility) {
+        self.type_def.map_visibility(f);
+    }
+
+    /// Returns the trait as a string of code formatted with a fresh
+    /// formatter, e.g. to render it without a scope.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Trait;
+    /// 
+    /// let mut foo_trait = Trait::new("Foo");
+    /// foo_trait.new_fn("bar");
+    /// 
+    /// assert_eq!(foo_trait.to_code_string(), "trait Foo {\n    fn bar();\n}");
+    /// ```
+    pub fn to_code_string(&self) -> String {
+        fmt_to_code_string(|fmt| self.fmt(fmt))
+    }
+
+    /// Returns an estimate of the rendered length of the trait.
+    pub(crate) fn estimated_len(&self) -> usize {
+        let fns: usize = self.fns.iter().map(|func| func.estimated_len() + 5).sum();
+        let assoc = (self.associated_tys.len() + self.assoc_consts.len()) * 32;
+
+        self.type_def.estimated_len() + self.parents.len() * 16 + fns + assoc
+    }
+
     /// Formats the scope using the given formatter.
     /// 
     /// # Arguments
@@ -272,21 +868,16 @@ impl Trait {
             // format associated types
             if !assoc.is_empty() {
                 for ty in assoc {
-                    let ty = &ty.0;
-
-                    write!(fmt, "type {}", ty.name)?;
-
-                    if !ty.bound.is_empty() {
-                        write!(fmt, ": ")?;
-                        fmt_bound_rhs(&ty.bound, fmt)?;
-                    }
-
-                    write!(fmt, ";\n")?;
+                    ty.fmt(fmt)?;
                 }
             }
 
+            for r#const in &self.assoc_consts {
+                r#const.fmt(fmt)?;
+            }
+
             for (i, func) in self.fns.iter().enumerate() {
-                if i != 0 || !assoc.is_empty() {
+                if i != 0 || !assoc.is_empty() || !self.assoc_consts.is_empty() {
                     write!(fmt, "\n")?;
                 }
 
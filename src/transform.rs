@@ -0,0 +1,233 @@
+use std::borrow::Cow;
+
+/// Defines a rule applied to the lines of function bodies.
+#[derive(Debug, Clone)]
+enum Rule {
+    /// Replace every occurrence of a string with another.
+    Replace(Cow<'static, str>, Cow<'static, str>),
+    /// Replace `.unwrap()` and `.expect(...)` calls with `?`.
+    UnwrapToTry,
+    /// Remove `.await`.
+    StripAwait,
+}
+
+/// Defines the rules used to derive another version of a scope with
+/// [`Scope::transformed`], e.g. a sync or panic-free version of the same
+/// generated code.
+///
+/// The rules are textual and apply to the lines of function bodies and the
+/// code before and after their blocks, in the order they were added.
+///
+/// [`Scope::transformed`]: crate::Scope::transformed
+#[derive(Debug, Clone, Default)]
+pub struct Transform {
+    /// The rules applied to each line, in the order they were added.
+    rules: Vec<Rule>,
+    /// Whether to remove `async` from functions.
+    strip_async: bool,
+}
+
+impl Transform {
+    /// Returns a new transform without rules.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Transform;
+    ///
+    /// let transform = Transform::new();
+    /// ```
+    pub fn new() -> Self {
+        Transform {
+            rules: vec![],
+            strip_async: false,
+        }
+    }
+
+    /// Replace every occurrence of the given code in function bodies.
+    ///
+    /// # Arguments
+    ///
+    /// * `from` - The code to replace.
+    /// * `to` - The code to replace it with.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Transform;
+    ///
+    /// let mut transform = Transform::new();
+    /// transform.replace("reqwest::Client", "reqwest::blocking::Client");
+    /// ```
+    pub fn replace<F, T>(&mut self, from: F, to: T) -> &mut Self
+    where
+        F: Into<Cow<'static, str>>,
+        T: Into<Cow<'static, str>>,
+    {
+        let from = from.into();
+
+        assert!(!from.is_empty(), "the code to replace can't be empty");
+
+        self.rules.push(Rule::Replace(from, to.into()));
+        self
+    }
+
+    /// Replace `.unwrap()` and `.expect(...)` calls in function bodies with
+    /// `?` to propagate the errors instead of panicking.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Transform;
+    ///
+    /// let mut transform = Transform::new();
+    /// transform.unwrap_to_try();
+    /// ```
+    pub fn unwrap_to_try(&mut self) -> &mut Self {
+        self.rules.push(Rule::UnwrapToTry);
+        self
+    }
+
+    /// Remove `async` from functions and `.await` from function bodies.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Transform;
+    ///
+    /// let mut transform = Transform::new();
+    /// transform.strip_async();
+    /// ```
+    pub fn strip_async(&mut self) -> &mut Self {
+        self.strip_async = true;
+        self.rules.push(Rule::StripAwait);
+        self
+    }
+
+    /// Returns whether `async` is removed from functions.
+    pub(crate) fn strips_async(&self) -> bool {
+        self.strip_async
+    }
+
+    /// Returns the given line with every rule applied.
+    ///
+    /// # Arguments
+    ///
+    /// * `line` - The line to transform.
+    pub(crate) fn apply(&self, line: &str) -> String {
+        let mut ret = line.to_string();
+
+        for rule in &self.rules {
+            ret = match rule {
+                Rule::Replace(from, to) => ret.replace(&**from, to),
+                Rule::UnwrapToTry => {
+                    let ret = remove_calls(&ret, ".unwrap(", "?");
+                    remove_calls(&ret, ".expect(", "?")
+                }
+                Rule::StripAwait => remove_keyword(&ret, ".await"),
+            };
+        }
+
+        ret
+    }
+}
+
+/// Returns the given code with every call starting with the given prefix,
+/// up to its closing parenthesis, replaced.
+///
+/// # Arguments
+///
+/// * `code` - The code to search.
+/// * `prefix` - The start of the call, including the opening parenthesis.
+/// * `to` - The code to replace the call with.
+fn remove_calls(code: &str, prefix: &str, to: &str) -> String {
+    let mut ret = String::with_capacity(code.len());
+    let mut rest = code;
+
+    while let Some(start) = rest.find(prefix) {
+        ret.push_str(&rest[..start]);
+
+        let args = &rest[start + prefix.len()..];
+
+        match closing_paren(args) {
+            Some(end) => {
+                ret.push_str(to);
+                rest = &args[end + 1..];
+            }
+            None => {
+                // Leave unbalanced calls as is.
+                ret.push_str(&rest[start..]);
+                rest = "";
+            }
+        }
+    }
+
+    ret.push_str(rest);
+    ret
+}
+
+/// Returns the index of the parenthesis closing the arguments of a call,
+/// skipping over string literals.
+///
+/// # Arguments
+///
+/// * `args` - The code after the opening parenthesis.
+fn closing_paren(args: &str) -> Option<usize> {
+    let mut depth = 0;
+    let mut in_str = false;
+    let mut escaped = false;
+
+    for (i, c) in args.char_indices() {
+        if in_str {
+            match c {
+                '\\' if !escaped => escaped = true,
+                '"' if !escaped => in_str = false,
+                _ => escaped = false,
+            }
+
+            continue;
+        }
+
+        match c {
+            '"' => in_str = true,
+            '(' => depth += 1,
+            ')' if depth == 0 => return Some(i),
+            ')' => depth -= 1,
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Returns the given code with every occurrence of the given keyword that
+/// isn't the start of a longer name removed, e.g. `.await` but not
+/// `.await_all()`.
+///
+/// # Arguments
+///
+/// * `code` - The code to search.
+/// * `keyword` - The keyword to remove.
+fn remove_keyword(code: &str, keyword: &str) -> String {
+    let mut ret = String::with_capacity(code.len());
+    let mut rest = code;
+
+    while let Some(start) = rest.find(keyword) {
+        let after = &rest[start + keyword.len()..];
+        let longer = match after.chars().next() {
+            Some(c) => c.is_alphanumeric() || c == '_',
+            None => false,
+        };
+
+        ret.push_str(&rest[..start]);
+
+        if longer {
+            ret.push_str(keyword);
+        }
+
+        rest = after;
+    }
+
+    ret.push_str(rest);
+    ret
+}
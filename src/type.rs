@@ -1,14 +1,42 @@
+use std::borrow::Cow;
 use std::fmt::{self, Write};
+use std::ops::Deref;
+use std::sync::Arc;
 
-use crate::formatter::Formatter;
+use crate::formatter::{fmt_bound_rhs, Formatter};
+use crate::path_rewrite::PathRewrite;
+use crate::visibility::{map_prefix_vis, Visibility};
+
+/// Defines the name of a type, which is either its own or shared with the
+/// other types interned by a `TypePool`.
+#[derive(Debug, Clone)]
+enum TypeName {
+    /// A string literal or an owned name.
+    Cow(Cow<'static, str>),
+    /// A name interned by a `TypePool`.
+    Shared(Arc<str>),
+}
+
+impl Deref for TypeName {
+    type Target = str;
+
+    fn deref(&self) -> &str {
+        match *self {
+            TypeName::Cow(ref name) => name,
+            TypeName::Shared(ref name) => name,
+        }
+    }
+}
 
 /// Defines a type.
 #[derive(Debug, Clone)]
 pub struct Type {
     /// The name of the type.
-    name: String,
+    name: TypeName,
     /// The type's generics.
     generics: Vec<Type>,
+    /// The bounds of an `impl Trait` type.
+    impl_bounds: Vec<Type>,
 }
 
 impl Type {
@@ -25,13 +53,61 @@ impl Type {
     /// 
     /// let foo_type = Type::new("Foo");
     /// ```
-    pub fn new(name: &str) -> Self {
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        Type {
+            name: TypeName::Cow(name.into()),
+            generics: vec![],
+            impl_bounds: vec![],
+        }
+    }
+
+    /// Returns a new type with the given name interned by a `TypePool`.
+    pub(crate) fn shared(name: Arc<str>) -> Self {
         Type {
-            name: name.to_string(),
+            name: TypeName::Shared(name),
             generics: vec![],
+            impl_bounds: vec![],
         }
     }
 
+    /// Return a new `impl Trait` type with the given bounds.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `bounds` - The traits that the type implements.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Type;
+    /// 
+    /// let reader_type = Type::impl_trait(["Read", "Send"]);
+    /// ```
+    pub fn impl_trait<I, T>(bounds: I) -> Self
+    where
+        I: IntoIterator<Item = T>,
+        T: Into<Type>,
+    {
+        Type {
+            name: TypeName::Cow(Cow::Borrowed("impl")),
+            generics: vec![],
+            impl_bounds: bounds.into_iter().map(Into::into).collect(),
+        }
+    }
+
+    /// Returns the name of the type without its generics.
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the type's generics.
+    pub(crate) fn generics(&self) -> &[Type] {
+        &self.generics
+    }
+
     /// Add a generic to the type.
     /// 
     /// # Arguments
@@ -60,6 +136,67 @@ impl Type {
         self
     }
 
+    /// Calls the given function with the type and every type nested in its
+    /// generics and `impl Trait` bounds.
+    pub(crate) fn visit_types_mut(&mut self, f: &mut dyn FnMut(&mut Type)) {
+        f(self);
+
+        for ty in self.generics.iter_mut().chain(self.impl_bounds.iter_mut()) {
+            ty.visit_types_mut(f);
+        }
+    }
+
+    /// Rewrites the path of the type. The types nested in its generics are
+    /// left as is, use `visit_types_mut` to rewrite them as well.
+    ///
+    /// # Arguments
+    ///
+    /// * `rewrite` - The path prefixes to rewrite.
+    pub(crate) fn rewrite_path(&mut self, rewrite: &PathRewrite) {
+        let rewritten = rewrite.apply(&self.name);
+
+        if rewritten != *self.name {
+            self.name = TypeName::Cow(rewritten.into());
+        }
+    }
+
+    /// Names the elided lifetimes of the references in the type and the
+    /// types nested in it with the given lifetime, e.g. `&str` becomes
+    /// `&'a str` and `Cow<'_, str>` becomes `Cow<'a, str>`. Returns whether
+    /// any lifetime was named.
+    ///
+    /// # Arguments
+    ///
+    /// * `lifetime` - The lifetime to use, e.g. `'a`.
+    pub(crate) fn name_elided_lifetimes(&mut self, lifetime: &str) -> bool {
+        let mut named = false;
+
+        self.visit_types_mut(&mut |ty| {
+            let renamed = name_elided_lifetimes(&ty.name, lifetime);
+
+            if renamed != *ty.name {
+                ty.name = TypeName::Cow(renamed.into());
+                named = true;
+            }
+        });
+
+        named
+    }
+
+    /// Maps the visibility written before the name of the type, e.g. for the
+    /// `pub u64` field of a tuple struct, with the given function.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `f` - The function mapping the visibility.
+    pub(crate) fn map_prefix_vis(&mut self, f: &dyn Fn(Visibility) -> Visibility) {
+        let mapped = map_prefix_vis(&self.name, f);
+
+        if mapped != *self.name {
+            self.name = TypeName::Cow(mapped.into());
+        }
+    }
+
     /// Formats the struct using the given formatter.
     /// 
     /// # Examples
@@ -73,10 +210,44 @@ impl Type {
     /// let mut foo_type = Type::new("Foo");
     /// foo_type.fmt(&mut fmt);
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        write!(fmt, "{}", self.name)?;
+        if !self.impl_bounds.is_empty() {
+            write!(fmt, "impl ")?;
+            return fmt_bound_rhs(&self.impl_bounds, fmt);
+        }
+
+        write!(fmt, "{}", &*self.name)?;
         Type::fmt_slice(&self.generics, fmt)
     }
 
+    /// Returns the type rendered as code.
+    pub(crate) fn fmt_to_string(&self) -> String {
+        let mut ret = String::new();
+        self.fmt(&mut Formatter::new(&mut ret)).unwrap();
+        ret
+    }
+
+    /// Collects every identifier and lifetime that appears in the type,
+    /// including in its generics.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `idents` - The list to add the identifiers to.
+    pub(crate) fn collect_idents(&self, idents: &mut Vec<String>) {
+        let mut ident = String::new();
+
+        for c in self.name.chars().chain(std::iter::once(' ')) {
+            if c.is_alphanumeric() || c == '_' || (c == '\'' && ident.is_empty()) {
+                ident.push(c);
+            } else if !ident.is_empty() {
+                idents.push(std::mem::take(&mut ident));
+            }
+        }
+
+        for ty in self.generics.iter().chain(self.impl_bounds.iter()) {
+            ty.collect_idents(idents);
+        }
+    }
+
     /// Formats the type using the given formatter with the given generics.
     /// 
     /// # Arguments
@@ -101,23 +272,65 @@ impl Type {
     }
 }
 
+// Borrowed strings are copied, as they may not live for `'static`. Use
+// `Type::new` with a string literal to store it without copying.
 impl<'a> From<&'a str> for Type {
     fn from(src: &'a str) -> Self {
-        Type::new(src)
+        Type::new(src.to_string())
     }
 }
 
+/// Names the elided lifetimes of the references in the given type name.
+///
+/// # Arguments
+///
+/// * `name` - The type name.
+/// * `lifetime` - The lifetime to use, e.g. `'a`.
+fn name_elided_lifetimes(name: &str, lifetime: &str) -> String {
+    let mut ret = String::with_capacity(name.len());
+    let mut chars = name.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match c {
+            '&' => {
+                ret.push('&');
+
+                if chars.peek() != Some(&'\'') {
+                    ret.push_str(lifetime);
+                    ret.push(' ');
+                }
+            }
+            '\'' if chars.peek() == Some(&'_') => {
+                chars.next();
+
+                // `'_x` is a lifetime of its own.
+                if matches!(chars.peek(), Some(c) if c.is_alphanumeric() || *c == '_') {
+                    ret.push_str("'_");
+                } else {
+                    ret.push_str(lifetime);
+                }
+            }
+            _ => ret.push(c),
+        }
+    }
+
+    ret
+}
+
 impl From<String> for Type {
     fn from(src: String) -> Self {
-        Type {
-            name: src,
-            generics: vec![],
-        }
+        Type::new(src)
     }
 }
 
 impl<'a> From<&'a String> for Type {
     fn from(src: &'a String) -> Self {
+        Type::new(src.clone())
+    }
+}
+
+impl From<Cow<'static, str>> for Type {
+    fn from(src: Cow<'static, str>) -> Self {
         Type::new(src)
     }
 }
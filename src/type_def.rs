@@ -1,8 +1,12 @@
+use std::borrow::Cow;
 use std::fmt::{self, Write};
 
+use crate::attributes::Attributes;
 use crate::bound::Bound;
-use crate::docs::Docs;
-use crate::formatter::{fmt_bounds, Formatter};
+use crate::docs::{DocTest, Docs};
+use crate::formatter::{fmt_bounds, fmt_to_code_string, Formatter};
+use crate::metadata::Metadata;
+use crate::visibility::{map_vis, Visibility};
 
 use crate::r#type::Type;
 
@@ -15,16 +19,15 @@ pub struct TypeDef {
     vis: Option<String>,
     /// The type definition's documentation.
     docs: Option<Docs>,
-    /// The types that the type definition should derive.
-    derive: Vec<String>,
-    /// The lint attribute to supress a warning or error for the type definition.
-    allow: Vec<String>,
-    /// The type definition's representation.
-    repr: Option<String>,
     /// The type definition's bounds.
     bounds: Vec<Bound>,
-    /// The type definition's macros.
-    macros: Vec<String>,
+    /// The type definition's attributes, including derives, lints, the
+    /// representation, and macros, in the order they were added.
+    pub attributes: Attributes,
+    /// Whether the default derives of the scope's policy are skipped.
+    no_default_derives: bool,
+    /// The type definition's metadata, which is never rendered.
+    metadata: Metadata,
 }
 
 impl TypeDef {
@@ -33,19 +36,75 @@ impl TypeDef {
     /// # Arguments
     /// 
     /// * `name` - The name of the type definition.
-    pub fn new(name: &str) -> Self {
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
         TypeDef {
             ty: Type::new(name),
             vis: None,
             docs: None,
-            derive: vec![],
-            allow: vec![],
-            repr: None,
             bounds: vec![],
-            macros: vec![],
+            attributes: Attributes::new(),
+            no_default_derives: false,
+            metadata: Metadata::new(),
         }
     }
 
+    /// Opts the type definition out of the default derives of the scope's
+    /// policy.
+    pub fn no_default_derives(&mut self) {
+        self.no_default_derives = true;
+    }
+
+    /// Adds the given default derives unless the type definition opted out.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `defaults` - The names of the types to derive.
+    pub(crate) fn derive_defaults(&mut self, defaults: &[Cow<'static, str>]) {
+        if !self.no_default_derives {
+            self.attributes.derive_defaults(defaults);
+        }
+    }
+
+    /// Returns the metadata of the type definition.
+    pub fn metadata(&self) -> &Metadata {
+        &self.metadata
+    }
+
+    /// Returns a mutable reference to the metadata of the type definition.
+    pub fn metadata_mut(&mut self) -> &mut Metadata {
+        &mut self.metadata
+    }
+
+    /// Returns the visibility of the type definition.
+    pub(crate) fn get_vis(&self) -> Option<&str> {
+        self.vis.as_deref()
+    }
+
+    /// Returns whether the type definition is documented.
+    pub(crate) fn has_docs(&self) -> bool {
+        self.docs.is_some()
+    }
+
+    /// Returns the head of the type definition without docs, attributes, or
+    /// visibility, e.g. `struct Foo<T> where T: Clone`.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `keyword` - The keyword of the definition, e.g. `struct`.
+    /// * `parents` - The parent traits of a trait definition.
+    pub(crate) fn signature(&self, keyword: &str, parents: &[Type]) -> String {
+        let mut def = self.clone();
+
+        def.docs = None;
+        def.attributes = Attributes::new();
+        def.vis = None;
+
+        fmt_to_code_string(|fmt| def.fmt_head(keyword, parents, fmt))
+    }
+
     /// Sets the visibility of the type definition.
     /// 
     /// # Arguments
@@ -76,8 +135,42 @@ impl TypeDef {
     /// # Arguments
     /// 
     /// * `macro` - The macro to add.
-    pub fn r#macro(&mut self, r#macro: &str) {
-        self.macros.push(r#macro.to_string());
+    pub fn r#macro<T>(&mut self, r#macro: T)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.raw(r#macro);
+    }
+
+    /// Returns whether the type definition has the given macro.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `r#macro` - The macro to look for.
+    pub fn has_macro(&self, r#macro: &str) -> bool {
+        self.attributes.has_raw(r#macro)
+    }
+
+    /// Removes every occurrence of the given macro from the type definition.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `r#macro` - The macro to remove.
+    pub fn remove_macro(&mut self, r#macro: &str) {
+        self.attributes.remove_raw(r#macro);
+    }
+
+    /// Adds an attribute to the type definition, without the surrounding
+    /// `#[...]` (e.g. `"some_attribute"`).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attribute` - The attribute to add.
+    pub fn attr<T>(&mut self, attribute: T)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.meta(attribute);
     }
 
     /// Adds documentation to the type definition.
@@ -85,8 +178,44 @@ impl TypeDef {
     /// * `docs` - The docs to add.
     /// 
     /// # Examples
-    pub fn doc(&mut self, docs: &str) {
-        self.docs = Some(Docs::new(docs));
+    pub fn doc<T>(&mut self, docs: T)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        match self.docs {
+            Some(ref mut existing) => existing.set_text(docs, false),
+            None => self.docs = Some(Docs::new(docs)),
+        }
+    }
+
+    /// Adds documentation to the type definition that is written as is,
+    /// without escaping markdown.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `docs` - The docs to add.
+    pub fn doc_raw<T>(&mut self, docs: T)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.docs
+            .get_or_insert_with(|| Docs::new(""))
+            .set_text(docs, true);
+    }
+
+    /// Adds an example to the documentation of the type definition.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `code` - The code of the example.
+    /// * `mode` - Whether rustdoc should run, only compile, or ignore it.
+    pub fn doc_example<T>(&mut self, code: T, mode: DocTest)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.docs
+            .get_or_insert_with(|| Docs::new(""))
+            .example(code, mode);
     }
 
     /// Add a new type that the type definition. should derive.
@@ -96,8 +225,27 @@ impl TypeDef {
     /// * `name` - The name of the derive.
     /// 
     /// # Examples
-    pub fn derive(&mut self, name: &str) {
-        self.derive.push(name.to_string());
+    pub fn derive<T>(&mut self, name: T)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.derive(name);
+    }
+
+    /// Add a new type that the type definition should derive along with the
+    /// helper attributes of its derive macro.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `name` - The name of the derive.
+    /// * `helpers` - The helper attributes of the derive macro.
+    pub fn derive_with<T, I, H>(&mut self, name: T, helpers: I)
+    where
+        T: Into<Cow<'static, str>>,
+        I: IntoIterator<Item = H>,
+        H: Into<Cow<'static, str>>,
+    {
+        self.attributes.derive_with(name, helpers);
     }
 
     /// Specify lint attribute to supress a warning or error.
@@ -105,8 +253,11 @@ impl TypeDef {
     /// # Arguments
     /// 
     /// * `allow` - The lint attribute to apply.
-    pub fn allow(&mut self, allow: &str) {
-        self.allow.push(allow.to_string());
+    pub fn allow<T>(&mut self, allow: T)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.allow(allow);
     }
 
     /// Specify representation.
@@ -114,8 +265,49 @@ impl TypeDef {
     /// # Arguments
     /// 
     /// * `repr` - The representation to specify.
-    pub fn repr(&mut self, repr: &str) {
-        self.repr = Some(repr.to_string());
+    pub fn repr<T>(&mut self, repr: T)
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.repr(repr);
+    }
+
+    /// Returns whether the type has a C compatible layout, i.e.
+    /// `#[repr(C)]` or `#[repr(transparent)]`.
+    pub fn is_repr_c(&self) -> bool {
+        match self.attributes.get_repr() {
+            Some(repr) => repr
+                .split(',')
+                .any(|r| r.trim() == "C" || r.trim() == "transparent"),
+            None => false,
+        }
+    }
+
+    /// Calls the given function with the type of the definition and the
+    /// types of its bounds.
+    pub(crate) fn visit_types_mut(&mut self, f: &mut dyn FnMut(&mut Type)) {
+        self.ty.visit_types_mut(f);
+
+        for bound in &mut self.bounds {
+            bound.visit_types_mut(f);
+        }
+    }
+
+    /// Maps the visibility of the type definition with the given function.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `f` - The function mapping the visibility.
+    pub(crate) fn map_visibility(&mut self, f: &dyn Fn(Visibility) -> Visibility) {
+        map_vis(&mut self.vis, f);
+    }
+
+    /// Returns an estimate of the rendered length of the head of the type
+    /// definition, including its docs and attributes.
+    pub(crate) fn estimated_len(&self) -> usize {
+        let docs = self.docs.as_ref().map_or(0, |docs| docs.estimated_len());
+
+        docs + self.attributes.estimated_len() + self.ty.name().len() + self.bounds.len() * 24 + 24
     }
 
     /// Formats the type definition using the given formatter.
@@ -133,10 +325,7 @@ impl TypeDef {
             docs.fmt(fmt)?;
         }
 
-        self.fmt_allow(fmt)?;
-        self.fmt_derive(fmt)?;
-        self.fmt_repr(fmt)?;
-        self.fmt_macros(fmt)?;
+        self.attributes.fmt(fmt)?;
 
         if let Some(ref vis) = self.vis {
             write!(fmt, "{} ", vis)?;
@@ -161,64 +350,4 @@ impl TypeDef {
 
         Ok(())
     }
-
-    /// Formats the allow using the given formatter.
-    /// 
-    /// # Arguments
-    /// 
-    /// * `fmt` - The formatter to use.
-    fn fmt_allow(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        for allow in &self.allow {
-            write!(fmt, "#[allow({})]\n", allow)?;
-        }
-
-        Ok(())
-    }
-
-    /// Formats the representation using the given formatter.
-    /// 
-    /// # Arguments
-    /// 
-    /// * `fmt` - The formatter to use.
-    fn fmt_repr(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        if let Some(ref repr) = self.repr {
-            write!(fmt, "#[repr({})]\n", repr)?;
-        }
-
-        Ok(())
-    }
-
-    /// Formats the derive using the given formatter.
-    /// 
-    /// # Arguments
-    /// 
-    /// * `fmt` - The formatter to use.
-    fn fmt_derive(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        if !self.derive.is_empty() {
-            write!(fmt, "#[derive(")?;
-
-            for (i, name) in self.derive.iter().enumerate() {
-                if i != 0 {
-                    write!(fmt, ", ")?
-                }
-                write!(fmt, "{}", name)?;
-            }
-
-            write!(fmt, ")]\n")?;
-        }
-
-        Ok(())
-    }
-
-    /// Formats the macros using the given formatter.
-    /// 
-    /// # Arguments
-    /// 
-    /// * `fmt` - The formatter to use.
-    fn fmt_macros(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
-        for m in self.macros.iter() {
-            write!(fmt, "{}\n", m)?;
-        }
-        Ok(())
-    }
 }
@@ -0,0 +1,219 @@
+use std::borrow::Cow;
+
+use indexmap::IndexMap;
+
+use crate::scope::Scope;
+
+use crate::r#type::Type;
+
+/// Defines the Rust type that an external type name maps to, along with the
+/// imports that the type requires.
+#[derive(Debug, Clone)]
+pub struct TypeMapping {
+    /// The Rust type.
+    ty: Type,
+    /// The imports as `(path, type)` pairs.
+    imports: Vec<(Cow<'static, str>, Cow<'static, str>)>,
+}
+
+impl TypeMapping {
+    /// Return a new mapping to the given type.
+    ///
+    /// # Arguments
+    ///
+    /// * `ty` - The Rust type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::TypeMapping;
+    ///
+    /// let mapping = TypeMapping::new("String");
+    /// ```
+    pub fn new<T>(ty: T) -> Self
+    where
+        T: Into<Type>,
+    {
+        TypeMapping {
+            ty: ty.into(),
+            imports: vec![],
+        }
+    }
+
+    /// Add an import that the type requires.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the type to import.
+    /// * `ty` - The type to import.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::TypeMapping;
+    ///
+    /// let mut mapping = TypeMapping::new("Uuid");
+    /// mapping.import("uuid", "Uuid");
+    /// ```
+    pub fn import<P, T>(&mut self, path: P, ty: T) -> &mut Self
+    where
+        P: Into<Cow<'static, str>>,
+        T: Into<Cow<'static, str>>,
+    {
+        self.imports.push((path.into(), ty.into()));
+        self
+    }
+
+    /// Returns the Rust type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::TypeMapping;
+    ///
+    /// let mapping = TypeMapping::new("String");
+    /// println!("{:?}", mapping.ty());
+    /// ```
+    pub fn ty(&self) -> &Type {
+        &self.ty
+    }
+}
+
+/// Maps external type names, e.g. from a schema, to Rust types along with
+/// the imports that they require.
+#[derive(Debug, Clone, Default)]
+pub struct TypeMap {
+    /// The mappings by external type name.
+    mappings: IndexMap<String, TypeMapping>,
+}
+
+impl TypeMap {
+    /// Returns an empty type map.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::TypeMap;
+    ///
+    /// let type_map = TypeMap::new();
+    /// ```
+    pub fn new() -> Self {
+        TypeMap {
+            mappings: IndexMap::new(),
+        }
+    }
+
+    /// Returns a type map with the common schema types: `string`, `boolean`,
+    /// `int32`, `int64`, `uint32`, `uint64`, `float`, `double`, `number`,
+    /// `bytes`, `uuid` (`uuid::Uuid`), and `date-time`
+    /// (`chrono::DateTime<chrono::Utc>`).
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::TypeMap;
+    ///
+    /// let mut type_map = TypeMap::schema();
+    /// type_map.insert("string", "std::borrow::Cow<'static, str>");
+    /// ```
+    pub fn schema() -> Self {
+        let mut type_map = TypeMap::new();
+
+        for (name, ty) in &[
+            ("string", "String"),
+            ("boolean", "bool"),
+            ("int32", "i32"),
+            ("int64", "i64"),
+            ("uint32", "u32"),
+            ("uint64", "u64"),
+            ("float", "f32"),
+            ("double", "f64"),
+            ("number", "f64"),
+            ("bytes", "Vec<u8>"),
+        ] {
+            type_map.insert(*name, *ty);
+        }
+
+        type_map.insert("uuid", "Uuid").import("uuid", "Uuid");
+        type_map
+            .insert("date-time", "DateTime<Utc>")
+            .import("chrono", "DateTime")
+            .import("chrono", "Utc");
+
+        type_map
+    }
+
+    /// Map an external type name to a Rust type, replacing any existing
+    /// mapping. Returns a mutable reference to the mapping to add imports.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The external type name.
+    /// * `ty` - The Rust type.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::TypeMap;
+    ///
+    /// let mut type_map = TypeMap::new();
+    /// type_map.insert("decimal", "Decimal").import("rust_decimal", "Decimal");
+    /// ```
+    pub fn insert<N, T>(&mut self, name: N, ty: T) -> &mut TypeMapping
+    where
+        N: Into<String>,
+        T: Into<Type>,
+    {
+        let name = name.into();
+
+        self.mappings.insert(name.clone(), TypeMapping::new(ty));
+        &mut self.mappings[&name]
+    }
+
+    /// Returns the mapping of the given external type name, if any.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The external type name.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::TypeMap;
+    ///
+    /// let type_map = TypeMap::schema();
+    /// assert!(type_map.get("uuid").is_some());
+    /// ```
+    pub fn get(&self, name: &str) -> Option<&TypeMapping> {
+        self.mappings.get(name)
+    }
+
+    /// Returns the Rust type of the given external type name, adding the
+    /// imports that it requires to the scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The external type name.
+    /// * `scope` - The scope to add the imports to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Scope,TypeMap};
+    ///
+    /// let mut scope = Scope::new();
+    /// let type_map = TypeMap::schema();
+    ///
+    /// let id_ty = type_map.resolve("uuid", &mut scope).unwrap();
+    /// scope.new_struct("User").field("id", id_ty);
+    /// ```
+    pub fn resolve(&self, name: &str, scope: &mut Scope) -> Option<Type> {
+        let mapping = self.get(name)?;
+
+        for (path, ty) in &mapping.imports {
+            scope.import(path, ty);
+        }
+
+        Some(mapping.ty.clone())
+    }
+}
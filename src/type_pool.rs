@@ -0,0 +1,198 @@
+use std::borrow::Cow;
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use crate::r#type::Type;
+
+/// Defines a name interned by a [`TypePool`], e.g. a type name or an
+/// identifier.
+///
+/// Handles are `Copy` and borrow the pool that interned them, so they can
+/// be passed wherever an `Into<Type>` or an `Into<Cow<'static, str>>` is
+/// accepted while the pool is alive. Only types share the storage of the
+/// name with the pool. Other names, e.g. of fields and functions, are
+/// stored as a `Cow<'static, str>` and are allocated again on each use.
+#[derive(Clone, Copy)]
+pub struct Interned<'p> {
+    /// The pool that interned the name.
+    pool: &'p TypePool,
+    /// The index of the name in the pool.
+    index: usize,
+}
+
+impl<'p> Interned<'p> {
+    /// Returns the interned name, shared with the pool.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::TypePool;
+    ///
+    /// let pool = TypePool::new();
+    /// assert_eq!(&*pool.intern("u64").name(), "u64");
+    /// ```
+    pub fn name(&self) -> Arc<str> {
+        self.pool.names.borrow().list[self.index].clone()
+    }
+}
+
+impl<'p> fmt::Debug for Interned<'p> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Interned").field(&self.name()).finish()
+    }
+}
+
+impl<'p> fmt::Display for Interned<'p> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.name())
+    }
+}
+
+impl<'p> PartialEq for Interned<'p> {
+    fn eq(&self, other: &Self) -> bool {
+        std::ptr::eq(self.pool, other.pool) && self.index == other.index
+    }
+}
+
+impl<'p> Eq for Interned<'p> {}
+
+impl<'p> Hash for Interned<'p> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.index.hash(state);
+    }
+}
+
+impl<'p> From<Interned<'p>> for Type {
+    fn from(src: Interned<'p>) -> Self {
+        Type::shared(src.name())
+    }
+}
+
+impl<'p> From<Interned<'p>> for Cow<'static, str> {
+    fn from(src: Interned<'p>) -> Self {
+        Cow::Owned(src.name().to_string())
+    }
+}
+
+/// Defines the names of a [`TypePool`], in the order they were interned.
+#[derive(Debug, Clone, Default)]
+struct Names {
+    /// The interned names.
+    list: Vec<Arc<str>>,
+    /// The index of each name in the list.
+    indices: HashMap<Arc<str>, usize>,
+}
+
+/// Defines an arena of interned names, so that the many repeated type
+/// names of a large generator, e.g. `String` or `u64`, share their storage
+/// instead of being allocated once per use. Identifiers can be interned
+/// too, but are copied when used, see [`Interned`].
+///
+/// A name is freed once the pool and every type using it are dropped.
+///
+/// The pool is not `Sync`, so it can't be shared between threads, e.g. by
+/// generators running in parallel. Each thread needs a pool of its own.
+///
+/// # Examples
+///
+/// ```
+/// use rust_codegen::{Scope, TypePool};
+///
+/// let pool = TypePool::new();
+/// let id = pool.intern("u64");
+///
+/// let mut scope = Scope::new();
+/// scope
+///     .new_struct("User")
+///     .field("id", id)
+///     .field("manager_id", id);
+///
+/// assert_eq!(pool.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TypePool {
+    /// The interned names.
+    names: RefCell<Names>,
+}
+
+impl TypePool {
+    /// Returns a new, empty pool.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::TypePool;
+    ///
+    /// let pool = TypePool::new();
+    /// ```
+    pub fn new() -> Self {
+        TypePool {
+            names: RefCell::new(Names::default()),
+        }
+    }
+
+    /// Returns a handle to the given name, interning it unless the pool
+    /// has it already.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name to intern, e.g. `String` or `Vec<u8>`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::TypePool;
+    ///
+    /// let pool = TypePool::new();
+    ///
+    /// assert_eq!(pool.intern("String"), pool.intern(&"String".to_string()));
+    /// ```
+    pub fn intern(&self, name: &str) -> Interned<'_> {
+        let mut names = self.names.borrow_mut();
+
+        if let Some(&index) = names.indices.get(name) {
+            return Interned { pool: self, index };
+        }
+
+        let index = names.list.len();
+        let name: Arc<str> = Arc::from(name);
+
+        names.list.push(name.clone());
+        names.indices.insert(name, index);
+
+        Interned { pool: self, index }
+    }
+
+    /// Returns the number of interned names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::TypePool;
+    ///
+    /// let pool = TypePool::new();
+    /// pool.intern("u64");
+    /// pool.intern("u64");
+    ///
+    /// assert_eq!(pool.len(), 1);
+    /// ```
+    pub fn len(&self) -> usize {
+        self.names.borrow().list.len()
+    }
+
+    /// Returns whether the pool has no interned names.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::TypePool;
+    ///
+    /// assert!(TypePool::new().is_empty());
+    /// ```
+    pub fn is_empty(&self) -> bool {
+        self.names.borrow().list.is_empty()
+    }
+}
@@ -0,0 +1,349 @@
+use std::borrow::Cow;
+
+use crate::block::Block;
+use crate::case::to_pascal_case;
+use crate::error_type::ErrorType;
+use crate::literal::quote_str;
+use crate::scope::Scope;
+use crate::struct_literal::StructLiteral;
+
+use crate::r#impl::Impl;
+use crate::r#struct::Struct;
+
+/// Defines a check of a field.
+#[derive(Debug, Clone)]
+struct Check {
+    /// The name of the error variant returned when the check fails.
+    variant: String,
+    /// The condition that holds for invalid values.
+    failure: String,
+    /// The display message of the error variant.
+    message: String,
+}
+
+/// Generates an `impl TryFrom<Raw> for Struct` validating the fields of the
+/// raw type, along with the error enum returned when a check fails.
+///
+/// The raw type is expected to have the same named fields as the struct.
+#[derive(Debug, Clone)]
+pub struct Validator {
+    /// The type to convert from, e.g. `RawUser`.
+    raw: Cow<'static, str>,
+    /// The name of the error enum, `{Struct}Error` by default.
+    error_name: Option<Cow<'static, str>>,
+    /// The checks along with the fields they check, in order.
+    checks: Vec<(String, Check)>,
+}
+
+impl Validator {
+    /// Returns a new validator converting from the given raw type.
+    ///
+    /// # Arguments
+    ///
+    /// * `raw` - The type to convert from, e.g. `RawUser`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Validator;
+    ///
+    /// let validator = Validator::new("RawUser");
+    /// ```
+    pub fn new<T>(raw: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        Validator {
+            raw: raw.into(),
+            error_name: None,
+            checks: vec![],
+        }
+    }
+
+    /// Set the name of the error enum, `{Struct}Error` by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the error enum.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Validator;
+    ///
+    /// let mut validator = Validator::new("RawUser");
+    /// validator.error_name("InvalidUser");
+    /// ```
+    pub fn error_name<T>(&mut self, name: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.error_name = Some(name.into());
+        self
+    }
+
+    /// Check that the given field isn't empty, e.g. a `String` or a `Vec`.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The name of the field.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Validator;
+    ///
+    /// let mut validator = Validator::new("RawUser");
+    /// validator.non_empty("name");
+    /// ```
+    pub fn non_empty(&mut self, field: &str) -> &mut Self {
+        self.push_check(
+            field,
+            format!("{}Empty", to_pascal_case(field)),
+            format!("raw.{}.is_empty()", field),
+            format!("`{}` must not be empty", field),
+        )
+    }
+
+    /// Check that the given field is within the given range.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The name of the field.
+    /// * `range` - The range expression, e.g. `0..=150`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Validator;
+    ///
+    /// let mut validator = Validator::new("RawUser");
+    /// validator.range("age", "0..=150");
+    /// ```
+    pub fn range(&mut self, field: &str, range: &str) -> &mut Self {
+        self.push_check(
+            field,
+            format!("{}OutOfRange", to_pascal_case(field)),
+            format!("!({}).contains(&raw.{})", range, field),
+            format!("`{}` must be in {}", field, range),
+        )
+    }
+
+    /// Check that the given field matches the given regular expression with
+    /// the `regex` crate.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The name of the field.
+    /// * `pattern` - The regular expression, e.g. `^\S+@\S+$`.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Validator;
+    ///
+    /// let mut validator = Validator::new("RawUser");
+    /// validator.regex("email", r"^\S+@\S+$");
+    /// ```
+    pub fn regex(&mut self, field: &str, pattern: &str) -> &mut Self {
+        self.push_check(
+            field,
+            format!("{}Invalid", to_pascal_case(field)),
+            format!(
+                "!regex::Regex::new({}).unwrap().is_match(&raw.{})",
+                quote_str(pattern),
+                field
+            ),
+            format!("`{}` must match `{}`", field, pattern),
+        )
+    }
+
+    /// Check the given field with a custom condition.
+    ///
+    /// # Arguments
+    ///
+    /// * `field` - The name of the field.
+    /// * `variant` - The name of the error variant returned when the check
+    ///   fails.
+    /// * `condition` - The condition that holds for valid values, referring to
+    ///   the raw value as `raw`, e.g. `raw.age % 2 == 0`.
+    /// * `message` - The display message of the error variant.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Validator;
+    ///
+    /// let mut validator = Validator::new("RawUser");
+    /// validator.check("age", "AgeOdd", "raw.age % 2 == 0", "`age` must be even");
+    /// ```
+    pub fn check(
+        &mut self,
+        field: &str,
+        variant: &str,
+        condition: &str,
+        message: &str,
+    ) -> &mut Self {
+        self.push_check(
+            field,
+            variant.to_string(),
+            format!("!({})", condition),
+            message.to_string(),
+        )
+    }
+
+    /// Returns the error enum returned when a check of the given struct
+    /// fails.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The struct to convert to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Struct,Validator};
+    ///
+    /// let mut user_struct = Struct::new("User");
+    /// user_struct.field("name", "String");
+    ///
+    /// let mut validator = Validator::new("RawUser");
+    /// validator.non_empty("name");
+    ///
+    /// let error = validator.to_error_type(&user_struct);
+    /// ```
+    pub fn to_error_type(&self, target: &Struct) -> ErrorType {
+        let mut error = ErrorType::new(self.error_name_of(target));
+
+        if let Some(vis) = target.type_def().get_vis() {
+            error.vis(vis);
+        }
+
+        for (_, check) in &self.checks {
+            // The message is a format string.
+            let message = check.message.replace('{', "{{").replace('}', "}}");
+
+            error.new_variant(check.variant.clone(), message);
+        }
+
+        error
+    }
+
+    /// Returns the `impl TryFrom<Raw>` block of the given struct, checking
+    /// the fields in the order the checks were added.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The struct to convert to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Struct,Validator};
+    ///
+    /// let mut user_struct = Struct::new("User");
+    /// user_struct.field("name", "String");
+    ///
+    /// let mut validator = Validator::new("RawUser");
+    /// validator.non_empty("name");
+    ///
+    /// let try_from = validator.to_impl(&user_struct);
+    /// ```
+    pub fn to_impl(&self, target: &Struct) -> Impl {
+        let fields = target.field_names();
+        let error_name = self.error_name_of(target);
+
+        for (field, _) in &self.checks {
+            if !fields.contains(field) {
+                panic!("unknown field `{}` in validator", field);
+            }
+        }
+
+        let mut try_from = Impl::new(target.ty());
+        try_from
+            .impl_trait(format!("TryFrom<{}>", self.raw))
+            .associate_type("Error", error_name.to_string());
+
+        let func = try_from
+            .new_fn("try_from")
+            .arg("raw", self.raw.clone())
+            .ret("Result<Self, Self::Error>");
+
+        for (_, check) in &self.checks {
+            let mut block = Block::new(format!("if {}", check.failure));
+            block.line(format!("return Err({}::{});", error_name, check.variant));
+
+            func.push_block(block);
+        }
+
+        let mut literal = StructLiteral::new("Self");
+
+        for field in &fields {
+            literal.field(field.clone(), format!("raw.{}", field));
+        }
+
+        func.line(format!("Ok({})", literal.to_code_string()));
+
+        try_from
+    }
+
+    /// Push the error enum, its impls, and the `impl TryFrom<Raw>` block of
+    /// the given struct to the given scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The struct to convert to.
+    /// * `scope` - The scope to push the items to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Scope,Struct,Validator};
+    ///
+    /// let mut scope = Scope::new();
+    /// let mut user_struct = Struct::new("User");
+    /// user_struct.field("name", "String");
+    ///
+    /// let mut validator = Validator::new("RawUser");
+    /// validator.non_empty("name");
+    ///
+    /// validator.push_to(&user_struct, &mut scope);
+    /// ```
+    pub fn push_to(&self, target: &Struct, scope: &mut Scope) {
+        self.to_error_type(target).push_to(scope);
+        scope.push_impl(self.to_impl(target));
+    }
+
+    /// Returns the name of the error enum of the given struct.
+    fn error_name_of(&self, target: &Struct) -> Cow<'static, str> {
+        match self.error_name {
+            Some(ref name) => name.clone(),
+            None => format!("{}Error", target.ty().name()).into(),
+        }
+    }
+
+    /// Adds a check of the given field.
+    fn push_check(
+        &mut self,
+        field: &str,
+        variant: String,
+        failure: String,
+        message: String,
+    ) -> &mut Self {
+        assert!(
+            !self.checks.iter().any(|(_, check)| check.variant == variant),
+            "the `{}` check was already added",
+            variant
+        );
+
+        self.checks.push((
+            field.to_string(),
+            Check {
+                variant,
+                failure,
+                message,
+            },
+        ));
+        self
+    }
+}
@@ -1,7 +1,12 @@
+use std::borrow::Cow;
 use std::fmt::{self, Write};
 
-use crate::fields::Fields;
+use crate::attributes::Attributes;
+use crate::docs::Docs;
+use crate::field::Field;
+use crate::fields::{fmt_named_fields, Fields};
 use crate::formatter::Formatter;
+use crate::serde::VariantAttrs;
 
 use crate::r#type::Type;
 
@@ -9,9 +14,13 @@ use crate::r#type::Type;
 #[derive(Debug, Clone)]
 pub struct Variant {
     /// The name of the variant.
-    name: String,
+    name: Cow<'static, str>,
     /// The variant's fields.
     fields: Fields,
+    /// The variant's documentation.
+    docs: Option<Docs>,
+    /// The variant's attributes.
+    attributes: Attributes,
 }
 
 impl Variant {
@@ -28,13 +37,28 @@ impl Variant {
     /// 
     /// let foo_variant = Variant::new("Foo");
     /// ```
-    pub fn new(name: &str) -> Self {
+    pub fn new<T>(name: T) -> Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
         Variant {
-            name: name.to_string(),
+            name: name.into(),
             fields: Fields::Empty,
+            docs: None,
+            attributes: Attributes::new(),
         }
     }
 
+    /// Returns the name of the variant.
+    pub(crate) fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the fields of the variant.
+    pub(crate) fn fields(&self) -> &Fields {
+        &self.fields
+    }
+
     /// Add a named field to the variant.
     /// 
     /// # Arguments
@@ -50,14 +74,37 @@ impl Variant {
     /// let mut foo_variant = Variant::new("Foo");
     /// foo_variant.named("Bar", "String");
     /// ```
-    pub fn named<T>(&mut self, name: &str, ty: T) -> &mut Self
+    pub fn named<N, T>(&mut self, name: N, ty: T) -> &mut Self
     where
+        N: Into<Cow<'static, str>>,
         T: Into<Type>,
     {
         self.fields.named(name, ty);
         self
     }
 
+    /// Push a named field to the variant, e.g. to document it.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `field` - The named field to push.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::{Field,Variant};
+    /// 
+    /// let mut foo_variant = Variant::new("Foo");
+    /// let mut bar_field = Field::new("bar", "String");
+    /// bar_field.doc_str("The bar.");
+    /// 
+    /// foo_variant.push_field(bar_field);
+    /// ```
+    pub fn push_field(&mut self, field: Field) -> &mut Self {
+        self.fields.push_named(field);
+        self
+    }
+
     /// Add a tuple field to the variant.
     /// 
     /// # Arguments
@@ -77,6 +124,112 @@ impl Variant {
         self
     }
 
+    /// Set the documentation of the variant.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `docs` - The documentation to set for the variant.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Variant;
+    /// 
+    /// let mut foo_variant = Variant::new("Foo");
+    /// foo_variant.doc("The foo variant.");
+    /// ```
+    pub fn doc<T>(&mut self, docs: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        match self.docs {
+            Some(ref mut existing) => existing.set_text(docs, false),
+            None => self.docs = Some(Docs::new(docs)),
+        }
+        self
+    }
+
+    /// Adds an attribute to the variant, without the surrounding `#[...]`
+    /// (e.g. `"default"`). An attribute that is already wrapped, e.g.
+    /// `"#[default]"`, is accepted as well.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attribute` - The attribute to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Variant;
+    /// 
+    /// let mut foo_variant = Variant::new("Foo");
+    /// foo_variant.attr("default");
+    /// ```
+    pub fn attr<T>(&mut self, attribute: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.meta(attribute);
+        self
+    }
+
+    /// Add a macro or attribute to the variant that is written as is (e.g.
+    /// `"#[cfg_attr(feature = \"serde\", serde(other))]"`).
+    /// 
+    /// # Arguments
+    /// 
+    /// * `r#macro` - The macro to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Variant;
+    /// 
+    /// let mut foo_variant = Variant::new("Foo");
+    /// foo_variant.r#macro("#[cfg_attr(feature = \"serde\", serde(other))]");
+    /// ```
+    pub fn r#macro<T>(&mut self, r#macro: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.attributes.raw(r#macro);
+        self
+    }
+
+    /// Adds the given serde attributes to the variant.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `attrs` - The serde attributes to add.
+    /// 
+    /// # Examples
+    /// 
+    /// ```
+    /// use rust_codegen::Variant;
+    /// use rust_codegen::serde::VariantAttrs;
+    /// 
+    /// let mut foo_variant = Variant::new("Foo");
+    /// foo_variant.serde(VariantAttrs::new().rename("foo"));
+    /// ```
+    pub fn serde(&mut self, attrs: &VariantAttrs) -> &mut Self {
+        if let Some(meta) = attrs.meta() {
+            self.attributes.meta(meta);
+        }
+        self
+    }
+
+    /// Calls the given function with every type of the variant's fields.
+    pub(crate) fn visit_types_mut(&mut self, f: &mut dyn FnMut(&mut Type)) {
+        self.fields.visit_types_mut(f);
+    }
+
+    /// Returns an estimate of the rendered length of the variant.
+    pub(crate) fn estimated_len(&self) -> usize {
+        let docs = self.docs.as_ref().map_or(0, |docs| docs.estimated_len());
+
+        docs + self.attributes.estimated_len() + self.name.len() + self.fields.estimated_len() + 8
+    }
+
     /// Formats the variant using the given formatter.
     /// 
     /// # Arguments
@@ -95,9 +248,39 @@ impl Variant {
     /// foo_variant.fmt(&mut fmt);
     /// ```
     pub fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+        self.fmt_with_comma(",", fmt)
+    }
+
+    /// Formats the variant followed by the given comma using the given
+    /// formatter.
+    /// 
+    /// # Arguments
+    /// 
+    /// * `comma` - The comma following the variant, if any.
+    /// * `fmt` - The formatter to use.
+    pub(crate) fn fmt_with_comma(&self, comma: &str, fmt: &mut Formatter<'_>) -> fmt::Result {
+        if let Some(ref docs) = self.docs {
+            docs.fmt(fmt)?;
+        }
+
+        self.attributes.fmt(fmt)?;
         write!(fmt, "{}", self.name)?;
-        self.fields.fmt(fmt)?;
-        write!(fmt, ",\n")?;
+
+        match self.fields {
+            Fields::Named(ref fields) => {
+                assert!(!fields.is_empty());
+
+                // The comma has to follow the closing brace of the fields.
+                fmt.fmt_brace_space()?;
+                write!(fmt, "{{\n")?;
+                fmt.indent(|fmt| fmt_named_fields(fields, fmt))?;
+                write!(fmt, "}}{}\n", comma)?;
+            }
+            _ => {
+                self.fields.fmt(fmt)?;
+                write!(fmt, "{}\n", comma)?;
+            }
+        }
 
         Ok(())
     }
@@ -0,0 +1,149 @@
+use std::borrow::Cow;
+
+/// Defines the visibility of an item, passed to the function of
+/// [`Scope::with_visibility_map`].
+///
+/// [`Scope::with_visibility_map`]: crate::Scope::with_visibility_map
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Visibility {
+    /// No visibility, or `pub(self)`.
+    Private,
+    /// `pub`.
+    Pub,
+    /// `pub(crate)`.
+    PubCrate,
+    /// `pub(super)`.
+    PubSuper,
+    /// `pub(in path)`, with the path.
+    PubIn(String),
+}
+
+impl Visibility {
+    /// Returns the visibility of the given visibility string, e.g.
+    /// `pub(crate)`.
+    ///
+    /// # Arguments
+    ///
+    /// * `vis` - The visibility string, if any.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Visibility;
+    ///
+    /// assert_eq!(Visibility::parse(Some("pub(crate)")), Visibility::PubCrate);
+    /// assert_eq!(Visibility::parse(None), Visibility::Private);
+    /// ```
+    pub fn parse(vis: Option<&str>) -> Self {
+        let vis = match vis {
+            Some(vis) => vis.split_whitespace().collect::<String>(),
+            None => return Visibility::Private,
+        };
+
+        match &vis[..] {
+            "" | "pub(self)" => Visibility::Private,
+            "pub" => Visibility::Pub,
+            "pub(crate)" => Visibility::PubCrate,
+            "pub(super)" => Visibility::PubSuper,
+            _ => match vis.strip_prefix("pub(in").and_then(|v| v.strip_suffix(')')) {
+                Some(path) => Visibility::PubIn(path.to_string()),
+                None => panic!("unsupported visibility `{}`", vis),
+            },
+        }
+    }
+
+    /// Returns the visibility string of the visibility, or `None` when it is
+    /// private.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::Visibility;
+    ///
+    /// assert_eq!(Visibility::PubSuper.to_vis(), Some("pub(super)".to_string()));
+    /// ```
+    pub fn to_vis(&self) -> Option<String> {
+        match *self {
+            Visibility::Private => None,
+            Visibility::Pub => Some("pub".to_string()),
+            Visibility::PubCrate => Some("pub(crate)".to_string()),
+            Visibility::PubSuper => Some("pub(super)".to_string()),
+            Visibility::PubIn(ref path) => Some(format!("pub(in {})", path)),
+        }
+    }
+}
+
+/// Maps the given visibility string with the given function.
+///
+/// # Arguments
+///
+/// * `vis` - The visibility string to map.
+/// * `f` - The function mapping the visibility.
+pub(crate) fn map_vis(vis: &mut Option<String>, f: &dyn Fn(Visibility) -> Visibility) {
+    *vis = f(Visibility::parse(vis.as_deref())).to_vis();
+}
+
+/// Maps the visibility written before a name, e.g. `pub id` for a struct
+/// field, with the given function.
+///
+/// # Arguments
+///
+/// * `name` - The name, starting with its visibility if any.
+/// * `f` - The function mapping the visibility.
+pub(crate) fn map_prefix_vis(name: &str, f: &dyn Fn(Visibility) -> Visibility) -> String {
+    let (vis, rest) = if name.starts_with("pub(") {
+        match name.find(')') {
+            Some(end) => (Some(&name[..=end]), &name[end + 1..]),
+            None => (None, name),
+        }
+    } else if name.starts_with("pub ") {
+        (Some("pub"), &name[3..])
+    } else {
+        (None, name)
+    };
+
+    let rest = rest.trim_start();
+
+    match f(Visibility::parse(vis)).to_vis() {
+        Some(vis) => format!("{} {}", vis, rest),
+        None => rest.to_string(),
+    }
+}
+
+/// Returns the given name without the visibility written before it, e.g. `id`
+/// for `pub(crate) id`.
+///
+/// # Arguments
+///
+/// * `name` - The name, starting with its visibility if any.
+pub(crate) fn strip_vis(name: &str) -> &str {
+    let rest = if name.starts_with("pub(") {
+        match name.find(')') {
+            Some(end) => &name[end + 1..],
+            None => name,
+        }
+    } else if name.starts_with("pub ") {
+        &name[3..]
+    } else {
+        name
+    };
+
+    rest.trim_start()
+}
+
+/// Maps the visibility written before a name in place.
+///
+/// # Arguments
+///
+/// * `name` - The name, starting with its visibility if any.
+/// * `f` - The function mapping the visibility.
+pub(crate) fn map_prefix_vis_mut(
+    name: &mut Cow<'static, str>,
+    f: &dyn Fn(Visibility) -> Visibility,
+) {
+    let mapped = map_prefix_vis(name, f);
+
+    if mapped != *name {
+        *name = mapped.into();
+    }
+}
@@ -0,0 +1,280 @@
+use std::borrow::Cow;
+
+use crate::block::Block;
+use crate::case::to_snake_case;
+use crate::fields::Fields;
+use crate::function::Function;
+use crate::scope::Scope;
+
+use crate::r#enum::Enum;
+use crate::r#impl::Impl;
+use crate::r#trait::Trait;
+use crate::r#type::Type;
+
+/// Generates a visitor trait for an enum with one method per variant, along
+/// with a `walk_*` function and an `accept` method that dispatch to it.
+#[derive(Debug, Clone)]
+pub struct Visitor {
+    /// The enum to visit.
+    r#enum: Enum,
+    /// The name of the visitor trait.
+    name: Cow<'static, str>,
+    /// The visibility of the generated items.
+    vis: Option<String>,
+}
+
+impl Visitor {
+    /// Return a new visitor generator for the given enum. The trait is named
+    /// after the enum, e.g. `ExprVisitor` for `Expr`.
+    ///
+    /// # Arguments
+    ///
+    /// * `item` - The enum to visit.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Enum,Visitor};
+    ///
+    /// let mut expr_enum = Enum::new("Expr");
+    /// expr_enum.new_variant("Literal").tuple("i64");
+    ///
+    /// let visitor = Visitor::new(&expr_enum);
+    /// ```
+    pub fn new(item: &Enum) -> Self {
+        Visitor {
+            name: format!("{}Visitor", item.ty().name()).into(),
+            r#enum: item.clone(),
+            vis: None,
+        }
+    }
+
+    /// Set the name of the visitor trait.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the trait.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Enum,Visitor};
+    ///
+    /// let expr_enum = Enum::new("Expr");
+    ///
+    /// let mut visitor = Visitor::new(&expr_enum);
+    /// visitor.name("Walker");
+    /// ```
+    pub fn name<T>(&mut self, name: T) -> &mut Self
+    where
+        T: Into<Cow<'static, str>>,
+    {
+        self.name = name.into();
+        self
+    }
+
+    /// Set the visibility of the generated trait and functions.
+    ///
+    /// # Arguments
+    ///
+    /// * `vis` - The visibility to set.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Enum,Visitor};
+    ///
+    /// let expr_enum = Enum::new("Expr");
+    ///
+    /// let mut visitor = Visitor::new(&expr_enum);
+    /// visitor.vis("pub");
+    /// ```
+    pub fn vis(&mut self, vis: &str) -> &mut Self {
+        self.vis = Some(vis.to_string());
+        self
+    }
+
+    /// Returns the visitor trait, with a `visit_*` method per variant which
+    /// receives references to the variant's fields.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Enum,Visitor};
+    ///
+    /// let expr_enum = Enum::new("Expr");
+    /// let visitor_trait = Visitor::new(&expr_enum).to_trait();
+    /// ```
+    pub fn to_trait(&self) -> Trait {
+        let mut r#trait = Trait::new(self.name.clone());
+
+        if let Some(ref vis) = self.vis {
+            r#trait.vis(vis);
+        }
+
+        for generic in self.r#enum.ty().generics() {
+            r#trait.generic(generic.name());
+        }
+
+        for variant in self.r#enum.variants() {
+            let func = r#trait
+                .new_fn(format!("visit_{}", to_snake_case(variant.name())))
+                .arg_mut_self();
+
+            for (name, ty) in bindings(variant.fields()) {
+                func.arg(name, format!("&{}", ty));
+            }
+        }
+
+        r#trait
+    }
+
+    /// Returns the `walk_*` function which calls the visitor method matching
+    /// the variant of the given node.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Enum,Visitor};
+    ///
+    /// let expr_enum = Enum::new("Expr");
+    /// let walk_fn = Visitor::new(&expr_enum).to_walk_fn();
+    /// ```
+    pub fn to_walk_fn(&self) -> Function {
+        let enum_name = self.r#enum.ty().name();
+        let mut func = Function::new(self.walk_fn_name());
+
+        if let Some(ref vis) = self.vis {
+            func.vis(vis);
+        }
+
+        for generic in self.r#enum.ty().generics() {
+            func.generic(generic.name());
+        }
+
+        func.generic(&format!("V: {} + ?Sized", self.visitor_ty().fmt_to_string()))
+            .arg("visitor", "&mut V")
+            .arg("node", format!("&{}", self.r#enum.ty().fmt_to_string()));
+
+        let mut body = Block::new("match node");
+
+        for variant in self.r#enum.variants() {
+            let bindings = bindings(variant.fields());
+            let names: Vec<&str> = bindings.iter().map(|b| &*b.0).collect();
+
+            let pattern = match *variant.fields() {
+                Fields::Empty => format!("{}::{}", enum_name, variant.name()),
+                Fields::Tuple(_) => {
+                    format!("{}::{}({})", enum_name, variant.name(), names.join(", "))
+                }
+                Fields::Named(_) => {
+                    format!("{}::{} {{ {} }}", enum_name, variant.name(), names.join(", "))
+                }
+            };
+
+            body.line(format!(
+                "{} => visitor.visit_{}({}),",
+                pattern,
+                to_snake_case(variant.name()),
+                names.join(", ")
+            ));
+        }
+
+        if self.r#enum.variants().is_empty() {
+            func.line("match *node {}");
+        } else {
+            func.push_block(body);
+        }
+
+        func
+    }
+
+    /// Returns the impl block adding an `accept` method to the enum which
+    /// walks the given visitor over it.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Enum,Visitor};
+    ///
+    /// let expr_enum = Enum::new("Expr");
+    /// let accept_impl = Visitor::new(&expr_enum).to_accept_impl();
+    /// ```
+    pub fn to_accept_impl(&self) -> Impl {
+        let mut accept = Impl::new(self.r#enum.ty());
+
+        for generic in self.r#enum.ty().generics() {
+            accept.generic(generic.name());
+        }
+
+        let func = accept
+            .new_fn("accept")
+            .generic(&format!("V: {} + ?Sized", self.visitor_ty().fmt_to_string()))
+            .arg_ref_self()
+            .arg("visitor", "&mut V")
+            .line(format!("{}(visitor, self)", self.walk_fn_name()));
+
+        if let Some(ref vis) = self.vis {
+            func.vis(vis);
+        }
+
+        accept
+    }
+
+    /// Push the visitor trait, the `walk_*` function, and the `accept`
+    /// impl block to the given scope.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope` - The scope to push the visitor to.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::{Enum,Scope,Visitor};
+    ///
+    /// let mut scope = Scope::new();
+    /// let mut expr_enum = Enum::new("Expr");
+    /// expr_enum.new_variant("Literal").tuple("i64");
+    ///
+    /// Visitor::new(&expr_enum).push_to(&mut scope);
+    /// ```
+    pub fn push_to(&self, scope: &mut Scope) {
+        scope.push_trait(self.to_trait());
+        scope.push_fn(self.to_walk_fn());
+        scope.push_impl(self.to_accept_impl());
+    }
+
+    /// Returns the name of the `walk_*` function.
+    fn walk_fn_name(&self) -> String {
+        format!("walk_{}", to_snake_case(self.r#enum.ty().name()))
+    }
+
+    /// Returns the visitor trait as a type, including the enum's generics.
+    fn visitor_ty(&self) -> Type {
+        let mut ty = Type::new(self.name.clone());
+
+        for generic in self.r#enum.ty().generics() {
+            ty.generic(generic);
+        }
+
+        ty
+    }
+}
+
+/// Returns the binding name and type of every field, `v0`, `v1`, etc. for
+/// tuple fields.
+fn bindings(fields: &Fields) -> Vec<(String, String)> {
+    match *fields {
+        Fields::Empty => vec![],
+        Fields::Tuple(ref tys) => tys
+            .iter()
+            .enumerate()
+            .map(|(i, ty)| (format!("v{}", i), ty.fmt_to_string()))
+            .collect(),
+        Fields::Named(ref fields) => fields
+            .iter()
+            .map(|f| (f.name.to_string(), f.ty.fmt_to_string()))
+            .collect(),
+    }
+}
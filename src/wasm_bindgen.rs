@@ -0,0 +1,155 @@
+//! Typed builders for `#[wasm_bindgen]` attributes.
+//!
+//! ```
+//! use rust_codegen::wasm_bindgen::BindgenAttrs;
+//! use rust_codegen::{Prelude, Scope};
+//!
+//! let mut scope = Scope::new();
+//! scope.use_prelude(&Prelude::wasm_bindgen());
+//!
+//! scope
+//!     .new_struct("Counter")
+//!     .vis("pub")
+//!     .wasm_bindgen(&BindgenAttrs::new())
+//!     .field("count", "u32");
+//!
+//! scope
+//!     .new_impl("Counter")
+//!     .wasm_bindgen(&BindgenAttrs::new())
+//!     .new_fn("new")
+//!     .vis("pub")
+//!     .wasm_bindgen(BindgenAttrs::new().constructor())
+//!     .ret("Counter")
+//!     .line("Counter { count: 0 }");
+//! ```
+
+use std::borrow::Cow;
+
+/// Defines the arguments of a `#[wasm_bindgen]` attribute.
+#[derive(Debug, Clone, Default)]
+pub struct BindgenAttrs {
+    /// The attribute arguments in the order they were added.
+    args: Vec<String>,
+}
+
+impl BindgenAttrs {
+    /// Returns a plain `#[wasm_bindgen]` attribute.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::wasm_bindgen::BindgenAttrs;
+    ///
+    /// let attrs = BindgenAttrs::new();
+    /// ```
+    pub fn new() -> Self {
+        BindgenAttrs { args: vec![] }
+    }
+
+    /// Export the item to JavaScript with the given name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The JavaScript name of the item.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::wasm_bindgen::BindgenAttrs;
+    ///
+    /// let mut attrs = BindgenAttrs::new();
+    /// attrs.js_name("doTheThing");
+    /// ```
+    pub fn js_name(&mut self, name: &str) -> &mut Self {
+        self.value("js_name", name);
+        self
+    }
+
+    /// Set the JavaScript name of the class that the functions of an impl
+    /// block belong to. Required when the struct was exported with
+    /// `js_name`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The JavaScript name of the class.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::wasm_bindgen::BindgenAttrs;
+    ///
+    /// let mut attrs = BindgenAttrs::new();
+    /// attrs.js_class("Counter");
+    /// ```
+    pub fn js_class(&mut self, name: &str) -> &mut Self {
+        self.value("js_class", name);
+        self
+    }
+
+    /// Mark the function as the JavaScript class constructor.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::wasm_bindgen::BindgenAttrs;
+    ///
+    /// let mut attrs = BindgenAttrs::new();
+    /// attrs.constructor();
+    /// ```
+    pub fn constructor(&mut self) -> &mut Self {
+        self.args.push("constructor".to_string());
+        self
+    }
+
+    /// Mark the function as a JavaScript property getter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::wasm_bindgen::BindgenAttrs;
+    ///
+    /// let mut attrs = BindgenAttrs::new();
+    /// attrs.getter();
+    /// ```
+    pub fn getter(&mut self) -> &mut Self {
+        self.args.push("getter".to_string());
+        self
+    }
+
+    /// Mark the function as a JavaScript property setter.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use rust_codegen::wasm_bindgen::BindgenAttrs;
+    ///
+    /// let mut attrs = BindgenAttrs::new();
+    /// attrs.setter();
+    /// ```
+    pub fn setter(&mut self) -> &mut Self {
+        self.args.push("setter".to_string());
+        self
+    }
+
+    /// Adds a key-value argument. Values that are valid identifiers are
+    /// written as is, anything else is written as a string literal.
+    fn value(&mut self, name: &str, value: &str) {
+        let is_ident = matches!(value.chars().next(), Some(c) if c.is_alphabetic() || c == '_')
+            && value.chars().all(|c| c.is_alphanumeric() || c == '_');
+
+        if is_ident {
+            self.args.push(format!("{} = {}", name, value));
+        } else {
+            self.args.push(format!("{} = {:?}", name, value));
+        }
+    }
+
+    /// Returns the attribute without the surrounding `#[...]`.
+    pub(crate) fn meta(&self) -> Cow<'static, str> {
+        if self.args.is_empty() {
+            "wasm_bindgen".into()
+        } else {
+            format!("wasm_bindgen({})", self.args.join(", ")).into()
+        }
+    }
+}
@@ -0,0 +1,117 @@
+use rust_codegen::*;
+
+#[test]
+fn asm_in_fn() {
+    let mut scope = Scope::new();
+
+    let mut nop = Asm::new();
+    nop.template("nop");
+
+    let mut add = Asm::new();
+    add.template("mov {tmp}, {x}")
+        .template("add {tmp}, {y}")
+        .template("mov {x}, {tmp}");
+    add.inout_named("x", "reg", "x")
+        .input_named("y", "reg", "y")
+        .output_named("tmp", "reg", "_")
+        .option("pure")
+        .option("nomem");
+
+    let mut body = Block::new("unsafe");
+    body.push_asm(&nop).push_asm(&add);
+
+    scope
+        .new_fn("add")
+        .arg("mut x", "u64")
+        .arg("y", "u64")
+        .ret("u64")
+        .push_block(body)
+        .line("x");
+
+    let expect = r#"
+fn add(mut x: u64, y: u64) -> u64 {
+    unsafe {
+        core::arch::asm!("nop");
+        core::arch::asm!(
+            "mov {tmp}, {x}",
+            "add {tmp}, {y}",
+            "mov {x}, {tmp}",
+            x = inout(reg) x,
+            y = in(reg) y,
+            tmp = out(reg) _,
+            options(pure, nomem),
+        );
+    }
+    x
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn naked_fn_and_global_asm() {
+    let mut scope = Scope::new();
+
+    let mut vectors = Asm::global();
+    vectors
+        .template(".section .vectors, \"ax\"")
+        .template("b {}")
+        .sym("reset");
+    scope.push_asm(vectors);
+
+    let mut trampoline = Asm::naked();
+    trampoline.template("jmp {}").sym("handler");
+
+    scope
+        .new_fn("trampoline")
+        .naked()
+        .extern_abi("C")
+        .push_asm(&trampoline);
+
+    let expect = r###"
+core::arch::global_asm!(
+    ".section .vectors, \"ax\"",
+    "b {}",
+    sym reset,
+);
+
+#[unsafe(naked)]
+extern "C" fn trampoline() {
+    core::arch::naked_asm!(
+        "jmp {}",
+        sym handler,
+    );
+}"###;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+#[should_panic(expected = "`in` operands are only allowed in `asm!`")]
+fn global_asm_rejects_register_operands() {
+    Asm::global().template("mov {}, 1").input("reg", "x");
+}
+
+#[test]
+fn asm_fails_to_format_out_of_place() {
+    let mut dst = String::new();
+    assert!(Asm::global().fmt(&mut Formatter::new(&mut dst)).is_err());
+
+    let mut vectors = Asm::global();
+    vectors.template("b reset");
+
+    let mut block = Block::new("unsafe");
+    block.push_asm(&vectors);
+
+    let mut dst = String::new();
+    assert!(block.fmt(&mut Formatter::new(&mut dst)).is_err());
+
+    let mut nop = Asm::new();
+    nop.template("nop");
+
+    let mut scope = Scope::new();
+    scope.push_asm(nop);
+
+    let mut dst = String::new();
+    assert!(scope.fmt(&mut Formatter::new(&mut dst)).is_err());
+}
@@ -41,3 +41,82 @@ fn hello_world() {
 
     assert_eq!(scope.to_string(), &expected[1..]);
 }
+
+#[test]
+fn block_with_cfg() {
+    let mut scope = Scope::new();
+    let new_fn = scope.new_fn("config_dir").ret("PathBuf");
+
+    let mut unix = Block::new("");
+    unix.cfg("unix").line("return PathBuf::from(\"/etc/app\");");
+
+    let mut windows = Block::new("");
+    windows
+        .cfg("windows")
+        .line("return PathBuf::from(r\"C:\\ProgramData\\app\");");
+
+    new_fn
+        .line_cfg("feature = \"tracing\"", "tracing::debug!(\"resolving config dir\");")
+        .push_block(unix)
+        .push_block(windows);
+
+    let expected = r#"
+fn config_dir() -> PathBuf {
+    #[cfg(feature = "tracing")]
+    tracing::debug!("resolving config dir");
+    #[cfg(unix)]
+    {
+        return PathBuf::from("/etc/app");
+    }
+    #[cfg(windows)]
+    {
+        return PathBuf::from(r"C:\ProgramData\app");
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expected[1..]);
+}
+
+#[test]
+fn labeled_block_with_break_value() {
+    let mut scope = Scope::new();
+    let new_fn = scope.new_fn("classify").arg("input", "&str").ret("State");
+
+    let mut empty = Block::new("if input.is_empty()");
+    empty.break_with("'state", "State::Empty");
+
+    let mut retry = Block::new("if input == \"retry\"");
+    retry.break_to("outer");
+
+    let mut outer = Block::new("loop");
+    outer.label("outer").push_block(retry).line("break;");
+
+    let mut state = Block::new("let state =");
+    state
+        .label("state")
+        .push_block(empty)
+        .push_block(outer)
+        .line("State::Ready")
+        .after(";");
+
+    new_fn.push_block(state).line("state");
+
+    let expected = r#"
+fn classify(input: &str) -> State {
+    let state = 'state: {
+        if input.is_empty() {
+            break 'state State::Empty;
+        }
+        'outer: loop {
+            if input == "retry" {
+                break 'outer;
+            }
+            break;
+        }
+        State::Ready
+    };
+    state
+}"#;
+
+    assert_eq!(scope.to_string(), &expected[1..]);
+}
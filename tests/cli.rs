@@ -0,0 +1,60 @@
+use rust_codegen::clap::Cli;
+use rust_codegen::*;
+
+#[test]
+fn cli_with_subcommands() {
+    let mut scope = Scope::new();
+
+    let mut cli = Cli::new("Cli");
+    cli.about("Manages the server.");
+
+    let serve_cmd = cli.new_subcommand("serve").about("Starts the server.");
+    serve_cmd
+        .arg("port", "u16")
+        .help("The port to listen on.")
+        .long()
+        .short('p')
+        .default_value("8080");
+    serve_cmd.arg("dry-run", "bool").long();
+
+    cli.new_subcommand("add-user").arg("name", "String");
+    cli.new_subcommand("version");
+
+    cli.push_to(&mut scope);
+
+    let expect = r#"
+use clap::{Parser, Subcommand};
+
+/// Manages the server.
+#[derive(Debug, Parser)]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Debug, Subcommand)]
+enum Command {
+    /// Starts the server.
+    Serve {
+        /// The port to listen on.
+        #[arg(long, short = 'p', default_value = "8080")]
+        port: u16,
+        #[arg(long)]
+        dry_run: bool,
+    },
+    AddUser {
+        name: String,
+    },
+    Version,
+}
+
+fn run(cli: Cli) {
+    match cli.command {
+        Command::Serve { port, dry_run } => todo!(),
+        Command::AddUser { name } => todo!(),
+        Command::Version => todo!(),
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
@@ -0,0 +1,30 @@
+use rust_codegen::*;
+
+// The current directory is changed, so this is the only test of the binary.
+#[test]
+fn deterministic_output_with_short_root() {
+    std::env::set_current_dir("/").unwrap();
+    std::env::remove_var("CARGO_MANIFEST_DIR");
+    std::env::remove_var("OUT_DIR");
+
+    let mut scope = Scope::new();
+    scope.deterministic();
+    scope.raw("// Generated from https://example.com/schema.json\n// See /usr/share/doc.");
+
+    let expect = r#"
+// Generated from https://example.com/schema.json
+// See /usr/share/doc."#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+
+    std::env::set_current_dir("/usr").unwrap();
+
+    let mut scope = Scope::new();
+    scope.deterministic();
+    scope.raw("// From /usr/share/doc, not /opt/usr/lib or https://example.com/usr/lib.");
+
+    let expect = r#"
+// From share/doc, not /opt/usr/lib or https://example.com/usr/lib."#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
@@ -0,0 +1,89 @@
+use rust_codegen::*;
+
+#[test]
+fn docs_escape_markdown() {
+    let mut scope = Scope::new();
+
+    scope.new_struct("Pet").doc(
+        "A pet, see [`Owner`] or <b>https://example.com/pets</b>.\nNames use `[a-z]+` and must not contain a ` character.",
+    );
+
+    let expect = r#"
+/// A pet, see \[`Owner`\] or \<b\><https://example.com/pets>\</b\>.
+/// Names use `[a-z]+` and must not contain a \` character.
+struct Pet;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn docs_keep_links_and_mark_code_blocks() {
+    let mut scope = Scope::new();
+
+    scope.new_fn("list_pets").doc(
+        "Lists pets, see [the guide](https://example.com/guide) or <https://example.com>.\n\n```\nGET /pets\n```\n\n```json\n[1, 2]",
+    );
+
+    let expect = r#"
+/// Lists pets, see [the guide](https://example.com/guide) or <https://example.com>.
+///
+/// ```text
+/// GET /pets
+/// ```
+///
+/// ```json
+/// [1, 2]
+/// ```
+fn list_pets() {
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn docs_raw() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_enum("Shape")
+        .doc_raw("See [`Circle`] and <https://example.com>.\n```\nlet x = 1;\n```");
+
+    let expect = r#"
+/// See [`Circle`] and <https://example.com>.
+/// ```
+/// let x = 1;
+/// ```
+enum Shape {
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn docs_paragraphs_and_lists() {
+    let mut scope = Scope::new();
+
+    let docs = format!(
+        "A pet in the store.\r\n\r\nStatuses:\r\n{}\r\n\r\nExample:\r\n{}",
+        Docs::bullet_list(&["available", "pending: waiting for\npayment"]),
+        Docs::code_block("json", "{ \"status\": \"sold\" }\r\n"),
+    );
+
+    scope.new_struct("Pet").doc(docs);
+
+    let expect = r#"
+/// A pet in the store.
+///
+/// Statuses:
+/// * available
+/// * pending: waiting for
+///   payment
+///
+/// Example:
+/// ```json
+/// { "status": "sold" }
+/// ```
+struct Pet;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
@@ -60,4 +60,159 @@ enum IpAddrKind {
 }"#;
 
     assert_eq!(scope.to_string(), &expect[1..]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn enum_with_attributes() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_enum("Shape")
+        .attr("serde(tag = \"type\")")
+        .derive("Serialize")
+        .push_variant(Variant::new("Circle"));
+
+    let expect = r#"
+#[serde(tag = "type")]
+#[derive(Serialize)]
+enum Shape {
+    Circle,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn enum_accessors() {
+    let mut scope = Scope::new();
+
+    let mut shape_enum = Enum::new("Shape");
+    shape_enum.new_variant("Empty");
+    shape_enum.new_variant("Circle").tuple("f64");
+    shape_enum
+        .new_variant("HTTPBox")
+        .named("width", "f64")
+        .named("label", "String");
+
+    scope.push_impl(shape_enum.accessors());
+
+    let expect = r#"
+impl Shape {
+    pub fn is_empty(&self) -> bool {
+        matches!(self, Self::Empty)
+    }
+
+    pub fn is_circle(&self) -> bool {
+        matches!(self, Self::Circle(..))
+    }
+
+    pub fn as_circle(&self) -> Option<&f64> {
+        match self {
+            Self::Circle(v0) => Some(v0),
+            _ => None,
+        }
+    }
+
+    pub fn into_circle(self) -> Result<f64, Self> {
+        match self {
+            Self::Circle(v0) => Ok(v0),
+            other => Err(other),
+        }
+    }
+
+    pub fn is_http_box(&self) -> bool {
+        matches!(self, Self::HTTPBox { .. })
+    }
+
+    pub fn as_http_box(&self) -> Option<(&f64, &String)> {
+        match self {
+            Self::HTTPBox { width, label } => Some((width, label)),
+            _ => None,
+        }
+    }
+
+    pub fn into_http_box(self) -> Result<(f64, String), Self> {
+        match self {
+            Self::HTTPBox { width, label } => Ok((width, label)),
+            other => Err(other),
+        }
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn enum_accessors_generic() {
+    let mut scope = Scope::new();
+
+    let mut maybe_enum = Enum::new("Maybe");
+    maybe_enum.generic("T");
+    maybe_enum.new_variant("Just").tuple("T");
+
+    scope.push_impl(maybe_enum.accessors());
+
+    let expect = r#"
+impl<T> Maybe<T> {
+    pub fn is_just(&self) -> bool {
+        matches!(self, Self::Just(..))
+    }
+
+    pub fn as_just(&self) -> Option<&T> {
+        match self {
+            Self::Just(v0) => Some(v0),
+        }
+    }
+
+    pub fn into_just(self) -> Result<T, Self> {
+        match self {
+            Self::Just(v0) => Ok(v0),
+        }
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn enum_variant_with_documented_field() {
+    let mut scope = Scope::new();
+
+    let mut radius_field = Field::new("radius", "f64");
+    radius_field.doc_str("The radius in meters.");
+
+    scope
+        .new_enum("Shape")
+        .new_variant("Circle")
+        .push_field(radius_field);
+
+    let expect = r#"
+enum Shape {
+    Circle {
+        /// The radius in meters.
+        radius: f64,
+    },
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn enum_with_wrapped_attributes() {
+    let mut scope = Scope::new();
+
+    let enum_ = scope.new_enum("Level").attr("#[repr(u8)]").attr("non_exhaustive");
+    enum_.new_variant("Low").attr("#[default]");
+    enum_.new_variant("High");
+
+    let expect = r#"
+#[repr(u8)]
+#[non_exhaustive]
+enum Level {
+    #[default]
+    Low,
+    High,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
@@ -0,0 +1,69 @@
+use rust_codegen::*;
+
+#[test]
+fn env_loader_from_env() {
+    let mut scope = Scope::new();
+
+    let mut config = Struct::new("Config");
+    config
+        .vis("pub")
+        .field("pub database_url", "String")
+        .field("pub port", "u16")
+        .field("pub log_level", "Option<String>");
+
+    let mut loader = EnvLoader::new();
+    loader
+        .prefix("APP_")
+        .var("database_url", "DATABASE_URL")
+        .default_value("port", "8080");
+
+    loader.push_to(&config, &mut scope);
+
+    let expect = r#"
+#[derive(Debug)]
+pub enum ConfigEnvError {
+    /// The environment variable with the given name isn't set.
+    Missing(&'static str),
+    /// The environment variable with the given name has a value that can't be parsed.
+    Invalid(&'static str, String),
+}
+
+impl std::fmt::Display for ConfigEnvError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ConfigEnvError::Missing(var) => write!(f, "missing environment variable `{}`", var),
+            ConfigEnvError::Invalid(var, value) => {
+                write!(f, "invalid value `{}` for environment variable `{}`", value, var)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ConfigEnvError {
+}
+
+impl Config {
+    /// Reads the config from the environment variables.
+    pub fn from_env() -> Result<Self, ConfigEnvError> {
+        let database_url = match std::env::var("DATABASE_URL") {
+            Ok(value) => value.parse().map_err(|_| ConfigEnvError::Invalid("DATABASE_URL", value))?,
+            Err(_) => return Err(ConfigEnvError::Missing("DATABASE_URL")),
+        };
+        let port = match std::env::var("APP_PORT") {
+            Ok(value) => value.parse().map_err(|_| ConfigEnvError::Invalid("APP_PORT", value))?,
+            Err(_) => 8080,
+        };
+        let log_level = match std::env::var("APP_LOG_LEVEL") {
+            Ok(value) => Some(value.parse().map_err(|_| ConfigEnvError::Invalid("APP_LOG_LEVEL", value))?),
+            Err(_) => None,
+        };
+        Ok(Self {
+            database_url,
+            port,
+            log_level,
+        })
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
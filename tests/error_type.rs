@@ -0,0 +1,85 @@
+use rust_codegen::*;
+
+#[test]
+fn error_type() {
+    let mut scope = Scope::new();
+
+    let mut client_error = ErrorType::new("ClientError");
+    client_error.vis("pub");
+    client_error.new_variant("NotFound", "resource not found");
+    client_error
+        .new_variant("Io", "i/o error: {0}")
+        .source("std::io::Error");
+    client_error
+        .new_variant("Json", "invalid response")
+        .source("serde_json::Error");
+
+    client_error.push_to(&mut scope);
+
+    let expect = r#"
+#[derive(Debug)]
+pub enum ClientError {
+    NotFound,
+    Io(std::io::Error),
+    Json(serde_json::Error),
+}
+
+impl std::fmt::Display for ClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ClientError::NotFound => write!(f, "resource not found"),
+            ClientError::Io(source) => write!(f, "i/o error: {0}", source),
+            ClientError::Json(_) => write!(f, "invalid response"),
+        }
+    }
+}
+
+impl std::error::Error for ClientError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            ClientError::Io(source) => Some(source),
+            ClientError::Json(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
+impl From<std::io::Error> for ClientError {
+    fn from(source: std::io::Error) -> Self {
+        ClientError::Io(source)
+    }
+}
+
+impl From<serde_json::Error> for ClientError {
+    fn from(source: serde_json::Error) -> Self {
+        ClientError::Json(source)
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn error_type_thiserror() {
+    let mut scope = Scope::new();
+
+    let mut client_error = ErrorType::new("ClientError");
+    client_error.thiserror(true);
+    client_error.new_variant("NotFound", "resource not found");
+    client_error
+        .new_variant("Io", "i/o error: {0}")
+        .source("std::io::Error");
+
+    client_error.push_to(&mut scope);
+
+    let expect = r#"
+#[derive(Debug, thiserror::Error)]
+enum ClientError {
+    #[error("resource not found")]
+    NotFound,
+    #[error("i/o error: {0}")]
+    Io(#[from] std::io::Error),
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
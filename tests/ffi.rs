@@ -0,0 +1,118 @@
+use rust_codegen::*;
+
+#[test]
+fn export_c_fn() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_struct("Point")
+        .repr("C")
+        .field("x", "f64")
+        .field("y", "f64");
+
+    scope
+        .new_fn("point_len")
+        .export_c()
+        .arg("point", "*const Point")
+        .ret("f64")
+        .line("0.0");
+
+    let expect = r#"
+#[repr(C)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[no_mangle]
+pub extern "C" fn point_len(point: *const Point) -> f64 {
+    0.0
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+    assert!(scope.check_ffi().is_empty());
+}
+
+#[test]
+fn check_ffi_violations() {
+    let mut scope = Scope::new();
+
+    scope.new_struct("Config").field("name", "String");
+
+    scope
+        .new_fn("configure")
+        .export_c()
+        .arg("config", "Config")
+        .arg("len", "usize")
+        .arg("name", "std::os::raw::c_char")
+        .ret("String");
+
+    scope.new_fn("reset").export_c().arg("config", "Option");
+
+    scope.new_fn("not_exported").arg("name", "String");
+
+    assert_eq!(
+        scope.check_ffi(),
+        vec![
+            "`configure` argument `config` has type `Config` which does not look FFI-safe",
+            "`configure` returns `String` which does not look FFI-safe",
+            "`reset` argument `config` has type `Option` which does not look FFI-safe",
+        ]
+    );
+}
+
+#[test]
+fn ffi_extern_block() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_fn("add")
+        .export_c()
+        .arg("a", "i32")
+        .arg("b", "i32")
+        .ret("i32")
+        .line("a + b");
+
+    scope
+        .new_module("ptr")
+        .new_fn("free_buf")
+        .export_c()
+        .arg("buf", "*mut u8");
+
+    scope
+        .new_fn("version")
+        .extern_abi("C")
+        .export_name("lib_version_v2")
+        .inline()
+        .ret("u32")
+        .line("2");
+
+    let mut extern_block = ExternBlock::new("C");
+    let mut raw_write = Function::new("raw_write");
+    raw_write
+        .attr("link_name = \"write\"")
+        .attr("cfg(unix)")
+        .must_use(None)
+        .arg("fd", "i32");
+    extern_block.push_fn(raw_write);
+
+    let mut stub = Scope::new();
+    stub.push_extern_block(scope.ffi_extern_block());
+    stub.push_extern_block(extern_block);
+
+    let expect = r#"
+extern "C" {
+    fn add(a: i32, b: i32) -> i32;
+    fn free_buf(buf: *mut u8);
+    #[link_name = "lib_version_v2"]
+    fn version() -> u32;
+}
+
+extern "C" {
+    #[link_name = "write"]
+    #[cfg(unix)]
+    fn raw_write(fd: i32);
+}"#;
+
+    assert_eq!(stub.to_string(), &expect[1..]);
+}
@@ -0,0 +1,120 @@
+use rust_codegen::*;
+
+#[test]
+fn flag_set_bitflags() {
+    let mut scope = Scope::new();
+
+    let mut permissions = FlagSet::new("Permissions", "u32");
+    permissions.vis("pub").doc("File permissions.").bitflags(true);
+    permissions.new_flag("READ", "1 << 0").doc("Read access.");
+    permissions.new_flag("WRITE", "1 << 1").doc("Write access.");
+    permissions.new_flag("ALL", "Self::READ.bits() | Self::WRITE.bits()");
+
+    permissions.push_to(&mut scope);
+
+    let expect = r#"
+bitflags::bitflags! {
+    /// File permissions.
+    pub struct Permissions: u32 {
+        /// Read access.
+        const READ = 1 << 0;
+        /// Write access.
+        const WRITE = 1 << 1;
+        const ALL = Self::READ.bits() | Self::WRITE.bits();
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn flag_set_plain() {
+    let mut scope = Scope::new();
+
+    let mut permissions = FlagSet::new("Permissions", "u8");
+    permissions.vis("pub");
+    permissions.new_flag("READ", "1 << 0").doc("Read access.");
+    permissions.new_flag("WRITE", "1 << 1");
+
+    permissions.push_to(&mut scope);
+
+    let expect = r#"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Permissions(u8);
+
+impl Permissions {
+    /// Read access.
+    pub const READ: Permissions = Permissions(1 << 0);
+    pub const WRITE: Permissions = Permissions(1 << 1);
+
+    pub fn empty() -> Self {
+        Permissions(0)
+    }
+
+    pub fn bits(&self) -> u8 {
+        self.0
+    }
+
+    pub fn contains(&self, other: Self) -> bool {
+        self.0 & other.0 == other.0
+    }
+
+    pub fn insert(&mut self, other: Self) {
+        self.0 |= other.0;
+    }
+
+    pub fn remove(&mut self, other: Self) {
+        self.0 &= !other.0;
+    }
+}
+
+impl std::ops::BitOr for Permissions {
+    type Output = Self;
+
+    fn bitor(self, rhs: Self) -> Self {
+        Permissions(self.0 | rhs.0)
+    }
+}
+
+impl std::ops::BitAnd for Permissions {
+    type Output = Self;
+
+    fn bitand(self, rhs: Self) -> Self {
+        Permissions(self.0 & rhs.0)
+    }
+}
+
+impl std::ops::BitXor for Permissions {
+    type Output = Self;
+
+    fn bitxor(self, rhs: Self) -> Self {
+        Permissions(self.0 ^ rhs.0)
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn impl_with_associated_const() {
+    let mut scope = Scope::new();
+
+    let foo_impl = scope.new_impl("Foo");
+    foo_impl
+        .new_associate_const("MAX", "u8", "255")
+        .vis("pub")
+        .doc("The largest value.");
+    foo_impl.new_fn("max").ret("u8").line("Self::MAX");
+
+    let expect = r#"
+impl Foo {
+    /// The largest value.
+    pub const MAX: u8 = 255;
+
+    fn max() -> u8 {
+        Self::MAX
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
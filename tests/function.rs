@@ -38,4 +38,699 @@ trait Foo {
 }"#;
 
     assert_eq!(scope.to_string(), &expect[1..]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn function_with_impl_trait() {
+    let mut scope = Scope::new();
+    scope
+        .new_fn("read_all")
+        .arg_impl("reader", ["Read", "Send"])
+        .ret_impl(["Iterator<Item = u8>"])
+        .line("reader.bytes().map(Result::unwrap)");
+
+    let expect = r#"
+fn read_all(reader: impl Read + Send) -> impl Iterator<Item = u8> {
+    reader.bytes().map(Result::unwrap)
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn function_body_template() {
+    let mut scope = Scope::new();
+
+    let fields = "let id = row.get(0)?;\nlet name = row.get(1)?;";
+
+    scope
+        .new_fn("from_row")
+        .arg("row", "&Row")
+        .ret("Result<User, Error>")
+        .body_template(
+            r#"
+            {fields}
+            if id < 0 {{
+                return Err(Error::InvalidId(id));
+            }}
+
+            Ok({ty} {{ id, name }})
+            "#,
+            &[("fields", fields), ("ty", "User")],
+        );
+
+    let expect = r#"
+fn from_row(row: &Row) -> Result<User, Error> {
+    let id = row.get(0)?;
+    let name = row.get(1)?;
+    if id < 0 {
+        return Err(Error::InvalidId(id));
+    }
+
+    Ok(User { id, name })
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+#[should_panic(expected = "unknown placeholder `{name}` in body template")]
+fn function_body_template_unknown_placeholder() {
+    Function::new("foo").body_template("let x = {name};", &[]);
+}
+
+#[test]
+fn fn_with_doc_examples() {
+    let mut scope = Scope::new();
+
+    let mut parse_fn = Function::new("parse");
+    parse_fn
+        .vis("pub")
+        .arg("input", "&str")
+        .ret("Option<u32>")
+        .line("input.parse().ok()");
+
+    let example = Docs::example_from_fn_in(&parse_fn, "numbers", None);
+    parse_fn
+        .doc_example(example, DocTest::Run)
+        .doc_example("assert_eq!(parse(\"42\"), Some(42));", DocTest::Ignore)
+        .doc("Parses a number.");
+
+    scope.push_fn(parse_fn);
+
+    let expect = r#"
+/// Parses a number.
+///
+/// # Examples
+///
+/// ```
+/// # use numbers::*;
+/// # fn example(input: &str) {
+/// let result = parse(input);
+/// # }
+/// ```
+///
+/// ```ignore
+/// assert_eq!(parse("42"), Some(42));
+/// ```
+pub fn parse(input: &str) -> Option<u32> {
+    input.parse().ok()
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn example_from_fn_signatures() {
+    let mut fetch_fn = Function::new("fetch");
+    fetch_fn
+        .set_async(true)
+        .generic("U")
+        .arg("url", "U")
+        .bound("U", "AsRef<str>")
+        .ret("Vec<u8>");
+
+    assert_eq!(
+        Docs::example_from_fn(&fetch_fn),
+        "# async fn example<U>(url: U) where U: AsRef<str> {\nlet result = fetch(url).await;\n# }"
+    );
+
+    let mut reset_fn = Function::new("reset");
+    reset_fn.arg_mut_self();
+
+    assert_eq!(Docs::example_from_fn(&reset_fn), "value.reset();");
+
+    let mut push_fn = Function::new("push");
+    push_fn.arg_mut_self().arg("item", "u32");
+
+    assert_eq!(
+        Docs::example_from_fn_in(&push_fn, "my_crate::stack", Some("Stack::new()")),
+        "# use my_crate::stack::*;\n# fn example(item: u32) {\nlet mut value = Stack::new();\nvalue.push(item);\n# }"
+    );
+}
+
+#[test]
+fn fn_with_cfg_arg() {
+    let mut scope = Scope::new();
+
+    let mut span_arg = Field::new("span", "&tracing::Span");
+    span_arg.cfg("feature = \"tracing\"").allow("unused_variables");
+
+    scope
+        .new_fn("handle")
+        .arg("path", "&str")
+        .push_arg(span_arg)
+        .line("let _ = path;");
+
+    let expect = r#"
+fn handle(path: &str, #[cfg(feature = "tracing")] #[allow(unused_variables)] span: &tracing::Span) {
+    let _ = path;
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn main_fn() {
+    let mut scope = Scope::new();
+
+    scope.new_main().line("println!(\"Hello, world!\");");
+
+    let expect = r#"
+fn main() {
+    println!("Hello, world!");
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn tokio_main_fn_with_result() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_main()
+        .tokio_main()
+        .ret_boxed_error()
+        .line("let body = fetch().await?;")
+        .line("println!(\"{}\", body);")
+        .line("Ok(())");
+
+    let expect = r#"
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let body = fetch().await?;
+    println!("{}", body);
+    Ok(())
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn main_fn_with_result_appends_ok() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_main()
+        .ret_boxed_error()
+        .line("let config = std::fs::read_to_string(\"config.toml\")?;")
+        .line("println!(\"{}\", config);");
+
+    let expect = r#"
+fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let config = std::fs::read_to_string("config.toml")?;
+    println!("{}", config);
+    Ok(())
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn call_chain_wraps_when_too_long() {
+    let mut scope = Scope::new();
+
+    let mut short = CallChain::new("client");
+    short.method("get", ["url"]).call("send").await_().try_();
+
+    let mut long = CallChain::new("self.client");
+    long.method("post", ["format!(\"{}/users\", self.base_url)"])
+        .method("bearer_auth", ["&self.token"])
+        .method("json", ["&body"])
+        .call("send")
+        .await_()
+        .try_()
+        .call("json::<User>")
+        .await_()
+        .try_();
+
+    scope
+        .new_fn("create_user")
+        .set_async(true)
+        .line(format!("let response = {};", short.to_code_string()))
+        .line(format!("let user = {};", long.to_code_string()))
+        .line("Ok(user)");
+
+    let expect = r#"
+async fn create_user() {
+    let response = client.get(url).send().await?;
+    let user = self.client
+        .post(format!("{}/users", self.base_url))
+        .bearer_auth(&self.token)
+        .json(&body)
+        .send()
+        .await?
+        .json::<User>()
+        .await?;
+    Ok(user)
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn expr_await_try_and_return() {
+    let mut scope = Scope::new();
+
+    let mut cached = Expr::new("cache.get(id)");
+    cached.await_();
+
+    let mut fetched = Expr::new("fetch(id)");
+    fetched.await_().try_().ok();
+
+    let mut early = Block::new("if let Some(user) = cached");
+    early.line(Expr::new("user").ok().to_return());
+
+    scope
+        .new_fn("load")
+        .set_async(true)
+        .arg("id", "u64")
+        .ret("Result<User, Error>")
+        .line(format!("let cached = {};", cached.to_code_string()))
+        .push_block(early)
+        .line(fetched.to_code_string());
+
+    let expect = r#"
+async fn load(id: u64) -> Result<User, Error> {
+    let cached = cache.get(id).await;
+    if let Some(user) = cached {
+        return Ok(user);
+    }
+    Ok(fetch(id).await?)
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn const_fn() {
+    let mut scope = Scope::new();
+
+    let mut fill = Block::new("while i < 256");
+    fill.line("table[i] = (i as u8).reverse_bits();")
+        .line("i += 1;");
+
+    scope
+        .new_fn("reversed_table")
+        .vis("pub")
+        .set_const(true)
+        .ret("[u8; 256]")
+        .line("let mut table = [0; 256];")
+        .line("let mut i = 0;")
+        .push_block(fill)
+        .line("table");
+
+    let expect = r#"
+pub const fn reversed_table() -> [u8; 256] {
+    let mut table = [0; 256];
+    let mut i = 0;
+    while i < 256 {
+        table[i] = (i as u8).reverse_bits();
+        i += 1;
+    }
+    table
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+    assert!(scope.check_const().is_empty());
+}
+
+#[test]
+fn check_const() {
+    let mut scope = Scope::new();
+
+    let mut names = Block::new("for i in 0..4");
+    names.line("out.push(i.to_string());");
+
+    scope
+        .new_fn("names")
+        .set_const(true)
+        .ret("Vec<String>")
+        .line("let mut out = vec![];")
+        .push_block(names)
+        .line("out");
+
+    // Functions that aren't `const` aren't checked.
+    scope.new_fn("label").ret("String").line("\"label\".to_string()");
+
+    scope
+        .new_module("table")
+        .new_impl("Table")
+        .new_fn("load")
+        .set_const(true)
+        .set_async(true)
+        .line("let bytes = read().await?;");
+
+    assert_eq!(
+        scope.check_const(),
+        vec![
+            "`names` is `const` but uses `to_string`, which isn't allowed in a `const fn`",
+            "`names` is `const` but uses `vec!`, which isn't allowed in a `const fn`",
+            "`names` is `const` but uses a `for` loop, which isn't allowed in a `const fn`",
+            "`table::Table::load` is `const` but also async",
+            "`table::Table::load` is `const` but uses `.await`, which isn't allowed in a `const fn`",
+            "`table::Table::load` is `const` but uses the `?` operator, which isn't allowed in a `const fn`",
+        ]
+    );
+}
+
+#[test]
+fn function_adjusted_copies() {
+    let mut scope = Scope::new();
+
+    let mut len = Function::new("len");
+    len.vis("pub").arg_ref_self().ret("usize").line("self.items.len()");
+
+    scope.new_trait("Len").push_fn(len.as_trait_fn());
+    scope.new_impl("List").push_fn(len.as_pub_crate());
+    scope
+        .new_impl("Buffer")
+        .push_fn(len.with_receiver(Receiver::RefMut).clone_with(|f| {
+            f.set_const(true);
+        }));
+    scope.push_fn(len.without_receiver().with_vis(Visibility::Private));
+
+    let expect = r#"
+trait Len {
+    fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
+impl List {
+    pub(crate) fn len(&self) -> usize {
+        self.items.len()
+    }
+}
+
+impl Buffer {
+    pub const fn len(&mut self) -> usize {
+        self.items.len()
+    }
+}
+
+fn len() -> usize {
+    self.items.len()
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn function_receivers() {
+    let mut scope = Scope::new();
+    let imp = scope.new_impl("Parser<'a>").generic("'a");
+
+    imp.new_fn("peek")
+        .receiver(Receiver::RefLifetime("'a".to_string()))
+        .ret("&'a str")
+        .line("self.input");
+    imp.new_fn("advance")
+        .receiver(Receiver::RefMutLifetime("'a".to_string()))
+        .line("self.pos += 1;");
+    imp.new_fn("boxed")
+        .receiver(Receiver::Typed("Box<Self>".into()))
+        .arg("limit", "usize")
+        .line("*self");
+
+    let expect = r#"
+impl<'a> Parser<'a> {
+    fn peek(&'a self) -> &'a str {
+        self.input
+    }
+
+    fn advance(&'a mut self) {
+        self.pos += 1;
+    }
+
+    fn boxed(self: Box<Self>, limit: usize) {
+        *self
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn function_optimization_hints() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_fn("len")
+        .inline()
+        .inline_always()
+        .must_use(None)
+        .must_use(Some("the \"length\" is computed"))
+        .ret("usize")
+        .line("0");
+
+    scope
+        .new_fn("fail")
+        .cold()
+        .inline_never()
+        .track_caller()
+        .track_caller()
+        .arg("msg", "&str")
+        .line("panic!(\"{}\", msg)");
+
+    let expect = r#"
+#[inline(always)]
+#[must_use = "the \"length\" is computed"]
+fn len() -> usize {
+    0
+}
+
+#[cold]
+#[inline(never)]
+#[track_caller]
+fn fail(msg: &str) {
+    panic!("{}", msg)
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn function_symbol_attributes() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_fn("reset")
+        .export_c()
+        .no_mangle()
+        .link_section(".text.boot")
+        .line("loop {}");
+
+    scope
+        .new_fn("init")
+        .export_c()
+        .export_name("plugin_init")
+        .export_name("plugin_init_v2");
+
+    scope
+        .new_fn("shutdown")
+        .export_name("plugin_shutdown")
+        .export_c();
+
+    let expect = r#"
+#[no_mangle]
+#[link_section = ".text.boot"]
+pub extern "C" fn reset() {
+    loop {}
+}
+
+#[export_name = "plugin_init_v2"]
+pub extern "C" fn init() {
+}
+
+#[no_mangle]
+pub extern "C" fn shutdown() {
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn function_test_attributes() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_fn("rejects_empty")
+        .test()
+        .test_should_panic(None)
+        .test_should_panic(Some("empty \"name\""))
+        .line("parse(\"\");");
+
+    scope
+        .new_fn("connects")
+        .test()
+        .test_with(TestRunner::Tokio)
+        .test_ignore(Some("requires a database"))
+        .line("connect().await;");
+
+    scope
+        .new_fn("slow")
+        .test_with(TestRunner::AsyncStd)
+        .test_ignore(None)
+        .line("sleep().await;");
+
+    scope
+        .new_fn("overflows")
+        .test_should_panic(Some("boom"))
+        .line("overflow();");
+
+    scope.new_fn("flaky").test_ignore(None).line("retry();");
+
+    let expect = r#"
+#[test]
+#[should_panic(expected = "empty \"name\"")]
+fn rejects_empty() {
+    parse("");
+}
+
+#[tokio::test]
+#[ignore = "requires a database"]
+async fn connects() {
+    connect().await;
+}
+
+#[async_std::test]
+#[ignore]
+async fn slow() {
+    sleep().await;
+}
+
+#[test]
+#[should_panic(expected = "boom")]
+fn overflows() {
+    overflow();
+}
+
+#[test]
+#[ignore]
+fn flaky() {
+    retry();
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn function_interned_names() {
+    let pool = TypePool::new();
+    let id = pool.intern("id");
+    let u64_ty = pool.intern("u64");
+
+    let mut scope = Scope::new();
+
+    scope
+        .new_fn(id)
+        .arg(id, u64_ty)
+        .ret(u64_ty)
+        .line(id);
+
+    let expect = r#"
+fn id(id: u64) -> u64 {
+    id
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+    assert_eq!(pool.len(), 2);
+}
+
+#[test]
+fn function_lazy_body() {
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+
+    let builds = Arc::new(AtomicUsize::new(0));
+    let counter = builds.clone();
+
+    let mut scope = Scope::new();
+
+    scope
+        .new_fn("sum")
+        .arg("values", "&[u32]")
+        .ret("u32")
+        .line("let mut total = 0;")
+        .body_with(move |body| {
+            counter.fetch_add(1, Ordering::SeqCst);
+
+            let mut block = Block::new("for value in values");
+            block.line("total += value;");
+
+            body.push_block(block).line("total");
+        });
+
+    let expect = r#"
+fn sum(values: &[u32]) -> u32 {
+    let mut total = 0;
+    for value in values {
+        total += value;
+    }
+    total
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+    assert_eq!(builds.load(Ordering::SeqCst), 1);
+
+    scope.body_mode(BodyMode::Omit);
+
+    assert_eq!(scope.to_string(), "fn sum(values: &[u32]) -> u32;");
+    assert_eq!(builds.load(Ordering::SeqCst), 1);
+
+    scope.body_mode(BodyMode::Keep);
+
+    let mut rename = Transform::new();
+    rename.replace("total", "acc");
+
+    let transformed = scope.transformed(&rename);
+
+    // The transformed body is built when rendered, not when transformed.
+    assert_eq!(builds.load(Ordering::SeqCst), 1);
+
+    let expect = r#"
+fn sum(values: &[u32]) -> u32 {
+    let mut acc = 0;
+    for value in values {
+        acc += value;
+    }
+    acc
+}"#;
+
+    assert_eq!(transformed.to_string(), &expect[1..]);
+    assert_eq!(builds.load(Ordering::SeqCst), 2);
+
+    scope.get_fn_mut("sum").unwrap().set_const(true);
+
+    assert_eq!(
+        scope.check_const(),
+        vec!["`sum` is `const` but uses a `for` loop, which isn't allowed in a `const fn`"]
+    );
+}
+
+#[test]
+fn function_to_async_with_boxed_future() {
+    let mut scope = Scope::new();
+
+    let mut free = Function::new("free");
+    free.arg("key", "&str")
+        .arg("parts", "Vec<&str>")
+        .ret("usize")
+        .line("key.len() + parts.len()");
+
+    let mut variant = AsyncVariant::new();
+    variant.boxed_future();
+
+    scope.push_fn(free.to_async(&variant));
+
+    let expect = r#"
+fn free<'a>(key: &'a str, parts: Vec<&'a str>) -> std::pin::Pin<Box<dyn std::future::Future<Output = usize> + Send + 'a>> {
+    Box::pin(async move {
+        key.len() + parts.len()
+    })
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
@@ -26,3 +26,145 @@ impl Foo for Bar {
 
     assert_eq!(scope.to_string(), &expect[1..]);
 }
+
+#[test]
+fn impl_to_async_with_boxed_future() {
+    let mut scope = Scope::new();
+
+    let mut imp = Impl::new("Bar");
+    imp.impl_trait("Client");
+    imp.new_fn("get")
+        .arg_ref_self()
+        .ret("String")
+        .line("self.name.clone()");
+    imp.new_fn("len")
+        .arg_ref_self()
+        .arg("key", "&str")
+        .ret("usize")
+        .line("key.len()");
+    imp.new_fn("connect")
+        .arg("name", "String")
+        .ret("Self")
+        .line("Bar { name }");
+
+    let mut variant = AsyncVariant::new();
+    variant.boxed_future();
+
+    scope.push_impl(imp.to_async(&variant));
+
+    let expect = r#"
+impl Client for Bar {
+    fn get<'a>(&'a self) -> std::pin::Pin<Box<dyn std::future::Future<Output = String> + Send + 'a>> {
+        Box::pin(async move {
+            self.name.clone()
+        })
+    }
+
+    fn len<'a>(&'a self, key: &'a str) -> std::pin::Pin<Box<dyn std::future::Future<Output = usize> + Send + 'a>> {
+        Box::pin(async move {
+            key.len()
+        })
+    }
+
+    fn connect(name: String) -> std::pin::Pin<Box<dyn std::future::Future<Output = Self> + Send>> {
+        Box::pin(async move {
+            Bar { name }
+        })
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn impl_with_associated_type_where_bound() {
+    let mut scope = Scope::new();
+
+    let imp = scope.new_impl("Bar");
+    imp.impl_trait("Lending");
+    imp.associate_type("Item", "u8");
+    imp.new_associate_type("Iter<'a>", "std::slice::Iter<'a, u8>")
+        .bound("Self", "'a");
+
+    let expect = r#"
+impl Lending for Bar {
+    type Item = u8;
+    type Iter<'a> = std::slice::Iter<'a, u8> where Self: 'a;
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn impl_trait_with_generics() {
+    let mut scope = Scope::new();
+
+    let mut my_trait = Type::new("MyTrait");
+    my_trait.generic("'a").generic("T");
+
+    scope
+        .new_impl("Wrapper")
+        .impl_trait_with_generics(["'a", "T: Serialize"], my_trait);
+
+    let expect = r#"
+impl<'a, T: Serialize> MyTrait<'a, T> for Wrapper<T> {
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+    assert!(scope.check_impl_generics().is_empty());
+}
+
+#[test]
+fn check_impl_generics() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_impl("Wrapper")
+        .target_generic("T")
+        .impl_trait_with_generics(["T", "U"], "MyTrait");
+
+    scope
+        .new_module("de")
+        .new_impl("Config")
+        .impl_trait("Deserialize<'de>");
+
+    assert_eq!(
+        scope.check_impl_generics(),
+        vec![
+            "impl block for `Wrapper` declares generic `U`, which is used by neither the trait nor the target",
+            "impl block for `de::Config` uses lifetime `'de`, which is not declared",
+        ]
+    );
+}
+
+#[test]
+fn impl_marker_attributes() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_impl("Config")
+        .impl_trait("Default")
+        .r#macro("#[cfg(feature = \"defaults\")]")
+        .tool_attr("my_tool::generated(schema = \"config\")")
+        .automatically_derived()
+        .automatically_derived()
+        .new_fn("default")
+        .tool_attr("my_tool::generated(schema = \"config\")")
+        .attr("inline")
+        .ret("Self")
+        .line("Config {}");
+
+    let expect = r#"
+#[my_tool::generated(schema = "config")]
+#[automatically_derived]
+#[cfg(feature = "defaults")]
+impl Default for Config {
+    #[my_tool::generated(schema = "config")]
+    #[inline]
+    fn default() -> Self {
+        Config {}
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
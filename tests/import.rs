@@ -26,4 +26,77 @@ mod foo {
 }"#;
 
     assert_eq!(scope.to_string(), &expect[1..]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn prune_unused_imports() {
+    let mut scope = Scope::new();
+    scope.import("std::collections", "HashMap");
+    scope.import("std::collections", "HashSet");
+    scope.import("std::fmt", "*");
+    scope.import("crate::model", "User").vis("pub");
+    scope.import("std::sync", "Arc");
+
+    scope
+        .new_module("cache")
+        .import("std::time", "Duration")
+        .import("std::time", "Instant")
+        .new_struct("Entry")
+        .field("created", "Instant");
+
+    scope
+        .new_struct("Registry")
+        .field("users", "HashMap<u32, Arc<str>>");
+
+    scope.prune_unused_imports();
+
+    let expect = r#"
+use std::collections::HashMap;
+use std::fmt::*;
+use std::sync::Arc;
+pub use crate::model::User;
+
+mod cache {
+    use std::time::Instant;
+
+    struct Entry {
+        created: Instant,
+    }
+}
+
+struct Registry {
+    users: HashMap<u32, Arc<str>>,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn conflicting_imports_are_aliased() {
+    let mut scope = Scope::new();
+    scope.import("std::fmt", "Result");
+    scope.import("std::fmt", "Display");
+
+    let io_result = scope.import("std::io", "Result").alias().unwrap().to_string();
+    scope.import("std::io", "Result");
+
+    scope
+        .new_fn("read")
+        .ret(format!("{}<String>", io_result))
+        .line("todo!()");
+
+    assert_eq!(
+        scope.import_aliases(),
+        vec![("std::io::Result".to_string(), "IoResult".to_string())]
+    );
+
+    let expect = r#"
+use std::fmt::{Result, Display};
+use std::io::Result as IoResult;
+
+fn read() -> IoResult<String> {
+    todo!()
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
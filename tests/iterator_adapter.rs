@@ -0,0 +1,56 @@
+use rust_codegen::*;
+
+#[test]
+fn iterator_adapter_with_stubs() {
+    let mut scope = Scope::new();
+
+    let mut pages = IteratorAdapter::new("Pages", "Page");
+    pages
+        .vis("pub")
+        .field("client", "Client")
+        .field("buffer", "std::collections::VecDeque<Page>");
+
+    let mut refill = Block::new("if self.buffer.is_empty()");
+    refill.line("self.buffer.extend(self.client.next_page()?);");
+
+    pages
+        .next_fn()
+        .push_block(refill)
+        .line("self.buffer.pop_front()");
+    pages.exact_size();
+    pages.double_ended().line("self.buffer.pop_back()");
+
+    pages.push_to(&mut scope);
+
+    let expect = r#"
+pub struct Pages {
+    client: Client,
+    buffer: std::collections::VecDeque<Page>,
+}
+
+impl Iterator for Pages {
+    type Item = Page;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.buffer.is_empty() {
+            self.buffer.extend(self.client.next_page()?);
+        }
+        self.buffer.pop_front()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        todo!()
+    }
+}
+
+impl ExactSizeIterator for Pages {
+}
+
+impl DoubleEndedIterator for Pages {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.buffer.pop_back()
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
@@ -0,0 +1,112 @@
+use rust_codegen::*;
+
+#[test]
+fn quote_str_escapes() {
+    assert_eq!(quote_str("hello"), r#""hello""#);
+    assert_eq!(quote_str("line\nbreak\r\t\0"), r#""line\nbreak\r\t\0""#);
+    assert_eq!(quote_str("bell\u{7}"), r#""bell\u{7}""#);
+    assert_eq!(quote_str("naïve ✓"), "\"naïve ✓\"");
+}
+
+#[test]
+fn quote_str_prefers_raw_when_shorter() {
+    assert_eq!(quote_str(r#"{"a": "b"}"#), r###"r#"{"a": "b"}"#"###);
+    assert_eq!(quote_str(r"C:\a\b\c"), r#"r"C:\a\b\c""#);
+    assert_eq!(quote_str("a \"quote\"\n"), r#""a \"quote\"\n""#);
+}
+
+#[test]
+fn quote_raw_str_hashes() {
+    assert_eq!(quote_raw_str("plain", 0), r#"r"plain""#);
+    assert_eq!(quote_raw_str("plain", 2), r###"r##"plain"##"###);
+    assert_eq!(quote_raw_str(r##"a "# b"##, 0), r###"r##"a "# b"##"###);
+}
+
+#[test]
+fn quote_str_in_body() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_fn("fixture")
+        .ret("&'static str")
+        .line(quote_str(r#"{"id": 1, "tags": ["a\b"]}"#));
+
+    let expect = r###"
+fn fixture() -> &'static str {
+    r#"{"id": 1, "tags": ["a\b"]}"#
+}"###;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn byte_literals() {
+    assert_eq!(byte_str(b"plain"), r#"b"plain""#);
+    assert_eq!(byte_str(&[0xff, b'\\', b'\t']), r#"b"\xff\\\t""#);
+    assert_eq!(byte_array(&[], 8), "&[]");
+}
+
+#[test]
+fn byte_array_in_const() {
+    let mut scope = Scope::new();
+
+    scope.raw(format!(
+        "const ICON: &[u8] = {};",
+        byte_array(&[0x89, 0x50, 0x4e, 0x47, 0x0d, 0x0a], 4)
+    ));
+
+    let expect = r#"
+const ICON: &[u8] = &[
+    0x89, 0x50, 0x4e, 0x47,
+    0x0d, 0x0a,
+];"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn numeric_literals() {
+    assert_eq!(int_literal(0u8, "u8"), "0u8");
+    assert_eq!(int_literal(999, ""), "999");
+    assert_eq!(int_literal(1000, "i32"), "1_000i32");
+    assert_eq!(int_literal(u64::MAX, "u64"), "18_446_744_073_709_551_615u64");
+    assert_eq!(int_literal(i64::MIN, ""), "-9_223_372_036_854_775_808");
+    assert_eq!(hex_literal(0x1u8, 2, "u8"), "0x01_u8");
+    assert_eq!(hex_literal(0x12345u32, 0, ""), "0x1_2345");
+}
+
+#[test]
+fn struct_literal_shorthand_and_spread() {
+    let mut short = StructLiteral::new("User");
+    short.shorthand("name").field("age", "age");
+    assert_eq!(short.to_code_string(), "User { name, age }");
+
+    let mut scope = Scope::new();
+    let mut config = StructLiteral::new("Self");
+    config
+        .field("host", "host.into()")
+        .field("port", "8080")
+        .spread_default();
+
+    scope
+        .new_fn("new")
+        .arg("host", "&str")
+        .ret("Self")
+        .line(config.to_code_string());
+
+    let expect = r#"
+fn new(host: &str) -> Self {
+    Self {
+        host: host.into(),
+        port: 8080,
+        ..Default::default()
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+
+    let mut update = StructLiteral::new("Config");
+    update.spread("other");
+    assert_eq!(update.to_code_string(), "Config { ..other }");
+    assert_eq!(StructLiteral::new("Empty").to_code_string(), "Empty {}");
+}
@@ -0,0 +1,101 @@
+use rust_codegen::*;
+
+#[test]
+fn merge_keeps_hand_edits() {
+    let previous = r#"
+pub struct Config {
+    pub port: u16,
+}
+
+impl Config {
+    pub fn new() -> Self {
+        Config { port: 80 }
+    }
+}
+"#;
+
+    let current = r#"
+pub struct Config {
+    pub port: u16,
+}
+
+impl Config {
+    pub fn new() -> Self {
+        // Use the alternate HTTP port in development.
+        Config { port: 8080 }
+    }
+}
+"#;
+
+    let generated = r#"
+pub struct Config {
+    pub host: String,
+    pub port: u16,
+}
+
+impl Config {
+    pub fn new() -> Self {
+        Config { port: 80 }
+    }
+}
+"#;
+
+    let expect = r#"
+pub struct Config {
+    pub host: String,
+    pub port: u16,
+}
+
+impl Config {
+    pub fn new() -> Self {
+        // Use the alternate HTTP port in development.
+        Config { port: 8080 }
+    }
+}
+"#;
+
+    let result = three_way_merge(previous, generated, current);
+
+    assert!(result.is_clean());
+    assert_eq!(result.merged, expect);
+}
+
+#[test]
+fn merge_reports_conflicts() {
+    let previous = "fn timeout() -> u64 {\n    30\n}\n";
+    let current = "fn timeout() -> u64 {\n    60\n}\n";
+    let generated = "fn timeout() -> u64 {\n    45\n}\n";
+
+    let result = three_way_merge(previous, generated, current);
+
+    let expect = r#"fn timeout() -> u64 {
+<<<<<<< current
+    60
+=======
+    45
+>>>>>>> generated
+}
+"#;
+
+    assert_eq!(result.merged, expect);
+    assert_eq!(
+        result.conflicts,
+        vec![MergeConflict {
+            line: 2,
+            previous: vec!["    30\n".to_string()],
+            current: vec!["    60\n".to_string()],
+            generated: vec!["    45\n".to_string()],
+        }]
+    );
+}
+
+#[test]
+fn merge_same_change_on_both_sides() {
+    let previous = "const A: u8 = 1;\nconst B: u8 = 2;";
+    let current = "const A: u8 = 1;\nconst B: u8 = 3;";
+
+    let result = three_way_merge(previous, current, current);
+
+    assert!(result.is_clean());
+    assert_eq!(result.merged, current);
+}
@@ -45,4 +45,219 @@ mod foo {
 }"#;
 
     assert_eq!(scope.to_string(), &expect[1..]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn module_item_accessors() {
+    let mut scope = Scope::new();
+    let module = scope.new_module("foo");
+    module.new_struct("Foo");
+    module.new_enum("Kind");
+    module.new_fn("run");
+    module.new_impl("Foo");
+
+    assert!(module.get_struct("Bar").is_none());
+    assert!(module.get_trait("Foo").is_none());
+
+    module.get_struct_mut("Foo").unwrap().field("kind", "Kind");
+    module.get_enum_mut("Kind").unwrap().new_variant("A");
+    module.get_fn_mut("run").unwrap().line("todo!()");
+    module.get_impl_mut("Foo").unwrap().new_fn("kind").line("self.kind");
+
+    let expect = r#"
+mod foo {
+    struct Foo {
+        kind: Kind,
+    }
+
+    enum Kind {
+        A,
+    }
+
+    fn run() {
+        todo!()
+    }
+
+    impl Foo {
+        fn kind() {
+            self.kind
+        }
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn module_new_trait() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_module("foo")
+        .new_trait("Animal")
+        .vis("pub")
+        .new_fn("name")
+        .arg_ref_self()
+        .ret("String");
+
+    let expect = r#"
+mod foo {
+    pub trait Animal {
+        fn name(&self) -> String;
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn module_with_raw() {
+    let mut scope = Scope::new();
+
+    let module = scope.new_module("foo");
+    module.new_struct("Foo");
+    module.raw("const TABLE: [u8; 2] = [\n    1,\n    2,\n];");
+    module.new_struct("Bar");
+
+    let expect = r#"
+mod foo {
+    struct Foo;
+
+    const TABLE: [u8; 2] = [
+        1,
+        2,
+    ];
+
+    struct Bar;
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn module_with_attributes() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_module("tests")
+        .allow("dead_code")
+        .attr("cfg(test)")
+        .new_fn("it_works")
+        .attr("test");
+
+    let expect = r#"
+#[allow(dead_code)]
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn it_works() {
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn platform_modules() {
+    let mut unix = Scope::new();
+    unix.new_fn("page_size")
+        .vis("pub")
+        .ret("usize")
+        .line("unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }");
+
+    let mut windows = Scope::new();
+    windows.new_fn("page_size").vis("pub").ret("usize").line("4096");
+
+    let mut platforms = PlatformModules::new();
+    platforms
+        .platform("unix", "unix", unix)
+        .platform("windows", "windows", windows)
+        .unsupported("unsupported platform");
+
+    let mut scope = Scope::new();
+    platforms.push_to(&mut scope);
+
+    let expect = r#"
+#[cfg(unix)]
+mod unix {
+    pub fn page_size() -> usize {
+        unsafe { libc::sysconf(libc::_SC_PAGESIZE) as usize }
+    }
+}
+
+#[cfg(windows)]
+mod windows {
+    pub fn page_size() -> usize {
+        4096
+    }
+}
+
+#[cfg(unix)]
+pub use self::unix::*;
+#[cfg(windows)]
+pub use self::windows::*;
+#[cfg(not(any(unix, windows)))]
+compile_error!("unsupported platform");"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn platform_modules_by_target() {
+    let mut platforms = PlatformModules::new();
+    platforms
+        .target_os("linux", Scope::new())
+        .target_arch("x86_64", Scope::new())
+        .vis("pub(crate)");
+
+    let expect = r#"
+#[cfg(target_os = "linux")]
+pub(crate) use self::linux::*;
+#[cfg(target_arch = "x86_64")]
+pub(crate) use self::x86_64::*;"#;
+
+    assert_eq!(platforms.to_reexports(), &expect[1..]);
+}
+
+#[test]
+fn reorder_modules() {
+    let mut scope = Scope::new();
+    scope.new_module("net");
+    scope.new_struct("Config");
+    scope.new_module("fs");
+    scope.new_module("io");
+
+    scope.reorder_modules(&["io", "fs"]);
+
+    let expect = r#"
+mod io {
+}
+
+struct Config;
+
+mod fs {
+}
+
+mod net {
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+    assert_eq!(
+        scope.outline(),
+        vec![
+            ("mod", "io".to_string()),
+            ("struct", "Config".to_string()),
+            ("mod", "fs".to_string()),
+            ("mod", "net".to_string()),
+        ]
+    );
+}
+
+#[test]
+#[should_panic(expected = "unknown module `db` in the module order")]
+fn reorder_unknown_module() {
+    let mut scope = Scope::new();
+    scope.new_module("net");
+
+    scope.reorder_modules(&["db"]);
+}
@@ -0,0 +1,55 @@
+use rust_codegen::pyo3::*;
+use rust_codegen::*;
+
+#[test]
+fn pyo3_module() {
+    let mut counter = Struct::new("Counter");
+    counter.vis("pub").field("count", "u32");
+
+    let mut counter_impl = Impl::new("Counter");
+    counter_impl
+        .new_fn("increment")
+        .arg_mut_self()
+        .line("self.count += 1;");
+
+    let mut add = Function::new("add");
+    add.arg("a", "i64").arg("b", "i64").ret("i64").line("a + b");
+
+    let mut py_module = PyModule::new("my_module");
+    py_module
+        .push_class(counter)
+        .push_methods(counter_impl)
+        .push_function(add);
+
+    let mut scope = Scope::new();
+    py_module.push_to(&mut scope);
+
+    let expect = r#"
+use pyo3::prelude::*;
+
+#[pyclass]
+pub struct Counter {
+    count: u32,
+}
+
+#[pymethods]
+impl Counter {
+    fn increment(&mut self) {
+        self.count += 1;
+    }
+}
+
+#[pyfunction]
+fn add(a: i64, b: i64) -> i64 {
+    a + b
+}
+
+#[pymodule]
+fn my_module(_py: Python<'_>, m: &PyModule) -> PyResult<()> {
+    m.add_class::<Counter>()?;
+    m.add_function(wrap_pyfunction!(add, m)?)?;
+    Ok(())
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
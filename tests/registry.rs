@@ -0,0 +1,68 @@
+use rust_codegen::*;
+
+#[test]
+fn registry_factory() {
+    let mut scope = Scope::new();
+
+    let mut registry = Registry::new("Handler");
+    registry
+        .vis("pub")
+        .import("crate::handlers", "Handler")
+        .import("crate::handlers", "LoginHandler")
+        .import("crate::handlers", "LogoutHandler")
+        .entry("login", "LoginHandler", "LoginHandler::new()")
+        .entry("logout", "LogoutHandler", "LogoutHandler::default()");
+
+    registry.push_to(&mut scope);
+
+    let expect = r#"
+use crate::handlers::{Handler, LoginHandler, LogoutHandler};
+
+/// Returns every registered `Handler`.
+pub fn all() -> Vec<Box<dyn Handler>> {
+    vec![
+        Box::new(LoginHandler::new()),
+        Box::new(LogoutHandler::default()),
+    ]
+}
+
+/// Returns the registered `Handler` with the given name.
+pub fn get(name: &str) -> Option<Box<dyn Handler>> {
+    match name {
+        "login" => Some(Box::new(LoginHandler::new())),
+        "logout" => Some(Box::new(LogoutHandler::default())),
+        _ => None,
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn registry_static() {
+    let mut scope = Scope::new();
+
+    let mut registry = Registry::new("Codec");
+    registry
+        .style(RegistryStyle::Static)
+        .vis("pub")
+        .import("crate::codecs", "Codec")
+        .entry("json", "JsonCodec", "JsonCodec")
+        .entry("messagePack", "MsgPackCodec", "MsgPackCodec::new()");
+
+    registry.push_to(&mut scope);
+
+    let expect = r#"
+use crate::codecs::Codec;
+
+static JSON: JsonCodec = JsonCodec;
+static MESSAGE_PACK: MsgPackCodec = MsgPackCodec::new();
+
+/// Every registered `Codec` by name.
+pub static REGISTRY: &[(&str, &(dyn Codec + Sync))] = &[
+    ("json", &JSON),
+    ("messagePack", &MESSAGE_PACK),
+];"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
@@ -0,0 +1,211 @@
+use rust_codegen::scaffold::{criterion_bench, Crate};
+use rust_codegen::*;
+
+#[test]
+fn scaffold_crate() {
+    let mut lib = Scope::new();
+    lib.new_fn("answer").vis("pub").ret("u32").line("42");
+
+    let mut bin = Scope::new();
+    bin.new_fn("main")
+        .line("println!(\"{}\", answers::answer());");
+
+    let mut generated = Crate::new("answers");
+    generated
+        .edition("2018")
+        .dependency("serde", r#"{ version = "1", features = ["derive"] }"#)
+        .dependency("indexmap", r#""1.9.3""#)
+        .lib(lib)
+        .bin(bin);
+
+    let tree = generated.to_source_tree();
+
+    let manifest = r#"
+[package]
+name = "answers"
+version = "0.1.0"
+edition = "2018"
+
+[dependencies]
+serde = { version = "1", features = ["derive"] }
+indexmap = "1.9.3"
+"#;
+
+    let paths: Vec<&str> = tree.files().map(|(path, _)| path).collect();
+    assert_eq!(paths, vec!["Cargo.toml", "src/lib.rs", "src/main.rs"]);
+
+    assert_eq!(tree.get("Cargo.toml"), Some(&manifest[1..]));
+    assert_eq!(
+        tree.get("src/lib.rs"),
+        Some("pub fn answer() -> u32 {\n    42\n}\n")
+    );
+    assert_eq!(
+        tree.get("src/main.rs"),
+        Some("fn main() {\n    println!(\"{}\", answers::answer());\n}\n")
+    );
+}
+
+#[test]
+fn scaffold_bin_without_main() {
+    let mut bin = Scope::new();
+    bin.new_struct("Config");
+
+    let tree = Crate::new("tool").bin(bin).to_source_tree();
+
+    assert!(tree.get("src/lib.rs").is_none());
+    assert_eq!(
+        tree.get("src/main.rs"),
+        Some("struct Config;\n\nfn main() {\n}\n")
+    );
+}
+
+#[test]
+fn write_source_tree() {
+    let root = std::env::temp_dir().join(format!("rust-codegen-{}", std::process::id()));
+
+    let mut tree = SourceTree::new();
+    tree.file("src/lib.rs", "").file("README.md", "# Generated\n");
+    tree.write_to(&root).unwrap();
+
+    let readme = std::fs::read_to_string(root.join("README.md")).unwrap();
+    let lib_exists = root.join("src/lib.rs").exists();
+    std::fs::remove_dir_all(&root).unwrap();
+
+    assert_eq!(readme, "# Generated\n");
+    assert!(lib_exists);
+}
+
+#[test]
+fn scaffold_examples_and_benches() {
+    let mut example = Scope::new();
+    example
+        .new_fn("main")
+        .line("println!(\"{}\", answers::answer());");
+
+    let mut bench = Scope::new();
+    bench
+        .new_fn("bench_answer")
+        .arg("c", "&mut Criterion")
+        .line("c.bench_function(\"answer\", |b| b.iter(answers::answer));");
+
+    let mut generated = Crate::new("answers");
+    generated
+        .lib(Scope::new())
+        .example("print", example)
+        .example("empty", Scope::new())
+        .bench("answer", bench)
+        .bench("skeleton", Scope::new());
+
+    let tree = generated.to_source_tree();
+
+    let manifest = r#"
+[package]
+name = "answers"
+version = "0.1.0"
+edition = "2021"
+
+[dev-dependencies]
+criterion = "0.5"
+
+[[bench]]
+name = "answer"
+harness = false
+
+[[bench]]
+name = "skeleton"
+harness = false
+"#;
+
+    let bench = r#"
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn bench_answer(c: &mut Criterion) {
+    c.bench_function("answer", |b| b.iter(answers::answer));
+}
+
+criterion_group!(benches, bench_answer);
+criterion_main!(benches);
+"#;
+
+    let skeleton = r#"
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn bench_skeleton(c: &mut Criterion) {
+    c.bench_function("skeleton", |b| b.iter(|| {}));
+}
+
+criterion_group!(benches, bench_skeleton);
+criterion_main!(benches);
+"#;
+
+    assert_eq!(tree.get("Cargo.toml"), Some(&manifest[1..]));
+    assert_eq!(
+        tree.get("examples/print.rs"),
+        Some("fn main() {\n    println!(\"{}\", answers::answer());\n}\n")
+    );
+    assert_eq!(tree.get("examples/empty.rs"), Some("fn main() {\n}\n"));
+    assert_eq!(tree.get("benches/answer.rs"), Some(&bench[1..]));
+    assert_eq!(tree.get("benches/skeleton.rs"), Some(&skeleton[1..]));
+}
+
+#[test]
+fn scaffold_bench_skeletons() {
+    let mut generated = Crate::new("codecs");
+    generated.bench_skeletons("json", ["encode", "decode"]);
+
+    let expect = r#"
+use criterion::{criterion_group, criterion_main, Criterion};
+
+fn bench_encode(c: &mut Criterion) {
+    c.bench_function("encode", |b| b.iter(|| {}));
+}
+
+fn bench_decode(c: &mut Criterion) {
+    c.bench_function("decode", |b| b.iter(|| {}));
+}
+
+criterion_group!(benches, bench_encode, bench_decode);
+criterion_main!(benches);"#;
+
+    assert_eq!(criterion_bench(["encode", "decode"]).to_string(), &expect[1..]);
+    assert_eq!(
+        generated.to_source_tree().get("benches/json.rs"),
+        Some(&format!("{}\n", &expect[1..])[..])
+    );
+}
+
+#[test]
+fn scaffold_integration_tests() {
+    let mut generated = Crate::new("my-codec");
+    generated.lib(Scope::new());
+
+    let conformance = generated.new_test("conformance");
+    conformance.scope().import("std::collections", "HashMap");
+    conformance
+        .new_test("round_trip")
+        .line("let map: HashMap<u8, u8> = HashMap::new();")
+        .line("assert_eq!(decode(&encode(&map)), map);");
+    conformance
+        .new_test("empty")
+        .line("assert!(decode(&[]).is_empty());");
+
+    let tree = generated.to_source_tree();
+
+    let expect = r#"
+use my_codec::*;
+use std::collections::HashMap;
+
+#[test]
+fn round_trip() {
+    let map: HashMap<u8, u8> = HashMap::new();
+    assert_eq!(decode(&encode(&map)), map);
+}
+
+#[test]
+fn empty() {
+    assert!(decode(&[]).is_empty());
+}
+"#;
+
+    assert_eq!(tree.get("tests/conformance.rs"), Some(&expect[1..]));
+}
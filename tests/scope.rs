@@ -7,10 +7,1545 @@ fn empty_scope() {
     assert_eq!(scope.to_string(), "");
 }
 
+#[test]
+fn scope_with_owned_names() {
+    let mut scope = Scope::new();
+    let name = String::from("Foo");
+
+    scope
+        .new_struct(name.clone())
+        .field(format!("{}_id", name.to_lowercase()), "usize");
+
+    scope.new_fn("new_foo").line(format!("{}::default()", name));
+
+    let expect = r#"
+struct Foo {
+    foo_id: usize,
+}
+
+fn new_foo() {
+    Foo::default()
+}"#;
+
+    assert_eq!(scope.clone().to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_with_prelude() {
+    let mut prelude = Prelude::serde();
+    prelude
+        .import("std::collections", "HashMap")
+        .inner_attr("forbid(unsafe_code)")
+        .allow("dead_code");
+
+    let mut scope = Scope::new();
+    scope.use_prelude(&prelude);
+    scope.new_struct("Foo").derive("Serialize");
+
+    let expect = r#"
+#![forbid(unsafe_code)]
+#![allow(dead_code)]
+
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+#[derive(Serialize)]
+struct Foo;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_policy() {
+    let mut scope = Scope::new();
+
+    scope
+        .policy()
+        .inline_accessors(true)
+        .inherit_field_docs(true)
+        .struct_derive("Debug")
+        .struct_derive("Clone");
+
+    let mut name_field = Field::new("name", "String");
+    name_field.doc_str("The name of the user.");
+
+    let mut user_struct = Struct::new("User");
+    user_struct
+        .derive("Clone")
+        .derive("PartialEq")
+        .push_field(name_field)
+        .field("age", "u8");
+
+    let mut getters = user_struct.getters();
+    getters.new_fn("is_adult").arg_ref_self().ret("bool").line("self.age >= 18");
+
+    scope.push_struct(user_struct);
+    scope.push_impl(getters);
+
+    let expect = r#"
+#[derive(Debug, Clone, PartialEq)]
+struct User {
+    /// The name of the user.
+    name: String,
+    age: u8,
+}
+
+impl User {
+    /// The name of the user.
+    #[inline]
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    #[inline]
+    pub fn age(&self) -> &u8 {
+        &self.age
+    }
+
+    fn is_adult(&self) -> bool {
+        self.age >= 18
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_policy_applies_to_modules() {
+    let mut scope = Scope::new();
+    scope.policy().struct_derive("Debug");
+
+    scope.new_module("a").new_struct("A");
+
+    let b_module = scope.new_module("b");
+    b_module.scope().policy().struct_derive("Default");
+    b_module.new_struct("B");
+
+    scope.new_struct("C");
+
+    let expect = r#"
+mod a {
+    #[derive(Debug)]
+    struct A;
+}
+
+mod b {
+    #[derive(Default)]
+    struct B;
+}
+
+#[derive(Debug)]
+struct C;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_policy_attribute_order() {
+    let mut scope = Scope::new();
+
+    scope.policy().attribute_order(&[
+        AttributeKind::Derive,
+        AttributeKind::Meta,
+        AttributeKind::Allow,
+    ]);
+
+    scope
+        .new_struct("User")
+        .repr("C")
+        .serde(serde::ContainerAttrs::new().rename_all(serde::RenameRule::CamelCase))
+        .allow("dead_code")
+        .derive("Serialize")
+        .attr("non_exhaustive")
+        .field("user_id", "u64");
+
+    scope.new_fn("load").allow("unused").attr("must_use").ret("u64").line("0");
+
+    let expect = r#"
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+#[allow(dead_code)]
+#[repr(C)]
+struct User {
+    user_id: u64,
+}
+
+#[must_use]
+#[allow(unused)]
+fn load() -> u64 {
+    0
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_policy_coverage_exclusion() {
+    let mut scope = Scope::new();
+
+    scope
+        .policy()
+        .exclude_from_coverage(CoverageExclusion::CoverageOff);
+
+    scope.new_struct("Id").tuple_field("u64");
+    scope.new_fn("parse").inline().ret("Id").line("Id(0)");
+    scope
+        .new_impl("Id")
+        .new_fn("get")
+        .arg_ref_self()
+        .ret("u64")
+        .line("self.0");
+
+    let module = scope.new_module("legacy");
+    module
+        .scope()
+        .policy()
+        .exclude_from_coverage(CoverageExclusion::NoCoverage);
+    module.new_fn("load");
+
+    let expect = r#"
+struct Id(u64);
+
+#[cfg_attr(coverage_nightly, coverage(off))]
+#[inline]
+fn parse() -> Id {
+    Id(0)
+}
+
+impl Id {
+    #[cfg_attr(coverage_nightly, coverage(off))]
+    fn get(&self) -> u64 {
+        self.0
+    }
+}
+
+mod legacy {
+    #[no_coverage]
+    fn load() {
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_without_policy_keeps_getters_plain() {
+    let mut scope = Scope::new();
+
+    let mut id_field = Field::new("id", "u64");
+    id_field.doc_str("The identifier.");
+
+    let mut user_struct = Struct::new("User");
+    user_struct.push_field(id_field);
+
+    scope.push_impl(user_struct.getters());
+
+    let expect = r#"
+impl User {
+    pub fn id(&self) -> &u64 {
+        &self.id
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_default_derives() {
+    let mut scope = Scope::new();
+    scope.default_derives(vec!["Debug", "Clone"]);
+
+    scope.new_struct("Config").derive("Default").field("port", "u16");
+    scope.new_enum("Level").push_variant(Variant::new("Low"));
+    scope.new_struct("Handle").no_default_derives().tuple_field("u32");
+
+    scope.new_module("inner").new_enum("Mode").derive("Debug").push_variant(Variant::new("Fast"));
+
+    let expect = r#"
+#[derive(Debug, Clone, Default)]
+struct Config {
+    port: u16,
+}
+
+#[derive(Debug, Clone)]
+enum Level {
+    Low,
+}
+
+struct Handle(u32);
+
+mod inner {
+    #[derive(Clone, Debug)]
+    enum Mode {
+        Fast,
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_allow_generated_lints() {
+    let mut scope = Scope::new();
+    scope.allow_generated_lints();
+    scope.new_struct("r#type");
+
+    let expect = r#"
+#![allow(dead_code, unused_imports, unused_variables, unused_mut, unused_parens, unreachable_patterns, non_camel_case_types, non_snake_case, non_upper_case_globals, clippy::all, clippy::pedantic)]
+
+struct r#type;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn scope_allow_custom_lints() {
+    let mut scope = Scope::new();
+    scope
+        .allow_lints(
+            GENERATED_LINTS
+                .iter()
+                .filter(|lint| !lint.starts_with("clippy::"))
+                .chain(&["missing_docs"]),
+        )
+        .allow_lints(Vec::<&str>::new());
+
+    assert_eq!(
+        scope.to_string(),
+        "#![allow(dead_code, unused_imports, unused_variables, unused_mut, unused_parens, unreachable_patterns, non_camel_case_types, non_snake_case, non_upper_case_globals, missing_docs)]"
+    );
+}
+
+#[test]
+fn rewrite_paths() {
+    let mut scope = Scope::new();
+    scope.import("my_models", "User");
+    scope.import("my_models::admin", "Role");
+    scope.import("my_models_v2", "User");
+
+    scope
+        .new_struct("Session")
+        .field("user", "Option<my_models::User>")
+        .field("legacy", "my_models_v2::User");
+
+    scope
+        .new_module("handlers")
+        .import("my_models", "Session")
+        .new_fn("login")
+        .arg("role", "&my_models::admin::Role")
+        .ret("my_models::Session")
+        .line("todo!()");
+
+    let mut rewrite = PathRewrite::new();
+    rewrite.rule("my_models::", "crate::models::");
+
+    scope.rewrite_paths(&rewrite);
+
+    let expect = r#"
+use crate::models::User;
+use crate::models::admin::Role;
+use my_models_v2::User as MyModelsV2User;
+
+struct Session {
+    user: Option<crate::models::User>,
+    legacy: my_models_v2::User,
+}
+
+mod handlers {
+    use crate::models::Session;
+
+    fn login(role: &crate::models::admin::Role) -> crate::models::Session {
+        todo!()
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn item_metadata() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_struct("User")
+        .field("id", "u64")
+        .metadata_mut()
+        .set("schema", "user.json")
+        .set("async", "true");
+
+    scope
+        .new_fn("load_user")
+        .ret("User")
+        .line("todo!()")
+        .metadata_mut()
+        .set("schema", "user.json");
+
+    let cloned = scope.clone();
+
+    let user = cloned.get_struct("User").unwrap();
+    assert_eq!(user.metadata().get("schema"), Some("user.json"));
+    assert_eq!(
+        user.metadata().iter().collect::<Vec<_>>(),
+        vec![("schema", "user.json"), ("async", "true")]
+    );
+    assert!(cloned.get_fn("load_user").unwrap().metadata().contains("schema"));
+
+    let expect = r#"
+struct User {
+    id: u64,
+}
+
+fn load_user() -> User {
+    todo!()
+}"#;
+
+    assert_eq!(cloned.to_string(), &expect[1..]);
+}
+
+#[test]
+fn source_map() {
+    let mut scope = Scope::new();
+    scope.import("std::fmt", "Display");
+
+    scope
+        .new_struct("User")
+        .field("id", "u64")
+        .metadata_mut()
+        .set("schema", "user.json#/definitions/User");
+
+    scope
+        .new_module("handlers")
+        .new_fn("login")
+        .arg("id", "u64")
+        .line("todo!()");
+
+    scope.raw("// end of generated code");
+
+    let (code, source_map) = scope.to_string_with_source_map();
+    assert_eq!(code, scope.to_string());
+
+    let spans: Vec<_> = source_map
+        .spans()
+        .iter()
+        .map(|span| (span.kind, span.path.as_str(), span.start_line, span.end_line))
+        .collect();
+
+    assert_eq!(
+        spans,
+        vec![
+            ("struct", "User", 3, 5),
+            ("mod", "handlers", 7, 11),
+            ("fn", "handlers::login", 8, 10),
+        ]
+    );
+
+    let user = source_map.lookup(4).unwrap();
+    assert_eq!(user.metadata.get("schema"), Some("user.json#/definitions/User"));
+    assert_eq!(source_map.lookup(9).unwrap().path, "handlers::login");
+    assert!(source_map.lookup(13).is_none());
+}
+
+#[test]
+fn scope_stats() {
+    let mut scope = Scope::new();
+
+    scope.new_struct("User").vis("pub").doc("A user.");
+    scope.new_struct("Row");
+    scope.new_fn("helper").vis("pub(crate)");
+
+    let models = scope.new_module("models").vis("pub");
+    models.new_enum("Role").vis("pub").new_variant("Admin");
+
+    let mut user_impl = Impl::new("User");
+    user_impl.new_fn("new").vis("pub").ret("Self").line("User");
+    user_impl.new_fn("validate").arg_ref_self();
+    scope.push_impl(user_impl);
+
+    let mut display_impl = Impl::new("User");
+    display_impl
+        .impl_trait("std::fmt::Display")
+        .new_fn("fmt")
+        .arg_ref_self()
+        .arg("f", "&mut std::fmt::Formatter<'_>")
+        .ret("std::fmt::Result")
+        .line("write!(f, \"user\")");
+    scope.push_impl(display_impl);
+
+    let stats = scope.stats();
+
+    assert_eq!(stats.count("struct"), 2);
+    assert_eq!(stats.count("enum"), 1);
+    assert_eq!(stats.count("impl"), 2);
+    assert_eq!(stats.count("trait"), 0);
+    assert_eq!(stats.public, 4);
+    assert_eq!(stats.private, 3);
+    assert_eq!(
+        stats.undocumented_public,
+        vec!["models", "models::Role", "User::new"]
+    );
+    assert_eq!(stats.rendered_len, scope.to_string().len());
+}
+
+#[test]
+fn custom_items() {
+    use std::fmt::{self, Write};
+
+    #[derive(Debug, Clone)]
+    struct Lazy {
+        name: &'static str,
+        ty: &'static str,
+        init: &'static str,
+    }
+
+    impl FormatItem for Lazy {
+        fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+            write!(fmt, "lazy_static!")?;
+            fmt.block(|fmt| {
+                write!(fmt, "static ref {}: {} = {};\n", self.name, self.ty, self.init)
+            })
+        }
+    }
+
+    let mut scope = Scope::new();
+    scope.new_struct("Config");
+    scope.new_module("globals").push_custom(Lazy {
+        name: "CONFIG",
+        ty: "Config",
+        init: "Config::load()",
+    });
+
+    let expect = r#"
+struct Config;
+
+mod globals {
+    lazy_static! {
+        static ref CONFIG: Config = Config::load();
+    }
+}"#;
+
+    assert_eq!(scope.clone().to_string(), &expect[1..]);
+    assert_eq!(scope.stats().count("custom"), 1);
+}
+
+#[test]
+fn custom_items_wrap_lists() {
+    use std::fmt::{self, Write};
+
+    #[derive(Debug, Clone)]
+    struct Vowels;
+
+    impl FormatItem for Vowels {
+        fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+            assert_eq!(fmt.indent_level(), 1);
+
+            write!(fmt, "const VOWELS: &[char] = ")?;
+            assert_eq!(fmt.column(), 28);
+
+            fmt.wrap_list("&[", &["'a'", "'e'", "'i'", "'o'", "'u'"], ",", "];")?;
+            write!(fmt, "\nconst PATTERN: &str = ")?;
+            fmt.wrap_list("\"", &["a", "e", "i", "o", "u"], " |", "\";")?;
+            write!(fmt, "\n")
+        }
+    }
+
+    let mut scope = Scope::new();
+    scope.max_width(48).new_module("text").push_custom(Vowels);
+
+    let expect = r#"
+mod text {
+    const VOWELS: &[char] = &[
+        'a',
+        'e',
+        'i',
+        'o',
+        'u',
+    ];
+    const PATTERN: &str = "a | e | i | o | u";
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn custom_items_delimited_groups() {
+    use std::fmt::{self, Write};
+
+    #[derive(Debug, Clone)]
+    struct Dispatch;
+
+    impl FormatItem for Dispatch {
+        fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+            fmt.braced("macro_rules! dispatch", |fmt| {
+                fmt.delimited("($value:expr) => ", Delimiter::Brace, ";", |fmt| {
+                    fmt.braced("match $value", |fmt| {
+                        fmt.delimited("0 => ", Delimiter::Brace, ",", |fmt| {
+                            write!(fmt, "start()")
+                        })?;
+                        fmt.delimited("_ => stop", Delimiter::Paren, ",", |fmt| {
+                            fmt.bracketed("&", |fmt| write!(fmt, "$value,\n"))
+                        })
+                    })
+                })
+            })
+        }
+    }
+
+    let mut scope = Scope::new();
+    scope.new_module("dispatch").push_custom(Dispatch);
+
+    let expect = r#"
+mod dispatch {
+    macro_rules! dispatch {
+        ($value:expr) => {
+            match $value {
+                0 => {
+                    start()
+                },
+                _ => stop(
+                    &[
+                        $value,
+                    ]
+                ),
+            }
+        };
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn custom_items_multi_line_writes() {
+    use std::fmt::{self, Write};
+
+    #[derive(Debug, Clone)]
+    struct Match;
+
+    impl FormatItem for Match {
+        fn fmt(&self, fmt: &mut Formatter<'_>) -> fmt::Result {
+            write!(fmt, "fn kind(value: u8) -> &'static str")?;
+            fmt.block(|fmt| {
+                write!(fmt, "match value")?;
+                fmt.block(|fmt| {
+                    write!(fmt, "0 => \"zero\",\n1 => {{\n    \"one\"\n}}")?;
+                    fmt.write_indented("_ => \"many\",")
+                })
+            })
+        }
+    }
+
+    let mut scope = Scope::new();
+    scope.new_module("kinds").push_custom(Match);
+
+    let expect = r#"
+mod kinds {
+    fn kind(value: u8) -> &'static str {
+        match value {
+            0 => "zero",
+            1 => {
+                "one"
+            }
+            _ => "many",
+        }
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn render_hooks() {
+    use std::fmt::{self, Write};
+
+    #[derive(Debug, Default)]
+    struct Separators {
+        rendered: Vec<String>,
+    }
+
+    impl RenderHook for Separators {
+        fn before_item(&mut self, kind: &str, name: &str, fmt: &mut Formatter<'_>) -> fmt::Result {
+            write!(fmt, "// ---- {} {} ----\n", kind, name)
+        }
+
+        fn after_item(&mut self, kind: &str, name: &str, _fmt: &mut Formatter<'_>) -> fmt::Result {
+            self.rendered.push(format!("{} {}", kind, name));
+            Ok(())
+        }
+    }
+
+    let mut scope = Scope::new();
+    scope.new_struct("Foo");
+    scope.new_module("bar").new_fn("baz");
+
+    let mut hook = Separators::default();
+
+    let expect = r#"
+// ---- struct Foo ----
+struct Foo;
+
+// ---- mod bar ----
+mod bar {
+    // ---- fn baz ----
+    fn baz() {
+    }
+}"#;
+
+    assert_eq!(scope.to_string_with_hook(&mut hook), &expect[1..]);
+    assert_eq!(hook.rendered, vec!["struct Foo", "fn baz", "mod bar"]);
+}
+
+#[test]
+fn with_visibility_map_makes_public_items_crate_visible() {
+    let mut scope = Scope::new();
+
+    scope.import("crate::error", "Error").vis("pub");
+
+    let module = scope.new_module("models").vis("pub");
+    module
+        .new_struct("User")
+        .vis("pub")
+        .derive("Debug")
+        .field("pub id", "u64")
+        .field("cache", "Cache");
+    module.new_struct("UserId").vis("pub").tuple_field("pub u64");
+
+    let imp = scope.new_impl("User");
+    imp.new_fn("id").vis("pub").arg_ref_self().ret("u64").line("self.id");
+    imp.new_fn("touch").arg_mut_self().line("self.cache.clear();");
+
+    scope
+        .new_impl("User")
+        .impl_trait("Clone")
+        .new_fn("clone")
+        .arg_ref_self()
+        .ret("Self")
+        .line("todo!()");
+
+    let internal = scope.with_visibility_map(|vis| match vis {
+        Visibility::Pub => Visibility::PubCrate,
+        vis => vis,
+    });
+
+    let expect = r#"
+pub(crate) use crate::error::Error;
+
+pub(crate) mod models {
+    #[derive(Debug)]
+    pub(crate) struct User {
+        pub(crate) id: u64,
+        cache: Cache,
+    }
+
+    pub(crate) struct UserId(pub(crate) u64);
+}
+
+impl User {
+    pub(crate) fn id(&self) -> u64 {
+        self.id
+    }
+
+    fn touch(&mut self) {
+        self.cache.clear();
+    }
+}
+
+impl Clone for User {
+    fn clone(&self) -> Self {
+        todo!()
+    }
+}"#;
+
+    assert_eq!(internal.to_string(), &expect[1..]);
+    assert!(scope.to_string().starts_with("pub use crate::error::Error;"));
+}
+
+#[test]
+fn transformed_sync_and_no_panic_profiles() {
+    let mut scope = Scope::new();
+
+    let mut on_error = Block::new("if response.status().is_client_error()");
+    on_error.line("let body = response.text().await.expect(\"body (utf-8)\");");
+    on_error.line("return Err(Error::Client(body));");
+
+    scope
+        .new_module("client")
+        .new_fn("get_user")
+        .vis("pub")
+        .set_async(true)
+        .arg("client", "&reqwest::Client")
+        .ret("Result<User, Error>")
+        .line("let response = client.get(URL).send().await.unwrap();")
+        .push_block(on_error)
+        .line("Ok(response.json().await.unwrap())");
+
+    let mut no_panic = Transform::new();
+    no_panic.unwrap_to_try();
+
+    let mut sync = Transform::new();
+    sync.strip_async()
+        .unwrap_to_try()
+        .replace(".text()", ".text_with_charset(\"utf-8\")");
+
+    let mut blocking = PathRewrite::new();
+    blocking.rule("reqwest::", "reqwest::blocking::");
+
+    let expect = r#"
+mod client {
+    pub async fn get_user(client: &reqwest::Client) -> Result<User, Error> {
+        let response = client.get(URL).send().await?;
+        if response.status().is_client_error() {
+            let body = response.text().await?;
+            return Err(Error::Client(body));
+        }
+        Ok(response.json().await?)
+    }
+}"#;
+
+    assert_eq!(scope.transformed(&no_panic).to_string(), &expect[1..]);
+
+    let expect = r#"
+mod client {
+    pub fn get_user(client: &reqwest::blocking::Client) -> Result<User, Error> {
+        let response = client.get(URL).send()?;
+        if response.status().is_client_error() {
+            let body = response.text_with_charset("utf-8")?;
+            return Err(Error::Client(body));
+        }
+        Ok(response.json()?)
+    }
+}"#;
+
+    let mut sync_scope = scope.transformed(&sync);
+    sync_scope.rewrite_paths(&blocking);
+
+    assert_eq!(sync_scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn render_features() {
+    let mut scope = Scope::new();
+
+    scope.new_struct("Value").vis("pub").field("pub raw", "String");
+
+    let to_json = scope.new_fn("to_json");
+    to_json
+        .vis("pub")
+        .arg("value", "&Value")
+        .ret("String")
+        .line("serde_json::to_string(&value.raw).unwrap()");
+    to_json.metadata_mut().feature("serde-json");
+
+    let mut from_json = Impl::new("Value");
+    from_json.new_fn("from_json").vis("pub").arg("json", "&str").ret("Self");
+    from_json.metadata_mut().feature("serde-json");
+    scope.push_impl(from_json);
+
+    scope
+        .new_struct("Json")
+        .vis("pub")
+        .tuple_field("String")
+        .metadata_mut()
+        .feature("serde-json");
+
+    scope
+        .new_enum("Format")
+        .vis("pub")
+        .push_variant(Variant::new("Compact"))
+        .push_variant(Variant::new("Pretty"))
+        .metadata_mut()
+        .feature("serde-json");
+
+    scope
+        .new_module("sync")
+        .new_fn("blocking")
+        .metadata_mut()
+        .feature("blocking");
+
+    let cfg = r#"
+pub struct Value {
+    pub raw: String,
+}
+
+#[cfg(feature = "serde-json")]
+pub fn to_json(value: &Value) -> String {
+    serde_json::to_string(&value.raw).unwrap()
+}
+
+#[cfg(feature = "serde-json")]
+impl Value {
+    pub fn from_json(json: &str) -> Self {
+    }
+}
+
+#[cfg(feature = "serde-json")]
+pub struct Json(String);
+
+#[cfg(feature = "serde-json")]
+pub enum Format {
+    Compact,
+    Pretty,
+}
+
+mod sync {
+    #[cfg(feature = "blocking")]
+    fn blocking() {
+    }
+}
+"#;
+
+    let tree = scope.render_features("src/lib.rs", FeatureLayout::Cfg);
+    let paths: Vec<&str> = tree.files().map(|(path, _)| path).collect();
+
+    assert_eq!(paths, vec!["src/lib.rs"]);
+    assert_eq!(tree.get("src/lib.rs"), Some(&cfg[1..]));
+
+    let lib = r#"
+pub struct Value {
+    pub raw: String,
+}
+
+mod sync {
+    #[cfg(feature = "blocking")]
+    fn blocking() {
+    }
+}
+
+#[cfg(feature = "serde-json")]
+mod serde_json;
+#[cfg(feature = "serde-json")]
+pub use self::serde_json::*;
+"#;
+
+    let feature = r#"
+use super::*;
+
+pub fn to_json(value: &Value) -> String {
+    serde_json::to_string(&value.raw).unwrap()
+}
+
+impl Value {
+    pub fn from_json(json: &str) -> Self {
+    }
+}
+
+pub struct Json(String);
+
+pub enum Format {
+    Compact,
+    Pretty,
+}
+"#;
+
+    let tree = scope.render_features("src/api.rs", FeatureLayout::Files);
+    let paths: Vec<&str> = tree.files().map(|(path, _)| path).collect();
+
+    assert_eq!(paths, vec!["src/api.rs", "src/api/serde_json.rs"]);
+    assert_eq!(tree.get("src/api.rs"), Some(&lib[1..]));
+    assert_eq!(tree.get("src/api/serde_json.rs"), Some(&feature[1..]));
+}
+
+#[test]
+fn deterministic_output() {
+    let root = std::env::current_dir().unwrap();
+    let schema = root.join("schemas").join("user.json");
+
+    let mut scope = Scope::new();
+    scope.deterministic();
+    scope.raw(format!(
+        "// Generated from {}\r\n// Do not edit.   ",
+        schema.display()
+    ));
+    scope
+        .new_struct("User")
+        .doc("A user.  ")
+        .field("name", "String");
+
+    let expect = r#"
+// Generated from schemas/user.json
+// Do not edit.
+
+/// A user.
+struct User {
+    name: String,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+    assert_eq!(scope.to_string_with_source_map().0, &expect[1..]);
+}
+
+#[test]
+#[should_panic(expected = "deterministic output must not contain timestamps, found `2024-01-31T12:00`")]
+fn deterministic_output_with_timestamp() {
+    let mut scope = Scope::new();
+    scope.deterministic();
+    scope.raw("// Generated at 2024-01-31T12:00:00Z");
+
+    scope.to_string();
+}
+
+#[test]
+fn diff_scopes() {
+    let mut old = Scope::new();
+    old.new_struct("User").field("name", "String");
+    old.new_fn("logout");
+    old.new_module("api").vis("pub").new_fn("login");
+
+    let mut new = Scope::new();
+    new.new_struct("User")
+        .field("name", "String")
+        .field("age", "u32");
+    new.new_module("api")
+        .vis("pub")
+        .new_fn("login")
+        .arg("token", "&str");
+    new.new_enum("Role").new_variant("Admin");
+
+    let diff = old.diff(&new);
+
+    let expect = r#"
+changed struct User
+removed fn logout
+changed fn api::login
+added enum Role
+"#;
+
+    assert_eq!(diff.to_string(), &expect[1..]);
+    assert_eq!(diff.added().count(), 1);
+    assert_eq!(diff.removed().count(), 1);
+    assert_eq!(diff.changed().count(), 2);
+
+    let login = diff.changed().find(|c| c.path == "api::login").unwrap();
+    assert_eq!(login.kind, "fn");
+    assert_eq!(login.before.as_deref(), Some("fn login() {\n}"));
+    assert_eq!(login.after.as_deref(), Some("fn login(token: &str) {\n}"));
+
+    let logout = diff.removed().next().unwrap();
+    assert_eq!(logout.change, ChangeKind::Removed);
+    assert_eq!(logout.after, None);
+
+    assert!(new.diff(&new).is_empty());
+}
+
+#[test]
+fn diff_semver() {
+    let mut old = Scope::new();
+    old.new_struct("Config")
+        .vis("pub")
+        .field("pub name", "String");
+    old.new_enum("Error")
+        .vis("pub")
+        .attr("non_exhaustive")
+        .new_variant("Io");
+    old.new_fn("connect").vis("pub").line("todo!()");
+    old.new_fn("helper");
+    let api = old.new_module("api").vis("pub");
+    api.new_fn("login").vis("pub").arg("user", "&str");
+    api.new_trait("Handler").vis("pub").new_fn("handle");
+
+    let mut new = Scope::new();
+    new.new_struct("Config")
+        .vis("pub")
+        .field("pub name", "String")
+        .field("pub port", "u16");
+    let error = new.new_enum("Error").vis("pub").attr("non_exhaustive");
+    error.new_variant("Io");
+    error.new_variant("Timeout");
+    new.new_fn("connect").vis("pub").line("unimplemented!()");
+    new.new_fn("helper").arg("verbose", "bool");
+    new.new_fn("disconnect").vis("pub");
+    let api = new.new_module("api").vis("pub");
+    api.new_fn("login").vis("pub").arg("user", "&str").arg("token", "&str");
+    let handler = api.new_trait("Handler").vis("pub");
+    handler.new_fn("handle");
+    handler.new_fn("name").ret("&str").line("\"handler\"");
+
+    let report = old.diff(&new).semver();
+
+    let expect = r#"
+major struct Config: added field `port` to constructible struct
+minor enum Error: added `Timeout`
+patch fn connect: changed implementation
+patch fn helper: private item
+major fn api::login: changed signature
+minor trait api::Handler: added `name`
+minor fn disconnect: added public item
+"#;
+
+    assert_eq!(report.to_string(), &expect[1..]);
+    assert_eq!(report.impact(), SemverImpact::Major);
+    assert_eq!(report.breaking().count(), 2);
+
+    let mut removed = Scope::new();
+    removed.new_module("api").vis("pub");
+
+    let report = old.diff(&removed).semver();
+    let breaking: Vec<&str> = report.breaking().map(|c| &c.path[..]).collect();
+
+    assert_eq!(
+        breaking,
+        vec!["Config", "Error", "connect", "api::login", "api::Handler"]
+    );
+    assert_eq!(new.diff(&new).semver().impact(), SemverImpact::Patch);
+}
+
+#[test]
+fn public_api() {
+    let mut scope = Scope::new();
+    scope.import("std::fmt", "Display");
+    scope.new_struct("User")
+        .vis("pub")
+        .doc("A user.")
+        .field("pub name", "String")
+        .field("password", "String");
+    scope.new_struct("Session").field("token", "String");
+
+    let imp = scope.new_impl("User");
+    imp.new_fn("new")
+        .vis("pub")
+        .arg("name", "String")
+        .ret("Self")
+        .line("Self { name, password: String::new() }");
+    imp.new_fn("hash").arg_ref_self().line("todo!()");
+
+    scope.new_impl("Session").new_fn("refresh").line("todo!()");
+    scope.new_impl("User")
+        .impl_trait("Display")
+        .new_fn("fmt")
+        .arg_ref_self()
+        .arg("f", "&mut fmt::Formatter")
+        .ret("fmt::Result")
+        .line("write!(f, \"{}\", self.name)");
+
+    let api = scope.new_module("api").vis("pub");
+    api.new_trait("Handler")
+        .vis("pub")
+        .new_fn("name")
+        .arg_ref_self()
+        .ret("&str")
+        .line("\"handler\"");
+    api.new_fn("route");
+    scope.new_module("internal").new_fn("secret").vis("pub");
+    scope.raw("const VERSION: u32 = 1;");
+
+    let expect = r#"
+use std::fmt::Display;
+
+/// A user.
+pub struct User {
+    pub name: String,
+}
+
+impl User {
+    pub fn new(name: String) -> Self {
+    }
+}
+
+impl Display for User {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+    }
+}
+
+pub mod api {
+    pub trait Handler {
+        fn name(&self) -> &str {
+        }
+    }
+}"#;
+
+    assert_eq!(scope.public_api().to_string(), &expect[1..]);
+}
+
+#[test]
+fn body_mode() {
+    let mut scope = Scope::new();
+    scope.body_mode(BodyMode::Unimplemented);
+    scope.new_fn("connect").vis("pub").line("Client::new()");
+
+    let tr = scope.new_trait("Store");
+    tr.new_fn("get").arg_ref_self().ret("u32");
+    tr.new_fn("name").arg_ref_self().ret("&str").line("\"store\"");
 
+    let module = scope.new_module("raw");
+    module.scope().body_mode(BodyMode::Omit);
+    module.new_fn("len").ret("usize").line("0");
 
+    scope.new_impl("Memory")
+        .impl_trait("Store")
+        .new_fn("get")
+        .arg_ref_self()
+        .ret("u32")
+        .line("self.value");
 
+    let expect = r#"
+pub fn connect() {
+    unimplemented!()
+}
+
+trait Store {
+    fn get(&self) -> u32;
+
+    fn name(&self) -> &str {
+        unimplemented!()
+    }
+}
+
+mod raw {
+    fn len() -> usize;
+}
+
+impl Store for Memory {
+    fn get(&self) -> u32 {
+        unimplemented!()
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn where_style() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_fn("merge")
+        .generic("K")
+        .generic("V")
+        .arg("map", "HashMap<K, V>")
+        .bound("K", "Eq + Hash")
+        .bound("V", "Clone")
+        .line("todo!()");
+
+    scope.where_style(WhereStyle::Rustfmt);
+
+    let expect = r#"
+fn merge<K, V>(map: HashMap<K, V>)
+where
+    K: Eq + Hash,
+    V: Clone,
+{
+    todo!()
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+
+    scope.where_style(WhereStyle::Compact);
+
+    let expect = r#"
+fn merge<K, V>(map: HashMap<K, V>)
+where K: Eq + Hash, V: Clone
+{
+    todo!()
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+
+    scope.where_style(WhereStyle::SameLine);
+
+    let expect = r#"
+fn merge<K, V>(map: HashMap<K, V>) where K: Eq + Hash, V: Clone {
+    todo!()
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn where_style_in_modules() {
+    let mut scope = Scope::new();
+    scope.where_style(WhereStyle::Rustfmt);
+
+    let module = scope.new_module("cache");
+    module
+        .new_impl("Cache<K>")
+        .generic("K")
+        .bound("K", "Hash")
+        .new_fn("len")
+        .arg_ref_self()
+        .ret("usize")
+        .line("self.len");
+
+    let expect = r#"
+mod cache {
+    impl<K> Cache<K>
+    where
+        K: Hash,
+    {
+        fn len(&self) -> usize {
+            self.len
+        }
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn brace_and_comma_style() {
+    let mut scope = Scope::new();
+
+    scope.new_struct("Point").field("x", "i32").field("y", "i32");
+
+    let shape = scope.new_enum("Shape");
+    shape.new_variant("Empty");
+    shape.new_variant("Circle").named("radius", "f64");
+
+    scope
+        .new_fn("origin")
+        .ret("Point")
+        .line("Point { x: 0, y: 0 }");
+
+    scope.trailing_comma(false).space_before_brace(false);
+
+    let expect = r#"
+struct Point{
+    x: i32,
+    y: i32
+}
+
+enum Shape{
+    Empty,
+    Circle{
+        radius: f64
+    }
+}
+
+fn origin() -> Point{
+    Point { x: 0, y: 0 }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn max_width_wraps_use_trees_and_derives() {
+    let mut scope = Scope::new();
+
+    for ty in ["Deserialize", "Deserializer", "Serialize", "Serializer"] {
+        scope.import("serde", ty);
+    }
+
+    scope.import("std::fmt", "Debug");
+    scope.import("std::fmt", "Display");
+
+    let module = scope.new_module("model");
+    module
+        .new_struct("User")
+        .derive("Debug")
+        .derive("Clone")
+        .derive("PartialEq")
+        .derive("Serialize")
+        .derive("Deserialize")
+        .field("id", "u64");
+
+    scope.max_width(40);
+
+    let expect = r#"
+use serde::{
+    Deserialize,
+    Deserializer,
+    Serialize,
+    Serializer,
+};
+use std::fmt::{Debug, Display};
+
+mod model {
+    #[derive(
+        Debug,
+        Clone,
+        PartialEq,
+        Serialize,
+        Deserialize,
+    )]
+    struct User {
+        id: u64,
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn rustfmt_skip_raw() {
+    let mut scope = Scope::new();
+
+    scope.raw("static OPCODES: [(&str, u8); 2] = [\n    (\"nop\",  0x00),\n    (\"halt\", 0xff),\n];");
+    scope.raw("#[rustfmt::skip]\nconst MASK: u8 = 0b1111_0000;");
+    scope
+        .new_module("tables")
+        .scope()
+        .raw("const ROW: [u8; 3] = [1,  2,  3];")
+        .rustfmt_skip_raw(false);
+    scope.new_fn("identity").rustfmt_skip().ret("[[u8; 2]; 2]").line("[[1, 0],\n [0, 1]]");
+
+    scope.rustfmt_skip_raw(true);
+
+    let expect = r#"
+#[rustfmt::skip]
+static OPCODES: [(&str, u8); 2] = [
+    ("nop",  0x00),
+    ("halt", 0xff),
+];
+
+#[rustfmt::skip]
+const MASK: u8 = 0b1111_0000;
+
+mod tables {
+    const ROW: [u8; 3] = [1,  2,  3];
+}
+
+#[rustfmt::skip]
+fn identity() -> [[u8; 2]; 2] {
+    [[1, 0],
+     [0, 1]]
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn render_region() {
+    let source = r#"
+use std::fmt;
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+mod generated {
+    // codegen:begin(status)
+    struct Stale;
+    // codegen:end
+}
+
+// Hand-written tail.
+"#;
+
+    let mut scope = Scope::new();
+    scope.new_enum("Status").vis("pub").new_variant("Active");
+    scope.new_fn("is_active").arg("status", "Status").ret("bool").line("true");
+
+    let expect = r#"
+use std::fmt;
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+mod generated {
+    // codegen:begin(status)
+    pub enum Status {
+        Active,
+    }
+
+    fn is_active(status: Status) -> bool {
+        true
+    }
+    // codegen:end
+}
+
+// Hand-written tail.
+"#;
+
+    let rendered = scope.render_region(&source[1..], "status").unwrap();
+    assert_eq!(rendered, &expect[1..]);
+
+    // Rendering again keeps the output as is.
+    assert_eq!(scope.render_region(&rendered, "status").unwrap(), rendered);
+}
+
+#[test]
+fn render_region_errors() {
+    let scope = Scope::new();
+
+    assert_eq!(
+        scope.render_region("// codegen:end\n", "a"),
+        Err(RegionError::MissingBegin("a".to_string()))
+    );
+    assert_eq!(
+        scope.render_region("// codegen:begin(a)\nfn main() {}\n", "a"),
+        Err(RegionError::MissingEnd("a".to_string()))
+    );
+    assert_eq!(
+        scope.render_region("// codegen:begin(a)\n// codegen:end\n// codegen:begin(a)\n", "a"),
+        Err(RegionError::Duplicate("a".to_string()))
+    );
+}
+
+#[test]
+fn scope_rendered_concurrently() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_struct("Point")
+        .field("x", "i32")
+        .field("y", "i32");
 
+    let (std, compact) = std::thread::scope(|s| {
+        let std = s.spawn(|| scope.to_string());
+        let compact = s.spawn(|| {
+            scope.to_string_with(|fmt| {
+                fmt.set_trailing_comma(false).set_space_before_brace(false);
+            })
+        });
 
+        (std.join().unwrap(), compact.join().unwrap())
+    });
 
+    let expect_std = r#"
+struct Point {
+    x: i32,
+    y: i32,
+}"#;
 
+    let expect_compact = r#"
+struct Point{
+    x: i32,
+    y: i32
+}"#;
+
+    assert_eq!(std, &expect_std[1..]);
+    assert_eq!(compact, &expect_compact[1..]);
+}
+
+#[test]
+fn scope_estimated_len() {
+    let mut scope = Scope::new();
+
+    scope.import("std::collections", "HashMap");
+    scope
+        .new_struct("Cache")
+        .derive("Debug")
+        .doc("Caches the users by id.")
+        .field("users", "HashMap<u64, String>");
+
+    let imp = scope.new_impl("Cache");
+    imp.new_fn("get")
+        .vis("pub")
+        .arg_ref_self()
+        .arg("id", "u64")
+        .ret("Option<&String>")
+        .line("self.users.get(&id)");
+
+    let mut block = Block::new("for (id, name) in names");
+    block.line("self.users.insert(id, name);");
+    imp.new_fn("extend")
+        .vis("pub")
+        .arg_mut_self()
+        .arg("names", "Vec<(u64, String)>")
+        .push_block(block);
+
+    let rendered = scope.to_string();
+    let estimate = scope.estimated_len();
+
+    assert!(estimate >= rendered.len() / 2 && estimate <= rendered.len() * 2);
+
+    let mut buf = String::from("stale contents");
+    scope.render_into(&mut buf);
+
+    assert_eq!(buf, rendered);
+}
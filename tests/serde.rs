@@ -0,0 +1,107 @@
+use rust_codegen::serde::*;
+use rust_codegen::*;
+
+#[test]
+fn struct_with_serde_attributes() {
+    let mut scope = Scope::new();
+
+    let mut first_name = Field::new("first_name", "String");
+    first_name.serde(FieldAttrs::new().rename("givenName"));
+
+    let mut nickname = Field::new("nickname", "Option<String>");
+    nickname.serde(FieldAttrs::new().default().skip_serializing_if("Option::is_none"));
+
+    let mut extra = Field::new("extra", "Extra");
+    extra.serde(FieldAttrs::new().flatten());
+
+    let mut cache = Field::new("cache", "Cache");
+    cache.serde(FieldAttrs::new().skip());
+
+    scope
+        .new_struct("Person")
+        .derive("serde::Serialize")
+        .serde(
+            ContainerAttrs::new()
+                .rename_all(RenameRule::CamelCase)
+                .deny_unknown_fields(),
+        )
+        .push_field(first_name)
+        .push_field(nickname)
+        .push_field(extra)
+        .push_field(cache);
+
+    let expect = r#"
+#[derive(serde::Serialize)]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+struct Person {
+    #[serde(rename = "givenName")]
+    first_name: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    nickname: Option<String>,
+    #[serde(flatten)]
+    extra: Extra,
+    #[serde(skip)]
+    cache: Cache,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn enum_with_serde_attributes() {
+    let mut scope = Scope::new();
+
+    let foo_enum = scope
+        .new_enum("Message")
+        .derive("serde::Deserialize")
+        .serde(ContainerAttrs::new().tag("type").content("data"));
+
+    foo_enum
+        .new_variant("Ping")
+        .serde(VariantAttrs::new().rename("ping"));
+    foo_enum
+        .new_variant("Text")
+        .serde(VariantAttrs::new().rename("text"))
+        .tuple("String");
+    foo_enum
+        .new_variant("Internal")
+        .serde(VariantAttrs::new().skip());
+
+    let expect = r#"
+#[derive(serde::Deserialize)]
+#[serde(tag = "type", content = "data")]
+enum Message {
+    #[serde(rename = "ping")]
+    Ping,
+    #[serde(rename = "text")]
+    Text(String),
+    #[serde(skip)]
+    Internal,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn serde_attribute_values_are_escaped() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_struct("Foo")
+        .serde(ContainerAttrs::new().rename("say \"hi\""))
+        .field("one", "usize");
+
+    let expect = r#"
+#[serde(rename = "say \"hi\"")]
+struct Foo {
+    one: usize,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+#[should_panic(expected = "a serde container cannot be both tagged and untagged")]
+fn serde_tagged_and_untagged() {
+    ContainerAttrs::new().tag("type").untagged();
+}
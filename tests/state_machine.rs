@@ -0,0 +1,80 @@
+use rust_codegen::*;
+
+#[test]
+fn state_machine_with_guards() {
+    let mut scope = Scope::new();
+
+    let mut machine = StateMachine::new("State", "Event");
+    machine
+        .vis("pub")
+        .state("Idle")
+        .state("Connecting")
+        .state("Closed")
+        .event("Connect")
+        .event("Timeout")
+        .arg("ctx", "&Context")
+        .transition("Idle", "Connect", "Connecting")
+        .transition("Idle", "Timeout", "Idle")
+        .guarded_transition("Connecting", "Timeout", "Connecting", "ctx.retries < 3")
+        .transition("Connecting", "Timeout", "Closed");
+
+    machine.push_to(&mut scope);
+
+    let expect = r#"
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum State {
+    Idle,
+    Connecting,
+    Closed,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Event {
+    Connect,
+    Timeout,
+}
+
+impl State {
+    /// Moves to the next state for the given event, returning whether a transition happened.
+    pub fn transition(&mut self, event: Event, ctx: &Context) -> bool {
+        match *self {
+            State::Idle => match event {
+                Event::Connect => {
+                    *self = State::Connecting;
+                    true
+                },
+                Event::Timeout => {
+                    *self = State::Idle;
+                    true
+                },
+            },
+            State::Connecting => match event {
+                Event::Timeout if ctx.retries < 3 => {
+                    *self = State::Connecting;
+                    true
+                },
+                Event::Timeout => {
+                    *self = State::Closed;
+                    true
+                },
+                _ => false,
+            },
+            State::Closed => false,
+        }
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+#[should_panic(expected = "unknown state `Open` in transition")]
+fn state_machine_unknown_state() {
+    let mut machine = StateMachine::new("State", "Event");
+    machine
+        .state("Idle")
+        .event("Connect")
+        .transition("Idle", "Connect", "Open");
+
+    machine.to_impl();
+}
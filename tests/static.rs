@@ -0,0 +1,38 @@
+use rust_codegen::*;
+
+#[test]
+fn static_symbol_attributes() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_static("VERSION", "u32", "2")
+        .vis("pub")
+        .doc("The version of the plugin API.")
+        .no_mangle()
+        .export_name("plugin_version");
+
+    scope
+        .new_static("INIT", "extern \"C\" fn()", "init")
+        .attr("used")
+        .link_section(".init_array");
+
+    scope
+        .new_static("COUNTER", "u64", "0")
+        .set_mut(true)
+        .export_name("counter")
+        .no_mangle();
+
+    let expect = r#"
+/// The version of the plugin API.
+#[export_name = "plugin_version"]
+pub static VERSION: u32 = 2;
+
+#[used]
+#[link_section = ".init_array"]
+static INIT: extern "C" fn() = init;
+
+#[no_mangle]
+static mut COUNTER: u64 = 0;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
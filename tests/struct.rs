@@ -409,4 +409,271 @@ struct Foo {
 }"#;
 
     assert_eq!(scope.to_string(), &expect[1..]);
-}
\ No newline at end of file
+}
+
+#[test]
+fn struct_attributes_keep_order() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_struct("Foo")
+        .doc("A foo.")
+        .derive("Serialize")
+        .attr("serde(rename_all = \"camelCase\")")
+        .allow("dead_code")
+        .derive("Debug")
+        .repr("C")
+        .field("one", "usize");
+
+    let expect = r#"
+/// A foo.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+#[allow(dead_code)]
+#[repr(C)]
+struct Foo {
+    one: usize,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_with_derive_helpers() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_struct("Foo")
+        .allow("dead_code")
+        .derive("Debug")
+        .derive_with("serde::Serialize", ["serde(rename_all = \"camelCase\")"])
+        .attr("non_exhaustive")
+        .field("one_two", "usize");
+
+    let expect = r#"
+#[allow(dead_code)]
+#[derive(Debug, serde::Serialize)]
+#[serde(rename_all = "camelCase")]
+#[non_exhaustive]
+struct Foo {
+    one_two: usize,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_with_no_run_example() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_struct("Client")
+        .doc_example("let client = Client::connect(\"localhost:80\");", DocTest::NoRun);
+
+    let expect = r#"
+/// # Examples
+///
+/// ```no_run
+/// let client = Client::connect("localhost:80");
+/// ```
+struct Client;"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_field_doc_str_and_annotations() {
+    let mut scope = Scope::new();
+
+    let mut count_field = Field::new("count", "u32");
+    count_field
+        .doc_str("The number of pets.\r\n\r\nNever negative.")
+        .push_doc_line("Defaults to zero.")
+        .push_annotation("#[serde(default)]")
+        .push_annotation("#[serde(alias = \"n\")]");
+
+    scope.new_struct("Stats").push_field(count_field);
+
+    let expect = r#"
+struct Stats {
+    /// The number of pets.
+    ///
+    /// Never negative.
+    /// Defaults to zero.
+    #[serde(default)]
+    #[serde(alias = "n")]
+    count: u32,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_with_cfg_and_allow_fields() {
+    let mut scope = Scope::new();
+
+    let mut trace_field = Field::new("trace_id", "u64");
+    trace_field
+        .doc_str("The trace identifier.")
+        .cfg("feature = \"tracing\"")
+        .allow("dead_code")
+        .push_annotation("#[serde(default)]");
+
+    scope
+        .new_struct("Request")
+        .field("path", "String")
+        .push_field(trace_field);
+
+    let expect = r#"
+struct Request {
+    path: String,
+    /// The trace identifier.
+    #[cfg(feature = "tracing")]
+    #[allow(dead_code)]
+    #[serde(default)]
+    trace_id: u64,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_to_code_string() {
+    let mut foo_struct = Struct::new("Foo");
+    foo_struct.derive("Debug").field("one", "usize");
+
+    let expect = r#"
+#[derive(Debug)]
+struct Foo {
+    one: usize,
+}"#;
+
+    assert_eq!(foo_struct.to_code_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_display_and_debug_impls() {
+    let mut scope = Scope::new();
+
+    let mut user = Struct::new("User");
+    user.field("pub name", "String")
+        .field("email", "String")
+        .field("password", "String");
+
+    scope.push_impl(user.display_impl("{name} <{email}> \"{{redacted}}\""));
+    scope.push_impl(user.debug_struct_impl(["name", "email"]));
+
+    let mut id = Struct::new("UserId");
+    id.tuple_field("u64");
+
+    scope.push_impl(id.debug_impl("UserId({0:#x})"));
+
+    let expect = r#"
+impl std::fmt::Display for User {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} <{}> \"{{redacted}}\"", self.name, self.email)
+    }
+}
+
+impl std::fmt::Debug for User {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("User")
+            .field("name", &self.name)
+            .field("email", &self.email)
+            .finish_non_exhaustive()
+    }
+}
+
+impl std::fmt::Debug for UserId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "UserId({:#x})", self.0)
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+#[should_panic(expected = "unknown field `age` in format template")]
+fn struct_display_impl_unknown_field() {
+    let mut user = Struct::new("User");
+    user.field("name", "String");
+
+    user.display_impl("{name} ({age})");
+}
+
+#[test]
+fn struct_key_field_impls() {
+    let mut scope = Scope::new();
+
+    let mut user = Struct::new("User");
+    user.field("pub tenant", "u32")
+        .field("pub id", "u64")
+        .field("name", "String");
+
+    for imp in user.eq_impls(["tenant", "id"]) {
+        scope.push_impl(imp);
+    }
+
+    for imp in user.ord_impls(["tenant", "id"]) {
+        scope.push_impl(imp);
+    }
+
+    scope.push_impl(user.hash_impl(["tenant", "id"]));
+
+    let expect = r#"
+impl PartialEq for User {
+    fn eq(&self, other: &Self) -> bool {
+        self.tenant == other.tenant
+            && self.id == other.id
+    }
+}
+
+impl Eq for User {
+}
+
+impl PartialOrd for User {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for User {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        self.tenant.cmp(&other.tenant)
+            .then_with(|| self.id.cmp(&other.id))
+    }
+}
+
+impl std::hash::Hash for User {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        self.tenant.hash(state);
+        self.id.hash(state);
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn struct_getters_with_pub_fields() {
+    let mut scope = Scope::new();
+
+    let mut user_struct = Struct::new("User");
+    user_struct.field("pub name", "String").field("pub(crate) age", "u8");
+
+    scope.push_impl(user_struct.getters());
+
+    let expect = r#"
+impl User {
+    pub fn name(&self) -> &String {
+        &self.name
+    }
+
+    pub fn age(&self) -> &u8 {
+        &self.age
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
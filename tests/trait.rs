@@ -22,3 +22,431 @@ trait Foo {
 
     assert_eq!(scope.to_string(), &expect[1..]);
 }
+
+#[test]
+fn trait_to_async() {
+    let mut scope = Scope::new();
+
+    let mut trt = Trait::new("Client");
+    trt.new_fn("get").arg_ref_self().ret("String");
+
+    let mut variant = AsyncVariant::new();
+    variant.async_trait(true);
+
+    scope.push_trait(trt.to_async(&variant));
+
+    let expect = r#"
+#[async_trait]
+trait Client {
+    async fn get(&self) -> String;
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn async_trait_strategy() {
+    let mut scope = Scope::new();
+
+    scope.new_trait("Client").new_fn("get").set_async(true);
+    scope.new_trait("Sync").new_fn("get");
+
+    scope
+        .new_module("client")
+        .new_impl("Foo")
+        .impl_trait("Client")
+        .new_fn("get")
+        .set_async(true);
+
+    scope
+        .new_impl("Bar")
+        .impl_trait("client::Client")
+        .new_fn("get")
+        .arg_ref_self();
+
+    scope.apply_async_trait_strategy(AsyncTraitStrategy::AsyncTrait);
+
+    let expect = r#"
+#[async_trait]
+trait Client {
+    async fn get();
+}
+
+trait Sync {
+    fn get();
+}
+
+mod client {
+    #[async_trait]
+    impl Client for Foo {
+        async fn get() {
+        }
+    }
+}
+
+#[async_trait]
+impl client::Client for Bar {
+    fn get(&self) {
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+
+    scope.apply_async_trait_strategy(AsyncTraitStrategy::Native);
+
+    assert!(!scope.to_string().contains("#[async_trait]"));
+}
+
+#[test]
+fn trait_with_associated_type_where_bound() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_trait("Lending")
+        .associated_type("Iter<'a>")
+        .bound("Iterator")
+        .where_bound("Self", "'a");
+
+    let expect = r#"
+trait Lending {
+    type Iter<'a>: Iterator where Self: 'a;
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn trait_wrapped_where_bounds() {
+    let mut scope = Scope::new();
+
+    let lending = scope.new_trait("Lending");
+
+    lending
+        .associated_type("Iter<'a>")
+        .bound("Iterator<Item = &'a Self::Item>")
+        .where_bound("Self", "'a");
+
+    lending
+        .new_fn("lend")
+        .generic("F")
+        .arg("f", "F")
+        .bound("F", "Fn(&Self::Item)");
+
+    scope.max_width(60).where_style(WhereStyle::Rustfmt);
+
+    let expect = r#"
+trait Lending {
+    type Iter<'a>: Iterator<Item = &'a Self::Item>
+    where
+        Self: 'a;
+
+    fn lend<F>(f: F)
+    where
+        F: Fn(&Self::Item);
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn check_object_safety() {
+    let mut scope = Scope::new();
+
+    let shape = scope.new_trait("Shape");
+    shape.dyn_compatible();
+    shape.new_associate_const("SIDES", "u32", "0");
+    shape.new_fn("area").arg_ref_self().ret("f64");
+    shape.new_fn("scale").generic("T").arg_ref_self().arg("by", "T");
+    shape.new_fn("duplicate").arg_ref_self().ret("Self");
+    shape.new_fn("new").ret("Self").bound("Self", "Sized");
+    shape.new_fn("unit").ret("f64");
+
+    // Traits that aren't marked for `dyn` use aren't checked.
+    scope.new_trait("Builder").new_fn("build").ret("Self");
+
+    assert_eq!(
+        scope.check_object_safety(),
+        vec![
+            "`Shape` has the associated constant `SIDES`",
+            "`Shape::scale` has generic parameters",
+            "`Shape::duplicate` returns `Self` by value",
+            "`Shape::unit` has no `self` receiver",
+        ]
+    );
+}
+
+#[test]
+fn trait_with_associated_const() {
+    let mut scope = Scope::new();
+
+    let trt = scope.new_trait("Shape");
+    trt.new_associate_const("SIDES", "u32", "0");
+    trt.new_fn("area").arg_ref_self().ret("f64");
+
+    let expect = r#"
+trait Shape {
+    const SIDES: u32 = 0;
+
+    fn area(&self) -> f64;
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn trait_delegate_impl() {
+    let mut scope = Scope::new();
+
+    let mut trt = Trait::new("Store");
+    trt.r#macro("#[async_trait]");
+    trt.new_fn("get")
+        .arg_ref_self()
+        .arg("key", "&str")
+        .ret("Option<String>");
+    trt.new_fn("set")
+        .set_async(true)
+        .arg_mut_self()
+        .arg("key", "String")
+        .arg("value", "String");
+    trt.new_fn("into_entries")
+        .arg_self()
+        .ret("Vec<(String, String)>");
+    trt.new_fn("open").ret("Self");
+
+    scope.push_impl(trt.delegate_impl("Cached", "inner"));
+
+    let expect = r#"
+#[async_trait]
+impl Store for Cached {
+    fn get(&self, key: &str) -> Option<String> {
+        self.inner.get(key)
+    }
+
+    async fn set(&mut self, key: String, value: String) {
+        self.inner.set(key, value).await
+    }
+
+    fn into_entries(self) -> Vec<(String, String)> {
+        self.inner.into_entries()
+    }
+
+    fn open() -> Self {
+        todo!()
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn trait_dispatch_impl() {
+    let mut scope = Scope::new();
+
+    let mut trt = Trait::new("Handler<Req>");
+    trt.new_fn("name").arg_ref_self().ret("&str");
+    trt.new_fn("handle")
+        .set_async(true)
+        .arg_mut_self()
+        .arg("req", "Req")
+        .ret("Response");
+    trt.new_fn("boxed").ret("Self");
+
+    let mut plugin = Enum::new("Plugin");
+    plugin.new_variant("Http").tuple("HttpHandler");
+    plugin.new_variant("Grpc").tuple("GrpcHandler");
+
+    scope.push_impl(trt.dispatch_impl(&plugin));
+
+    let expect = r#"
+impl Handler<Req> for Plugin {
+    fn name(&self) -> &str {
+        match self {
+            Self::Http(inner) => inner.name(),
+            Self::Grpc(inner) => inner.name(),
+        }
+    }
+
+    async fn handle(&mut self, req: Req) -> Response {
+        match self {
+            Self::Http(inner) => inner.handle(req).await,
+            Self::Grpc(inner) => inner.handle(req).await,
+        }
+    }
+
+    fn boxed() -> Self {
+        todo!()
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+#[should_panic(expected = "variant `Empty` must wrap a single type to dispatch `Handler`")]
+fn trait_dispatch_impl_unit_variant() {
+    let mut trt = Trait::new("Handler");
+    trt.new_fn("name").arg_ref_self().ret("&str");
+
+    let mut plugin = Enum::new("Plugin");
+    plugin.new_variant("Empty");
+
+    trt.dispatch_impl(&plugin);
+}
+
+#[test]
+fn trait_shared_fn_sig() {
+    let mut scope = Scope::new();
+
+    let mut get = FnSig::new("get");
+    get.arg_ref_self().arg("key", "&str").ret("Option<String>");
+
+    scope.new_trait("Store").push_sig(get.clone()).doc("Returns the value of a key.");
+
+    get.set_async(true);
+
+    scope.new_trait("AsyncStore").push_sig(get.clone());
+    scope
+        .new_impl("Memory")
+        .impl_trait("AsyncStore")
+        .new_fn_from_sig(get)
+        .line("self.map.get(key).cloned()");
+
+    let expect = r#"
+trait Store {
+    /// Returns the value of a key.
+    fn get(&self, key: &str) -> Option<String>;
+}
+
+trait AsyncStore {
+    async fn get(&self, key: &str) -> Option<String>;
+}
+
+impl AsyncStore for Memory {
+    async fn get(&self, key: &str) -> Option<String> {
+        self.map.get(key).cloned()
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn trait_sync_sigs() {
+    let mut scope = Scope::new();
+
+    let mut get = FnSig::new("get");
+    get.arg_ref_self().arg("key", "&str").ret("Option<String>");
+
+    scope.new_trait("Store").push_sig(get.clone());
+    scope
+        .new_module("memory")
+        .new_impl("Memory")
+        .impl_trait("crate::Store")
+        .new_fn_from_sig(get)
+        .doc("Looks up the key in the map.")
+        .line("self.map.get(key).cloned()");
+
+    scope
+        .get_trait_mut("Store")
+        .unwrap()
+        .get_fn_mut("get")
+        .unwrap()
+        .set_async(true)
+        .ret("Option<&str>");
+
+    scope.sync_trait_sigs();
+
+    let expect = r#"
+trait Store {
+    async fn get(&self, key: &str) -> Option<&str>;
+}
+
+mod memory {
+    impl crate::Store for Memory {
+        /// Looks up the key in the map.
+        async fn get(&self, key: &str) -> Option<&str> {
+            self.map.get(key).cloned()
+        }
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn trait_fn_vis_dropped() {
+    let mut scope = Scope::new();
+
+    let mut len = Function::new("len");
+    len.vis("pub").arg_ref_self().ret("usize").line("0");
+
+    scope.new_trait("Len").push_fn(len.clone());
+    scope.new_impl("List").push_fn(len);
+
+    let expect = r#"
+trait Len {
+    fn len(&self) -> usize {
+        0
+    }
+}
+
+impl List {
+    pub fn len(&self) -> usize {
+        0
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+    assert_eq!(
+        scope.check_trait_fn_vis(),
+        vec!["`Len::len` has visibility `pub` but trait fns can't have one"]
+    );
+
+    let mut dest = String::new();
+    let mut fmt = Formatter::new(&mut dest);
+    fmt.set_trait_fn_vis(TraitFnVis::Error);
+
+    assert!(scope.fmt(&mut fmt).is_err());
+    assert!(scope
+        .try_to_string_with(|fmt| {
+            fmt.set_trait_fn_vis(TraitFnVis::Error);
+        })
+        .is_err());
+    assert_eq!(scope.try_to_string_with(|_| {}).unwrap(), &expect[1..]);
+}
+
+#[test]
+fn trait_documented_associated_items() {
+    let mut scope = Scope::new();
+
+    let trt = scope.new_trait("Codec");
+    trt.associated_type("Item").bound("Clone").doc("The decoded item.");
+    trt.associated_type("Buf").attr("cfg(feature = \"bytes\")");
+
+    let imp = scope.new_impl("Json").impl_trait("Codec");
+    imp.new_associate_type("Item", "Value").doc("A JSON value.");
+    imp.new_associate_type("Buf", "Bytes").attr("cfg(feature = \"bytes\")");
+    imp.new_associate_const("NAME", "&'static str", "\"json\"")
+        .doc("The name of the codec.")
+        .attr("allow(dead_code)");
+
+    let expect = r#"
+trait Codec {
+    /// The decoded item.
+    type Item: Clone;
+    #[cfg(feature = "bytes")]
+    type Buf;
+}
+
+impl Codec for Json {
+    /// A JSON value.
+    type Item = Value;
+    #[cfg(feature = "bytes")]
+    type Buf = Bytes;
+    /// The name of the codec.
+    #[allow(dead_code)]
+    const NAME: &'static str = "json";
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
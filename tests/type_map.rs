@@ -0,0 +1,50 @@
+use rust_codegen::*;
+
+#[test]
+fn type_map_resolve() {
+    let mut scope = Scope::new();
+
+    let mut type_map = TypeMap::schema();
+    type_map
+        .insert("decimal", "Decimal")
+        .import("rust_decimal", "Decimal");
+
+    let fields = [
+        ("id", "uuid"),
+        ("name", "string"),
+        ("balance", "decimal"),
+        ("created_at", "date-time"),
+    ];
+
+    let mut account = Struct::new("Account");
+
+    for (name, schema_ty) in &fields {
+        let ty = type_map.resolve(schema_ty, &mut scope).unwrap();
+        account.field(*name, ty);
+    }
+
+    scope.push_struct(account);
+
+    let expect = r#"
+use uuid::Uuid;
+use rust_decimal::Decimal;
+use chrono::{DateTime, Utc};
+
+struct Account {
+    id: Uuid,
+    name: String,
+    balance: Decimal,
+    created_at: DateTime<Utc>,
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn type_map_unknown() {
+    let mut scope = Scope::new();
+    let type_map = TypeMap::new();
+
+    assert!(type_map.resolve("string", &mut scope).is_none());
+    assert_eq!(scope.to_string(), "");
+}
@@ -0,0 +1,76 @@
+use rust_codegen::*;
+
+#[test]
+fn validator_try_from_with_error_enum() {
+    let mut scope = Scope::new();
+
+    let mut user = Struct::new("User");
+    user.vis("pub")
+        .field("pub name", "String")
+        .field("pub email", "String")
+        .field("pub age", "u8");
+
+    let mut validator = Validator::new("RawUser");
+    validator
+        .non_empty("name")
+        .regex("email", r"^\S+@\S+$")
+        .range("age", "18..=150");
+
+    validator.push_to(&user, &mut scope);
+
+    let expect = r#"
+#[derive(Debug)]
+pub enum UserError {
+    NameEmpty,
+    EmailInvalid,
+    AgeOutOfRange,
+}
+
+impl std::fmt::Display for UserError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UserError::NameEmpty => write!(f, "`name` must not be empty"),
+            UserError::EmailInvalid => write!(f, "`email` must match `^\\S+@\\S+$`"),
+            UserError::AgeOutOfRange => write!(f, "`age` must be in 18..=150"),
+        }
+    }
+}
+
+impl std::error::Error for UserError {
+}
+
+impl TryFrom<RawUser> for User {
+    type Error = UserError;
+
+    fn try_from(raw: RawUser) -> Result<Self, Self::Error> {
+        if raw.name.is_empty() {
+            return Err(UserError::NameEmpty);
+        }
+        if !regex::Regex::new(r"^\S+@\S+$").unwrap().is_match(&raw.email) {
+            return Err(UserError::EmailInvalid);
+        }
+        if !(18..=150).contains(&raw.age) {
+            return Err(UserError::AgeOutOfRange);
+        }
+        Ok(Self {
+            name: raw.name,
+            email: raw.email,
+            age: raw.age,
+        })
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+#[should_panic(expected = "unknown field `nickname` in validator")]
+fn validator_unknown_field() {
+    let mut user = Struct::new("User");
+    user.field("name", "String");
+
+    let mut validator = Validator::new("RawUser");
+    validator.non_empty("nickname");
+
+    validator.to_impl(&user);
+}
@@ -0,0 +1,71 @@
+use rust_codegen::*;
+
+#[test]
+fn enum_visitor() {
+    let mut scope = Scope::new();
+
+    let mut expr_enum = Enum::new("Expr");
+    expr_enum.new_variant("Literal").tuple("i64");
+    expr_enum
+        .new_variant("BinaryOp")
+        .named("lhs", "Box<Expr>")
+        .named("rhs", "Box<Expr>");
+    expr_enum.new_variant("Unit");
+
+    Visitor::new(&expr_enum).vis("pub").push_to(&mut scope);
+
+    let expect = r#"
+pub trait ExprVisitor {
+    fn visit_literal(&mut self, v0: &i64);
+
+    fn visit_binary_op(&mut self, lhs: &Box<Expr>, rhs: &Box<Expr>);
+
+    fn visit_unit(&mut self);
+}
+
+pub fn walk_expr<V: ExprVisitor + ?Sized>(visitor: &mut V, node: &Expr) {
+    match node {
+        Expr::Literal(v0) => visitor.visit_literal(v0),
+        Expr::BinaryOp { lhs, rhs } => visitor.visit_binary_op(lhs, rhs),
+        Expr::Unit => visitor.visit_unit(),
+    }
+}
+
+impl Expr {
+    pub fn accept<V: ExprVisitor + ?Sized>(&self, visitor: &mut V) {
+        walk_expr(visitor, self)
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn enum_visitor_generic() {
+    let mut scope = Scope::new();
+
+    let mut tree_enum = Enum::new("Tree");
+    tree_enum.generic("T");
+    tree_enum.new_variant("Leaf").tuple("T");
+
+    Visitor::new(&tree_enum).name("TreeWalker").push_to(&mut scope);
+
+    let expect = r#"
+trait TreeWalker<T> {
+    fn visit_leaf(&mut self, v0: &T);
+}
+
+fn walk_tree<T, V: TreeWalker<T> + ?Sized>(visitor: &mut V, node: &Tree<T>) {
+    match node {
+        Tree::Leaf(v0) => visitor.visit_leaf(v0),
+    }
+}
+
+impl<T> Tree<T> {
+    fn accept<V: TreeWalker<T> + ?Sized>(&self, visitor: &mut V) {
+        walk_tree(visitor, self)
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
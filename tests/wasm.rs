@@ -0,0 +1,86 @@
+use rust_codegen::wasm_bindgen::*;
+use rust_codegen::*;
+
+#[test]
+fn wasm_bindgen_exports() {
+    let mut scope = Scope::new();
+    scope.use_prelude(&Prelude::wasm_bindgen());
+
+    scope
+        .new_struct("Counter")
+        .vis("pub")
+        .wasm_bindgen(BindgenAttrs::new().js_name("JsCounter"))
+        .field("count", "u32");
+
+    let counter_impl = scope
+        .new_impl("Counter")
+        .wasm_bindgen(BindgenAttrs::new().js_class("JsCounter"));
+
+    counter_impl
+        .new_fn("new")
+        .vis("pub")
+        .wasm_bindgen(BindgenAttrs::new().constructor())
+        .ret("Counter")
+        .line("Counter { count: 0 }");
+
+    counter_impl
+        .new_fn("count")
+        .vis("pub")
+        .wasm_bindgen(BindgenAttrs::new().getter())
+        .arg_ref_self()
+        .ret("u32")
+        .line("self.count");
+
+    counter_impl
+        .new_fn("set_count")
+        .vis("pub")
+        .wasm_bindgen(BindgenAttrs::new().setter().js_name("count"))
+        .arg_mut_self()
+        .arg("count", "u32")
+        .line("self.count = count;");
+
+    let expect = r#"
+use wasm_bindgen::prelude::*;
+
+#[wasm_bindgen(js_name = JsCounter)]
+pub struct Counter {
+    count: u32,
+}
+
+#[wasm_bindgen(js_class = JsCounter)]
+impl Counter {
+    #[wasm_bindgen(constructor)]
+    pub fn new() -> Counter {
+        Counter { count: 0 }
+    }
+
+    #[wasm_bindgen(getter)]
+    pub fn count(&self) -> u32 {
+        self.count
+    }
+
+    #[wasm_bindgen(setter, js_name = count)]
+    pub fn set_count(&mut self, count: u32) {
+        self.count = count;
+    }
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}
+
+#[test]
+fn wasm_bindgen_js_name_literal() {
+    let mut scope = Scope::new();
+
+    scope
+        .new_fn("greet")
+        .vis("pub")
+        .wasm_bindgen(BindgenAttrs::new().js_name("greet-user"));
+
+    let expect = r#"
+#[wasm_bindgen(js_name = "greet-user")]
+pub fn greet() {
+}"#;
+
+    assert_eq!(scope.to_string(), &expect[1..]);
+}